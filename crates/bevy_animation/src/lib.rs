@@ -86,6 +86,13 @@ impl AnimationClip {
         self.paths.get(path).and_then(|id| self.curves.get(*id))
     }
 
+    /// Iterates over every [`EntityPath`] animated by this clip, with its curves.
+    pub fn paths(&self) -> impl Iterator<Item = (&EntityPath, &Vec<VariableCurve>)> {
+        self.paths
+            .iter()
+            .map(|(path, id)| (path, &self.curves[*id]))
+    }
+
     /// Duration of the clip, represented in seconds
     #[inline]
     pub fn duration(&self) -> f32 {
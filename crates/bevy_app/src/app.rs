@@ -15,6 +15,8 @@ use std::{
     fmt::Debug,
     panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
 };
+#[cfg(feature = "schedule_graphs")]
+use std::{fs, io, path::Path};
 
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
@@ -780,6 +782,36 @@ impl App {
         self
     }
 
+    /// Registers `alias` as another type path of the type `T` in the
+    /// [`TypeRegistry`](bevy_reflect::TypeRegistry) resource, so that data serialized before `T`
+    /// was renamed or moved can still be deserialized.
+    ///
+    /// See [`bevy_reflect::TypeRegistry::register_type_alias`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_type_alias<T: bevy_reflect::GetTypeRegistration>(
+        &mut self,
+        alias: impl Into<String>,
+    ) -> &mut Self {
+        let registry = self.world.resource_mut::<AppTypeRegistry>();
+        registry.write().register_type_alias::<T>(alias);
+        self
+    }
+
+    /// Registers a [`Migration`](bevy_reflect::serde::Migration) updating data serialized with
+    /// an older version of the type `T` in the [`TypeRegistry`](bevy_reflect::TypeRegistry)
+    /// resource, so that scenes saved with that version can still be loaded.
+    ///
+    /// See [`bevy_reflect::TypeRegistry::register_migration`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_migration<T: bevy_reflect::GetTypeRegistration>(
+        &mut self,
+        migration: bevy_reflect::serde::Migration,
+    ) -> &mut Self {
+        let registry = self.world.resource_mut::<AppTypeRegistry>();
+        registry.write().register_migration::<T>(migration);
+        self
+    }
+
     /// Adds the type data `D` to type `T` in the [`TypeRegistry`](bevy_reflect::TypeRegistry) resource.
     ///
     /// Most of the time [`App::register_type`] can be used instead to register a type you derived [`Reflect`](bevy_reflect::Reflect) for.
@@ -921,6 +953,48 @@ impl App {
 
         self
     }
+
+    /// Writes the graphs of the schedules in [`Schedules`] to the directory `dir`, to visualize
+    /// them.
+    ///
+    /// Each schedule is written as a DOT file and a JSON file named after its label, see
+    /// [`ScheduleGraphExport`](bevy_ecs::schedule::ScheduleGraphExport). The schedules of each
+    /// [`SubApp`] are written to a subdirectory named after the label of the sub app.
+    ///
+    /// Requires the `schedule_graphs` feature.
+    #[cfg(feature = "schedule_graphs")]
+    pub fn write_schedule_graphs(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        if let Some(schedules) = self.world.get_resource::<Schedules>() {
+            for export in schedules.export_graphs(self.world.components()) {
+                let name = file_name(&export.label);
+                fs::write(dir.join(format!("{name}.dot")), export.to_dot())?;
+                let json = serde_json::to_string_pretty(&export)?;
+                fs::write(dir.join(format!("{name}.json")), json)?;
+            }
+        }
+        for (label, sub_app) in &self.sub_apps {
+            let sub_app_dir = dir.join(file_name(&format!("{label:?}")));
+            sub_app.app.write_schedule_graphs(sub_app_dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replaces the characters of a label that may not be valid in a file name.
+#[cfg(feature = "schedule_graphs")]
+fn file_name(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
 }
 
 fn run_once(mut app: App) {
@@ -1038,4 +1112,32 @@ mod tests {
         app.world.run_schedule(OnEnter(AppState::MainMenu));
         assert_eq!(app.world.entities().len(), 2);
     }
+
+    #[test]
+    #[cfg(feature = "schedule_graphs")]
+    fn write_schedule_graphs() {
+        use crate::{self as bevy_app, AppLabel, SubApp, Update};
+        use bevy_ecs::schedule::IntoSystemConfigs;
+
+        #[derive(AppLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        struct ExampleApp;
+
+        let mut app = App::new();
+        app.add_systems(Update, (foo, bar.after(foo)))
+            .add_systems(OnEnter(AppState::MainMenu), foo);
+        let mut sub_app = App::new();
+        sub_app.add_systems(Update, bar);
+        app.insert_sub_app(ExampleApp, SubApp::new(sub_app, |_, _| {}));
+
+        let dir = std::env::temp_dir().join("bevy_app_write_schedule_graphs");
+        app.write_schedule_graphs(&dir).unwrap();
+
+        let update = std::fs::read_to_string(dir.join("Update.dot")).unwrap();
+        assert!(update.contains("label=\"foo\""));
+        assert!(update.contains("system_0 -> system_1;"));
+        assert!(dir.join("Update.json").exists());
+        assert!(dir.join("OnEnter_MainMenu_.dot").exists());
+        assert!(dir.join("ExampleApp").join("Update.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -44,3 +44,10 @@ impl_downcast!(Plugin);
 ///
 /// See `bevy_dynamic_plugin/src/loader.rs#dynamically_load_plugin`.
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// A type representing an unsafe function that returns the [`TypeId`](std::any::TypeId) of
+/// [`App`](crate::App) as seen by a dynamically loaded plugin.
+/// It is used to check that the plugin was built against the same Bevy as the program.
+///
+/// See `bevy_dynamic_plugin/src/reload.rs#load_reloadable_plugin`.
+pub type PluginAbi = unsafe fn() -> std::any::TypeId;
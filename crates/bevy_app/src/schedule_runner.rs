@@ -24,6 +24,16 @@ pub enum RunMode {
     },
     /// Indicates that the [`App`]'s schedule should run only once.
     Once,
+    /// Indicates that the [`App`]'s schedule should run repeatedly as fast as possible, with time
+    /// advancing by exactly `delta` at each update instead of following the wall-clock time.
+    ///
+    /// This makes simulations reproducible, see [`ScheduleRunnerSettings::fixed_step`].
+    FixedStep {
+        /// The [`Duration`] time advances by at each update.
+        delta: Duration,
+        /// The number of updates to run, or [`None`] to run until an [`AppExit`] event is sent.
+        frames: Option<u64>,
+    },
 }
 
 impl Default for RunMode {
@@ -39,6 +49,8 @@ impl Default for RunMode {
 pub struct ScheduleRunnerSettings {
     /// Determines whether the [`Schedule`](bevy_ecs::schedule::Schedule) is run once or repeatedly.
     pub run_mode: RunMode,
+    /// The seed inserted as the [`Seed`] resource, if any.
+    pub seed: Option<u64>,
 }
 
 impl ScheduleRunnerSettings {
@@ -46,6 +58,7 @@ impl ScheduleRunnerSettings {
     pub fn run_once() -> Self {
         ScheduleRunnerSettings {
             run_mode: RunMode::Once,
+            seed: None,
         }
     }
 
@@ -55,10 +68,43 @@ impl ScheduleRunnerSettings {
             run_mode: RunMode::Loop {
                 wait: Some(wait_duration),
             },
+            seed: None,
+        }
+    }
+
+    /// See [`RunMode::FixedStep`].
+    ///
+    /// `Time<Real>` advances by `delta` at each update, unless its `TimeUpdateStrategy` is set to
+    /// a manual strategy, and `Time<Virtual>` follows it, so that `FixedUpdate` runs the same
+    /// number of times at each run of the [`App`]. `delta` should not exceed the maximum delta of
+    /// `Time<Virtual>`, which clamps it. Together with a [`Seed`] for the random number generators,
+    /// this lets headless simulations and tests produce the same results on every run, as long as
+    /// systems whose order matters are ordered.
+    pub fn fixed_step(delta: Duration, frames: Option<u64>) -> Self {
+        ScheduleRunnerSettings {
+            run_mode: RunMode::FixedStep { delta, frames },
+            seed: None,
         }
     }
+
+    /// Sets the seed inserted as the [`Seed`] resource.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
 }
 
+/// The seed random number generators should be initialized with, for the results of an [`App`]
+/// to be reproducible.
+///
+/// It is inserted by the [`ScheduleRunnerPlugin`] when [`ScheduleRunnerSettings::seed`] is set.
+/// Bevy doesn't use it itself: it is a value for the random number generators of the app to read
+/// when they are created, for example with `StdRng::seed_from_u64(seed.0)` from the `rand` crate,
+/// instead of seeding them from the entropy of the system.
+#[derive(Resource, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Seed(pub u64);
+
 /// Configures an [`App`] to run its [`Schedule`](bevy_ecs::schedule::Schedule) according to a given
 /// [`RunMode`].
 ///
@@ -80,12 +126,34 @@ impl Plugin for ScheduleRunnerPlugin {
             .world
             .get_resource_or_insert_with(ScheduleRunnerSettings::default)
             .to_owned();
+        if let Some(seed) = settings.seed {
+            app.insert_resource(Seed(seed));
+        }
         app.set_runner(move |mut app: App| {
             let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
             match settings.run_mode {
                 RunMode::Once => {
                     app.update();
                 }
+                RunMode::FixedStep { frames, .. } => {
+                    let mut frame = 0;
+                    while !matches!(frames, Some(frames) if frame >= frames) {
+                        app.update();
+                        frame += 1;
+
+                        if let Some(app_exit_events) =
+                            app.world.get_resource_mut::<Events<AppExit>>()
+                        {
+                            if app_exit_event_reader
+                                .iter(&app_exit_events)
+                                .last()
+                                .is_some()
+                            {
+                                break;
+                            }
+                        }
+                    }
+                }
                 RunMode::Loop { wait } => {
                     let mut tick = move |app: &mut App,
                                          wait: Option<Duration>|
@@ -168,3 +236,114 @@ impl Plugin for ScheduleRunnerPlugin {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ScheduleRunnerPlugin, ScheduleRunnerSettings, Seed};
+    use crate::{App, AppExit, Startup, Update};
+    use bevy_ecs::prelude::*;
+    use bevy_utils::Duration;
+    use std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    };
+
+    #[derive(Resource)]
+    struct Frames(Arc<AtomicU64>);
+
+    fn count_frames(frames: Res<Frames>) {
+        frames.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn fixed_step_runs_frames() {
+        let frames = Arc::new(AtomicU64::new(0));
+        let mut app = App::new();
+        app.insert_resource(
+            ScheduleRunnerSettings::fixed_step(Duration::from_millis(10), Some(5)).with_seed(42),
+        )
+        .insert_resource(Frames(frames.clone()))
+        .add_plugin(ScheduleRunnerPlugin)
+        .add_systems(Update, count_frames);
+        assert_eq!(app.world.get_resource::<Seed>(), Some(&Seed(42)));
+        app.run();
+        assert_eq!(frames.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn fixed_step_stops_on_exit() {
+        let frames = Arc::new(AtomicU64::new(0));
+        App::new()
+            .insert_resource(ScheduleRunnerSettings::fixed_step(
+                Duration::from_millis(10),
+                None,
+            ))
+            .insert_resource(Frames(frames.clone()))
+            .add_plugin(ScheduleRunnerPlugin)
+            .add_systems(
+                Update,
+                (
+                    count_frames,
+                    |frames: Res<Frames>, mut exit: EventWriter<AppExit>| {
+                        if frames.0.load(Ordering::Relaxed) == 3 {
+                            exit.send(AppExit);
+                        }
+                    },
+                )
+                    .chain(),
+            )
+            .run();
+        assert_eq!(frames.load(Ordering::Relaxed), 3);
+    }
+
+    /// A random walk, whose generator is seeded from the [`Seed`].
+    #[derive(Resource)]
+    struct Walk {
+        state: u64,
+        positions: Arc<Mutex<Vec<i64>>>,
+    }
+
+    fn seed_walk(seed: Res<Seed>, mut walk: ResMut<Walk>) {
+        walk.state = seed.0;
+    }
+
+    fn step_walk(mut walk: ResMut<Walk>) {
+        // xorshift64*, seeds of 0 being avoided.
+        let mut state = walk.state | 1;
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        walk.state = state;
+        let step = (state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 62) as i64 - 2;
+        let mut positions = walk.positions.lock().unwrap();
+        let position = positions.last().copied().unwrap_or(0) + step;
+        positions.push(position);
+    }
+
+    fn run_walk(seed: u64) -> Vec<i64> {
+        let positions = Arc::default();
+        App::new()
+            .insert_resource(
+                ScheduleRunnerSettings::fixed_step(Duration::from_millis(10), Some(20))
+                    .with_seed(seed),
+            )
+            .insert_resource(Walk {
+                state: 0,
+                positions: Arc::clone(&positions),
+            })
+            .add_plugin(ScheduleRunnerPlugin)
+            .add_systems(Startup, seed_walk)
+            .add_systems(Update, step_walk)
+            .run();
+        let positions = positions.lock().unwrap().clone();
+        positions
+    }
+
+    #[test]
+    fn same_seed_same_state() {
+        let positions = run_walk(42);
+        assert_eq!(positions.len(), 20);
+        assert_eq!(run_walk(42), positions);
+        assert_ne!(run_walk(7), positions);
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{AudioSink, AudioSource, Decodable, SpatialAudioSink};
+use crate::{AudioBus, AudioSink, AudioSource, Decodable, SpatialAudioSink};
 use bevy_asset::{Asset, Handle, HandleId};
-use bevy_ecs::system::Resource;
+use bevy_ecs::{component::Component, system::Resource};
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
 use parking_lot::RwLock;
@@ -252,7 +252,9 @@ where
 }
 
 /// Settings to control playback from the start.
-#[derive(Clone, Copy, Debug)]
+///
+/// Also used as a component with [`AudioBundle`](crate::AudioBundle) to play sounds from entities.
+#[derive(Component, Clone, Copy, Debug)]
 pub struct PlaybackSettings {
     /// Play in repeat
     pub repeat: bool,
@@ -260,6 +262,8 @@ pub struct PlaybackSettings {
     pub volume: f32,
     /// Speed to play at.
     pub speed: f32,
+    /// Mixer bus to play on.
+    pub bus: AudioBus,
 }
 
 impl Default for PlaybackSettings {
@@ -274,6 +278,7 @@ impl PlaybackSettings {
         repeat: false,
         volume: 1.0,
         speed: 1.0,
+        bus: AudioBus::MASTER,
     };
 
     /// Will play the associate audio source in a loop.
@@ -281,6 +286,7 @@ impl PlaybackSettings {
         repeat: true,
         volume: 1.0,
         speed: 1.0,
+        bus: AudioBus::MASTER,
     };
 
     /// Helper to set the volume from start of playback.
@@ -294,6 +300,12 @@ impl PlaybackSettings {
         self.speed = speed;
         self
     }
+
+    /// Helper to set the [`AudioBus`] to play on.
+    pub const fn with_bus(mut self, bus: AudioBus) -> Self {
+        self.bus = bus;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -1,4 +1,6 @@
-use crate::{Audio, AudioSource, Decodable, SpatialAudioSink, SpatialSettings};
+use crate::{
+    Audio, AudioMixer, AudioSource, Decodable, PlaybackSettings, SpatialAudioSink, SpatialSettings,
+};
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_utils::tracing::warn;
@@ -52,19 +54,14 @@ where
 
 impl<Source> AudioOutput<Source>
 where
-    Source: Asset + Decodable,
-    f32: rodio::cpal::FromSample<Source::DecoderItem>,
+    Source: Decodable,
 {
-    fn play_source(&self, audio_source: &Source, repeat: bool) -> Option<Sink> {
+    fn play_source(&self, source: impl rodio::Source<Item = f32> + Send + 'static) -> Option<Sink> {
         self.stream_handle
             .as_ref()
             .and_then(|stream_handle| match Sink::try_new(stream_handle) {
                 Ok(sink) => {
-                    if repeat {
-                        sink.append(audio_source.decoder().repeat_infinite());
-                    } else {
-                        sink.append(audio_source.decoder());
-                    }
+                    sink.append(source);
                     Some(sink)
                 }
                 Err(err) => {
@@ -76,8 +73,7 @@ where
 
     fn play_spatial_source(
         &self,
-        audio_source: &Source,
-        repeat: bool,
+        source: impl rodio::Source<Item = f32> + Send + 'static,
         spatial: SpatialSettings,
     ) -> Option<SpatialSink> {
         self.stream_handle.as_ref().and_then(|stream_handle| {
@@ -88,11 +84,7 @@ where
                 spatial.right_ear,
             ) {
                 Ok(sink) => {
-                    if repeat {
-                        sink.append(audio_source.decoder().repeat_infinite());
-                    } else {
-                        sink.append(audio_source.decoder());
-                    }
+                    sink.append(source);
                     Some(sink)
                 }
                 Err(err) => {
@@ -102,6 +94,26 @@ where
             }
         })
     }
+}
+
+impl<Source> AudioOutput<Source>
+where
+    Source: Asset + Decodable,
+    f32: rodio::cpal::FromSample<Source::DecoderItem>,
+{
+    fn routed_source(
+        audio_source: &Source,
+        settings: &PlaybackSettings,
+        mixer: &mut AudioMixer,
+    ) -> impl rodio::Source<Item = f32> + Send {
+        let decoder = audio_source.decoder().convert_samples::<f32>();
+        let source: Box<dyn rodio::Source<Item = f32> + Send> = if settings.repeat {
+            Box::new(decoder.repeat_infinite())
+        } else {
+            Box::new(decoder)
+        };
+        mixer.route(settings.bus, source)
+    }
 
     fn try_play_queued(
         &self,
@@ -109,6 +121,7 @@ where
         audio: &mut Audio<Source>,
         sinks: &mut Assets<AudioSink>,
         spatial_sinks: &mut Assets<SpatialAudioSink>,
+        mixer: &mut AudioMixer,
     ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
@@ -117,9 +130,8 @@ where
             let config = queue.pop_front().unwrap();
             if let Some(audio_source) = audio_sources.get(&config.source_handle) {
                 if let Some(spatial) = config.spatial {
-                    if let Some(sink) =
-                        self.play_spatial_source(audio_source, config.settings.repeat, spatial)
-                    {
+                    let source = Self::routed_source(audio_source, &config.settings, mixer);
+                    if let Some(sink) = self.play_spatial_source(source, spatial) {
                         sink.set_speed(config.settings.speed);
                         sink.set_volume(config.settings.volume);
 
@@ -127,7 +139,9 @@ where
                         let _ = spatial_sinks
                             .set(config.sink_handle, SpatialAudioSink { sink: Some(sink) });
                     }
-                } else if let Some(sink) = self.play_source(audio_source, config.settings.repeat) {
+                } else if let Some(sink) =
+                    self.play_source(Self::routed_source(audio_source, &config.settings, mixer))
+                {
                     sink.set_speed(config.settings.speed);
                     sink.set_volume(config.settings.volume);
 
@@ -150,10 +164,17 @@ pub fn play_queued_audio_system<Source: Asset + Decodable>(
     mut audio: ResMut<Audio<Source>>,
     mut sinks: ResMut<Assets<AudioSink>>,
     mut spatial_sinks: ResMut<Assets<SpatialAudioSink>>,
+    mut mixer: ResMut<AudioMixer>,
 ) where
     f32: rodio::cpal::FromSample<Source::DecoderItem>,
 {
     if let Some(audio_sources) = audio_sources {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut sinks, &mut spatial_sinks);
+        audio_output.try_play_queued(
+            &*audio_sources,
+            &mut *audio,
+            &mut sinks,
+            &mut spatial_sinks,
+            &mut mixer,
+        );
     };
 }
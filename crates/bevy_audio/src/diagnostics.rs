@@ -0,0 +1,141 @@
+use crate::{AudioGenerator, AudioStream};
+use bevy_app::prelude::*;
+use bevy_asset::Assets;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::prelude::*;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Adds latency and underrun diagnostics for [`AudioGenerator`] and [`AudioStream`] sources to
+/// an App.
+///
+/// The latency is the highest among all sources, and the underruns are summed across all
+/// sources since the previous frame.
+#[derive(Default)]
+pub struct AudioDiagnosticsPlugin;
+
+impl Plugin for AudioDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::setup_system)
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl AudioDiagnosticsPlugin {
+    /// Highest latency of a parameter change among all [`AudioGenerator`]s.
+    pub const GENERATOR_LATENCY: DiagnosticId =
+        DiagnosticId::from_u128(89474403699073248921589056651793655282);
+    /// Number of blocks that [`AudioGenerator`]s took too long to render.
+    pub const GENERATOR_UNDERRUNS: DiagnosticId =
+        DiagnosticId::from_u128(82638696874471499471075935263252730027);
+    /// Highest duration of buffered samples among all [`AudioStream`]s.
+    pub const STREAM_LATENCY: DiagnosticId =
+        DiagnosticId::from_u128(239715861230170826205878086020322408135);
+    /// Number of times an [`AudioStream`] ran out of samples.
+    pub const STREAM_UNDERRUNS: DiagnosticId =
+        DiagnosticId::from_u128(214407818156935754903142980756189893686);
+
+    /// Registers the audio diagnostics.
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(
+            Diagnostic::new(Self::GENERATOR_LATENCY, "audio_generator_latency", 20)
+                .with_suffix("ms"),
+        );
+        diagnostics.add(
+            Diagnostic::new(Self::GENERATOR_UNDERRUNS, "audio_generator_underruns", 20)
+                .with_smoothing_factor(0.0),
+        );
+        diagnostics.add(
+            Diagnostic::new(Self::STREAM_LATENCY, "audio_stream_latency", 20).with_suffix("ms"),
+        );
+        diagnostics.add(
+            Diagnostic::new(Self::STREAM_UNDERRUNS, "audio_stream_underruns", 20)
+                .with_smoothing_factor(0.0),
+        );
+    }
+
+    /// Measures the audio diagnostics from every generator and stream.
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        generators: Option<Res<Assets<AudioGenerator>>>,
+        streams: Option<Res<Assets<AudioStream>>>,
+    ) {
+        if let Some(generators) = generators {
+            if let Some((latency, underruns)) =
+                Self::collect(generators.iter().map(|(_, generator)| generator.stats()))
+            {
+                diagnostics.add_measurement(Self::GENERATOR_LATENCY, || latency);
+                diagnostics.add_measurement(Self::GENERATOR_UNDERRUNS, || underruns);
+            }
+        }
+        if let Some(streams) = streams {
+            if let Some((latency, underruns)) =
+                Self::collect(streams.iter().map(|(_, stream)| stream.stats()))
+            {
+                diagnostics.add_measurement(Self::STREAM_LATENCY, || latency);
+                diagnostics.add_measurement(Self::STREAM_UNDERRUNS, || underruns);
+            }
+        }
+    }
+
+    /// Returns the highest latency in milliseconds and the sum of new underruns.
+    fn collect<'a>(stats: impl Iterator<Item = &'a AudioSourceStats>) -> Option<(f64, f64)> {
+        stats.fold(None, |acc, stats| {
+            let latency = stats.latency().as_secs_f64() * 1000.0;
+            let underruns = stats.take_underruns() as f64;
+            Some(match acc {
+                Some((max_latency, total_underruns)) => {
+                    (f64::max(max_latency, latency), total_underruns + underruns)
+                }
+                None => (latency, underruns),
+            })
+        })
+    }
+}
+
+/// Latency and underrun statistics shared between an audio source and its decoders.
+///
+/// Cloning this returns a new handle to the same statistics.
+#[derive(Clone, Default, Debug)]
+pub struct AudioSourceStats {
+    inner: Arc<StatsInner>,
+}
+
+#[derive(Default, Debug)]
+struct StatsInner {
+    latency_nanos: AtomicU64,
+    underruns: AtomicU64,
+    total_underruns: AtomicU64,
+}
+
+impl AudioSourceStats {
+    /// Last measured latency.
+    pub fn latency(&self) -> Duration {
+        Duration::from_nanos(self.inner.latency_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Number of underruns since the source was created.
+    pub fn total_underruns(&self) -> u64 {
+        self.inner.total_underruns.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of underruns since the last call, and resets it.
+    pub fn take_underruns(&self) -> u64 {
+        self.inner.underruns.swap(0, Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_latency(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.inner.latency_nanos.store(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_underrun(&self) {
+        self.inner.underruns.fetch_add(1, Ordering::Relaxed);
+        self.inner.total_underruns.fetch_add(1, Ordering::Relaxed);
+    }
+}
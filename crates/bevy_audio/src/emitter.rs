@@ -0,0 +1,361 @@
+use crate::{
+    Audio, AudioSink, AudioSinkPlayback, AudioSource, Decodable, PlaybackSettings, SpatialAudioSink,
+};
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    query::{With, Without},
+    removal_detection::RemovedComponents,
+    system::{Commands, Query, Res, ResMut, Resource},
+};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+use bevy_utils::HashMap;
+
+/// Bundle for an entity playing a sound.
+///
+/// Playback starts as soon as the source is loaded. The sound is stopped when the entity is
+/// despawned or its source handle is removed.
+///
+/// Once playing, a [`Handle<AudioSink>`] is inserted on the entity that can be used to control
+/// playback through the [`AudioSink`] asset.
+#[derive(Bundle)]
+pub struct AudioBundle<Source = AudioSource>
+where
+    Source: Asset + Decodable,
+{
+    /// Source of the sound.
+    pub source: Handle<Source>,
+    /// Settings to start the playback with.
+    pub settings: PlaybackSettings,
+}
+
+impl<Source> Default for AudioBundle<Source>
+where
+    Source: Asset + Decodable,
+{
+    fn default() -> Self {
+        Self {
+            source: Default::default(),
+            settings: Default::default(),
+        }
+    }
+}
+
+/// Bundle for an entity playing a sound positioned in space.
+///
+/// The emitter follows the [`GlobalTransform`] of the entity, and is heard from the entity
+/// with an [`AudioListener`].
+///
+/// Once playing, a [`Handle<SpatialAudioSink>`] is inserted on the entity that can be used to
+/// control playback through the [`SpatialAudioSink`] asset.
+#[derive(Bundle)]
+pub struct SpatialAudioBundle<Source = AudioSource>
+where
+    Source: Asset + Decodable,
+{
+    /// Source of the sound.
+    pub source: Handle<Source>,
+    /// Settings to start the playback with.
+    pub settings: PlaybackSettings,
+    /// Marks the sound as spatial.
+    pub spatial: SpatialAudio,
+}
+
+impl<Source> Default for SpatialAudioBundle<Source>
+where
+    Source: Asset + Decodable,
+{
+    fn default() -> Self {
+        Self {
+            source: Default::default(),
+            settings: Default::default(),
+            spatial: SpatialAudio,
+        }
+    }
+}
+
+/// Marker for entities whose sound is emitted from their [`GlobalTransform`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct SpatialAudio;
+
+/// Component for the entity spatial sounds are heard from, usually the camera or the head.
+///
+/// Only one listener is supported. If several entities have this component, one is picked
+/// arbitrarily.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AudioListener {
+    /// Distance between the left and the right ear.
+    pub gap: f32,
+}
+
+impl Default for AudioListener {
+    fn default() -> Self {
+        Self { gap: 1.0 }
+    }
+}
+
+impl AudioListener {
+    /// Creates a listener with its ears separated by `gap`.
+    pub const fn new(gap: f32) -> Self {
+        Self { gap }
+    }
+}
+
+/// Sink of an entity playing a sound.
+#[derive(Debug)]
+enum EmitterSink {
+    Global(Handle<AudioSink>),
+    Spatial(Handle<SpatialAudioSink>),
+}
+
+impl EmitterSink {
+    /// Returns `true` if the sink was created and played its whole sound.
+    ///
+    /// Sinks are created by the audio output once the source is loaded, until then the sound
+    /// hasn't finished.
+    fn finished(
+        &self,
+        sinks: &Assets<AudioSink>,
+        spatial_sinks: &Assets<SpatialAudioSink>,
+    ) -> bool {
+        match self {
+            EmitterSink::Global(sink) => matches!(sinks.get(sink), Some(sink) if sink.empty()),
+            EmitterSink::Spatial(sink) => {
+                matches!(spatial_sinks.get(sink), Some(sink) if sink.empty())
+            }
+        }
+    }
+
+    fn stop(&self, sinks: &Assets<AudioSink>, spatial_sinks: &Assets<SpatialAudioSink>) {
+        match self {
+            EmitterSink::Global(sink) => {
+                if let Some(sink) = sinks.get(sink) {
+                    sink.stop();
+                }
+            }
+            EmitterSink::Spatial(sink) => {
+                if let Some(sink) = spatial_sinks.get(sink) {
+                    sink.stop();
+                }
+            }
+        }
+    }
+}
+
+/// Sinks of every entity playing a sound, used to stop them once the entity is removed.
+#[derive(Resource, Default, Debug)]
+pub struct AudioEmitterSinks {
+    sinks: HashMap<Entity, EmitterSink>,
+}
+
+/// Starts playback for entities with a source handle and [`PlaybackSettings`] that aren't
+/// playing yet.
+pub fn play_audio_emitters_system<Source: Asset + Decodable>(
+    mut commands: Commands,
+    audio: Res<Audio<Source>>,
+    listener: Query<(&AudioListener, &GlobalTransform)>,
+    emitters: Query<
+        (
+            Entity,
+            &Handle<Source>,
+            &PlaybackSettings,
+            Option<&GlobalTransform>,
+            Option<&SpatialAudio>,
+        ),
+        (
+            Without<Handle<AudioSink>>,
+            Without<Handle<SpatialAudioSink>>,
+        ),
+    >,
+    mut emitter_sinks: ResMut<AudioEmitterSinks>,
+    sinks: Res<Assets<AudioSink>>,
+    spatial_sinks: Res<Assets<SpatialAudioSink>>,
+) {
+    let (gap, listener) = listener
+        .iter()
+        .next()
+        .map(|(listener, transform)| (listener.gap, transform.compute_transform()))
+        .unwrap_or((AudioListener::default().gap, Transform::IDENTITY));
+
+    for (entity, source, settings, transform, spatial) in &emitters {
+        if emitter_sinks.sinks.contains_key(&entity) {
+            continue;
+        }
+        if spatial.is_some() {
+            let emitter = transform
+                .map(GlobalTransform::translation)
+                .unwrap_or_default();
+            let weak =
+                audio.play_spatial_with_settings(source.clone(), *settings, listener, gap, emitter);
+            let sink = spatial_sinks.get_handle(weak);
+            commands.entity(entity).insert(sink.clone());
+            emitter_sinks
+                .sinks
+                .insert(entity, EmitterSink::Spatial(sink));
+        } else {
+            let sink = sinks.get_handle(audio.play_with_settings(source.clone(), *settings));
+            commands.entity(entity).insert(sink.clone());
+            emitter_sinks
+                .sinks
+                .insert(entity, EmitterSink::Global(sink));
+        }
+    }
+}
+
+/// Moves the emitter of every playing spatial sound to its entity's [`GlobalTransform`], and
+/// the ears to the [`AudioListener`].
+pub fn update_spatial_audio_system(
+    listener: Query<(&AudioListener, &GlobalTransform)>,
+    emitters: Query<(&GlobalTransform, &Handle<SpatialAudioSink>), With<SpatialAudio>>,
+    spatial_sinks: Res<Assets<SpatialAudioSink>>,
+) {
+    let listener = listener
+        .iter()
+        .next()
+        .map(|(listener, transform)| (listener.gap, transform.compute_transform()));
+
+    for (transform, sink) in &emitters {
+        let Some(sink) = spatial_sinks.get(sink) else {
+            continue;
+        };
+        sink.set_emitter_position(transform.translation());
+        if let Some((gap, listener)) = listener {
+            sink.set_listener_position(listener, gap);
+        }
+    }
+}
+
+/// Stops the sounds of entities that were despawned or had their source handle removed.
+pub fn stop_removed_audio_emitters_system<Source: Asset + Decodable>(
+    mut removed: RemovedComponents<Handle<Source>>,
+    mut emitter_sinks: ResMut<AudioEmitterSinks>,
+    sinks: Res<Assets<AudioSink>>,
+    spatial_sinks: Res<Assets<SpatialAudioSink>>,
+) {
+    for entity in removed.iter() {
+        if let Some(sink) = emitter_sinks.sinks.remove(&entity) {
+            sink.stop(&sinks, &spatial_sinks);
+        }
+    }
+}
+
+/// Forgets the sinks of emitters whose sound finished, and stops the sounds of emitters
+/// despawned without their removal being detected, like emitters of unregistered sources.
+pub fn remove_finished_audio_emitters_system(
+    mut emitter_sinks: ResMut<AudioEmitterSinks>,
+    entities: Query<()>,
+    sinks: Res<Assets<AudioSink>>,
+    spatial_sinks: Res<Assets<SpatialAudioSink>>,
+) {
+    emitter_sinks.sinks.retain(|&entity, sink| {
+        if !entities.contains(entity) {
+            sink.stop(&sinks, &spatial_sinks);
+            return false;
+        }
+        !sink.finished(&sinks, &spatial_sinks)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AudioSource;
+    use bevy_app::{App, Update};
+    use bevy_asset::{AddAsset, AssetPlugin, HandleId};
+    use bevy_math::Vec3;
+    use rodio::{source::SineWave, Sink};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_asset::<AudioSource>()
+            .add_asset::<AudioSink>()
+            .add_asset::<SpatialAudioSink>()
+            .init_resource::<Audio<AudioSource>>()
+            .init_resource::<AudioEmitterSinks>();
+        app
+    }
+
+    #[test]
+    fn spatial_emitter_follows_listener_and_emitter() {
+        let mut app = app();
+        app.add_systems(Update, play_audio_emitters_system::<AudioSource>);
+        app.world.spawn((
+            AudioListener::new(2.0),
+            GlobalTransform::from_translation(Vec3::new(10.0, 0.0, 0.0)),
+        ));
+        let emitter = app
+            .world
+            .spawn((
+                SpatialAudioBundle {
+                    source: Handle::<AudioSource>::weak(HandleId::random::<AudioSource>()),
+                    ..Default::default()
+                },
+                GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 5.0)),
+            ))
+            .id();
+        app.update();
+
+        let queue = app.world.resource::<Audio<AudioSource>>().queue.read();
+        let spatial = queue[0].spatial.as_ref().unwrap();
+        assert_eq!(spatial.left_ear, [9.0, 0.0, 0.0]);
+        assert_eq!(spatial.right_ear, [11.0, 0.0, 0.0]);
+        assert_eq!(spatial.emitter, [0.0, 0.0, 5.0]);
+        assert_eq!(
+            app.world
+                .get::<Handle<SpatialAudioSink>>(emitter)
+                .unwrap()
+                .id(),
+            queue[0].sink_handle
+        );
+        assert!(app
+            .world
+            .resource::<AudioEmitterSinks>()
+            .sinks
+            .contains_key(&emitter));
+    }
+
+    #[test]
+    fn finished_and_despawned_emitters_are_removed() {
+        let mut app = app();
+        app.add_systems(Update, remove_finished_audio_emitters_system);
+
+        let (playing, _output) = Sink::new_idle();
+        playing.append(SineWave::new(440.0));
+        let (finished, _finished_output) = Sink::new_idle();
+        let mut sinks = app.world.resource_mut::<Assets<AudioSink>>();
+        let playing = sinks.add(AudioSink {
+            sink: Some(playing),
+        });
+        let finished = sinks.add(AudioSink {
+            sink: Some(finished),
+        });
+        let queued = Handle::<AudioSink>::weak(HandleId::random::<AudioSink>());
+
+        let mut spawn = |sink: Handle<AudioSink>| {
+            let entity = app.world.spawn_empty().id();
+            app.world
+                .resource_mut::<AudioEmitterSinks>()
+                .sinks
+                .insert(entity, EmitterSink::Global(sink));
+            entity
+        };
+        let playing = spawn(playing);
+        let finished = spawn(finished);
+        let queued = spawn(queued);
+        app.update();
+
+        let emitter_sinks = &app.world.resource::<AudioEmitterSinks>().sinks;
+        assert!(emitter_sinks.contains_key(&playing));
+        assert!(!emitter_sinks.contains_key(&finished));
+        assert!(emitter_sinks.contains_key(&queued));
+
+        app.world.despawn(playing);
+        app.update();
+        let emitter_sinks = &app.world.resource::<AudioEmitterSinks>().sinks;
+        assert!(!emitter_sinks.contains_key(&playing));
+        assert!(emitter_sinks.contains_key(&queued));
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{AudioSourceStats, Decodable};
+use bevy_reflect::TypeUuid;
+use bevy_utils::{HashMap, Instant};
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Named values shared between the ECS and the callback of an [`AudioGenerator`].
+///
+/// Cloning this returns a new handle to the same values.
+#[derive(Clone, Default)]
+pub struct GeneratorParameters {
+    inner: Arc<ParametersInner>,
+}
+
+#[derive(Default)]
+struct ParametersInner {
+    values: RwLock<HashMap<Cow<'static, str>, f32>>,
+    updated: RwLock<Option<Instant>>,
+    version: AtomicU64,
+}
+
+impl GeneratorParameters {
+    /// Sets the value of a parameter.
+    ///
+    /// The new value is seen by the callback the next time it renders a block.
+    pub fn set(&self, name: impl Into<Cow<'static, str>>, value: f32) {
+        self.inner.values.write().insert(name.into(), value);
+        *self.inner.updated.write() = Some(Instant::now());
+        self.inner.version.fetch_add(1, Ordering::Release);
+    }
+
+    /// Gets the value of a parameter.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.inner.values.read().get(name).copied()
+    }
+
+    /// Gets the value of a parameter, or `default` if it hasn't been set.
+    pub fn get_or(&self, name: &str, default: f32) -> f32 {
+        self.get(name).unwrap_or(default)
+    }
+
+    fn version(&self) -> u64 {
+        self.inner.version.load(Ordering::Acquire)
+    }
+
+    fn updated(&self) -> Option<Instant> {
+        *self.inner.updated.read()
+    }
+}
+
+impl fmt::Debug for GeneratorParameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GeneratorParameters")
+            .field("values", &*self.inner.values.read())
+            .finish()
+    }
+}
+
+/// Callback filling a block of interleaved samples. See [`AudioGenerator::new`].
+pub type GeneratorCallback = Box<dyn FnMut(&GeneratorParameters, &mut [f32]) + Send>;
+
+/// A source of audio computed by a callback on the audio thread.
+///
+/// The callback is called every time a new block of samples is needed, with the
+/// [`GeneratorParameters`] of the generator, which can be changed from systems to drive it.
+/// Register it with [`AddAudioSource::add_audio_source`](crate::AddAudioSource) before playing it.
+///
+/// ```
+/// # use bevy_audio::AudioGenerator;
+/// let generator = AudioGenerator::new(44_100, 1, || {
+///     let mut phase = 0.0f32;
+///     move |parameters, samples| {
+///         let step = parameters.get_or("frequency", 440.0) / 44_100.0;
+///         for sample in samples {
+///             *sample = (phase * std::f32::consts::TAU).sin();
+///             phase = (phase + step) % 1.0;
+///         }
+///     }
+/// });
+/// generator.parameters().set("frequency", 220.0);
+/// ```
+#[derive(TypeUuid, Clone)]
+#[uuid = "4f1a0d8c-5b2e-4b6f-9d77-2f0f2bb5e1a3"]
+pub struct AudioGenerator {
+    sample_rate: u32,
+    channels: u16,
+    block_frames: usize,
+    factory: Arc<dyn Fn() -> GeneratorCallback + Send + Sync>,
+    parameters: GeneratorParameters,
+    stats: AudioSourceStats,
+}
+
+impl AudioGenerator {
+    /// Number of frames rendered per callback by default.
+    pub const DEFAULT_BLOCK_FRAMES: usize = 512;
+
+    /// Creates a generator producing `channels` interleaved channels at `sample_rate`.
+    ///
+    /// `factory` is called each time the generator starts playing to create the callback for
+    /// that playback, so that each playback has its own state.
+    pub fn new<F, C>(sample_rate: u32, channels: u16, factory: F) -> Self
+    where
+        F: Fn() -> C + Send + Sync + 'static,
+        C: FnMut(&GeneratorParameters, &mut [f32]) + Send + 'static,
+    {
+        Self {
+            sample_rate,
+            channels,
+            block_frames: Self::DEFAULT_BLOCK_FRAMES,
+            factory: Arc::new(move || Box::new(factory()) as GeneratorCallback),
+            parameters: GeneratorParameters::default(),
+            stats: AudioSourceStats::default(),
+        }
+    }
+
+    /// Helper to set the number of frames rendered per callback.
+    ///
+    /// Smaller blocks lower the latency of parameter changes, at the cost of more calls.
+    pub fn with_block_frames(mut self, block_frames: usize) -> Self {
+        self.block_frames = block_frames.max(1);
+        self
+    }
+
+    /// The parameters fed to the callback.
+    pub fn parameters(&self) -> &GeneratorParameters {
+        &self.parameters
+    }
+
+    /// Latency and underrun statistics of this generator.
+    ///
+    /// The latency is the time between a parameter change and the callback seeing it. An
+    /// underrun is counted each time the callback takes longer to render a block than the block
+    /// takes to play.
+    pub fn stats(&self) -> &AudioSourceStats {
+        &self.stats
+    }
+}
+
+impl fmt::Debug for AudioGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioGenerator")
+            .field("sample_rate", &self.sample_rate)
+            .field("channels", &self.channels)
+            .field("block_frames", &self.block_frames)
+            .field("parameters", &self.parameters)
+            .finish()
+    }
+}
+
+/// Decoder of an [`AudioGenerator`], calling its callback on the audio thread.
+pub struct GeneratorDecoder {
+    sample_rate: u32,
+    channels: u16,
+    callback: GeneratorCallback,
+    parameters: GeneratorParameters,
+    stats: AudioSourceStats,
+    block: Vec<f32>,
+    position: usize,
+    seen_version: u64,
+}
+
+impl GeneratorDecoder {
+    fn render_block(&mut self) {
+        let version = self.parameters.version();
+        if version != self.seen_version {
+            self.seen_version = version;
+            if let Some(updated) = self.parameters.updated() {
+                self.stats.set_latency(updated.elapsed());
+            }
+        }
+
+        let start = Instant::now();
+        (self.callback)(&self.parameters, &mut self.block);
+        let frames = self.block.len() / self.channels.max(1) as usize;
+        let budget = Duration::from_secs_f64(frames as f64 / self.sample_rate.max(1) as f64);
+        if start.elapsed() > budget {
+            self.stats.add_underrun();
+        }
+        self.position = 0;
+    }
+}
+
+impl Iterator for GeneratorDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position >= self.block.len() {
+            self.render_block();
+        }
+        let sample = self.block[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl rodio::Source for GeneratorDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Decodable for AudioGenerator {
+    type DecoderItem = f32;
+    type Decoder = GeneratorDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        let len = self.block_frames * self.channels.max(1) as usize;
+        GeneratorDecoder {
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            callback: (self.factory)(),
+            parameters: self.parameters.clone(),
+            stats: self.stats.clone(),
+            block: vec![0.0; len],
+            position: len,
+            seen_version: self.parameters.version(),
+        }
+    }
+}
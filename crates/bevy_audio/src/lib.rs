@@ -26,28 +26,41 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod diagnostics;
+mod emitter;
+mod generator;
+mod mixer;
 mod sinks;
+mod stream;
 
 #[allow(missing_docs)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        Audio, AudioOutput, AudioSink, AudioSinkPlayback, AudioSource, Decodable, PlaybackSettings,
-        SpatialAudioSink,
+        Audio, AudioBundle, AudioBus, AudioListener, AudioMixer, AudioOutput, AudioSink,
+        AudioSinkPlayback, AudioSource, Decodable, PlaybackSettings, SpatialAudio,
+        SpatialAudioBundle, SpatialAudioSink,
     };
 }
 
 pub use audio::*;
 pub use audio_output::*;
 pub use audio_source::*;
+pub use diagnostics::*;
+pub use emitter::*;
+pub use generator::*;
+pub use mixer::*;
 
 pub use rodio::cpal::Sample as CpalSample;
 pub use rodio::source::Source;
 pub use rodio::Sample;
 pub use sinks::*;
+pub use stream::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Asset};
+use bevy_ecs::prelude::*;
+use bevy_transform::TransformSystem;
 
 /// Adds support for audio playback to a Bevy Application
 ///
@@ -62,7 +75,26 @@ impl Plugin for AudioPlugin {
             .add_asset::<AudioSink>()
             .add_asset::<SpatialAudioSink>()
             .init_resource::<Audio<AudioSource>>()
-            .add_systems(PostUpdate, play_queued_audio_system::<AudioSource>);
+            .init_resource::<AudioMixer>()
+            .init_resource::<AudioEmitterSinks>()
+            .add_systems(
+                PostUpdate,
+                (
+                    play_audio_emitters_system::<AudioSource>,
+                    play_queued_audio_system::<AudioSource>,
+                    update_audio_mixer_system,
+                )
+                    .chain()
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_spatial_audio_system.after(TransformSystem::TransformPropagate),
+                    stop_removed_audio_emitters_system::<AudioSource>,
+                    remove_finished_audio_emitters_system,
+                ),
+            );
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         app.init_asset_loader::<AudioLoader>();
@@ -78,6 +110,17 @@ impl AddAudioSource for App {
         self.add_asset::<T>()
             .init_resource::<Audio<T>>()
             .init_resource::<AudioOutput<T>>()
-            .add_systems(PostUpdate, play_queued_audio_system::<T>)
+            .init_resource::<AudioMixer>()
+            .init_resource::<AudioEmitterSinks>()
+            .add_systems(
+                PostUpdate,
+                (
+                    play_audio_emitters_system::<T>,
+                    play_queued_audio_system::<T>.before(update_audio_mixer_system),
+                )
+                    .chain()
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_systems(PostUpdate, stop_removed_audio_emitters_system::<T>)
     }
 }
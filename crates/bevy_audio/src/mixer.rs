@@ -0,0 +1,373 @@
+use bevy_ecs::system::{Res, Resource};
+use bevy_utils::HashMap;
+use rodio::Source;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Name of a mixer bus that playback can be routed to.
+///
+/// A few buses are provided by default, but any name can be used. Buses that aren't configured
+/// in the [`AudioMixer`] play with their default [`AudioBusSettings`].
+///
+/// ```
+/// # use bevy_audio::{AudioBus, PlaybackSettings};
+/// const AMBIENCE: AudioBus = AudioBus::new("ambience");
+///
+/// let settings = PlaybackSettings::LOOP.with_bus(AMBIENCE);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AudioBus(&'static str);
+
+impl AudioBus {
+    /// The bus every other bus is mixed into. Playback is routed here if no other bus is chosen.
+    pub const MASTER: AudioBus = AudioBus("master");
+    /// Bus for background music.
+    pub const MUSIC: AudioBus = AudioBus("music");
+    /// Bus for sound effects.
+    pub const SFX: AudioBus = AudioBus("sfx");
+    /// Bus for dialogue and voice chat.
+    pub const VOICE: AudioBus = AudioBus("voice");
+    /// Bus for user interface sounds.
+    pub const UI: AudioBus = AudioBus("ui");
+
+    /// Creates a bus with the given name.
+    pub const fn new(name: &'static str) -> Self {
+        Self(name)
+    }
+
+    /// The name of this bus.
+    pub const fn name(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self::MASTER
+    }
+}
+
+/// An effect applied to every sound played on an [`AudioBus`].
+///
+/// Effects are inserted when playback starts, so changing the effect chain of a bus only affects
+/// sounds started after the change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AudioEffect {
+    /// Attenuates frequencies above `cutoff` (in Hz).
+    LowPass {
+        /// The cutoff frequency in Hz.
+        cutoff: u32,
+    },
+    /// Mixes a delayed and attenuated copy of the sound back into itself.
+    Reverb {
+        /// Delay between the sound and its echo.
+        delay: Duration,
+        /// Volume of the echo relative to the sound.
+        amplitude: f32,
+    },
+}
+
+/// Attenuation applied to a bus while another bus is playing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ducking {
+    /// The bus whose playback triggers the ducking.
+    pub trigger: AudioBus,
+    /// Volume multiplier applied while `trigger` has sounds playing.
+    pub volume: f32,
+}
+
+/// Settings of a single [`AudioBus`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AudioBusSettings {
+    /// Volume of the bus.
+    ///
+    /// The value `1.0` is the "normal" volume. It multiplies the volume of every sound on the bus.
+    pub volume: f32,
+    /// Silences the bus without changing its volume.
+    pub muted: bool,
+    /// Pauses every sound on the bus.
+    pub paused: bool,
+    /// Effects applied, in order, to sounds started on this bus.
+    pub effects: Vec<AudioEffect>,
+    /// Optional attenuation while another bus is playing.
+    pub ducking: Option<Ducking>,
+}
+
+impl Default for AudioBusSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+            paused: false,
+            effects: Vec::new(),
+            ducking: None,
+        }
+    }
+}
+
+impl AudioBusSettings {
+    /// Helper to set the volume of the bus.
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Helper to add an effect to the bus.
+    pub fn with_effect(mut self, effect: AudioEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Helper to duck the bus to `volume` while `trigger` is playing.
+    pub fn with_ducking(mut self, trigger: AudioBus, volume: f32) -> Self {
+        self.ducking = Some(Ducking { trigger, volume });
+        self
+    }
+}
+
+/// Use this [`Resource`] to control the mixer buses that sounds are routed to.
+///
+/// Changes are picked up by playing sounds every frame, so this can be driven directly from a
+/// settings menu.
+///
+/// ```
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_audio::{AudioBus, AudioMixer};
+/// fn mute_music(mut mixer: ResMut<AudioMixer>) {
+///     mixer.bus_mut(AudioBus::MUSIC).muted = true;
+///     mixer.bus_mut(AudioBus::MUSIC).ducking = None;
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct AudioMixer {
+    buses: HashMap<AudioBus, AudioBusSettings>,
+    controls: HashMap<AudioBus, Arc<BusControl>>,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        let mut mixer = Self {
+            buses: HashMap::default(),
+            controls: HashMap::default(),
+        };
+        for bus in [
+            AudioBus::MASTER,
+            AudioBus::MUSIC,
+            AudioBus::SFX,
+            AudioBus::VOICE,
+            AudioBus::UI,
+        ] {
+            mixer.buses.insert(bus, AudioBusSettings::default());
+        }
+        mixer
+    }
+}
+
+impl AudioMixer {
+    /// Gets the settings of a bus, if it has been configured.
+    pub fn bus(&self, bus: AudioBus) -> Option<&AudioBusSettings> {
+        self.buses.get(&bus)
+    }
+
+    /// Gets the settings of a bus, configuring it with default settings if needed.
+    pub fn bus_mut(&mut self, bus: AudioBus) -> &mut AudioBusSettings {
+        self.buses.entry(bus).or_default()
+    }
+
+    /// Replaces the settings of a bus.
+    pub fn set_bus(&mut self, bus: AudioBus, settings: AudioBusSettings) {
+        self.buses.insert(bus, settings);
+    }
+
+    /// Iterates over every configured bus and its settings.
+    pub fn buses(&self) -> impl Iterator<Item = (AudioBus, &AudioBusSettings)> {
+        self.buses.iter().map(|(bus, settings)| (*bus, settings))
+    }
+
+    /// Returns `true` if any sound routed to `bus` hasn't finished yet.
+    pub fn is_playing(&self, bus: AudioBus) -> bool {
+        self.controls
+            .get(&bus)
+            .map(|control| control.active.load(Ordering::Relaxed))
+            .unwrap_or(0)
+            > 0
+    }
+
+    /// Pauses every bus.
+    pub fn pause_all(&mut self) {
+        self.bus_mut(AudioBus::MASTER).paused = true;
+    }
+
+    /// Resumes the master bus. Buses paused individually stay paused.
+    pub fn resume_all(&mut self) {
+        self.bus_mut(AudioBus::MASTER).paused = false;
+    }
+
+    /// Wraps `source` so that it follows the live state of `bus`, applying the effects of the bus.
+    pub(crate) fn route(
+        &mut self,
+        bus: AudioBus,
+        mut source: Box<dyn Source<Item = f32> + Send>,
+    ) -> impl Source<Item = f32> + Send {
+        if let Some(settings) = self.buses.get(&bus) {
+            for effect in &settings.effects {
+                source = match *effect {
+                    AudioEffect::LowPass { cutoff } => Box::new(source.low_pass(cutoff)),
+                    AudioEffect::Reverb { delay, amplitude } => {
+                        Box::new(source.buffered().reverb(delay, amplitude))
+                    }
+                };
+            }
+        }
+
+        let control = self.controls.entry(bus).or_default().clone();
+        self.update_control(bus);
+        let guard = ActiveGuard::new(control);
+        source.pausable(false).amplify(1.0).periodic_access(
+            Duration::from_millis(5),
+            move |source| {
+                let control = &guard.0;
+                source.set_factor(f32::from_bits(control.gain.load(Ordering::Relaxed)));
+                source
+                    .inner_mut()
+                    .set_paused(control.paused.load(Ordering::Relaxed));
+            },
+        )
+    }
+
+    /// Computes the effective gain and pause state of `bus` and publishes it to its sounds.
+    fn update_control(&self, bus: AudioBus) {
+        let Some(control) = self.controls.get(&bus) else {
+            return;
+        };
+        let master = self.buses.get(&AudioBus::MASTER);
+        let settings = self.buses.get(&bus);
+
+        let mut gain = 1.0;
+        let mut paused = false;
+        for settings in [master, settings].into_iter().flatten() {
+            gain *= if settings.muted { 0.0 } else { settings.volume };
+            paused |= settings.paused;
+            if let Some(ducking) = settings.ducking {
+                if self.is_playing(ducking.trigger) {
+                    gain *= ducking.volume;
+                }
+            }
+            if bus == AudioBus::MASTER {
+                break;
+            }
+        }
+
+        control.gain.store(gain.to_bits(), Ordering::Relaxed);
+        control.paused.store(paused, Ordering::Relaxed);
+    }
+}
+
+/// Publishes the state of the [`AudioMixer`] to every sound routed through it.
+pub fn update_audio_mixer_system(mixer: Res<AudioMixer>) {
+    for bus in mixer.controls.keys() {
+        mixer.update_control(*bus);
+    }
+}
+
+/// State of a bus shared with the audio thread.
+#[derive(Debug)]
+pub(crate) struct BusControl {
+    gain: AtomicU32,
+    paused: AtomicBool,
+    active: AtomicUsize,
+}
+
+impl Default for BusControl {
+    fn default() -> Self {
+        Self {
+            gain: AtomicU32::new(1.0f32.to_bits()),
+            paused: AtomicBool::new(false),
+            active: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// Counts a sound as playing on its bus for as long as it is alive.
+struct ActiveGuard(Arc<BusControl>);
+
+impl ActiveGuard {
+    fn new(control: Arc<BusControl>) -> Self {
+        control.active.fetch_add(1, Ordering::Relaxed);
+        Self(control)
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::source::SineWave;
+
+    fn sine() -> Box<dyn Source<Item = f32> + Send> {
+        Box::new(SineWave::new(440.0))
+    }
+
+    /// Returns the gain and pause state published to the sounds of `bus`.
+    fn control(mixer: &AudioMixer, bus: AudioBus) -> (f32, bool) {
+        mixer.update_control(bus);
+        let control = &mixer.controls[&bus];
+        (
+            f32::from_bits(control.gain.load(Ordering::Relaxed)),
+            control.paused.load(Ordering::Relaxed),
+        )
+    }
+
+    #[test]
+    fn bus_volume_and_mute() {
+        let mut mixer = AudioMixer::default();
+        let _music = mixer.route(AudioBus::MUSIC, sine());
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (1.0, false));
+
+        mixer.bus_mut(AudioBus::MASTER).volume = 0.5;
+        mixer.bus_mut(AudioBus::MUSIC).volume = 0.5;
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.25, false));
+
+        mixer.bus_mut(AudioBus::MUSIC).muted = true;
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.0, false));
+        mixer.bus_mut(AudioBus::MUSIC).muted = false;
+
+        mixer.pause_all();
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.25, true));
+        mixer.resume_all();
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.25, false));
+    }
+
+    #[test]
+    fn bus_ducking() {
+        let mut mixer = AudioMixer::default();
+        mixer.set_bus(
+            AudioBus::MUSIC,
+            AudioBusSettings::default()
+                .with_volume(0.8)
+                .with_ducking(AudioBus::VOICE, 0.5),
+        );
+        let _music = mixer.route(AudioBus::MUSIC, sine());
+        assert!(mixer.is_playing(AudioBus::MUSIC));
+        assert!(!mixer.is_playing(AudioBus::VOICE));
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.8, false));
+
+        let voice = mixer.route(AudioBus::VOICE, sine());
+        assert!(mixer.is_playing(AudioBus::VOICE));
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.4, false));
+
+        drop(voice);
+        assert!(!mixer.is_playing(AudioBus::VOICE));
+        assert_eq!(control(&mixer, AudioBus::MUSIC), (0.8, false));
+    }
+}
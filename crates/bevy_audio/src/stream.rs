@@ -0,0 +1,264 @@
+use crate::{AudioSourceStats, Decodable};
+use bevy_reflect::TypeUuid;
+use parking_lot::Mutex;
+use std::{collections::VecDeque, fmt, sync::Arc, time::Duration};
+
+/// A source of audio fed at runtime through a ring buffer, for example from voice chat or
+/// decoded video.
+///
+/// Samples are pushed with an [`AudioStreamSender`] from any thread, and are played in the order
+/// they were pushed. When the buffer runs dry, silence is played until more samples arrive.
+/// Register it with [`AddAudioSource::add_audio_source`](crate::AddAudioSource) before playing it.
+///
+/// All playbacks of a stream read from the same buffer, so a stream should only be played once
+/// at a time.
+///
+/// ```
+/// # use bevy_audio::AudioStream;
+/// let stream = AudioStream::new(48_000, 2, 48_000);
+/// let sender = stream.sender();
+/// // Usually done on a networking or decoding thread.
+/// sender.push(&[0.0, 0.0, 0.5, 0.5]);
+/// ```
+#[derive(TypeUuid, Clone)]
+#[uuid = "b7c3e0a4-8d61-4f0e-a0b5-6c9e43d2f7e1"]
+pub struct AudioStream {
+    sample_rate: u32,
+    channels: u16,
+    buffer: Arc<StreamBuffer>,
+    stats: AudioSourceStats,
+}
+
+struct StreamBuffer {
+    samples: Mutex<VecDeque<f32>>,
+    capacity: usize,
+}
+
+impl AudioStream {
+    /// Creates a stream of `channels` interleaved channels at `sample_rate`, buffering up to
+    /// `capacity_frames` frames.
+    pub fn new(sample_rate: u32, channels: u16, capacity_frames: usize) -> Self {
+        let capacity = capacity_frames * channels.max(1) as usize;
+        Self {
+            sample_rate,
+            channels,
+            buffer: Arc::new(StreamBuffer {
+                samples: Mutex::new(VecDeque::with_capacity(capacity)),
+                capacity,
+            }),
+            stats: AudioSourceStats::default(),
+        }
+    }
+
+    /// Creates a new sender pushing samples to this stream.
+    pub fn sender(&self) -> AudioStreamSender {
+        AudioStreamSender {
+            buffer: self.buffer.clone(),
+            channels: self.channels,
+        }
+    }
+
+    /// Latency and underrun statistics of this stream.
+    ///
+    /// The latency is the duration of the samples waiting in the buffer. An underrun is counted
+    /// each time the buffer runs dry during playback.
+    pub fn stats(&self) -> &AudioSourceStats {
+        &self.stats
+    }
+}
+
+impl fmt::Debug for AudioStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioStream")
+            .field("sample_rate", &self.sample_rate)
+            .field("channels", &self.channels)
+            .field("capacity", &self.buffer.capacity)
+            .finish()
+    }
+}
+
+/// Pushes samples to an [`AudioStream`].
+#[derive(Clone)]
+pub struct AudioStreamSender {
+    buffer: Arc<StreamBuffer>,
+    channels: u16,
+}
+
+impl AudioStreamSender {
+    /// Pushes interleaved samples to the stream.
+    ///
+    /// Only whole frames that fit in the buffer are accepted. Returns the number of samples
+    /// accepted, the rest is dropped.
+    pub fn push(&self, samples: &[f32]) -> usize {
+        let channels = self.channels.max(1) as usize;
+        let mut buffer = self.buffer.samples.lock();
+        let free = self.buffer.capacity.saturating_sub(buffer.len());
+        let accepted = samples.len().min(free) / channels * channels;
+        buffer.extend(&samples[..accepted]);
+        accepted
+    }
+
+    /// Number of samples waiting to be played.
+    pub fn buffered(&self) -> usize {
+        self.buffer.samples.lock().len()
+    }
+
+    /// Drops every sample waiting to be played.
+    pub fn clear(&self) {
+        self.buffer.samples.lock().clear();
+    }
+}
+
+impl fmt::Debug for AudioStreamSender {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AudioStreamSender")
+            .field("channels", &self.channels)
+            .finish()
+    }
+}
+
+/// Decoder of an [`AudioStream`], reading from its buffer on the audio thread.
+pub struct StreamDecoder {
+    sample_rate: u32,
+    channels: u16,
+    buffer: Arc<StreamBuffer>,
+    stats: AudioSourceStats,
+    chunk: Vec<f32>,
+    position: usize,
+    started: bool,
+    starved: bool,
+}
+
+impl StreamDecoder {
+    /// Number of frames taken from the buffer at once, to avoid locking it for every sample.
+    const CHUNK_FRAMES: usize = 128;
+
+    fn refill(&mut self) {
+        let channels = self.channels.max(1) as usize;
+        self.chunk.clear();
+        self.position = 0;
+
+        let mut buffer = self.buffer.samples.lock();
+        let len = buffer.len().min(Self::CHUNK_FRAMES * channels) / channels * channels;
+        self.chunk.extend(buffer.drain(..len));
+        let remaining = buffer.len();
+        drop(buffer);
+
+        self.stats.set_latency(Duration::from_secs_f64(
+            remaining as f64 / (self.sample_rate.max(1) as f64 * channels as f64),
+        ));
+
+        if self.chunk.is_empty() {
+            if self.started && !self.starved {
+                self.stats.add_underrun();
+            }
+            self.starved = true;
+            self.chunk.resize(channels, 0.0);
+        } else {
+            self.started = true;
+            self.starved = false;
+        }
+    }
+}
+
+impl Iterator for StreamDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.position >= self.chunk.len() {
+            self.refill();
+        }
+        let sample = self.chunk[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl rodio::Source for StreamDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl Decodable for AudioStream {
+    type DecoderItem = f32;
+    type Decoder = StreamDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        StreamDecoder {
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            buffer: self.buffer.clone(),
+            stats: self.stats.clone(),
+            chunk: Vec::with_capacity(Self::Decoder::CHUNK_FRAMES * self.channels.max(1) as usize),
+            position: 0,
+            started: false,
+            starved: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_accepts_whole_frames_that_fit() {
+        let stream = AudioStream::new(4, 2, 3);
+        let sender = stream.sender();
+        assert_eq!(sender.push(&[0.1, 0.2, 0.3]), 2);
+        assert_eq!(sender.push(&[0.4, 0.5, 0.6, 0.7, 0.8, 0.9]), 4);
+        assert_eq!(sender.buffered(), 6);
+        assert_eq!(sender.push(&[1.0, 1.0]), 0);
+        sender.clear();
+        assert_eq!(sender.buffered(), 0);
+    }
+
+    #[test]
+    fn decoder_plays_in_order_then_silence() {
+        let stream = AudioStream::new(4, 2, 8);
+        let sender = stream.sender();
+        let mut decoder = stream.decoder();
+
+        // Nothing was pushed yet: silence, but no underrun before playback started.
+        assert_eq!(decoder.next(), Some(0.0));
+        assert_eq!(decoder.next(), Some(0.0));
+        assert_eq!(stream.stats().total_underruns(), 0);
+
+        sender.push(&[0.1, 0.2, 0.3, 0.4]);
+        let samples: Vec<_> = decoder.by_ref().take(4).collect();
+        assert_eq!(samples, [0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(sender.buffered(), 0);
+
+        // The buffer ran dry once, however long it stays dry.
+        let samples: Vec<_> = decoder.by_ref().take(4).collect();
+        assert_eq!(samples, [0.0; 4]);
+        assert_eq!(stream.stats().total_underruns(), 1);
+
+        sender.push(&[0.5, 0.6]);
+        assert_eq!(decoder.next(), Some(0.5));
+        assert_eq!(decoder.next(), Some(0.6));
+    }
+
+    #[test]
+    fn decoder_reports_latency() {
+        let stream = AudioStream::new(4, 1, 1024);
+        let sender = stream.sender();
+        let mut decoder = stream.decoder();
+        sender.push(&[0.0; StreamDecoder::CHUNK_FRAMES + 8]);
+        decoder.next();
+        assert_eq!(stream.stats().latency(), Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,148 @@
+//! The commands run by the console, and the builtin ones.
+
+use crate::ConsoleCommandError;
+use bevy_ecs::prelude::*;
+use bevy_log::LogRecords;
+use std::{collections::BTreeMap, fmt::Write};
+
+/// Lists the commands, or shows the help of the command given as argument.
+pub const HELP_COMMAND: &str = "help";
+/// Drops the records displayed by the console.
+pub const CLEAR_COMMAND: &str = "clear";
+
+/// A console command, run with the arguments of the command line.
+///
+/// Returns the output displayed by the console, which may be empty.
+pub type ConsoleCommandFn = fn(&mut World, &[&str]) -> Result<String, ConsoleCommandError>;
+
+/// A command registered in [`ConsoleCommands`].
+#[derive(Clone, Copy)]
+pub struct ConsoleCommand {
+    /// A one-line description of the command and its arguments, shown by `help`.
+    pub help: &'static str,
+    /// Runs the command.
+    pub run: ConsoleCommandFn,
+}
+
+/// The commands the console runs, by name.
+///
+/// This holds the builtin commands by default, and custom commands can be inserted, usually
+/// with [`AddConsoleCommand::add_console_command`](crate::AddConsoleCommand::add_console_command).
+#[derive(Resource)]
+pub struct ConsoleCommands(BTreeMap<String, ConsoleCommand>);
+
+impl Default for ConsoleCommands {
+    fn default() -> Self {
+        let mut commands = Self(BTreeMap::new());
+        commands.insert(
+            HELP_COMMAND,
+            "help [command]: lists the commands, or shows the help of a command",
+            help,
+        );
+        commands.insert(CLEAR_COMMAND, "clear: clears the console", clear);
+        commands
+    }
+}
+
+impl ConsoleCommands {
+    /// Adds a command, replacing any command with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, help: &'static str, run: ConsoleCommandFn) {
+        self.0.insert(name.into(), ConsoleCommand { help, run });
+    }
+
+    /// Removes the command with the given name, returning it if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<ConsoleCommand> {
+        self.0.remove(name)
+    }
+
+    /// Returns the command with the given name.
+    pub fn get(&self, name: &str) -> Option<ConsoleCommand> {
+        self.0.get(name).copied()
+    }
+
+    /// Returns the names and commands, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &ConsoleCommand)> {
+        self.0
+            .iter()
+            .map(|(name, command)| (name.as_str(), command))
+    }
+}
+
+fn help(world: &mut World, args: &[&str]) -> Result<String, ConsoleCommandError> {
+    let commands = world.resource::<ConsoleCommands>();
+    match args {
+        [] => {
+            let mut output = String::new();
+            for (name, command) in commands.iter() {
+                let _ = writeln!(output, "{name:<12} {}", command.help);
+            }
+            Ok(output.trim_end().to_string())
+        }
+        [name] => commands
+            .get(name)
+            .map(|command| command.help.to_string())
+            .ok_or_else(|| ConsoleCommandError::UnknownCommand(name.to_string())),
+        _ => Err(ConsoleCommandError::InvalidArguments(
+            "expected at most one command".to_string(),
+        )),
+    }
+}
+
+fn clear(world: &mut World, _args: &[&str]) -> Result<String, ConsoleCommandError> {
+    if let Some(mut records) = world.get_resource_mut::<LogRecords>() {
+        records.clear();
+    }
+    Ok(String::new())
+}
+
+/// Splits a command line into its words.
+///
+/// Words are separated by whitespace, unless quoted with `"`. In quotes, `\"` and `\\` escape a
+/// quote and a backslash.
+pub fn parse_command_line(line: &str) -> Result<Vec<String>, ConsoleCommandError> {
+    let mut words = Vec::new();
+    let mut chars = line.chars();
+    let mut word: Option<String> = None;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(ConsoleCommandError::UnterminatedQuote),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(ConsoleCommandError::UnterminatedQuote),
+                    }
+                }
+            }
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Parses and runs a command line, returning the output of the command.
+///
+/// Blank lines do nothing.
+pub fn run_console_command(world: &mut World, line: &str) -> Result<String, ConsoleCommandError> {
+    let words = parse_command_line(line)?;
+    let Some((name, args)) = words.split_first() else {
+        return Ok(String::new());
+    };
+    let command = world
+        .get_resource::<ConsoleCommands>()
+        .and_then(|commands| commands.get(name))
+        .ok_or_else(|| ConsoleCommandError::UnknownCommand(name.clone()))?;
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    (command.run)(world, &args)
+}
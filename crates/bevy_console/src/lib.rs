@@ -0,0 +1,271 @@
+#![allow(clippy::type_complexity)]
+#![warn(missing_docs)]
+
+//! This crate provides an in-game console, to read the logs and run commands where no terminal
+//! is at hand, like in a headset.
+//!
+//! The [`ConsoleCommandsPlugin`] holds the [`ConsoleCommands`] registry. Each command is a
+//! function run against the [`World`] with the words of a one-line command, like
+//! `spawn_enemies 3 "big bad"`. Commands are run by sending [`RunConsoleCommand`] events, and
+//! their output is stored in the [`LogRecords`] resource, along with the logs captured when
+//! [`LogPlugin::capture`](bevy_log::LogPlugin::capture) is enabled.
+//!
+//! With the `bevy_ui` feature, the `ConsolePlugin` displays the records and an input line in a
+//! `bevy_ui` overlay, toggled with the `` ` `` key.
+//!
+//! # Example
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_console::{AddConsoleCommand, ConsoleCommandError, ConsoleCommandsPlugin};
+//! # use bevy_ecs::prelude::*;
+//! #[derive(Component)]
+//! struct Enemy;
+//!
+//! fn spawn_enemies(world: &mut World, args: &[&str]) -> Result<String, ConsoleCommandError> {
+//!     let [count] = args else {
+//!         return Err(ConsoleCommandError::InvalidArguments("expected a count".into()));
+//!     };
+//!     let count: usize = count
+//!         .parse()
+//!         .map_err(|_| ConsoleCommandError::InvalidArguments(format!("invalid count `{count}`")))?;
+//!     world.spawn_batch((0..count).map(|_| Enemy));
+//!     Ok(format!("spawned {count} enemies"))
+//! }
+//!
+//! App::new()
+//!     .add_plugin(ConsoleCommandsPlugin)
+//!     .add_console_command("spawn_enemies", "spawn_enemies <count>: spawns enemies", spawn_enemies);
+//! ```
+
+pub mod commands;
+#[cfg(feature = "bevy_ui")]
+mod ui;
+
+pub use commands::{parse_command_line, run_console_command, ConsoleCommandFn, ConsoleCommands};
+#[cfg(feature = "bevy_ui")]
+pub use ui::*;
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::{event::ManualEventReader, prelude::*};
+use bevy_log::{error, info, Level, LogRecord, LogRecords};
+use thiserror::Error;
+
+/// The target of the [`LogRecord`]s of the commands run and their output.
+pub const CONSOLE_TARGET: &str = "console";
+
+/// Adds the [`ConsoleCommands`] registry, and runs the [`RunConsoleCommand`] events.
+#[derive(Default)]
+pub struct ConsoleCommandsPlugin;
+
+impl Plugin for ConsoleCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConsoleCommands>()
+            .add_event::<RunConsoleCommand>()
+            .add_systems(Last, run_console_commands);
+    }
+}
+
+/// Runs a one-line command, see [`run_console_command`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunConsoleCommand(pub String);
+
+/// An error running a console command.
+#[derive(Debug, Error)]
+pub enum ConsoleCommandError {
+    /// No command has the name.
+    #[error("unknown command `{0}`, see `help`")]
+    UnknownCommand(String),
+    /// A quote of the command line isn't closed.
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+    /// The arguments of the command are invalid.
+    #[error("invalid arguments: {0}")]
+    InvalidArguments(String),
+    /// The command failed.
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// Adds console commands to an [`App`].
+pub trait AddConsoleCommand {
+    /// Adds a command to the [`ConsoleCommands`], replacing any command with the same name.
+    ///
+    /// `help` is a one-line description of the command and its arguments, shown by `help`.
+    fn add_console_command(
+        &mut self,
+        name: impl Into<String>,
+        help: &'static str,
+        run: ConsoleCommandFn,
+    ) -> &mut Self;
+}
+
+impl AddConsoleCommand for App {
+    fn add_console_command(
+        &mut self,
+        name: impl Into<String>,
+        help: &'static str,
+        run: ConsoleCommandFn,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .insert(name, help, run);
+        self
+    }
+}
+
+/// Runs the commands of the [`RunConsoleCommand`] events.
+///
+/// The command lines and their output are stored in [`LogRecords`] if it exists, and logged
+/// otherwise.
+pub fn run_console_commands(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<RunConsoleCommand>>,
+) {
+    let lines: Vec<String> = reader
+        .iter(world.resource::<Events<RunConsoleCommand>>())
+        .map(|RunConsoleCommand(line)| line.clone())
+        .collect();
+    for line in lines {
+        let result = run_console_command(world, &line);
+        match world.get_resource_mut::<LogRecords>() {
+            Some(mut records) => {
+                records.push(LogRecord::new(
+                    Level::INFO,
+                    CONSOLE_TARGET,
+                    format!("> {line}"),
+                ));
+                match result {
+                    Ok(output) if output.is_empty() => {}
+                    Ok(output) => records.push(LogRecord::new(Level::INFO, CONSOLE_TARGET, output)),
+                    Err(err) => {
+                        records.push(LogRecord::new(
+                            Level::ERROR,
+                            CONSOLE_TARGET,
+                            err.to_string(),
+                        ));
+                    }
+                }
+            }
+            None => match result {
+                Ok(output) => info!(target: CONSOLE_TARGET, "> {line}\n{output}"),
+                Err(err) => error!(target: CONSOLE_TARGET, "> {line}: {err}"),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CLEAR_COMMAND, HELP_COMMAND};
+    use bevy_log::{info_span, warn, CaptureLayer};
+    use tracing_subscriber::{prelude::*, Registry};
+
+    #[derive(Component)]
+    struct Enemy;
+
+    fn spawn_enemies(world: &mut World, args: &[&str]) -> Result<String, ConsoleCommandError> {
+        let [count] = args else {
+            return Err(ConsoleCommandError::InvalidArguments(
+                "expected a count".to_string(),
+            ));
+        };
+        let count: usize = count
+            .parse()
+            .map_err(|_| ConsoleCommandError::Failed(format!("invalid count `{count}`")))?;
+        world.spawn_batch((0..count).map(|_| Enemy));
+        Ok(format!("spawned {count} enemies"))
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(ConsoleCommandsPlugin).add_console_command(
+            "spawn_enemies",
+            "spawn_enemies <count>: spawns enemies",
+            spawn_enemies,
+        );
+        app
+    }
+
+    #[test]
+    fn parse_command_lines() {
+        assert_eq!(
+            parse_command_line(r#"  say "hello \"you\"" a\b ""  "#).unwrap(),
+            ["say", r#"hello "you""#, r"a\b", ""]
+        );
+        assert!(parse_command_line("").unwrap().is_empty());
+        assert!(matches!(
+            parse_command_line(r#"say "hello"#),
+            Err(ConsoleCommandError::UnterminatedQuote)
+        ));
+    }
+
+    #[test]
+    fn run_commands() {
+        let mut app = app();
+        let world = &mut app.world;
+
+        assert_eq!(
+            run_console_command(world, "spawn_enemies 3").unwrap(),
+            "spawned 3 enemies"
+        );
+        assert_eq!(world.query::<&Enemy>().iter(world).count(), 3);
+        assert!(matches!(
+            run_console_command(world, "spawn_enemies"),
+            Err(ConsoleCommandError::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            run_console_command(world, "despawn_enemies"),
+            Err(ConsoleCommandError::UnknownCommand(name)) if name == "despawn_enemies"
+        ));
+        assert_eq!(run_console_command(world, " ").unwrap(), "");
+
+        let help = run_console_command(world, HELP_COMMAND).unwrap();
+        for name in [CLEAR_COMMAND, HELP_COMMAND, "spawn_enemies"] {
+            assert!(help.contains(name));
+        }
+        assert_eq!(
+            run_console_command(world, "help spawn_enemies").unwrap(),
+            "spawn_enemies <count>: spawns enemies"
+        );
+    }
+
+    #[test]
+    fn capture_logs_and_command_output() {
+        let (layer, records) = CaptureLayer::new();
+        let subscriber = Registry::default().with(layer);
+        bevy_utils::tracing::subscriber::with_default(subscriber, || {
+            let _span = info_span!("frame", index = 7).entered();
+            warn!(enemies = 3, "too many enemies");
+        });
+
+        let mut app = app();
+        app.insert_resource(records)
+            .add_event::<LogRecord>()
+            .add_systems(bevy_app::First, bevy_log::capture_log_records);
+        app.world
+            .send_event(RunConsoleCommand("spawn_enemies 2".to_string()));
+        app.world.send_event(RunConsoleCommand("oops".to_string()));
+        app.update();
+
+        let records: Vec<_> = app.world.resource::<LogRecords>().iter().cloned().collect();
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0].level, Level::WARN);
+        assert_eq!(records[0].message, "too many enemies");
+        assert_eq!(
+            records[0].fields,
+            [("enemies".to_string(), "3".to_string())]
+        );
+        assert_eq!(records[0].spans[0].to_string(), "frame{index=7}");
+        assert_eq!(records[1].message, "> spawn_enemies 2");
+        assert_eq!(records[2].message, "spawned 2 enemies");
+        assert_eq!(records[3].message, "> oops");
+        assert_eq!(records[4].level, Level::ERROR);
+        assert_eq!(app.world.resource::<Events<LogRecord>>().len(), 1);
+
+        app.world
+            .send_event(RunConsoleCommand(CLEAR_COMMAND.to_string()));
+        app.update();
+        assert_eq!(app.world.resource::<LogRecords>().len(), 1);
+    }
+}
@@ -0,0 +1,432 @@
+use crate::{AddConsoleCommand, ConsoleCommandError, ConsoleCommandsPlugin, RunConsoleCommand};
+use bevy_app::{App, Plugin, PostUpdate, Update};
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_log::{warn, CaptureLayer, Level, LogRecord, LogRecords};
+use bevy_render::{color::Color, view::Visibility};
+use bevy_text::{Font, Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    FlexDirection, PositionType, Size, Style, UiRect, UiSystem, Val, ZIndex,
+};
+use bevy_window::ReceivedCharacter;
+
+/// Sets the filter of the records displayed by the [`ConsolePlugin`], see [`ConsoleFilter`].
+pub const FILTER_COMMAND: &str = "filter";
+
+/// Displays the [`LogRecords`] and an input line running commands in a `bevy_ui` overlay.
+///
+/// The console is toggled with [`ConsoleSettings::toggle_key`]. While it is open, the typed
+/// characters are written to the input line, `Enter` runs the command, `Up` and `Down` browse
+/// the previous commands and `Escape` closes the console. The keys are still received by the
+/// other systems of the app.
+///
+/// The [`ConsoleCommandsPlugin`] is added if it wasn't already. Logs are only displayed if
+/// [`LogPlugin::capture`](bevy_log::LogPlugin::capture) is enabled, otherwise the console only
+/// displays the commands run and their output.
+#[derive(Default)]
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ConsoleCommandsPlugin>() {
+            app.add_plugin(ConsoleCommandsPlugin);
+        }
+        if !app.world.contains_resource::<LogRecords>() {
+            warn!("LogPlugin::capture is disabled, logs won't be displayed in the console");
+            let (_, records) = CaptureLayer::new();
+            app.insert_resource(records);
+        }
+        app.init_resource::<ConsoleSettings>()
+            .init_resource::<ConsoleFilter>()
+            .init_resource::<Console>()
+            .add_console_command(
+                FILTER_COMMAND,
+                "filter [level|target|text <value> | reset]: filters the console",
+                filter,
+            )
+            .add_systems(Update, console_input)
+            .add_systems(PostUpdate, update_console_ui.before(UiSystem::Flex));
+    }
+}
+
+/// The appearance and key bindings of the console.
+#[derive(Resource, Clone, Debug)]
+pub struct ConsoleSettings {
+    /// The font of the console. It must be set for the text to be displayed.
+    pub font: Handle<Font>,
+    /// The size of the font.
+    pub font_size: f32,
+    /// The color of the background of the console.
+    pub background_color: Color,
+    /// The maximum number of records displayed, the latest ones.
+    pub visible_records: usize,
+    /// The key opening and closing the console.
+    pub toggle_key: KeyCode,
+}
+
+impl Default for ConsoleSettings {
+    fn default() -> Self {
+        Self {
+            font: Handle::default(),
+            font_size: 16.0,
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.8),
+            visible_records: 20,
+            toggle_key: KeyCode::Grave,
+        }
+    }
+}
+
+/// Filters the records displayed by the console, set by the `filter` command.
+#[derive(Resource, Clone, Debug)]
+pub struct ConsoleFilter {
+    /// Only records at this level or more severe are displayed.
+    pub level: Level,
+    /// If set, only records whose target starts with this prefix are displayed.
+    pub target: Option<String>,
+    /// If set, only records whose message contains this text are displayed.
+    pub text: Option<String>,
+}
+
+impl Default for ConsoleFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::TRACE,
+            target: None,
+            text: None,
+        }
+    }
+}
+
+impl ConsoleFilter {
+    /// Returns `true` if the record passes this filter.
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        let target_matches = match &self.target {
+            Some(target) => record.target.starts_with(target.as_str()),
+            None => true,
+        };
+        let text_matches = match &self.text {
+            Some(text) => record.message.contains(text.as_str()),
+            None => true,
+        };
+        record.level <= self.level && target_matches && text_matches
+    }
+}
+
+fn filter(world: &mut World, args: &[&str]) -> Result<String, ConsoleCommandError> {
+    let mut filter = world.resource_mut::<ConsoleFilter>();
+    match args {
+        [] => {}
+        ["level", level] => {
+            filter.level = level.parse().map_err(|_| {
+                ConsoleCommandError::InvalidArguments(format!("invalid level `{level}`"))
+            })?;
+        }
+        ["target", target] => filter.target = Some(target.to_string()),
+        ["text", text] => filter.text = Some(text.to_string()),
+        ["reset"] => *filter = ConsoleFilter::default(),
+        _ => {
+            return Err(ConsoleCommandError::InvalidArguments(
+                "expected `level <level>`, `target <prefix>`, `text <text>` or `reset`".to_string(),
+            ))
+        }
+    }
+    Ok(format!(
+        "level: {}, target: {}, text: {}",
+        filter.level,
+        filter.target.as_deref().unwrap_or("*"),
+        filter.text.as_deref().unwrap_or("*"),
+    ))
+}
+
+/// The state of the console.
+#[derive(Resource, Debug, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>,
+    history_index: Option<usize>,
+}
+
+impl Console {
+    /// Returns `true` if the console is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens the console.
+    pub fn open(&mut self) {
+        self.open = true;
+    }
+
+    /// Closes the console.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Opens the console if it's closed, and closes it otherwise.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Returns the content of the input line.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the commands run from the input line, from the oldest to the latest.
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|&index| index < self.history.len()),
+        };
+        self.history_index = index;
+        self.input = index
+            .map(|index| self.history[index].clone())
+            .unwrap_or_default();
+    }
+}
+
+/// The root node of the console.
+#[derive(Component)]
+pub struct ConsoleRoot;
+
+/// The text of the console displaying the records.
+#[derive(Component)]
+pub struct ConsoleLog;
+
+/// The text of the console displaying the input line.
+#[derive(Component)]
+pub struct ConsoleInput;
+
+/// Toggles the console, and edits its input line while it's open.
+pub fn console_input(
+    mut console: ResMut<Console>,
+    settings: Res<ConsoleSettings>,
+    keys: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut commands: EventWriter<RunConsoleCommand>,
+) {
+    // The character of the toggle key isn't written to the input line.
+    if keys.just_pressed(settings.toggle_key) {
+        console.toggle();
+        characters.clear();
+        return;
+    }
+    if !console.is_open() {
+        characters.clear();
+        return;
+    }
+
+    for character in characters.iter() {
+        if !character.char.is_control() {
+            console.input.push(character.char);
+        }
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+    if keys.just_pressed(KeyCode::Up) {
+        console.browse_history(true);
+    }
+    if keys.just_pressed(KeyCode::Down) {
+        console.browse_history(false);
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        let line = std::mem::take(&mut console.input);
+        console.history_index = None;
+        if !line.trim().is_empty() {
+            console.history.push(line.clone());
+            commands.send(RunConsoleCommand(line));
+        }
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        console.close();
+    }
+}
+
+/// Spawns the console the first time it's opened, and updates its visibility and texts.
+#[allow(clippy::too_many_arguments)]
+pub fn update_console_ui(
+    mut commands: Commands,
+    console: Res<Console>,
+    settings: Res<ConsoleSettings>,
+    filter: Res<ConsoleFilter>,
+    records: Res<LogRecords>,
+    mut roots: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut logs: Query<&mut Text, With<ConsoleLog>>,
+    mut inputs: Query<&mut Text, (With<ConsoleInput>, Without<ConsoleLog>)>,
+) {
+    if roots.is_empty() {
+        if console.is_open() {
+            spawn_console(&mut commands, &console, &settings, &filter, &records);
+        }
+        return;
+    }
+
+    let visibility = if console.is_open() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut root_visibility in &mut roots {
+        root_visibility.set_if_neq(visibility);
+    }
+    if !console.is_open() {
+        return;
+    }
+
+    let settings_changed = settings.is_changed();
+    if settings_changed || filter.is_changed() || records.is_changed() {
+        for mut text in &mut logs {
+            text.sections = log_sections(&settings, &filter, &records);
+        }
+    }
+    if settings_changed || console.is_changed() {
+        for mut text in &mut inputs {
+            text.sections = vec![input_section(&settings, &console)];
+        }
+    }
+}
+
+fn spawn_console(
+    commands: &mut Commands,
+    console: &Console,
+    settings: &ConsoleSettings,
+    filter: &ConsoleFilter,
+    records: &LogRecords,
+) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..Default::default()
+                },
+                background_color: settings.background_color.into(),
+                z_index: ZIndex::Global(i32::MAX),
+                ..Default::default()
+            },
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections(log_sections(settings, filter, records)),
+                ConsoleLog,
+            ));
+            parent.spawn((
+                TextBundle::from_sections([input_section(settings, console)]),
+                ConsoleInput,
+            ));
+        });
+}
+
+fn log_sections(
+    settings: &ConsoleSettings,
+    filter: &ConsoleFilter,
+    records: &LogRecords,
+) -> Vec<TextSection> {
+    let mut sections: Vec<_> = records
+        .iter()
+        .rev()
+        .filter(|record| filter.matches(record))
+        .take(settings.visible_records)
+        .map(|record| {
+            TextSection::new(
+                format!("{record}\n"),
+                TextStyle {
+                    font: settings.font.clone(),
+                    font_size: settings.font_size,
+                    color: level_color(record.level),
+                },
+            )
+        })
+        .collect();
+    sections.reverse();
+    sections
+}
+
+fn input_section(settings: &ConsoleSettings, console: &Console) -> TextSection {
+    TextSection::new(
+        format!("> {}_", console.input),
+        TextStyle {
+            font: settings.font.clone(),
+            font_size: settings.font_size,
+            color: Color::WHITE,
+        },
+    )
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::ERROR => Color::rgb(1.0, 0.3, 0.3),
+        Level::WARN => Color::rgb(1.0, 0.8, 0.3),
+        Level::INFO => Color::WHITE,
+        Level::DEBUG => Color::rgb(0.6, 0.6, 0.6),
+        _ => Color::rgb(0.4, 0.4, 0.4),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_commands() {
+        let mut app = App::new();
+        app.init_resource::<Input<KeyCode>>()
+            .add_event::<ReceivedCharacter>()
+            .add_plugin(ConsolePlugin);
+        let window = app.world.spawn_empty().id();
+        let type_line = |app: &mut App, line: &str| {
+            for char in line.chars() {
+                app.world.send_event(ReceivedCharacter { window, char });
+            }
+            app.update();
+            let mut keys = app.world.resource_mut::<Input<KeyCode>>();
+            keys.press(KeyCode::Return);
+            app.update();
+            app.world.resource_mut::<Input<KeyCode>>().reset_all();
+        };
+
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Grave);
+        app.world
+            .send_event(ReceivedCharacter { window, char: '`' });
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().reset_all();
+        assert!(app.world.resource::<Console>().is_open());
+        assert_eq!(app.world.resource::<Console>().input(), "");
+
+        type_line(&mut app, "filter level warn");
+        assert_eq!(app.world.resource::<ConsoleFilter>().level, Level::WARN);
+        type_line(&mut app, "filter target bevy_");
+        assert_eq!(app.world.resource::<Console>().history().len(), 2);
+        app.update();
+
+        let filter = app.world.resource::<ConsoleFilter>();
+        let records = app.world.resource::<LogRecords>();
+        assert!(records
+            .iter()
+            .any(|record| record.message == "> filter level warn"));
+        assert!(!records.iter().any(|record| filter.matches(record)));
+        assert!(filter.matches(&LogRecord::new(Level::ERROR, "bevy_ecs", "oops")));
+
+        let mut texts = app.world.query_filtered::<&Text, With<ConsoleInput>>();
+        assert_eq!(texts.single(&app.world).sections[0].value, "> _");
+    }
+}
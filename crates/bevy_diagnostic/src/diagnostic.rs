@@ -211,6 +211,11 @@ impl Diagnostics {
         self.diagnostics.insert(diagnostic.id, diagnostic);
     }
 
+    /// Removes a [`Diagnostic`], returning it if it was added.
+    pub fn remove(&mut self, id: DiagnosticId) -> Option<Diagnostic> {
+        self.diagnostics.remove(&id)
+    }
+
     pub fn get(&self, id: DiagnosticId) -> Option<&Diagnostic> {
         self.diagnostics.get(&id)
     }
@@ -0,0 +1,308 @@
+use crate::{json, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_log::error;
+use bevy_utils::{Duration, HashMap, Instant};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// An App Plugin that exports the measurements of diagnostics, to CSV and JSON Lines files or to
+/// custom [`DiagnosticsExporter`]s added to the [`DiagnosticsExporters`] resource.
+///
+/// The measurements added after the plugin are exported every frame, so no measurement is missed no
+/// matter the history length of the diagnostics.
+#[derive(Default)]
+pub struct ExportDiagnosticsPlugin {
+    /// The CSV file to export to, see [`CsvDiagnosticsExporter`].
+    pub csv_path: Option<PathBuf>,
+    /// The JSON Lines file to export to, see [`JsonLinesDiagnosticsExporter`].
+    pub json_lines_path: Option<PathBuf>,
+    /// The diagnostics to export, or `None` to export all of them.
+    pub filter: Option<Vec<DiagnosticId>>,
+}
+
+impl Plugin for ExportDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut exporters = DiagnosticsExporters {
+            exporters: Vec::new(),
+            filter: self.filter.clone(),
+            origin: Instant::now(),
+            last_exported: HashMap::default(),
+        };
+        if let Some(path) = &self.csv_path {
+            match CsvDiagnosticsExporter::create(path) {
+                Ok(exporter) => exporters.add(exporter),
+                Err(err) => error!("Failed to create diagnostics file {:?}: {}", path, err),
+            }
+        }
+        if let Some(path) = &self.json_lines_path {
+            match JsonLinesDiagnosticsExporter::create(path) {
+                Ok(exporter) => exporters.add(exporter),
+                Err(err) => error!("Failed to create diagnostics file {:?}: {}", path, err),
+            }
+        }
+        app.insert_resource(exporters)
+            .add_systems(Last, Self::export_diagnostics_system);
+    }
+}
+
+impl ExportDiagnosticsPlugin {
+    /// Passes the measurements added since the last frame to the [`DiagnosticsExporters`].
+    ///
+    /// Exporters failing are removed, with an error logged.
+    pub fn export_diagnostics_system(
+        mut exporters: ResMut<DiagnosticsExporters>,
+        diagnostics: Res<Diagnostics>,
+    ) {
+        let exporters = &mut *exporters;
+        let mut measurements = Vec::new();
+        for diagnostic in diagnostics.iter() {
+            if let Some(filter) = &exporters.filter {
+                if !filter.contains(&diagnostic.id) {
+                    continue;
+                }
+            }
+            let Some(newest) = diagnostic.measurement() else {
+                continue;
+            };
+            let last_exported = exporters
+                .last_exported
+                .insert(diagnostic.id, newest.time)
+                .unwrap_or(exporters.origin);
+            measurements.extend(
+                diagnostic
+                    .measurements()
+                    .filter(|measurement| measurement.time > last_exported)
+                    .map(|measurement| ExportedMeasurement {
+                        id: diagnostic.id,
+                        name: &diagnostic.name,
+                        suffix: &diagnostic.suffix,
+                        time: measurement.time.saturating_duration_since(exporters.origin),
+                        value: measurement.value,
+                    }),
+            );
+        }
+        if measurements.is_empty() {
+            return;
+        }
+        measurements.sort_by_key(|measurement| measurement.time);
+
+        exporters
+            .exporters
+            .retain_mut(|exporter| match exporter.export(&measurements) {
+                Ok(()) => true,
+                Err(err) => {
+                    error!(
+                        "Failed to export diagnostics, the exporter is removed: {}",
+                        err
+                    );
+                    false
+                }
+            });
+    }
+}
+
+/// A measurement of a diagnostic, passed to [`DiagnosticsExporter`]s.
+#[derive(Debug, Clone, Copy)]
+pub struct ExportedMeasurement<'a> {
+    /// The id of the diagnostic.
+    pub id: DiagnosticId,
+    /// The name of the diagnostic.
+    pub name: &'a str,
+    /// The unit of the value, see [`Diagnostic::with_suffix`](crate::Diagnostic::with_suffix).
+    pub suffix: &'a str,
+    /// The time of the measurement, since the [`ExportDiagnosticsPlugin`] was added.
+    pub time: Duration,
+    /// The measured value.
+    pub value: f64,
+}
+
+/// A sink for the measurements of diagnostics, added to the [`DiagnosticsExporters`].
+pub trait DiagnosticsExporter: Send + Sync + 'static {
+    /// Exports the measurements added since the last call, sorted by time.
+    fn export(&mut self, measurements: &[ExportedMeasurement]) -> io::Result<()>;
+}
+
+/// The exporters used by the [`ExportDiagnosticsPlugin`].
+#[derive(Resource)]
+pub struct DiagnosticsExporters {
+    exporters: Vec<Box<dyn DiagnosticsExporter>>,
+    filter: Option<Vec<DiagnosticId>>,
+    origin: Instant,
+    last_exported: HashMap<DiagnosticId, Instant>,
+}
+
+impl DiagnosticsExporters {
+    /// Adds an exporter, getting the measurements from the next frame on.
+    pub fn add(&mut self, exporter: impl DiagnosticsExporter) {
+        self.exporters.push(Box::new(exporter));
+    }
+}
+
+/// Exports measurements as CSV, with a `time,name,value,suffix` header, the time being in seconds.
+pub struct CsvDiagnosticsExporter {
+    writer: Box<dyn Write + Send + Sync>,
+}
+
+impl CsvDiagnosticsExporter {
+    /// Creates an exporter writing to `writer`, writing the header right away.
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> io::Result<Self> {
+        let mut writer: Box<dyn Write + Send + Sync> = Box::new(writer);
+        writeln!(writer, "time,name,value,suffix")?;
+        Ok(Self { writer })
+    }
+
+    /// Creates an exporter writing to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl DiagnosticsExporter for CsvDiagnosticsExporter {
+    fn export(&mut self, measurements: &[ExportedMeasurement]) -> io::Result<()> {
+        for measurement in measurements {
+            writeln!(
+                self.writer,
+                "{},{},{},{}",
+                measurement.time.as_secs_f64(),
+                csv_field(measurement.name),
+                measurement.value,
+                csv_field(measurement.suffix),
+            )?;
+        }
+        self.writer.flush()
+    }
+}
+
+/// Quotes a CSV field if it contains characters that would break the row.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Exports measurements as [JSON Lines](https://jsonlines.org), one
+/// `{ "time", "id", "name", "value", "suffix" }` object per measurement, the time being in
+/// seconds.
+pub struct JsonLinesDiagnosticsExporter {
+    writer: Box<dyn Write + Send + Sync>,
+}
+
+impl JsonLinesDiagnosticsExporter {
+    /// Creates an exporter writing to `writer`.
+    pub fn new(writer: impl Write + Send + Sync + 'static) -> Self {
+        Self {
+            writer: Box::new(writer),
+        }
+    }
+
+    /// Creates an exporter writing to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl DiagnosticsExporter for JsonLinesDiagnosticsExporter {
+    fn export(&mut self, measurements: &[ExportedMeasurement]) -> io::Result<()> {
+        let writer = &mut self.writer;
+        for measurement in measurements {
+            writer.write_all(b"{\"time\":")?;
+            json::write_number(writer, measurement.time.as_secs_f64())?;
+            write!(writer, ",\"id\":\"{}\",\"name\":", measurement.id.0)?;
+            json::write_string(writer, measurement.name)?;
+            writer.write_all(b",\"value\":")?;
+            json::write_number(writer, measurement.value)?;
+            writer.write_all(b",\"suffix\":")?;
+            json::write_string(writer, measurement.suffix)?;
+            writeln!(writer, "}}")?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Diagnostic;
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    const ID: DiagnosticId = DiagnosticId::from_u128(301869744281573962418367020245931586719);
+
+    /// A writer whose output can be read while it is owned by an exporter.
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Output {
+        fn take_lines(&self) -> Vec<String> {
+            let bytes = std::mem::take(&mut *self.0.lock().unwrap());
+            String::from_utf8(bytes)
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        }
+    }
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn measure(app: &mut App, values: &[f64]) {
+        let mut diagnostics = app.world.resource_mut::<Diagnostics>();
+        for value in values {
+            diagnostics.add_measurement(ID, || *value);
+        }
+    }
+
+    #[test]
+    fn export_measurements() {
+        let mut app = App::new();
+        app.init_resource::<Diagnostics>();
+        app.world
+            .resource_mut::<Diagnostics>()
+            .add(Diagnostic::new(ID, "frame, time", 10).with_suffix("ms"));
+        // Measurements added before the plugin aren't exported.
+        measure(&mut app, &[1.0]);
+        app.add_plugin(ExportDiagnosticsPlugin::default());
+
+        let csv = Output::default();
+        let json_lines = Output::default();
+        let mut exporters = app.world.resource_mut::<DiagnosticsExporters>();
+        exporters.add(CsvDiagnosticsExporter::new(csv.clone()).unwrap());
+        exporters.add(JsonLinesDiagnosticsExporter::new(json_lines.clone()));
+        assert_eq!(csv.take_lines(), vec!["time,name,value,suffix"]);
+
+        measure(&mut app, &[2.0, 3.5]);
+        app.update();
+        let csv_lines = csv.take_lines();
+        assert_eq!(csv_lines.len(), 2);
+        let json_lines = json_lines.take_lines();
+        assert_eq!(json_lines.len(), 2);
+        for ((csv, json), value) in csv_lines.iter().zip(&json_lines).zip([2.0, 3.5]) {
+            let (time, rest) = csv.split_once(',').unwrap();
+            assert_eq!(rest, format!("\"frame, time\",{value},ms"));
+
+            let json: Value = serde_json::from_str(json).unwrap();
+            assert_eq!(json["id"], ID.0.to_string());
+            assert_eq!(json["name"], "frame, time");
+            assert_eq!(json["value"], value);
+            assert_eq!(json["suffix"], "ms");
+            assert_eq!(json["time"].as_f64(), time.parse().ok());
+        }
+
+        app.update();
+        assert!(csv.take_lines().is_empty());
+    }
+}
@@ -0,0 +1,60 @@
+//! Writing of the JSON exported by diagnostics, which is simple enough not to need a JSON library.
+
+use std::io::{self, Write};
+
+/// Writes `value` as a JSON string, with its quotes.
+pub(crate) fn write_string(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    writer.write_all(b"\"")?;
+    let mut start = 0;
+    for (index, char) in value.char_indices() {
+        if !matches!(char, '"' | '\\' | '\u{0}'..='\u{1f}') {
+            continue;
+        }
+        writer.write_all(&value.as_bytes()[start..index])?;
+        match char {
+            '"' => writer.write_all(b"\\\"")?,
+            '\\' => writer.write_all(b"\\\\")?,
+            '\n' => writer.write_all(b"\\n")?,
+            '\r' => writer.write_all(b"\\r")?,
+            '\t' => writer.write_all(b"\\t")?,
+            _ => write!(writer, "\\u{:04x}", char as u32)?,
+        }
+        // The escaped characters are all one byte long.
+        start = index + 1;
+    }
+    writer.write_all(&value.as_bytes()[start..])?;
+    writer.write_all(b"\"")
+}
+
+/// Writes `value` as a JSON number, or `null` if it isn't finite as JSON has no such numbers.
+pub(crate) fn write_number(writer: &mut impl Write, value: f64) -> io::Result<()> {
+    if value.is_finite() {
+        write!(writer, "{value}")
+    } else {
+        writer.write_all(b"null")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn write_values() {
+        let mut json = Vec::new();
+        json.push(b'[');
+        write_string(&mut json, "a \"quoted\" \\ path\n\tdone\u{1}, é").unwrap();
+        json.push(b',');
+        write_number(&mut json, 2.5).unwrap();
+        json.push(b',');
+        write_number(&mut json, f64::NAN).unwrap();
+        json.push(b']');
+
+        let json: Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!(["a \"quoted\" \\ path\n\tdone\u{1}, é", 2.5, null])
+        );
+    }
+}
@@ -2,16 +2,29 @@
 
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod export_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
+mod json;
 mod log_diagnostics_plugin;
+mod profiler_diagnostics_plugin;
 mod system_information_diagnostics_plugin;
+mod threshold_diagnostics_plugin;
 
 use bevy_app::prelude::*;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use export_diagnostics_plugin::{
+    CsvDiagnosticsExporter, DiagnosticsExporter, DiagnosticsExporters, ExportDiagnosticsPlugin,
+    ExportedMeasurement, JsonLinesDiagnosticsExporter,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use profiler_diagnostics_plugin::{FrameProfile, ProfilerDiagnosticsPlugin};
 pub use system_information_diagnostics_plugin::SystemInformationDiagnosticsPlugin;
+pub use threshold_diagnostics_plugin::{
+    DiagnosticThreshold, DiagnosticThresholdExceeded, DiagnosticThresholds, ThresholdComparison,
+    ThresholdDiagnosticsPlugin,
+};
 
 /// Adds core diagnostics resources to an App.
 #[derive(Default)]
@@ -0,0 +1,220 @@
+use crate::{json, Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{ProfileSpan, ProfileSpanKind, ScheduleProfiler},
+};
+use bevy_utils::{get_short_name, HashMap, Instant};
+use std::{collections::VecDeque, io};
+
+/// Adds a diagnostic for the wall time of every system and schedule run in the main world, in
+/// milliseconds per frame.
+///
+/// The timings are recorded by the executors through a [`ScheduleProfiler`], without the need
+/// for an external profiler. The diagnostics are keyed by the name of the system, or the label of
+/// the schedule, which [`FrameProfile::diagnostic_id`] maps to their [`DiagnosticId`]. The spans
+/// of the last frames are kept in the [`FrameProfile`] resource, which can export them as
+/// Chrome trace events.
+pub struct ProfilerDiagnosticsPlugin {
+    /// The number of measurements kept by each diagnostic, to compute rolling averages.
+    pub max_history_length: usize,
+    /// The number of frames whose spans are kept in the [`FrameProfile`].
+    pub max_trace_frames: usize,
+}
+
+impl Default for ProfilerDiagnosticsPlugin {
+    fn default() -> Self {
+        ProfilerDiagnosticsPlugin {
+            max_history_length: 120,
+            max_trace_frames: 10,
+        }
+    }
+}
+
+impl Plugin for ProfilerDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ScheduleProfiler::new())
+            .insert_resource(FrameProfile {
+                origin: Instant::now(),
+                diagnostic_ids: HashMap::default(),
+                frames: VecDeque::new(),
+                max_history_length: self.max_history_length,
+                max_trace_frames: self.max_trace_frames,
+            })
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl ProfilerDiagnosticsPlugin {
+    /// Adds the spans recorded since the last frame to the [`Diagnostics`] and the
+    /// [`FrameProfile`].
+    ///
+    /// Systems and schedules running several times in a frame get a single measurement, the sum
+    /// of their runs.
+    pub fn diagnostic_system(
+        profiler: Res<ScheduleProfiler>,
+        mut profile: ResMut<FrameProfile>,
+        mut diagnostics: ResMut<Diagnostics>,
+    ) {
+        let spans = profiler.drain();
+
+        let mut frame_times: HashMap<DiagnosticId, f64> = HashMap::default();
+        for span in &spans {
+            let id = profile.diagnostic_id_or_add(&span.name, &mut diagnostics);
+            *frame_times.entry(id).or_default() += span.duration.as_secs_f64() * 1000.0;
+        }
+        for (id, time) in frame_times {
+            diagnostics.add_measurement(id, || time);
+        }
+
+        if profile.max_trace_frames > 0 {
+            if profile.frames.len() == profile.max_trace_frames {
+                profile.frames.pop_front();
+            }
+            profile.frames.push_back(spans);
+        }
+    }
+}
+
+/// The timings recorded by the [`ProfilerDiagnosticsPlugin`].
+#[derive(Resource)]
+pub struct FrameProfile {
+    /// The instant Chrome trace event timestamps are relative to.
+    origin: Instant,
+    diagnostic_ids: HashMap<String, DiagnosticId>,
+    frames: VecDeque<Vec<ProfileSpan>>,
+    max_history_length: usize,
+    max_trace_frames: usize,
+}
+
+impl FrameProfile {
+    /// Returns the id of the diagnostic measuring the system or schedule with the given name,
+    /// if it ran since the plugin was added.
+    ///
+    /// Schedules are named after the [`Debug`] representation of their label.
+    pub fn diagnostic_id(&self, name: &str) -> Option<DiagnosticId> {
+        self.diagnostic_ids.get(name).copied()
+    }
+
+    /// Returns the spans of the last frames, in the order the runs finished.
+    pub fn spans(&self) -> impl Iterator<Item = &ProfileSpan> {
+        self.frames.iter().flatten()
+    }
+
+    /// Returns the spans of the last frames as a
+    /// [Chrome trace event](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+    /// JSON object, which can be opened in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+    pub fn to_chrome_trace(&self) -> String {
+        let mut trace = Vec::new();
+        self.write_chrome_trace(&mut trace)
+            .expect("writing to a `Vec` doesn't fail");
+        String::from_utf8(trace).expect("the trace is written from strings")
+    }
+
+    /// Writes the spans of the last frames as a Chrome trace event JSON object.
+    ///
+    /// See [`FrameProfile::to_chrome_trace`].
+    pub fn write_chrome_trace(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writer.write_all(b"{\"traceEvents\":[")?;
+        for (index, span) in self.spans().enumerate() {
+            if index > 0 {
+                writer.write_all(b",")?;
+            }
+            let category = match span.kind {
+                ProfileSpanKind::Schedule => "schedule",
+                ProfileSpanKind::System => "system",
+                ProfileSpanKind::ApplySystemBuffers => "apply_system_buffers",
+            };
+            let start = span.start.saturating_duration_since(self.origin);
+            writer.write_all(b"{\"name\":")?;
+            json::write_string(&mut writer, &span.name)?;
+            write!(writer, ",\"cat\":\"{category}\",\"ph\":\"X\",\"ts\":")?;
+            json::write_number(&mut writer, start.as_secs_f64() * 1_000_000.0)?;
+            writer.write_all(b",\"dur\":")?;
+            json::write_number(&mut writer, span.duration.as_secs_f64() * 1_000_000.0)?;
+            write!(writer, ",\"pid\":0,\"tid\":{}}}", span.thread)?;
+        }
+        writer.write_all(b"],\"displayTimeUnit\":\"ms\"}")
+    }
+
+    fn diagnostic_id_or_add(&mut self, name: &str, diagnostics: &mut Diagnostics) -> DiagnosticId {
+        if let Some(id) = self.diagnostic_ids.get(name) {
+            return *id;
+        }
+        let id = DiagnosticId::default();
+        // Keep the name readable in logs, full system names being long.
+        let short_name: String = get_short_name(name)
+            .chars()
+            .take(MAX_DIAGNOSTIC_NAME_WIDTH)
+            .collect();
+        diagnostics.add(Diagnostic::new(id, short_name, self.max_history_length).with_suffix("ms"));
+        self.diagnostic_ids.insert(name.to_string(), id);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_utils::Duration;
+    use serde_json::Value;
+
+    fn busy() {
+        std::thread::sleep(Duration::from_millis(2));
+    }
+
+    #[test]
+    fn records_frame() {
+        let mut app = App::new();
+        app.init_resource::<Diagnostics>()
+            .add_plugin(ProfilerDiagnosticsPlugin {
+                max_history_length: 10,
+                max_trace_frames: 2,
+            })
+            .add_systems(Update, busy);
+        app.update();
+
+        let profile = app.world.resource::<FrameProfile>();
+        let diagnostics = app.world.resource::<Diagnostics>();
+        let system = profile
+            .diagnostic_id(concat!(module_path!(), "::busy"))
+            .unwrap();
+        let diagnostic = diagnostics.get(system).unwrap();
+        assert_eq!(diagnostic.name, "busy");
+        assert_eq!(diagnostic.suffix, "ms");
+        assert!(diagnostic.value().unwrap() >= 2.0);
+        let schedule = profile.diagnostic_id("Update").unwrap();
+        assert!(diagnostics.get(schedule).unwrap().value().unwrap() >= 2.0);
+        // `Last` is still running when the spans of the frame are collected.
+        assert!(profile.diagnostic_id("Last").is_none());
+
+        let trace: Value = serde_json::from_str(&profile.to_chrome_trace()).unwrap();
+        assert_eq!(trace["displayTimeUnit"], "ms");
+        let events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(events.len(), profile.spans().count());
+        let busy = events
+            .iter()
+            .find(|event| event["name"] == concat!(module_path!(), "::busy"))
+            .unwrap();
+        assert_eq!(busy["cat"], "system");
+        assert_eq!(busy["ph"], "X");
+        assert_eq!(busy["pid"], 0);
+        assert!(busy["ts"].as_f64().unwrap() >= 0.0);
+        assert!(busy["dur"].as_f64().unwrap() >= 2000.0);
+        assert!(busy["tid"].is_u64());
+        let update = events
+            .iter()
+            .find(|event| event["name"] == "Update")
+            .unwrap();
+        assert_eq!(update["cat"], "schedule");
+        assert!(update["ts"].as_f64() <= busy["ts"].as_f64());
+
+        // Only the last `max_trace_frames` frames are kept.
+        app.update();
+        app.update();
+        let profile = app.world.resource::<FrameProfile>();
+        assert_eq!(profile.frames.len(), 2);
+        let diagnostics = app.world.resource::<Diagnostics>();
+        assert_eq!(diagnostics.get(system).unwrap().history_len(), 3);
+    }
+}
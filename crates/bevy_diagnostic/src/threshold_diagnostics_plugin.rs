@@ -0,0 +1,269 @@
+use crate::{DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_utils::Instant;
+
+/// An App Plugin that checks measurements of diagnostics against [`DiagnosticThreshold`]s.
+///
+/// When a diagnostic crosses one of its thresholds, a [`DiagnosticThresholdExceeded`] event is
+/// sent and a warning is logged. Thresholds can also be added at runtime to the
+/// [`DiagnosticThresholds`] resource.
+#[derive(Default)]
+pub struct ThresholdDiagnosticsPlugin {
+    /// The thresholds to add to the [`DiagnosticThresholds`], with the id of the diagnostic they
+    /// are checked against.
+    pub thresholds: Vec<(DiagnosticId, DiagnosticThreshold)>,
+}
+
+impl ThresholdDiagnosticsPlugin {
+    /// Adds a threshold for the diagnostic with the given id.
+    #[must_use]
+    pub fn with_threshold(mut self, id: DiagnosticId, threshold: DiagnosticThreshold) -> Self {
+        self.thresholds.push((id, threshold));
+        self
+    }
+}
+
+impl Plugin for ThresholdDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let mut thresholds = DiagnosticThresholds::default();
+        for (id, threshold) in &self.thresholds {
+            thresholds.add(*id, *threshold);
+        }
+        app.insert_resource(thresholds)
+            .add_event::<DiagnosticThresholdExceeded>()
+            .add_systems(Last, Self::check_thresholds_system);
+    }
+}
+
+impl ThresholdDiagnosticsPlugin {
+    /// Checks the measurements added since the last frame against the [`DiagnosticThresholds`].
+    pub fn check_thresholds_system(
+        mut thresholds: ResMut<DiagnosticThresholds>,
+        diagnostics: Res<Diagnostics>,
+        mut exceeded: EventWriter<DiagnosticThresholdExceeded>,
+    ) {
+        for state in &mut thresholds.thresholds {
+            let Some(diagnostic) = diagnostics.get(state.id) else {
+                continue;
+            };
+            let Some(newest) = diagnostic.measurement() else {
+                continue;
+            };
+            let last_checked = std::mem::replace(&mut state.last_checked, newest.time);
+            let threshold = state.threshold;
+            for measurement in diagnostic.measurements() {
+                if measurement.time <= last_checked {
+                    continue;
+                }
+                if !threshold.is_exceeded_by(measurement.value) {
+                    state.exceeding_measurements = 0;
+                    continue;
+                }
+                state.exceeding_measurements += 1;
+                // Only report when the threshold is crossed, not for every measurement after.
+                if state.exceeding_measurements != threshold.measurements.max(1) {
+                    continue;
+                }
+                let comparison = match threshold.comparison {
+                    ThresholdComparison::Above => "above",
+                    ThresholdComparison::Below => "below",
+                };
+                warn!(
+                    "Diagnostic {} was {} {}{} for {} measurements (latest: {:.6}{})",
+                    diagnostic.name,
+                    comparison,
+                    threshold.limit,
+                    diagnostic.suffix,
+                    state.exceeding_measurements,
+                    measurement.value,
+                    diagnostic.suffix
+                );
+                exceeded.send(DiagnosticThresholdExceeded {
+                    id: state.id,
+                    threshold,
+                    value: measurement.value,
+                });
+            }
+        }
+    }
+}
+
+/// Whether a [`DiagnosticThreshold`] is exceeded by values above or below its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdComparison {
+    /// Values greater than the limit exceed the threshold.
+    Above,
+    /// Values lower than the limit exceed the threshold.
+    Below,
+}
+
+/// A limit a diagnostic should stay within.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiagnosticThreshold {
+    /// The value the measurements are compared to, in the unit of the diagnostic.
+    pub limit: f64,
+    /// Whether values above or below the limit exceed the threshold.
+    pub comparison: ThresholdComparison,
+    /// The number of consecutive measurements exceeding the limit for the threshold to be
+    /// exceeded.
+    pub measurements: usize,
+}
+
+impl DiagnosticThreshold {
+    /// A threshold exceeded by a single measurement greater than `limit`.
+    pub fn above(limit: f64) -> Self {
+        Self {
+            limit,
+            comparison: ThresholdComparison::Above,
+            measurements: 1,
+        }
+    }
+
+    /// A threshold exceeded by a single measurement lower than `limit`.
+    pub fn below(limit: f64) -> Self {
+        Self {
+            limit,
+            comparison: ThresholdComparison::Below,
+            measurements: 1,
+        }
+    }
+
+    /// Sets the number of consecutive measurements exceeding the limit for the threshold to be
+    /// exceeded.
+    #[must_use]
+    pub fn for_measurements(mut self, measurements: usize) -> Self {
+        self.measurements = measurements;
+        self
+    }
+
+    /// Returns whether a value exceeds the limit.
+    pub fn is_exceeded_by(&self, value: f64) -> bool {
+        match self.comparison {
+            ThresholdComparison::Above => value > self.limit,
+            ThresholdComparison::Below => value < self.limit,
+        }
+    }
+}
+
+/// Sent by the [`ThresholdDiagnosticsPlugin`] when a diagnostic exceeds one of its thresholds.
+///
+/// It is sent once each time the threshold is crossed, and not again until a measurement within
+/// the limit is added.
+#[derive(Debug, Clone, Copy)]
+pub struct DiagnosticThresholdExceeded {
+    /// The id of the diagnostic.
+    pub id: DiagnosticId,
+    /// The threshold that was exceeded.
+    pub threshold: DiagnosticThreshold,
+    /// The measurement that made the threshold exceeded.
+    pub value: f64,
+}
+
+/// The thresholds checked by the [`ThresholdDiagnosticsPlugin`].
+#[derive(Resource, Default)]
+pub struct DiagnosticThresholds {
+    thresholds: Vec<ThresholdState>,
+}
+
+struct ThresholdState {
+    id: DiagnosticId,
+    threshold: DiagnosticThreshold,
+    /// The time of the newest measurement checked, or of the addition of the threshold.
+    last_checked: Instant,
+    exceeding_measurements: usize,
+}
+
+impl DiagnosticThresholds {
+    /// Adds a threshold for the diagnostic with the given id, checked from the next measurement
+    /// on.
+    pub fn add(&mut self, id: DiagnosticId, threshold: DiagnosticThreshold) -> &mut Self {
+        self.thresholds.push(ThresholdState {
+            id,
+            threshold,
+            last_checked: Instant::now(),
+            exceeding_measurements: 0,
+        });
+        self
+    }
+
+    /// Removes the thresholds of the diagnostic with the given id.
+    pub fn remove(&mut self, id: DiagnosticId) {
+        self.thresholds.retain(|state| state.id != id);
+    }
+
+    /// Returns the thresholds of the diagnostic with the given id.
+    pub fn get(&self, id: DiagnosticId) -> impl Iterator<Item = &DiagnosticThreshold> {
+        self.thresholds
+            .iter()
+            .filter(move |state| state.id == id)
+            .map(|state| &state.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Diagnostic;
+    use bevy_ecs::event::Events;
+
+    const ID: DiagnosticId = DiagnosticId::from_u128(165253937734851362870497287307830435552);
+
+    fn exceeded(app: &App) -> Vec<f64> {
+        app.world
+            .resource::<Events<DiagnosticThresholdExceeded>>()
+            .iter_current_update_events()
+            .map(|event| {
+                assert_eq!(event.id, ID);
+                event.value
+            })
+            .collect()
+    }
+
+    fn measure(app: &mut App, values: &[f64]) {
+        let mut diagnostics = app.world.resource_mut::<Diagnostics>();
+        for value in values {
+            diagnostics.add_measurement(ID, || *value);
+        }
+    }
+
+    #[test]
+    fn threshold_exceeded() {
+        let mut app = App::new();
+        app.init_resource::<Diagnostics>();
+        app.world
+            .resource_mut::<Diagnostics>()
+            .add(Diagnostic::new(ID, "test", 10));
+        // Measurements added before the threshold aren't checked.
+        measure(&mut app, &[20.0, 20.0]);
+        app.add_plugin(
+            ThresholdDiagnosticsPlugin::default()
+                .with_threshold(ID, DiagnosticThreshold::above(10.0).for_measurements(2)),
+        );
+        app.update();
+        assert!(exceeded(&app).is_empty());
+
+        measure(&mut app, &[20.0]);
+        app.update();
+        assert!(exceeded(&app).is_empty());
+
+        measure(&mut app, &[30.0]);
+        app.update();
+        assert_eq!(exceeded(&app), vec![30.0]);
+
+        // The threshold is only reported again after a measurement within the limit.
+        measure(&mut app, &[40.0]);
+        app.update();
+        assert!(exceeded(&app).is_empty());
+
+        measure(&mut app, &[5.0, 50.0, 60.0]);
+        app.update();
+        assert_eq!(exceeded(&app), vec![60.0]);
+
+        app.world.resource_mut::<DiagnosticThresholds>().remove(ID);
+        measure(&mut app, &[5.0, 70.0, 80.0]);
+        app.update();
+        assert!(exceeded(&app).is_empty());
+    }
+}
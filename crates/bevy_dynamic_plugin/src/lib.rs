@@ -1,5 +1,7 @@
 #![allow(clippy::type_complexity)]
 
 mod loader;
+mod reload;
 
 pub use loader::*;
+pub use reload::*;
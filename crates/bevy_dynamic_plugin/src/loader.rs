@@ -1,9 +1,11 @@
 use libloading::{Library, Symbol};
-use std::ffi::OsStr;
+use std::{ffi::OsStr, path::Path};
 use thiserror::Error;
 
 use bevy_app::{App, CreatePlugin, Plugin};
 
+use crate::load_reloadable_plugin;
+
 /// Errors that can occur when loading a dynamic plugin
 #[derive(Debug, Error)]
 pub enum DynamicPluginLoadError {
@@ -37,6 +39,14 @@ pub trait DynamicPluginExt {
     ///
     /// Same as [`dynamically_load_plugin`].
     unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> &mut Self;
+
+    /// Loads a plugin that is rebuilt whenever its library changes, see
+    /// [`load_reloadable_plugin`].
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_reloadable_plugin`].
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(&mut self, path: P) -> &mut Self;
 }
 
 impl DynamicPluginExt for App {
@@ -46,4 +56,9 @@ impl DynamicPluginExt for App {
         plugin.build(self);
         self
     }
+
+    unsafe fn load_reloadable_plugin<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        load_reloadable_plugin(self, path).unwrap();
+        self
+    }
 }
@@ -0,0 +1,762 @@
+use crate::DynamicPluginLoadError;
+use bevy_app::{App, AppTypeRegistry, CreatePlugin, First, Plugin, PluginAbi};
+use bevy_ecs::{
+    prelude::*,
+    reflect::{ReflectComponent, ReflectResource},
+    schedule::{BoxedScheduleLabel, Schedules},
+};
+use bevy_log::{error, info};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::{Duration, HashMap, HashSet, Instant};
+use libloading::{Library, Symbol};
+use serde::de::DeserializeSeed;
+use std::{
+    any::TypeId,
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::SystemTime,
+};
+use thiserror::Error;
+
+/// Errors that can occur when loading or reloading a reloadable dynamic plugin
+#[derive(Debug, Error)]
+pub enum DynamicPluginReloadError {
+    #[error(transparent)]
+    Load(#[from] DynamicPluginLoadError),
+    #[error("cannot copy library {path:?} to {copy:?} to load it: {error}")]
+    Copy {
+        path: PathBuf,
+        copy: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("library {0:?} was built against a different build of Bevy than this program")]
+    AbiMismatch(PathBuf),
+    #[error("type `{type_path}` changed since it was first loaded, the program must be restarted to use the new version of the plugin")]
+    TypeChanged { type_path: String },
+}
+
+/// Dynamically links a plugin at the given path, rebuilding it whenever the library changes.
+///
+/// The plugin must be created by deriving `DynamicPlugin`, which also exports the
+/// `_bevy_plugin_abi` symbol with the [`PluginAbi`] signature, used to check the plugin was built
+/// against the same Bevy as this program.
+///
+/// The library is checked for changes in [`First`], see [`ReloadablePlugins`]. When it changes:
+/// - the new library is loaded, from a copy so that it can be loaded next to the current one,
+/// - the components and resources of the types the plugin registered in the [`AppTypeRegistry`]
+///   are serialized and removed from the world, and these types are unregistered,
+/// - the systems of the plugin are removed,
+/// - the new plugin is built, and the serialized components and resources are restored, using
+///   the [migrations](bevy_reflect::serde::ReflectMigrations) of their types if their version
+///   changed.
+///
+/// If the new library cannot be loaded, the current plugin keeps running. Components and resources
+/// that cannot be restored, for example because their type was removed, are dropped with an error
+/// logged.
+///
+/// The systems of the plugin are kept in their own schedules, run by an exclusive system added to
+/// the schedules of the app with the same labels. They can be ordered relative to each other but
+/// not relative to the systems of the app. Systems added to the startup schedules only run after
+/// the first load, and only the main app is rebuilt: systems added to sub apps are not removed.
+///
+/// Libraries are never unloaded, so each reload leaks the previous library. Its code can run for as
+/// long as the world exists: the world keeps the drop functions of the components and resources
+/// the plugin used, and values of types that are not reflected aren't removed on reload.
+///
+/// # Safety
+///
+/// Same as [`dynamically_load_plugin`](crate::dynamically_load_plugin), for every version of the
+/// library. In addition, the layout of the component and resource types of the plugin must not
+/// change between versions: the world keeps the layout of a type from its first use. Such changes
+/// are detected for reflected types by first building the new plugin in an empty app, in which
+/// case it isn't built in the app and the current plugin keeps running, with a
+/// [`DynamicPluginReloadError::TypeChanged`] error. Each version of the plugin is thus built twice,
+/// so building it must not have effects outside of the app.
+pub unsafe fn load_reloadable_plugin(
+    app: &mut App,
+    path: impl AsRef<Path>,
+) -> Result<(), DynamicPluginReloadError> {
+    let path = path.as_ref().to_path_buf();
+    let modified = modified_time(&path);
+    let plugin = load_library_copy(&path)?;
+
+    if !app.world.contains_resource::<ReloadablePlugins>() {
+        app.init_resource::<ReloadablePlugins>()
+            .add_systems(First, reload_plugins_system);
+        // The runner of `First` can't be added while it runs, when the plugins are reloaded.
+        add_schedule_runners(&mut app.world, vec![Box::new(First)]);
+    }
+
+    check_plugin_types(&app.world.resource::<ReloadablePlugins>().shapes, &*plugin)?;
+    let built = build_plugin(app, &*plugin);
+    let labels = built.labels();
+    let registry = app.world.resource::<AppTypeRegistry>().clone();
+    let mut plugins = app.world.resource_mut::<ReloadablePlugins>();
+    let new_shapes = check_types(&plugins.shapes, &registry.read(), &built.types)?;
+    plugins.shapes.extend(new_shapes);
+    plugins.plugins.push(ReloadablePlugin {
+        path,
+        modified,
+        pending: None,
+        built: Some(built),
+        saved: None,
+    });
+    add_schedule_runners(&mut app.world, labels);
+    Ok(())
+}
+
+/// The plugins loaded with [`load_reloadable_plugin`].
+#[derive(Resource)]
+pub struct ReloadablePlugins {
+    plugins: Vec<ReloadablePlugin>,
+    /// The schedules of the app with a system running the schedules of the plugins.
+    runners: HashSet<BoxedScheduleLabel>,
+    /// The reflected shape of the types of the plugins when they were first registered.
+    shapes: HashMap<TypeId, String>,
+    check_interval: Duration,
+    last_check: Option<Instant>,
+}
+
+impl Default for ReloadablePlugins {
+    fn default() -> Self {
+        Self {
+            plugins: Vec::new(),
+            runners: HashSet::default(),
+            shapes: HashMap::default(),
+            check_interval: Duration::from_millis(500),
+            last_check: None,
+        }
+    }
+}
+
+impl ReloadablePlugins {
+    /// Returns the paths of the libraries of the plugins.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.plugins.iter().map(|plugin| plugin.path.as_path())
+    }
+
+    /// Returns the interval at which the libraries are checked for changes.
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    /// Sets the interval at which the libraries are checked for changes, `500ms` by default.
+    ///
+    /// A library is reloaded once it stayed unchanged for a check, so that it isn't loaded while
+    /// it is being written.
+    pub fn set_check_interval(&mut self, check_interval: Duration) {
+        self.check_interval = check_interval;
+    }
+}
+
+/// Checks that the types registered by a plugin have the same shape as when they were first
+/// registered, if they have the same [`TypeId`], returning the shapes of the types registered for
+/// the first time.
+fn check_types(
+    shapes: &HashMap<TypeId, String>,
+    registry: &TypeRegistry,
+    types: &[TypeId],
+) -> Result<Vec<(TypeId, String)>, DynamicPluginReloadError> {
+    let mut new_shapes = Vec::new();
+    for type_id in types {
+        let Some(registration) = registry.get(*type_id) else {
+            continue;
+        };
+        let shape = format!("{:?}", registration.type_info());
+        match shapes.get(type_id) {
+            Some(previous) if *previous != shape => {
+                return Err(DynamicPluginReloadError::TypeChanged {
+                    type_path: registration.type_path().to_string(),
+                });
+            }
+            Some(_) => {}
+            None => new_shapes.push((*type_id, shape)),
+        }
+    }
+    Ok(new_shapes)
+}
+
+/// Builds `plugin` in an empty app to check the shapes of the types it registers, before it is
+/// built in the world of the app where values of a type whose layout changed would be invalid.
+fn check_plugin_types(
+    shapes: &HashMap<TypeId, String>,
+    plugin: &dyn Plugin,
+) -> Result<(), DynamicPluginReloadError> {
+    let mut app = App::new();
+    plugin.build(&mut app);
+    let registry = app.world.resource::<AppTypeRegistry>().read();
+    let types: Vec<TypeId> = registry.iter().map(TypeRegistration::type_id).collect();
+    check_types(shapes, &registry, &types)?;
+    Ok(())
+}
+
+struct ReloadablePlugin {
+    path: PathBuf,
+    /// The modification time of the loaded library.
+    modified: Option<SystemTime>,
+    /// The modification time of the library seen at the last check, if it changed.
+    pending: Option<SystemTime>,
+    built: Option<BuiltPlugin>,
+    /// The state saved when the previous plugin was removed, if it couldn't be restored yet.
+    saved: Option<SavedState>,
+}
+
+impl ReloadablePlugin {
+    /// Returns whether the library changed and stayed unchanged since the last check.
+    fn poll(&mut self) -> bool {
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            self.pending = None;
+            return false;
+        }
+        if modified != self.pending {
+            self.pending = modified;
+            return false;
+        }
+        self.modified = modified;
+        self.pending = None;
+        modified.is_some()
+    }
+
+    /// Replaces the plugin with the one of the current library, returning the labels of its
+    /// schedules.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`load_reloadable_plugin`].
+    unsafe fn reload(
+        &mut self,
+        world: &mut World,
+        shapes: &mut HashMap<TypeId, String>,
+    ) -> Result<Vec<BoxedScheduleLabel>, DynamicPluginReloadError> {
+        // Load the new library first, so that the current plugin keeps running if it fails.
+        let plugin = load_library_copy(&self.path)?;
+        self.rebuild(world, shapes, &*plugin)
+    }
+
+    /// Replaces the plugin with `plugin`, if the types it registers didn't change.
+    fn rebuild(
+        &mut self,
+        world: &mut World,
+        shapes: &mut HashMap<TypeId, String>,
+        plugin: &dyn Plugin,
+    ) -> Result<Vec<BoxedScheduleLabel>, DynamicPluginReloadError> {
+        check_plugin_types(shapes, plugin)?;
+        let registry = world.resource::<AppTypeRegistry>().clone();
+
+        if let Some(built) = self.built.take() {
+            self.saved = Some(SavedState::save(world, &registry, &built.types));
+        }
+
+        let mut app = App::empty();
+        std::mem::swap(&mut app.world, world);
+        let built = build_plugin(&mut app, plugin);
+        std::mem::swap(&mut app.world, world);
+
+        // The plugin registered the same types in the empty app, unless its build depends on the
+        // app it is built in.
+        match check_types(shapes, &registry.read(), &built.types) {
+            Ok(new_shapes) => shapes.extend(new_shapes),
+            Err(err) => {
+                // Only remove the values inserted by the failed build, `self.saved` keeps the
+                // state of the previous version for the next one.
+                SavedState::save(world, &registry, &built.types);
+                return Err(err);
+            }
+        }
+        if let Some(saved) = self.saved.take() {
+            saved.restore(world, &registry.read());
+        }
+        let labels = built.labels();
+        self.built = Some(built);
+        Ok(labels)
+    }
+}
+
+/// The systems and types added by building a plugin.
+struct BuiltPlugin {
+    /// The schedules of the plugin, `None` while they run.
+    schedules: HashMap<BoxedScheduleLabel, Option<Schedule>>,
+    /// The types the plugin registered in the [`AppTypeRegistry`].
+    types: Vec<TypeId>,
+}
+
+impl BuiltPlugin {
+    fn labels(&self) -> Vec<BoxedScheduleLabel> {
+        self.schedules.keys().cloned().collect()
+    }
+}
+
+/// Builds `plugin`, keeping the systems it adds in their own schedules.
+fn build_plugin(app: &mut App, plugin: &dyn Plugin) -> BuiltPlugin {
+    let registry = app
+        .world
+        .get_resource_or_insert_with(AppTypeRegistry::default)
+        .clone();
+    let registered: HashSet<TypeId> = registry
+        .read()
+        .iter()
+        .map(TypeRegistration::type_id)
+        .collect();
+
+    let app_schedules = std::mem::take(&mut *app.world.resource_mut::<Schedules>());
+    plugin.build(app);
+    let mut plugin_schedules =
+        std::mem::replace(&mut *app.world.resource_mut::<Schedules>(), app_schedules);
+
+    let labels: Vec<BoxedScheduleLabel> = plugin_schedules
+        .iter()
+        .map(|(label, _)| label.dyn_clone())
+        .collect();
+    let schedules = labels
+        .into_iter()
+        .map(|label| {
+            let schedule = plugin_schedules.remove(&*label);
+            (label, schedule)
+        })
+        .collect();
+    let types = registry
+        .read()
+        .iter()
+        .map(TypeRegistration::type_id)
+        .filter(|type_id| !registered.contains(type_id))
+        .collect();
+    BuiltPlugin { schedules, types }
+}
+
+/// Adds a system running the schedules of the plugins with the same label to the schedules of the
+/// app, creating them if needed.
+fn add_schedule_runners(world: &mut World, labels: Vec<BoxedScheduleLabel>) {
+    for label in labels {
+        let mut plugins = world.resource_mut::<ReloadablePlugins>();
+        if !plugins.runners.insert(label.clone()) {
+            continue;
+        }
+        let runner = run_plugin_schedules(label.clone());
+        let mut schedules = world.resource_mut::<Schedules>();
+        if let Some(schedule) = schedules.get_mut(&*label) {
+            schedule.add_systems(runner);
+        } else {
+            let mut schedule = Schedule::new();
+            schedule.add_systems(runner);
+            schedules.insert(label, schedule);
+        }
+    }
+}
+
+fn run_plugin_schedules(label: BoxedScheduleLabel) -> impl FnMut(&mut World) {
+    move |world: &mut World| {
+        let plugin_count = world.resource::<ReloadablePlugins>().plugins.len();
+        for index in 0..plugin_count {
+            // Take the schedule out while it runs, so that it can run the schedules of the app.
+            let mut plugins = world.resource_mut::<ReloadablePlugins>();
+            let Some(mut schedule) = plugins.plugins[index]
+                .built
+                .as_mut()
+                .and_then(|built| built.schedules.get_mut(&*label))
+                .and_then(Option::take)
+            else {
+                continue;
+            };
+            schedule.run(world);
+            let mut plugins = world.resource_mut::<ReloadablePlugins>();
+            if let Some(slot) = plugins.plugins[index]
+                .built
+                .as_mut()
+                .and_then(|built| built.schedules.get_mut(&*label))
+            {
+                *slot = Some(schedule);
+            }
+        }
+    }
+}
+
+/// Reloads the plugins whose library changed.
+fn reload_plugins_system(world: &mut World) {
+    let now = Instant::now();
+    let mut plugins = world.resource_mut::<ReloadablePlugins>();
+    if let Some(last_check) = plugins.last_check {
+        if now.duration_since(last_check) < plugins.check_interval {
+            return;
+        }
+    }
+    plugins.last_check = Some(now);
+    let changed: Vec<usize> = plugins
+        .plugins
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, plugin)| plugin.poll().then_some(index))
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+
+    let labels = world.resource_scope(|world, mut plugins: Mut<ReloadablePlugins>| {
+        let plugins = &mut *plugins;
+        let mut labels = Vec::new();
+        for index in changed {
+            let plugin = &mut plugins.plugins[index];
+            // SAFETY: the library was loaded with `load_reloadable_plugin`, whose safety
+            // requirements hold for its later versions.
+            match unsafe { plugin.reload(world, &mut plugins.shapes) } {
+                Ok(plugin_labels) => {
+                    info!("Reloaded dynamic plugin {:?}", plugin.path);
+                    labels.extend(plugin_labels);
+                }
+                Err(err) => error!("Failed to reload dynamic plugin {:?}: {}", plugin.path, err),
+            }
+        }
+        labels
+    });
+    add_schedule_runners(world, labels);
+}
+
+/// The components and resources of a plugin, serialized while it is rebuilt.
+struct SavedState {
+    resources: Vec<SavedValue>,
+    components: Vec<(Entity, SavedValue)>,
+}
+
+struct SavedValue {
+    type_path: String,
+    version: u32,
+    data: String,
+}
+
+impl SavedState {
+    /// Serializes and removes the components and resources of `types`, and unregisters them.
+    fn save(world: &mut World, registry: &AppTypeRegistry, types: &[TypeId]) -> Self {
+        let mut state = SavedState {
+            resources: Vec::new(),
+            components: Vec::new(),
+        };
+        {
+            let registry = registry.read();
+            for type_id in types {
+                let Some(registration) = registry.get(*type_id) else {
+                    continue;
+                };
+                if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                    let entities: Vec<Entity> = world
+                        .iter_entities()
+                        .filter(|entity| reflect_component.contains(*entity))
+                        .map(|entity| entity.id())
+                        .collect();
+                    for entity in entities {
+                        if let Some(value) = reflect_component.reflect(world.entity(entity)) {
+                            match SavedValue::save(value, registration, &registry) {
+                                Ok(value) => state.components.push((entity, value)),
+                                Err(err) => error!(
+                                    "Cannot save `{}` of entity {:?}, it is dropped: {}",
+                                    registration.type_path(),
+                                    entity,
+                                    err
+                                ),
+                            }
+                        }
+                        reflect_component.remove(&mut world.entity_mut(entity));
+                    }
+                }
+                if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                    if let Some(value) = reflect_resource.reflect(world) {
+                        match SavedValue::save(value, registration, &registry) {
+                            Ok(value) => state.resources.push(value),
+                            Err(err) => error!(
+                                "Cannot save resource `{}`, it is dropped: {}",
+                                registration.type_path(),
+                                err
+                            ),
+                        }
+                    }
+                    reflect_resource.remove(world);
+                }
+            }
+        }
+        let mut registry = registry.write();
+        for type_id in types {
+            registry.remove(*type_id);
+        }
+        state
+    }
+
+    /// Inserts the saved components and resources, using the types registered in `registry` with
+    /// the same type paths.
+    fn restore(self, world: &mut World, registry: &TypeRegistry) {
+        for value in self.resources {
+            let result = value
+                .restore(registry)
+                .and_then(|(registration, restored)| {
+                    let reflect_resource = registration
+                        .data::<ReflectResource>()
+                        .ok_or_else(|| "the type is no longer a resource".to_string())?;
+                    reflect_resource.insert(world, &*restored);
+                    Ok(())
+                });
+            if let Err(err) = result {
+                error!(
+                    "Cannot restore resource `{}`, it is dropped: {}",
+                    value.type_path, err
+                );
+            }
+        }
+        for (entity, value) in self.components {
+            let result = value
+                .restore(registry)
+                .and_then(|(registration, restored)| {
+                    let reflect_component = registration
+                        .data::<ReflectComponent>()
+                        .ok_or_else(|| "the type is no longer a component".to_string())?;
+                    if let Some(mut entity) = world.get_entity_mut(entity) {
+                        reflect_component.insert(&mut entity, &*restored);
+                    }
+                    Ok(())
+                });
+            if let Err(err) = result {
+                error!(
+                    "Cannot restore `{}` of entity {:?}, it is dropped: {}",
+                    value.type_path, entity, err
+                );
+            }
+        }
+    }
+}
+
+impl SavedValue {
+    fn save(
+        value: &dyn Reflect,
+        registration: &TypeRegistration,
+        registry: &TypeRegistry,
+    ) -> Result<Self, ron::Error> {
+        Ok(SavedValue {
+            type_path: registration.type_path().to_string(),
+            version: registration.version(),
+            data: ron::to_string(&TypedReflectSerializer::new(value, registry))?,
+        })
+    }
+
+    /// Deserializes the value with the type registered with its type path.
+    fn restore<'a>(
+        &self,
+        registry: &'a TypeRegistry,
+    ) -> Result<(&'a TypeRegistration, Box<dyn Reflect>), String> {
+        let registration = registry
+            .get_with_type_path(&self.type_path)
+            .ok_or_else(|| "the type is no longer registered".to_string())?;
+        let mut deserializer =
+            ron::de::Deserializer::from_str(&self.data).map_err(|err| err.to_string())?;
+        let value = TypedReflectDeserializer::new(registration, registry)
+            .with_version(self.version)
+            .deserialize(&mut deserializer)
+            .map_err(|err| err.to_string())?;
+        Ok((registration, value))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Loads a copy of the library at `path`, since loading the same path again would return the
+/// library already loaded, and creates its plugin.
+///
+/// # Safety
+///
+/// Same as [`load_reloadable_plugin`].
+unsafe fn load_library_copy(path: &Path) -> Result<Box<dyn Plugin>, DynamicPluginReloadError> {
+    static COPIES: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let copy = std::env::temp_dir().join(format!(
+        "{}-{}-{}",
+        std::process::id(),
+        COPIES.fetch_add(1, Ordering::Relaxed),
+        file_name
+    ));
+    if let Err(error) = fs::copy(path, &copy) {
+        return Err(DynamicPluginReloadError::Copy {
+            path: path.to_path_buf(),
+            copy,
+            error,
+        });
+    }
+    let lib = Library::new(&copy).map_err(DynamicPluginLoadError::Library);
+    // The library stays mapped once loaded, this fails on platforms locking loaded files.
+    let _ = fs::remove_file(&copy);
+    let lib = lib?;
+
+    let abi: Symbol<PluginAbi> = lib
+        .get(b"_bevy_plugin_abi")
+        .map_err(DynamicPluginLoadError::Plugin)?;
+    if abi() != TypeId::of::<App>() {
+        return Err(DynamicPluginReloadError::AbiMismatch(path.to_path_buf()));
+    }
+    let func: Symbol<CreatePlugin> = lib
+        .get(b"_bevy_create_plugin")
+        .map_err(DynamicPluginLoadError::Plugin)?;
+    let plugin = Box::from_raw(func());
+    // Never unload the library, the world may run its code until it is dropped, see
+    // `load_reloadable_plugin`.
+    std::mem::forget(lib);
+    Ok(plugin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::std_traits::ReflectDefault;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Default)]
+    struct Health(u32);
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource, Default)]
+    struct Score {
+        value: u64,
+        name: String,
+    }
+
+    #[test]
+    fn check_types_detects_changed_shapes() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Health>();
+        let mut shapes = HashMap::default();
+        let types = [TypeId::of::<Health>(), TypeId::of::<Score>()];
+
+        let new_shapes = check_types(&shapes, &registry, &types).unwrap();
+        // Types that aren't registered have no shape to check.
+        assert_eq!(new_shapes.len(), 1);
+        shapes.extend(new_shapes);
+        assert!(check_types(&shapes, &registry, &types).unwrap().is_empty());
+
+        shapes.insert(TypeId::of::<Health>(), "Health(u64)".to_string());
+        let err = check_types(&shapes, &registry, &types).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamicPluginReloadError::TypeChanged { type_path }
+                if type_path == concat!(module_path!(), "::Health")
+        ));
+    }
+
+    struct HealthPlugin;
+
+    impl Plugin for HealthPlugin {
+        fn build(&self, app: &mut App) {
+            app.register_type::<Health>().insert_resource(Score {
+                value: 0,
+                name: "new".to_string(),
+            });
+        }
+    }
+
+    #[test]
+    fn changed_layout_is_not_built() {
+        let mut app = App::new();
+        app.register_type::<Score>();
+        let entity = app.world.spawn(Health(3)).id();
+        let mut plugin = ReloadablePlugin {
+            path: PathBuf::new(),
+            modified: None,
+            pending: None,
+            built: Some(BuiltPlugin {
+                schedules: HashMap::default(),
+                types: vec![TypeId::of::<Health>()],
+            }),
+            saved: None,
+        };
+
+        // The previous version of `Health` had another layout, so the new plugin must not insert
+        // any value in the world.
+        let mut shapes = HashMap::default();
+        shapes.insert(TypeId::of::<Health>(), "Health(u64)".to_string());
+        let err = plugin
+            .rebuild(&mut app.world, &mut shapes, &HealthPlugin)
+            .unwrap_err();
+        assert!(matches!(err, DynamicPluginReloadError::TypeChanged { .. }));
+        assert!(!app.world.contains_resource::<Score>());
+        assert_eq!(app.world.get::<Health>(entity), Some(&Health(3)));
+        assert!(plugin.built.is_some());
+
+        // With the same layout, the plugin is rebuilt.
+        let mut registry = TypeRegistry::default();
+        registry.register::<Health>();
+        let mut shapes = HashMap::default();
+        shapes.extend(check_types(&shapes, &registry, &[TypeId::of::<Health>()]).unwrap());
+        plugin
+            .rebuild(&mut app.world, &mut shapes, &HealthPlugin)
+            .unwrap();
+        assert_eq!(app.world.resource::<Score>().name, "new");
+        assert_eq!(app.world.get::<Health>(entity), Some(&Health(3)));
+    }
+
+    #[test]
+    fn poll_waits_for_unchanged_library() {
+        let path = std::env::temp_dir().join(format!("{}-poll-library", std::process::id()));
+        fs::write(&path, "library").unwrap();
+        let mut plugin = ReloadablePlugin {
+            modified: modified_time(&path),
+            path: path.clone(),
+            pending: None,
+            built: None,
+            saved: None,
+        };
+        assert!(!plugin.poll());
+
+        // The library is reloaded on the second check seeing the same change.
+        plugin.modified = Some(SystemTime::UNIX_EPOCH);
+        assert!(!plugin.poll());
+        assert!(plugin.poll());
+        assert!(!plugin.poll());
+
+        // A removed library is not reloaded, until it is written again.
+        fs::remove_file(&path).unwrap();
+        assert!(!plugin.poll());
+        assert!(!plugin.poll());
+        fs::write(&path, "library").unwrap();
+        assert!(!plugin.poll());
+        assert!(plugin.poll());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn saved_state_is_restored() {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        registry.write().register::<Score>();
+        let entity = world.spawn(Health(3)).id();
+        let other = world.spawn_empty().id();
+        world.insert_resource(Score {
+            value: 42,
+            name: "player".to_string(),
+        });
+
+        let types = [TypeId::of::<Health>(), TypeId::of::<Score>()];
+        let saved = SavedState::save(&mut world, &registry, &types);
+        assert!(world.get::<Health>(entity).is_none());
+        assert!(!world.contains_resource::<Score>());
+        assert!(registry.read().get(TypeId::of::<Health>()).is_none());
+        assert!(registry.read().get(TypeId::of::<Score>()).is_none());
+
+        registry.write().register::<Health>();
+        registry.write().register::<Score>();
+        saved.restore(&mut world, &registry.read());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(3)));
+        assert!(world.get::<Health>(other).is_none());
+        assert_eq!(world.resource::<Score>().value, 42);
+        assert_eq!(world.resource::<Score>().name, "player");
+
+        // Values whose type isn't registered again are dropped.
+        let saved = SavedState::save(&mut world, &registry, &types);
+        registry.write().register::<Health>();
+        saved.restore(&mut world, &registry.read());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(3)));
+        assert!(!world.contains_resource::<Score>());
+    }
+}
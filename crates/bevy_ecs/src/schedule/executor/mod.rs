@@ -1,8 +1,10 @@
 mod multi_threaded;
+mod profiler;
 mod simple;
 mod single_threaded;
 
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
+pub use self::profiler::*;
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
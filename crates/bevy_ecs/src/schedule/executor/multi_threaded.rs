@@ -15,7 +15,8 @@ use crate::{
     prelude::Resource,
     query::Access,
     schedule::{
-        is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        is_apply_system_buffers, BoxedCondition, ExecutorKind, ProfileSpanKind, ScheduleProfiler,
+        SystemExecutor, SystemSchedule,
     },
     system::BoxedSystem,
     world::World,
@@ -99,6 +100,8 @@ pub struct MultiThreadedExecutor {
     unapplied_systems: FixedBitSet,
     /// Setting when true applies system buffers after all systems have run
     apply_final_buffers: bool,
+    /// The profiler of the world the schedule is running in, if any.
+    profiler: Option<ScheduleProfiler>,
 }
 
 impl Default for MultiThreadedExecutor {
@@ -164,6 +167,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        self.profiler = world.get_resource::<ScheduleProfiler>().cloned();
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
@@ -214,6 +219,8 @@ impl SystemExecutor for MultiThreadedExecutor {
             },
         );
 
+        self.profiler = None;
+
         if self.apply_final_buffers {
             // Do one final apply buffers after all systems have completed
             // Commands should be applied while on the scope's thread, not the executor's thread
@@ -252,6 +259,7 @@ impl MultiThreadedExecutor {
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_buffers: true,
+            profiler: None,
         }
     }
 
@@ -438,13 +446,18 @@ impl MultiThreadedExecutor {
         let system_span = info_span!("system", name = &*system.name());
 
         let sender = self.sender.clone();
+        let profiler = self.profiler.clone();
         let task = async move {
             #[cfg(feature = "trace")]
             let system_guard = system_span.enter();
+            let scope = profiler.as_ref().map(ScheduleProfiler::start);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY: access is compatible
                 unsafe { system.run_unsafe((), world) };
             }));
+            if let Some(scope) = scope {
+                scope.finish(ProfileSpanKind::System, system.name());
+            }
             #[cfg(feature = "trace")]
             drop(system_guard);
             if res.is_err() {
@@ -491,16 +504,22 @@ impl MultiThreadedExecutor {
         let system_span = info_span!("system", name = &*system.name());
 
         let sender = self.sender.clone();
+        let profiler = self.profiler.clone();
         if is_apply_system_buffers(system) {
             // TODO: avoid allocation
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
+            let system_name = profiler.as_ref().map(|_| system.name());
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let scope = profiler.as_ref().map(ScheduleProfiler::start);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     apply_system_buffers(&unapplied_systems, systems, world);
                 }));
+                if let (Some(scope), Some(system_name)) = (scope, system_name) {
+                    scope.finish(ProfileSpanKind::ApplySystemBuffers, system_name);
+                }
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 if res.is_err() {
@@ -521,9 +540,13 @@ impl MultiThreadedExecutor {
             let task = async move {
                 #[cfg(feature = "trace")]
                 let system_guard = system_span.enter();
+                let scope = profiler.as_ref().map(ScheduleProfiler::start);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     system.run((), world);
                 }));
+                if let Some(scope) = scope {
+                    scope.finish(ProfileSpanKind::System, system.name());
+                }
                 #[cfg(feature = "trace")]
                 drop(system_guard);
                 if res.is_err() {
@@ -0,0 +1,178 @@
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use bevy_utils::{Duration, Instant};
+
+use crate::{self as bevy_ecs, system::Resource};
+
+/// What a [`ProfileSpan`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileSpanKind {
+    /// A run of a [`Schedule`](crate::schedule::Schedule), named after its label.
+    Schedule,
+    /// A run of a system.
+    System,
+    /// A run of [`apply_system_buffers`](crate::schedule::apply_system_buffers), applying the
+    /// buffers of the systems that ran before it.
+    ApplySystemBuffers,
+}
+
+/// The wall time spent running a system or a schedule, recorded by a [`ScheduleProfiler`].
+#[derive(Debug, Clone)]
+pub struct ProfileSpan {
+    /// What the span measures.
+    pub kind: ProfileSpanKind,
+    /// The name of the system, or the label of the schedule.
+    pub name: Cow<'static, str>,
+    /// When the run started.
+    pub start: Instant,
+    /// How long the run took.
+    pub duration: Duration,
+    /// An index identifying the thread the run happened on, unique for the process.
+    pub thread: u64,
+}
+
+/// Records the wall time of every system and schedule run in the [`World`](crate::world::World)
+/// it is inserted in.
+///
+/// The executors read this resource at the start of each schedule run, so profiling is enabled
+/// by inserting it and disabled by removing it, and costs nothing when disabled. Clones share
+/// the same recorded spans.
+#[derive(Resource, Clone, Default)]
+pub struct ScheduleProfiler {
+    spans: Arc<Mutex<Vec<ProfileSpan>>>,
+}
+
+impl ScheduleProfiler {
+    /// Creates a profiler with no recorded spans.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts timing a run, to be recorded with [`ProfileScope::finish`].
+    pub fn start(&self) -> ProfileScope<'_> {
+        ProfileScope {
+            profiler: self,
+            start: Instant::now(),
+        }
+    }
+
+    /// Records a span.
+    pub fn record(&self, span: ProfileSpan) {
+        self.spans.lock().unwrap().push(span);
+    }
+
+    /// Removes and returns the spans recorded so far, in the order the runs finished.
+    pub fn drain(&self) -> Vec<ProfileSpan> {
+        std::mem::take(&mut *self.spans.lock().unwrap())
+    }
+}
+
+/// A run being timed by a [`ScheduleProfiler`].
+pub struct ProfileScope<'a> {
+    profiler: &'a ScheduleProfiler,
+    start: Instant,
+}
+
+impl ProfileScope<'_> {
+    /// Records the run as having finished now.
+    pub fn finish(self, kind: ProfileSpanKind, name: Cow<'static, str>) {
+        self.profiler.record(ProfileSpan {
+            kind,
+            name,
+            start: self.start,
+            duration: self.start.elapsed(),
+            thread: thread_index(),
+        });
+    }
+}
+
+/// Returns an index identifying the current thread.
+fn thread_index() -> u64 {
+    static NEXT_THREAD_INDEX: AtomicU64 = AtomicU64::new(0);
+    thread_local! {
+        static THREAD_INDEX: u64 = NEXT_THREAD_INDEX.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_INDEX.with(|index| *index)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        schedule::{ExecutorKind, ProfileSpanKind, ScheduleLabel, ScheduleProfiler, Schedules},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn exclusive(_world: &mut World) {}
+
+    fn profile(executor: ExecutorKind) {
+        let simple = executor == ExecutorKind::Simple;
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule
+            .set_executor_kind(executor)
+            .add_systems((spawn, apply_system_buffers, exclusive).chain());
+        world.init_resource::<Schedules>();
+        world
+            .resource_mut::<Schedules>()
+            .insert(TestSchedule, schedule);
+        let profiler = ScheduleProfiler::new();
+        world.insert_resource(profiler.clone());
+
+        world.run_schedule(TestSchedule);
+        let spans = profiler.drain();
+        let names: Vec<_> = spans
+            .iter()
+            .map(|span| (span.kind, span.name.rsplit("::").next().unwrap()))
+            .collect();
+        // The simple executor runs `apply_system_buffers` as a regular system.
+        let apply_system_buffers_kind = if simple {
+            ProfileSpanKind::System
+        } else {
+            ProfileSpanKind::ApplySystemBuffers
+        };
+        let expected = vec![
+            (ProfileSpanKind::System, "spawn"),
+            (apply_system_buffers_kind, "apply_system_buffers"),
+            (ProfileSpanKind::System, "exclusive"),
+            (ProfileSpanKind::Schedule, "TestSchedule"),
+        ];
+        assert_eq!(names, expected);
+        let schedule = spans.last().unwrap();
+        assert!(spans
+            .iter()
+            .all(|span| span.start >= schedule.start && span.duration <= schedule.duration));
+
+        world.remove_resource::<ScheduleProfiler>();
+        world.run_schedule(TestSchedule);
+        assert!(profiler.drain().is_empty());
+    }
+
+    #[test]
+    fn profile_single_threaded() {
+        profile(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn profile_simple() {
+        profile(ExecutorKind::Simple);
+    }
+
+    #[test]
+    fn profile_multi_threaded() {
+        profile(ExecutorKind::MultiThreaded);
+    }
+}
@@ -3,7 +3,10 @@ use bevy_utils::tracing::info_span;
 use fixedbitset::FixedBitSet;
 
 use crate::{
-    schedule::{BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        BoxedCondition, ExecutorKind, ProfileSpanKind, ScheduleProfiler, SystemExecutor,
+        SystemSchedule,
+    },
     world::World,
 };
 
@@ -34,6 +37,7 @@ impl SystemExecutor for SimpleExecutor {
     }
 
     fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+        let profiler = world.get_resource::<ScheduleProfiler>().cloned();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -78,7 +82,11 @@ impl SystemExecutor for SimpleExecutor {
             let system = &mut schedule.systems[system_index];
             #[cfg(feature = "trace")]
             let system_span = info_span!("system", name = &*name).entered();
+            let scope = profiler.as_ref().map(ScheduleProfiler::start);
             system.run((), world);
+            if let Some(scope) = scope {
+                scope.finish(ProfileSpanKind::System, system.name());
+            }
             #[cfg(feature = "trace")]
             system_span.exit();
 
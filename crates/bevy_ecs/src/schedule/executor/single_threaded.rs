@@ -4,7 +4,8 @@ use fixedbitset::FixedBitSet;
 
 use crate::{
     schedule::{
-        is_apply_system_buffers, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
+        is_apply_system_buffers, BoxedCondition, ExecutorKind, ProfileSpanKind, ScheduleProfiler,
+        SystemExecutor, SystemSchedule,
     },
     world::World,
 };
@@ -44,6 +45,7 @@ impl SystemExecutor for SingleThreadedExecutor {
     }
 
     fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+        let profiler = world.get_resource::<ScheduleProfiler>().cloned();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -89,13 +91,22 @@ impl SystemExecutor for SingleThreadedExecutor {
             if is_apply_system_buffers(system) {
                 #[cfg(feature = "trace")]
                 let system_span = info_span!("system", name = &*name).entered();
+                let scope = profiler.as_ref().map(ScheduleProfiler::start);
+                let system_name = system.name();
                 self.apply_system_buffers(schedule, world);
+                if let Some(scope) = scope {
+                    scope.finish(ProfileSpanKind::ApplySystemBuffers, system_name);
+                }
                 #[cfg(feature = "trace")]
                 system_span.exit();
             } else {
                 #[cfg(feature = "trace")]
                 let system_span = info_span!("system", name = &*name).entered();
+                let scope = profiler.as_ref().map(ScheduleProfiler::start);
                 system.run((), world);
+                if let Some(scope) = scope {
+                    scope.finish(ProfileSpanKind::System, system.name());
+                }
                 #[cfg(feature = "trace")]
                 system_span.exit();
                 self.unapplied_systems.insert(system_index);
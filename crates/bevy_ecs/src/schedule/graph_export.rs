@@ -0,0 +1,404 @@
+use std::fmt::Write;
+
+use bevy_utils::{
+    get_short_name,
+    petgraph::{graphmap::DiGraphMap, Direction},
+    HashMap,
+};
+use serde::Serialize;
+
+use crate::{
+    component::Components,
+    schedule::{apply_system_buffers, BoxedCondition, NodeId, ScheduleGraph, ScheduleLabel},
+    system::System,
+};
+
+/// The kind of a node of a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportedNodeKind {
+    /// A system that can run in parallel with other systems.
+    System,
+    /// A system with exclusive access to the [`World`](crate::world::World).
+    ExclusiveSystem,
+    /// An instance of [`apply_system_buffers`], a sync point applying the buffers of the systems
+    /// that ran before it.
+    ApplySystemBuffers,
+    /// A system set.
+    Set,
+}
+
+/// A system or system set of a [`ScheduleGraphExport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedNode {
+    /// The identifier of the node, unique in the schedule.
+    pub id: String,
+    /// The kind of the node.
+    pub kind: ExportedNodeKind,
+    /// The name of the system or system set.
+    pub name: String,
+    /// The names of the run conditions of the node.
+    pub conditions: Vec<String>,
+}
+
+/// An edge between two nodes of a [`ScheduleGraphExport`], identified by [`ExportedNode::id`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportedEdge {
+    /// The node the edge starts from.
+    pub from: String,
+    /// The node the edge goes to.
+    pub to: String,
+}
+
+/// Two systems of a [`ScheduleGraphExport`] with conflicting access and no order between them.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedAmbiguity {
+    /// The first system.
+    pub a: String,
+    /// The second system.
+    pub b: String,
+    /// The names of the components the systems conflict on.
+    ///
+    /// If this is empty, the systems conflict on [`World`](crate::world::World) access.
+    pub conflicts: Vec<String>,
+}
+
+/// A snapshot of the graph of a [`Schedule`](super::Schedule), for visualization.
+///
+/// This is created with [`Schedule::export_graph`](super::Schedule::export_graph), and can be
+/// rendered as [DOT](https://graphviz.org/doc/info/lang.html) with [`to_dot`](Self::to_dot), or
+/// serialized with any [`serde`] format, such as JSON.
+///
+/// The [`SystemTypeSet`](super::SystemTypeSet)s of the systems that were added once are merged
+/// with their system, so that ordering relative to a system function is shown as an edge to the
+/// system. The topological order and the ambiguities are only known once the schedule has been
+/// initialized.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleGraphExport {
+    /// The label of the schedule.
+    pub label: String,
+    /// The systems and system sets of the schedule.
+    pub nodes: Vec<ExportedNode>,
+    /// The edges from the system sets to the systems and sets they contain.
+    pub hierarchy: Vec<ExportedEdge>,
+    /// The edges from the nodes to the nodes that run after them.
+    pub dependencies: Vec<ExportedEdge>,
+    /// The systems that conflict with each other, and that may run in any order.
+    pub ambiguities: Vec<ExportedAmbiguity>,
+    /// The systems in the order the schedule runs them, respecting the dependencies.
+    pub order: Vec<String>,
+}
+
+/// Returns the [`ExportedNode::id`] of a node.
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn condition_names(conditions: &[BoxedCondition]) -> Vec<String> {
+    conditions
+        .iter()
+        .map(|condition| condition.name().to_string())
+        .collect()
+}
+
+impl ScheduleGraphExport {
+    /// Exports `graph`, whose systems and conditions may have been moved out into the executable
+    /// schedule and are thus passed separately.
+    pub(super) fn new(
+        label: &dyn ScheduleLabel,
+        graph: &ScheduleGraph,
+        systems: &[(NodeId, &dyn System<In = (), Out = ()>, &[BoxedCondition])],
+        set_conditions: &HashMap<NodeId, &[BoxedCondition]>,
+        order: &[NodeId],
+        components: &Components,
+    ) -> Self {
+        let hierarchy = graph.hierarchy().graph();
+
+        // Merge the system type sets having a single system with that system.
+        let mut aliases = HashMap::default();
+        let mut sets: Vec<_> = graph.system_sets().map(|(id, set, _)| (id, set)).collect();
+        sets.sort_by_key(|(id, _)| *id);
+        sets.retain(|&(id, set)| {
+            if set.system_type().is_none() {
+                return true;
+            }
+            let mut members = hierarchy.neighbors_directed(id, Direction::Outgoing);
+            match (members.next(), members.next()) {
+                (Some(system), None) => {
+                    aliases.insert(id, system);
+                    false
+                }
+                _ => true,
+            }
+        });
+        let alias = |id: NodeId| aliases.get(&id).copied().unwrap_or(id);
+
+        let mut names = HashMap::default();
+        let mut nodes = Vec::with_capacity(systems.len() + sets.len());
+        for &(id, system, conditions) in systems {
+            // `System::type_id` is the type of the function, unlike `Any::type_id`.
+            let kind = if System::type_id(system) == std::any::Any::type_id(&apply_system_buffers) {
+                ExportedNodeKind::ApplySystemBuffers
+            } else if system.is_exclusive() {
+                ExportedNodeKind::ExclusiveSystem
+            } else {
+                ExportedNodeKind::System
+            };
+            names.insert(id, system.name().to_string());
+            nodes.push(ExportedNode {
+                id: node_key(id),
+                kind,
+                name: system.name().to_string(),
+                conditions: condition_names(conditions),
+            });
+        }
+        for &(id, set) in &sets {
+            let name = if set.is_anonymous() {
+                let members: Vec<_> = hierarchy
+                    .neighbors_directed(id, Direction::Outgoing)
+                    .map(|member| {
+                        names
+                            .get(&alias(member))
+                            .cloned()
+                            .unwrap_or_else(|| format!("{:?}", graph.set_at(member)))
+                    })
+                    .collect();
+                format!("({})", members.join(", "))
+            } else {
+                format!("{set:?}")
+            };
+            nodes.push(ExportedNode {
+                id: node_key(id),
+                kind: ExportedNodeKind::Set,
+                name,
+                conditions: set_conditions
+                    .get(&id)
+                    .map(|conditions| condition_names(conditions))
+                    .unwrap_or_default(),
+            });
+        }
+
+        let edges = |graph: &DiGraphMap<NodeId, ()>| {
+            let mut edges = Vec::new();
+            for (from, to, _) in graph.all_edges() {
+                let (from, to) = (alias(from), alias(to));
+                let edge = ExportedEdge {
+                    from: node_key(from),
+                    to: node_key(to),
+                };
+                if from != to && !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+            edges
+        };
+
+        Self {
+            label: format!("{label:?}"),
+            nodes,
+            hierarchy: edges(hierarchy),
+            dependencies: edges(graph.dependency().graph()),
+            ambiguities: graph
+                .conflicting_systems()
+                .iter()
+                .map(|(a, b, conflicts)| ExportedAmbiguity {
+                    a: node_key(*a),
+                    b: node_key(*b),
+                    conflicts: conflicts
+                        .iter()
+                        .filter_map(|id| components.get_info(*id))
+                        .map(|info| info.name().to_string())
+                        .collect(),
+                })
+                .collect(),
+            order: order.iter().map(|&id| node_key(id)).collect(),
+        }
+    }
+
+    /// Renders the graph in the [DOT](https://graphviz.org/doc/info/lang.html) language.
+    ///
+    /// Systems are boxes, labeled with their short name and their run conditions. Exclusive
+    /// systems are bold, and [`apply_system_buffers`] sync points are filled octagons. System
+    /// sets are dashed boxes, with dashed gray edges to their members. Dependencies are black
+    /// edges, and ambiguities are red dotted lines labeled with the conflicting components.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        // Writing to a `String` can't fail.
+        let _ = self.write_dot(&mut dot);
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String) -> std::fmt::Result {
+        writeln!(dot, "digraph {} {{", quote(&self.label))?;
+        writeln!(dot, "\tlabel={};", quote(&self.label))?;
+        writeln!(dot, "\tlabelloc=t;")?;
+        writeln!(dot, "\trankdir=LR;")?;
+        writeln!(dot, "\tnode [shape=box];")?;
+
+        for node in &self.nodes {
+            let style = match node.kind {
+                ExportedNodeKind::System => "",
+                ExportedNodeKind::ExclusiveSystem => ", style=bold",
+                ExportedNodeKind::ApplySystemBuffers => {
+                    ", shape=octagon, style=filled, fillcolor=lightgray"
+                }
+                ExportedNodeKind::Set => ", style=\"rounded,dashed\"",
+            };
+            let mut label = escape(&get_short_name(&node.name));
+            for condition in &node.conditions {
+                write!(label, "\\nif {}", escape(&get_short_name(condition)))?;
+            }
+            writeln!(
+                dot,
+                "\t{} [label=\"{label}\", tooltip={}{style}];",
+                node.id,
+                quote(&node.name)
+            )?;
+        }
+
+        for edge in &self.hierarchy {
+            writeln!(
+                dot,
+                "\t{} -> {} [style=dashed, color=gray, arrowhead=none, constraint=false];",
+                edge.from, edge.to
+            )?;
+        }
+        for edge in &self.dependencies {
+            writeln!(dot, "\t{} -> {};", edge.from, edge.to)?;
+        }
+        for ambiguity in &self.ambiguities {
+            let conflicts: Vec<_> = ambiguity
+                .conflicts
+                .iter()
+                .map(|name| get_short_name(name))
+                .collect();
+            let label = if conflicts.is_empty() {
+                "World".to_string()
+            } else {
+                conflicts.join(", ")
+            };
+            writeln!(
+                dot,
+                "\t{} -> {} [dir=none, style=dotted, color=red, constraint=false, label={}];",
+                ambiguity.a,
+                ambiguity.b,
+                quote(&label)
+            )?;
+        }
+        writeln!(dot, "}}")
+    }
+}
+
+/// Escapes a string to be used in a quoted DOT string.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Quotes a string as a DOT identifier.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", escape(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        schedule::{ExportedNodeKind, ScheduleGraphExport, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSet;
+
+    #[derive(Resource)]
+    struct Counter(u32);
+
+    fn first(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn second(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn third(_world: &mut World) {}
+
+    fn enabled() -> bool {
+        true
+    }
+
+    fn node<'a>(export: &'a ScheduleGraphExport, name: &str) -> &'a str {
+        &export
+            .nodes
+            .iter()
+            .find(|node| node.name.ends_with(name))
+            .unwrap()
+            .id
+    }
+
+    #[test]
+    fn export_graph() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(TestSet.run_if(enabled))
+            .add_systems((
+                first.in_set(TestSet),
+                second.in_set(TestSet),
+                apply_system_buffers.after(TestSet),
+                third.after(apply_system_buffers),
+            ));
+        schedule.initialize(&mut world).unwrap();
+        let export = schedule.export_graph(&TestSchedule, world.components());
+
+        assert_eq!(export.label, "TestSchedule");
+        let set = export
+            .nodes
+            .iter()
+            .find(|node| node.kind == ExportedNodeKind::Set)
+            .unwrap();
+        assert_eq!(set.name, "TestSet");
+        assert!(set.conditions[0].ends_with("enabled"));
+        assert_eq!(export.nodes.len(), 5);
+
+        let (first, second, apply, third) = (
+            node(&export, "::first"),
+            node(&export, "::second"),
+            node(&export, "apply_system_buffers"),
+            node(&export, "::third"),
+        );
+        let kind = |id: &str| export.nodes.iter().find(|node| node.id == id).unwrap().kind;
+        assert_eq!(kind(apply), ExportedNodeKind::ApplySystemBuffers);
+        assert_eq!(kind(third), ExportedNodeKind::ExclusiveSystem);
+
+        let has_edge = |edges: &[super::ExportedEdge], from: &str, to: &str| {
+            edges.iter().any(|edge| edge.from == from && edge.to == to)
+        };
+        assert!(has_edge(&export.hierarchy, &set.id, first));
+        assert!(has_edge(&export.hierarchy, &set.id, second));
+        assert!(has_edge(&export.dependencies, &set.id, apply));
+        assert!(has_edge(&export.dependencies, apply, third));
+
+        assert_eq!(export.ambiguities.len(), 1);
+        let ambiguity = &export.ambiguities[0];
+        assert!([first, second].contains(&&*ambiguity.a));
+        assert!([first, second].contains(&&*ambiguity.b));
+        assert!(ambiguity.conflicts[0].ends_with("Counter"));
+
+        assert_eq!(export.order.len(), 4);
+        assert_eq!(export.order[3], third);
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph \"TestSchedule\" {"));
+        assert!(dot.contains(&format!("\t{apply} -> {third};")));
+        assert!(dot.contains("label=\"TestSet\\nif enabled\""));
+        assert!(dot.contains("color=red"));
+    }
+}
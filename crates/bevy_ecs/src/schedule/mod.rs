@@ -1,6 +1,7 @@
 mod condition;
 mod config;
 mod executor;
+mod graph_export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
@@ -10,6 +11,7 @@ mod state;
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
+pub use self::graph_export::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
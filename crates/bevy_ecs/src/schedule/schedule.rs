@@ -87,6 +87,16 @@ impl Schedules {
             .map(|(label, schedule)| (&**label, schedule))
     }
 
+    /// Exports the graphs of all schedules, sorted by label, see [`Schedule::export_graph`].
+    pub fn export_graphs(&self, components: &Components) -> Vec<ScheduleGraphExport> {
+        let mut exports: Vec<_> = self
+            .iter()
+            .map(|(label, schedule)| schedule.export_graph(label, components))
+            .collect();
+        exports.sort_by(|a, b| a.label.cmp(&b.label));
+        exports
+    }
+
     /// Iterates the change ticks of all systems in all stored schedules and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
@@ -266,6 +276,53 @@ impl Schedule {
         &mut self.graph
     }
 
+    /// Exports the graph of this schedule, labeled `label`, for visualization.
+    ///
+    /// `components` names the components the systems conflict on, see
+    /// [`ScheduleGraph::conflicting_systems`].
+    pub fn export_graph(
+        &self,
+        label: &dyn ScheduleLabel,
+        components: &Components,
+    ) -> ScheduleGraphExport {
+        // The systems and conditions are moved out into the executable schedule when it's built.
+        let graph = &self.graph;
+        let executable = &self.executable;
+        let mut systems: Vec<_> = graph
+            .systems()
+            .chain(
+                executable
+                    .system_ids
+                    .iter()
+                    .zip(&executable.systems)
+                    .zip(&executable.system_conditions)
+                    .map(|((&id, system), conditions)| (id, &**system, conditions.as_slice())),
+            )
+            .collect();
+        systems.sort_by_key(|(id, _, _)| *id);
+        let set_conditions = graph
+            .system_set_conditions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, conditions)| Some((NodeId::Set(index), conditions.as_deref()?)))
+            .chain(
+                executable
+                    .set_ids
+                    .iter()
+                    .zip(&executable.set_conditions)
+                    .map(|(&id, conditions)| (id, conditions.as_slice())),
+            )
+            .collect();
+        ScheduleGraphExport::new(
+            label,
+            graph,
+            &systems,
+            &set_conditions,
+            &executable.system_ids,
+            components,
+        )
+    }
+
     /// Iterates the change ticks of all systems in the schedule and clamps any older than
     /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
     /// This prevents overflow and thus prevents false positives.
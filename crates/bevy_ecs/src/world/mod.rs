@@ -18,7 +18,7 @@ use crate::{
     event::{Event, Events},
     query::{DebugCheckedUnwrap, QueryState, ReadOnlyWorldQuery, WorldQuery},
     removal_detection::RemovedComponentEvents,
-    schedule::{Schedule, ScheduleLabel, Schedules},
+    schedule::{ProfileSpanKind, Schedule, ScheduleLabel, ScheduleProfiler, Schedules},
     storage::{ResourceData, Storages},
     system::Resource,
     world::error::TryRunScheduleError,
@@ -1743,14 +1743,24 @@ impl World {
         &mut self,
         label: &dyn ScheduleLabel,
     ) -> Result<(), TryRunScheduleError> {
-        let Some((extracted_label, mut schedule)) = self.resource_mut::<Schedules>().remove_entry(label) else {
+        let Some((extracted_label, mut schedule)) =
+            self.resource_mut::<Schedules>().remove_entry(label)
+        else {
             return Err(TryRunScheduleError(label.dyn_clone()));
         };
 
         // TODO: move this span to Schedule::run
         #[cfg(feature = "trace")]
         let _span = bevy_utils::tracing::info_span!("schedule", name = ?extracted_label).entered();
+        let profiler = self.get_resource::<ScheduleProfiler>().cloned();
+        let scope = profiler.as_ref().map(ScheduleProfiler::start);
         schedule.run(self);
+        if let Some(scope) = scope {
+            scope.finish(
+                ProfileSpanKind::Schedule,
+                format!("{extracted_label:?}").into(),
+            );
+        }
         self.resource_mut::<Schedules>()
             .insert(extracted_label, schedule);
 
@@ -0,0 +1,1340 @@
+use std::{io::Cursor, path::Path};
+
+#[cfg(feature = "bevy_animation")]
+use bevy_animation::{AnimationClip, AnimationPlayer, EntityPath, Keyframes};
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_core::Name;
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_hierarchy::Children;
+use bevy_log::warn;
+use bevy_math::{Affine2, Mat4};
+use bevy_pbr::{AlphaMode, DirectionalLight, PointLight, SpotLight, StandardMaterial};
+use bevy_render::{
+    camera::{Camera, OrthographicProjection, PerspectiveProjection, Projection},
+    color::Color,
+    mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, Mesh, VertexAttributeValues,
+    },
+    render_resource::PrimitiveTopology,
+    texture::Image,
+};
+use bevy_transform::components::Transform;
+use bevy_utils::HashMap;
+use gltf::json::{
+    self,
+    accessor::{ComponentType, GenericComponentType, Type},
+    buffer::Target,
+    extensions::{
+        self,
+        scene::khr_lights_punctual::{self, KhrLightsPunctual},
+        texture::{
+            TextureTransform, TextureTransformOffset, TextureTransformRotation,
+            TextureTransformScale,
+        },
+    },
+    material::{EmissiveFactor, PbrBaseColorFactor, PbrMetallicRoughness, StrengthFactor},
+    mesh::{Mode, Semantic},
+    scene::UnitQuaternion,
+    validation::{Checked, USize64},
+    Index, Value,
+};
+use thiserror::Error;
+
+/// An error that occurs when exporting a glTF file.
+#[derive(Error, Debug)]
+pub enum GltfExportError {
+    #[error("entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    #[error("failed to serialize glTF: {0}")]
+    Json(#[from] json::Error),
+    #[error("failed to write glTF: {0}")]
+    Gltf(#[from] gltf::Error),
+    #[error("failed to write glTF file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// The container format of an exported glTF file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GltfExportFormat {
+    /// Binary glTF (`.glb`), with the JSON and the binary data in a single file.
+    #[default]
+    Binary,
+    /// JSON glTF (`.gltf`), with the binary data embedded as a base64 data URI.
+    Embedded,
+}
+
+/// Exports a hierarchy of entities as a glTF file, that can be loaded back with the
+/// [`GltfLoader`](crate::GltfLoader).
+///
+/// The root entity and all of its descendants are exported as glTF nodes, with their [`Name`],
+/// [`Transform`], [`Handle<Mesh>`] and [`Handle<StandardMaterial>`], lights, cameras and
+/// [`SkinnedMesh`]. Textures are encoded as PNG. Animation clips added with
+/// [`GltfExporter::with_animation`] are exported for the named entities they animate.
+///
+/// Some properties of [`StandardMaterial`] have no glTF equivalent, like `depth_bias`, and are
+/// not exported.
+///
+/// ```no_run
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_gltf::GltfExporter;
+/// #[derive(Resource)]
+/// struct Level(Entity);
+///
+/// fn save_level(world: &mut World) {
+///     let level = world.resource::<Level>().0;
+///     GltfExporter::new(world)
+///         .save(level, "level.glb")
+///         .expect("failed to save the level");
+/// }
+/// ```
+pub struct GltfExporter<'w> {
+    world: &'w World,
+    format: GltfExportFormat,
+    #[cfg(feature = "bevy_animation")]
+    animations: Vec<Handle<AnimationClip>>,
+}
+
+impl<'w> GltfExporter<'w> {
+    /// Creates an exporter reading entities and assets from `world`.
+    pub fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            format: GltfExportFormat::default(),
+            #[cfg(feature = "bevy_animation")]
+            animations: Vec::new(),
+        }
+    }
+
+    /// Helper to set the container format of the exported file.
+    pub fn with_format(mut self, format: GltfExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Helper to add an [`AnimationClip`] to the exported file.
+    ///
+    /// The paths of the clip are resolved from the exported root and from every exported entity
+    /// with an [`AnimationPlayer`].
+    #[cfg(feature = "bevy_animation")]
+    pub fn with_animation(mut self, animation: Handle<AnimationClip>) -> Self {
+        self.animations.push(animation);
+        self
+    }
+
+    /// Exports `root` and its descendants, returning the content of the glTF file.
+    pub fn export(&self, root: Entity) -> Result<Vec<u8>, GltfExportError> {
+        if self.world.get_entity(root).is_none() {
+            return Err(GltfExportError::NoSuchEntity(root));
+        }
+
+        let mut context = ExportContext::new(self.world);
+        let root_node = context.export_node(root);
+        context.export_skins();
+        #[cfg(feature = "bevy_animation")]
+        context.export_animations(root, &self.animations);
+        let scene = context.root.push(json::Scene {
+            extensions: None,
+            extras: Default::default(),
+            name: None,
+            nodes: vec![root_node],
+        });
+        context.root.scene = Some(scene);
+        context.finish(self.format)
+    }
+
+    /// Exports `root` and its descendants to a file.
+    ///
+    /// The format is picked from the extension of `path`: `.gltf` files are written with
+    /// [`GltfExportFormat::Embedded`], others with [`GltfExportFormat::Binary`].
+    pub fn save(&self, root: Entity, path: impl AsRef<Path>) -> Result<(), GltfExportError> {
+        let path = path.as_ref();
+        let format = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gltf") => GltfExportFormat::Embedded,
+            _ => GltfExportFormat::Binary,
+        };
+        let bytes = GltfExporter {
+            format,
+            ..self.clone()
+        }
+        .export(root)?;
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+}
+
+impl<'w> Clone for GltfExporter<'w> {
+    fn clone(&self) -> Self {
+        Self {
+            world: self.world,
+            format: self.format,
+            #[cfg(feature = "bevy_animation")]
+            animations: self.animations.clone(),
+        }
+    }
+}
+
+/// State of a single export.
+struct ExportContext<'w> {
+    world: &'w World,
+    root: json::Root,
+    buffer: Vec<u8>,
+    lights: Vec<khr_lights_punctual::Light>,
+    nodes: HashMap<Entity, Index<json::Node>>,
+    meshes: HashMap<(HandleId, Option<HandleId>), Index<json::Mesh>>,
+    materials: HashMap<HandleId, Index<json::Material>>,
+    textures: HashMap<HandleId, Option<Index<json::Texture>>>,
+    skins: Vec<(Index<json::Node>, SkinnedMesh)>,
+}
+
+impl<'w> ExportContext<'w> {
+    fn new(world: &'w World) -> Self {
+        let mut root = json::Root::default();
+        root.asset.generator = Some(format!("bevy_gltf {}", env!("CARGO_PKG_VERSION")));
+        Self {
+            world,
+            root,
+            buffer: Vec::new(),
+            lights: Vec::new(),
+            nodes: HashMap::default(),
+            meshes: HashMap::default(),
+            materials: HashMap::default(),
+            textures: HashMap::default(),
+            skins: Vec::new(),
+        }
+    }
+
+    fn use_extension(&mut self, extension: &str) {
+        if !self
+            .root
+            .extensions_used
+            .iter()
+            .any(|used| used == extension)
+        {
+            self.root.extensions_used.push(extension.to_string());
+        }
+    }
+
+    /// Exports `entity` and its descendants as nodes, and returns the node of `entity`.
+    fn export_node(&mut self, entity: Entity) -> Index<json::Node> {
+        let world = self.world;
+        let transform = world.get::<Transform>(entity).copied().unwrap_or_default();
+
+        let mesh = world.get::<Handle<Mesh>>(entity).and_then(|mesh| {
+            let material = world.get::<Handle<StandardMaterial>>(entity);
+            self.export_mesh(mesh, material)
+        });
+        let camera = world
+            .get::<Camera>(entity)
+            .and_then(|_| self.export_camera(entity));
+        let light = self.export_light(entity).map(|light| {
+            self.use_extension("KHR_lights_punctual");
+            extensions::scene::Node {
+                khr_lights_punctual: Some(KhrLightsPunctual { light }),
+                ..Default::default()
+            }
+        });
+
+        let node = self.root.push(json::Node {
+            camera,
+            children: None,
+            extensions: light,
+            extras: Default::default(),
+            matrix: None,
+            mesh,
+            name: world.get::<Name>(entity).map(|name| name.to_string()),
+            rotation: Some(UnitQuaternion(transform.rotation.to_array())),
+            scale: Some(transform.scale.to_array()),
+            translation: Some(transform.translation.to_array()),
+            skin: None,
+            weights: None,
+        });
+        self.nodes.insert(entity, node);
+        if let Some(skinned_mesh) = world.get::<SkinnedMesh>(entity) {
+            self.skins.push((node, skinned_mesh.clone()));
+        }
+
+        if let Some(children) = world.get::<Children>(entity) {
+            let children = children
+                .iter()
+                .map(|child| self.export_node(*child))
+                .collect::<Vec<_>>();
+            if !children.is_empty() {
+                self.root.nodes[node.value()].children = Some(children);
+            }
+        }
+        node
+    }
+
+    fn export_camera(&mut self, entity: Entity) -> Option<Index<json::Camera>> {
+        let world = self.world;
+        let projection = match world.get::<Projection>(entity) {
+            Some(projection) => projection.clone(),
+            None => match (
+                world.get::<PerspectiveProjection>(entity),
+                world.get::<OrthographicProjection>(entity),
+            ) {
+                (Some(perspective), _) => Projection::Perspective(perspective.clone()),
+                (None, Some(orthographic)) => Projection::Orthographic(orthographic.clone()),
+                (None, None) => return None,
+            },
+        };
+
+        let camera = match projection {
+            Projection::Perspective(perspective) => json::Camera {
+                name: None,
+                orthographic: None,
+                perspective: Some(json::camera::Perspective {
+                    aspect_ratio: Some(perspective.aspect_ratio),
+                    yfov: perspective.fov,
+                    zfar: Some(perspective.far),
+                    znear: perspective.near,
+                    extensions: None,
+                    extras: Default::default(),
+                }),
+                type_: Checked::Valid(json::camera::Type::Perspective),
+                extensions: None,
+                extras: Default::default(),
+            },
+            // The loader maps `xmag` to the scale of a projection one unit wide, which is what
+            // is done in reverse here.
+            Projection::Orthographic(orthographic) => json::Camera {
+                name: None,
+                orthographic: Some(json::camera::Orthographic {
+                    xmag: orthographic.scale,
+                    ymag: orthographic.scale,
+                    zfar: orthographic.far,
+                    znear: orthographic.near,
+                    extensions: None,
+                    extras: Default::default(),
+                }),
+                perspective: None,
+                type_: Checked::Valid(json::camera::Type::Orthographic),
+                extensions: None,
+                extras: Default::default(),
+            },
+        };
+        Some(self.root.push(camera))
+    }
+
+    fn export_light(&mut self, entity: Entity) -> Option<Index<khr_lights_punctual::Light>> {
+        let world = self.world;
+        let name = world.get::<Name>(entity).map(|name| name.to_string());
+        // NOTE: Bevy uses luminous power for point and spot lights while KHR_punctual_lights uses
+        // luminous intensity, see the loader for the opposite conversion.
+        let light = if let Some(light) = world.get::<PointLight>(entity) {
+            khr_lights_punctual::Light {
+                color: linear_rgb(light.color),
+                extensions: None,
+                extras: Default::default(),
+                intensity: light.intensity / (4.0 * std::f32::consts::PI),
+                name,
+                range: Some(light.range),
+                spot: None,
+                type_: Checked::Valid(khr_lights_punctual::Type::Point),
+            }
+        } else if let Some(light) = world.get::<SpotLight>(entity) {
+            khr_lights_punctual::Light {
+                color: linear_rgb(light.color),
+                extensions: None,
+                extras: Default::default(),
+                intensity: light.intensity / (4.0 * std::f32::consts::PI),
+                name,
+                range: Some(light.range),
+                spot: Some(khr_lights_punctual::Spot {
+                    inner_cone_angle: light.inner_angle,
+                    outer_cone_angle: light.outer_angle,
+                }),
+                type_: Checked::Valid(khr_lights_punctual::Type::Spot),
+            }
+        } else if let Some(light) = world.get::<DirectionalLight>(entity) {
+            khr_lights_punctual::Light {
+                color: linear_rgb(light.color),
+                extensions: None,
+                extras: Default::default(),
+                intensity: light.illuminance,
+                name,
+                range: None,
+                spot: None,
+                type_: Checked::Valid(khr_lights_punctual::Type::Directional),
+            }
+        } else {
+            return None;
+        };
+        self.lights.push(light);
+        Some(Index::new(self.lights.len() as u32 - 1))
+    }
+
+    fn export_mesh(
+        &mut self,
+        mesh_handle: &Handle<Mesh>,
+        material: Option<&Handle<StandardMaterial>>,
+    ) -> Option<Index<json::Mesh>> {
+        let key = (mesh_handle.id(), material.map(Handle::id));
+        if let Some(mesh) = self.meshes.get(&key) {
+            return Some(*mesh);
+        }
+
+        let world = self.world;
+        let Some(mesh) = world
+            .get_resource::<Assets<Mesh>>()
+            .and_then(|meshes| meshes.get(mesh_handle))
+        else {
+            warn!("Mesh {:?} is not loaded and won't be exported", mesh_handle);
+            return None;
+        };
+
+        let mode = match mesh.primitive_topology() {
+            PrimitiveTopology::PointList => Mode::Points,
+            PrimitiveTopology::LineList => Mode::Lines,
+            PrimitiveTopology::LineStrip => Mode::LineStrip,
+            PrimitiveTopology::TriangleList => Mode::Triangles,
+            PrimitiveTopology::TriangleStrip => Mode::TriangleStrip,
+        };
+
+        let mut attributes = std::collections::BTreeMap::new();
+        for (attribute, semantic) in [
+            (Mesh::ATTRIBUTE_POSITION, Semantic::Positions),
+            (Mesh::ATTRIBUTE_NORMAL, Semantic::Normals),
+            (Mesh::ATTRIBUTE_TANGENT, Semantic::Tangents),
+            (Mesh::ATTRIBUTE_UV_0, Semantic::TexCoords(0)),
+            (Mesh::ATTRIBUTE_COLOR, Semantic::Colors(0)),
+            (Mesh::ATTRIBUTE_JOINT_INDEX, Semantic::Joints(0)),
+            (Mesh::ATTRIBUTE_JOINT_WEIGHT, Semantic::Weights(0)),
+        ] {
+            let Some(values) = mesh.attribute(attribute.id) else {
+                continue;
+            };
+            let (bytes, component_type, type_) = match values {
+                VertexAttributeValues::Float32x2(values) => (
+                    f32_bytes(values.iter().flatten().copied()),
+                    ComponentType::F32,
+                    Type::Vec2,
+                ),
+                VertexAttributeValues::Float32x3(values) => (
+                    f32_bytes(values.iter().flatten().copied()),
+                    ComponentType::F32,
+                    Type::Vec3,
+                ),
+                VertexAttributeValues::Float32x4(values) => (
+                    f32_bytes(values.iter().flatten().copied()),
+                    ComponentType::F32,
+                    Type::Vec4,
+                ),
+                VertexAttributeValues::Uint16x4(values) => (
+                    values
+                        .iter()
+                        .flatten()
+                        .flat_map(|value| value.to_le_bytes())
+                        .collect(),
+                    ComponentType::U16,
+                    Type::Vec4,
+                ),
+                _ => {
+                    warn!(
+                        "Attribute {} has a format unsupported by glTF and won't be exported",
+                        attribute.name
+                    );
+                    continue;
+                }
+            };
+            // glTF requires the bounds of positions.
+            let bounds = match values {
+                VertexAttributeValues::Float32x3(positions) if semantic == Semantic::Positions => {
+                    let (min, max) = positions.iter().fold(
+                        ([f32::MAX; 3], [f32::MIN; 3]),
+                        |(mut min, mut max), position| {
+                            for i in 0..3 {
+                                min[i] = min[i].min(position[i]);
+                                max[i] = max[i].max(position[i]);
+                            }
+                            (min, max)
+                        },
+                    );
+                    Some((Value::from(min.to_vec()), Value::from(max.to_vec())))
+                }
+                _ => None,
+            };
+            let accessor = self.push_accessor(
+                &bytes,
+                values.len(),
+                component_type,
+                type_,
+                Some(Target::ArrayBuffer),
+                bounds,
+            );
+            attributes.insert(Checked::Valid(semantic), accessor);
+        }
+
+        let indices = mesh.indices().map(|indices| match indices {
+            Indices::U16(indices) => self.push_accessor(
+                &indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect::<Vec<_>>(),
+                indices.len(),
+                ComponentType::U16,
+                Type::Scalar,
+                Some(Target::ElementArrayBuffer),
+                None,
+            ),
+            Indices::U32(indices) => self.push_accessor(
+                &indices
+                    .iter()
+                    .flat_map(|index| index.to_le_bytes())
+                    .collect::<Vec<_>>(),
+                indices.len(),
+                ComponentType::U32,
+                Type::Scalar,
+                Some(Target::ElementArrayBuffer),
+                None,
+            ),
+        });
+
+        let material = material.and_then(|material| self.export_material(material));
+
+        let index = self.root.push(json::Mesh {
+            extensions: None,
+            extras: Default::default(),
+            name: None,
+            primitives: vec![json::mesh::Primitive {
+                attributes,
+                extensions: None,
+                extras: Default::default(),
+                indices,
+                material,
+                mode: Checked::Valid(mode),
+                targets: None,
+            }],
+            weights: None,
+        });
+        self.meshes.insert(key, index);
+        Some(index)
+    }
+
+    fn export_material(
+        &mut self,
+        material_handle: &Handle<StandardMaterial>,
+    ) -> Option<Index<json::Material>> {
+        if let Some(material) = self.materials.get(&material_handle.id()) {
+            return Some(*material);
+        }
+        let world = self.world;
+        let Some(material) = world
+            .get_resource::<Assets<StandardMaterial>>()
+            .and_then(|materials| materials.get(material_handle))
+        else {
+            warn!(
+                "Material {:?} is not loaded and won't be exported",
+                material_handle
+            );
+            return None;
+        };
+
+        let texture_transform = texture_transform(material.uv_transform);
+        if texture_transform.is_some() {
+            self.use_extension("KHR_texture_transform");
+        }
+        let texture_info = |context: &mut Self, image: &Option<Handle<Image>>| {
+            let index = context.export_texture(image.as_ref()?)?;
+            Some(json::texture::Info {
+                index,
+                tex_coord: 0,
+                extensions: texture_transform.clone().map(|texture_transform| {
+                    extensions::texture::Info {
+                        texture_transform: Some(texture_transform),
+                        ..Default::default()
+                    }
+                }),
+                extras: Default::default(),
+            })
+        };
+
+        let base_color_texture = texture_info(self, &material.base_color_texture);
+        let metallic_roughness_texture = texture_info(self, &material.metallic_roughness_texture);
+        let emissive_texture = texture_info(self, &material.emissive_texture);
+        let normal_texture = texture_info(self, &material.normal_map_texture).map(|info| {
+            json::material::NormalTexture {
+                index: info.index,
+                scale: 1.0,
+                tex_coord: 0,
+                extensions: None,
+                extras: Default::default(),
+            }
+        });
+        let occlusion_texture = texture_info(self, &material.occlusion_texture).map(|info| {
+            json::material::OcclusionTexture {
+                index: info.index,
+                strength: StrengthFactor(1.0),
+                tex_coord: 0,
+                extensions: None,
+                extras: Default::default(),
+            }
+        });
+
+        let (alpha_mode, alpha_cutoff) = match material.alpha_mode {
+            AlphaMode::Opaque => (json::material::AlphaMode::Opaque, None),
+            AlphaMode::Mask(cutoff) => (
+                json::material::AlphaMode::Mask,
+                Some(json::material::AlphaCutoff(cutoff)),
+            ),
+            _ => (json::material::AlphaMode::Blend, None),
+        };
+
+        let mut material_extensions = extensions::material::Material::default();
+        if material.unlit {
+            self.use_extension("KHR_materials_unlit");
+            material_extensions.unlit = Some(extensions::material::Unlit {});
+        }
+
+        // glTF limits the emissive factor to 1.0, brighter colors are scaled down and the rest
+        // is moved to the emissive strength.
+        let mut emissive = linear_rgb(material.emissive);
+        let emissive_strength = emissive.iter().copied().fold(1.0, f32::max);
+        if emissive_strength > 1.0 {
+            self.use_extension("KHR_materials_emissive_strength");
+            emissive = emissive.map(|channel| channel / emissive_strength);
+            material_extensions.emissive_strength = Some(extensions::material::EmissiveStrength {
+                emissive_strength: extensions::material::EmissiveStrengthFactor(emissive_strength),
+            });
+        }
+
+        if material.specular_transmission > 0.0 {
+            self.use_extension("KHR_materials_transmission");
+            material_extensions.transmission = Some(extensions::material::Transmission {
+                transmission_factor: extensions::material::TransmissionFactor(
+                    material.specular_transmission,
+                ),
+                transmission_texture: None,
+                extras: Default::default(),
+            });
+        }
+
+        if material.thickness > 0.0 || material.attenuation_distance.is_finite() {
+            self.use_extension("KHR_materials_volume");
+            material_extensions.volume = Some(extensions::material::Volume {
+                thickness_factor: extensions::material::ThicknessFactor(material.thickness),
+                thickness_texture: None,
+                attenuation_distance: extensions::material::AttenuationDistance(
+                    material.attenuation_distance,
+                ),
+                attenuation_color: extensions::material::AttenuationColor(linear_rgb(
+                    material.attenuation_color,
+                )),
+                extras: Default::default(),
+            });
+        }
+
+        if material.ior != 1.5 {
+            self.use_extension("KHR_materials_ior");
+            material_extensions.ior = Some(extensions::material::Ior {
+                ior: extensions::material::IndexOfRefraction(material.ior),
+                extras: Default::default(),
+            });
+        }
+
+        // glTF derives the reflectance of dielectrics from the index of refraction, and scales it
+        // with the specular factor. See the loader for the opposite conversion.
+        let ior_f0 = ((material.ior - 1.0) / (material.ior + 1.0)).powi(2);
+        let f0 = 0.16 * material.reflectance * material.reflectance;
+        let specular_factor = if ior_f0 > 0.0 {
+            (f0 / ior_f0).min(1.0)
+        } else {
+            1.0
+        };
+        let specular_tint = linear_rgb(material.specular_tint);
+        if (specular_factor - 1.0).abs() > 1e-4 || specular_tint != [1.0; 3] {
+            self.use_extension("KHR_materials_specular");
+            material_extensions.specular = Some(extensions::material::Specular {
+                specular_factor: extensions::material::SpecularFactor(specular_factor),
+                specular_texture: None,
+                specular_color_factor: extensions::material::SpecularColorFactor(specular_tint),
+                specular_color_texture: None,
+                extras: Default::default(),
+            });
+        }
+
+        // Clearcoat and sheen aren't parsed by the `gltf` crate, so they are written as raw
+        // extension values.
+        if material.clearcoat > 0.0 {
+            self.use_extension("KHR_materials_clearcoat");
+            material_extensions.others.insert(
+                "KHR_materials_clearcoat".to_string(),
+                Value::from_iter([
+                    ("clearcoatFactor", Value::from(material.clearcoat)),
+                    (
+                        "clearcoatRoughnessFactor",
+                        Value::from(material.clearcoat_perceptual_roughness),
+                    ),
+                ]),
+            );
+        }
+        let sheen_color = linear_rgb(material.sheen_color);
+        if sheen_color != [0.0; 3] {
+            self.use_extension("KHR_materials_sheen");
+            material_extensions.others.insert(
+                "KHR_materials_sheen".to_string(),
+                Value::from_iter([
+                    ("sheenColorFactor", Value::from(sheen_color.to_vec())),
+                    (
+                        "sheenRoughnessFactor",
+                        Value::from(material.sheen_perceptual_roughness),
+                    ),
+                ]),
+            );
+        }
+
+        let index = self.root.push(json::Material {
+            alpha_cutoff,
+            alpha_mode: Checked::Valid(alpha_mode),
+            double_sided: material.double_sided,
+            name: None,
+            pbr_metallic_roughness: PbrMetallicRoughness {
+                base_color_factor: PbrBaseColorFactor(material.base_color.as_linear_rgba_f32()),
+                base_color_texture,
+                metallic_factor: StrengthFactor(material.metallic),
+                roughness_factor: StrengthFactor(material.perceptual_roughness),
+                metallic_roughness_texture,
+                extensions: None,
+                extras: Default::default(),
+            },
+            normal_texture,
+            occlusion_texture,
+            emissive_texture,
+            emissive_factor: EmissiveFactor(emissive),
+            extensions: Some(material_extensions),
+            extras: Default::default(),
+        });
+        self.materials.insert(material_handle.id(), index);
+        Some(index)
+    }
+
+    /// Exports an image as a PNG texture.
+    fn export_texture(&mut self, image_handle: &Handle<Image>) -> Option<Index<json::Texture>> {
+        if let Some(texture) = self.textures.get(&image_handle.id()) {
+            return *texture;
+        }
+        let texture = self.encode_image(image_handle).map(|png| {
+            let buffer_view = self.push_view(&png, None);
+            let source = self.root.push(json::Image {
+                buffer_view: Some(buffer_view),
+                mime_type: Some(json::image::MimeType("image/png".to_string())),
+                name: None,
+                uri: None,
+                extensions: None,
+                extras: Default::default(),
+            });
+            self.root.push(json::Texture {
+                name: None,
+                sampler: None,
+                source,
+                extensions: None,
+                extras: Default::default(),
+            })
+        });
+        self.textures.insert(image_handle.id(), texture);
+        texture
+    }
+
+    fn encode_image(&self, image_handle: &Handle<Image>) -> Option<Vec<u8>> {
+        let Some(image) = self
+            .world
+            .get_resource::<Assets<Image>>()
+            .and_then(|images| images.get(image_handle))
+        else {
+            warn!(
+                "Image {:?} is not loaded and won't be exported",
+                image_handle
+            );
+            return None;
+        };
+        let image = match image.clone().try_into_dynamic() {
+            Ok(image) => image,
+            Err(err) => {
+                warn!("Image {:?} won't be exported: {}", image_handle, err);
+                return None;
+            }
+        };
+        let mut png = Vec::new();
+        if let Err(err) = image.write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png) {
+            warn!("Failed to encode image {:?}: {}", image_handle, err);
+            return None;
+        }
+        Some(png)
+    }
+
+    /// Exports the skins of the exported skinned meshes, once every node is known.
+    fn export_skins(&mut self) {
+        let world = self.world;
+        for (node, skinned_mesh) in std::mem::take(&mut self.skins) {
+            let Some(joints) = skinned_mesh
+                .joints
+                .iter()
+                .map(|joint| self.nodes.get(joint).copied())
+                .collect::<Option<Vec<_>>>()
+            else {
+                warn!(
+                    "Skin of node {} has joints outside of the exported hierarchy and won't be exported",
+                    node.value()
+                );
+                continue;
+            };
+            let Some(inverse_bindposes) = world
+                .get_resource::<Assets<SkinnedMeshInverseBindposes>>()
+                .and_then(|inverse_bindposes| {
+                    inverse_bindposes.get(&skinned_mesh.inverse_bindposes)
+                })
+            else {
+                warn!(
+                    "Inverse bindposes of node {} are not loaded, its skin won't be exported",
+                    node.value()
+                );
+                continue;
+            };
+            let inverse_bind_matrices = self.push_accessor(
+                &f32_bytes(inverse_bindposes.iter().flat_map(Mat4::to_cols_array)),
+                inverse_bindposes.len(),
+                ComponentType::F32,
+                Type::Mat4,
+                None,
+                None,
+            );
+            let skin = self.root.push(json::Skin {
+                extensions: None,
+                extras: Default::default(),
+                inverse_bind_matrices: Some(inverse_bind_matrices),
+                joints,
+                name: None,
+                skeleton: None,
+            });
+            self.root.nodes[node.value()].skin = Some(skin);
+        }
+    }
+
+    /// Exports the animations, resolving their paths from `root` and every exported
+    /// [`AnimationPlayer`].
+    #[cfg(feature = "bevy_animation")]
+    fn export_animations(&mut self, root: Entity, animations: &[Handle<AnimationClip>]) {
+        use json::animation::{Channel, Interpolation, Property, Sampler, Target as ChannelTarget};
+
+        let world = self.world;
+        let mut paths = HashMap::default();
+        for &entity in self.nodes.keys() {
+            if entity == root || world.get::<AnimationPlayer>(entity).is_some() {
+                self.collect_paths(entity, EntityPath::default(), &mut paths);
+            }
+        }
+
+        for handle in animations {
+            let Some(clip) = world
+                .get_resource::<Assets<AnimationClip>>()
+                .and_then(|clips| clips.get(handle))
+            else {
+                warn!("Animation {:?} is not loaded and won't be exported", handle);
+                continue;
+            };
+
+            let mut channels = Vec::new();
+            let mut samplers = Vec::new();
+            for (path, curves) in clip.paths() {
+                let Some(&node) = paths.get(path) else {
+                    warn!(
+                        "Animation {:?} targets {:?} which isn't exported",
+                        handle, path.parts
+                    );
+                    continue;
+                };
+                for curve in curves {
+                    let timestamps = &curve.keyframe_timestamps;
+                    let (min, max) = timestamps
+                        .iter()
+                        .fold((f32::MAX, f32::MIN), |(min, max), time| {
+                            (min.min(*time), max.max(*time))
+                        });
+                    let input = self.push_accessor(
+                        &f32_bytes(timestamps.iter().copied()),
+                        timestamps.len(),
+                        ComponentType::F32,
+                        Type::Scalar,
+                        None,
+                        Some((Value::from(vec![min]), Value::from(vec![max]))),
+                    );
+                    let (property, bytes, count, type_) = match &curve.keyframes {
+                        Keyframes::Translation(translations) => (
+                            Property::Translation,
+                            f32_bytes(translations.iter().flat_map(|v| v.to_array())),
+                            translations.len(),
+                            Type::Vec3,
+                        ),
+                        Keyframes::Rotation(rotations) => (
+                            Property::Rotation,
+                            f32_bytes(rotations.iter().flat_map(|q| q.to_array())),
+                            rotations.len(),
+                            Type::Vec4,
+                        ),
+                        Keyframes::Scale(scales) => (
+                            Property::Scale,
+                            f32_bytes(scales.iter().flat_map(|v| v.to_array())),
+                            scales.len(),
+                            Type::Vec3,
+                        ),
+                    };
+                    let output =
+                        self.push_accessor(&bytes, count, ComponentType::F32, type_, None, None);
+                    samplers.push(Sampler {
+                        extensions: None,
+                        extras: Default::default(),
+                        input,
+                        interpolation: Checked::Valid(Interpolation::Linear),
+                        output,
+                    });
+                    channels.push(Channel {
+                        sampler: Index::new(samplers.len() as u32 - 1),
+                        target: ChannelTarget {
+                            extensions: None,
+                            extras: Default::default(),
+                            node,
+                            path: Checked::Valid(property),
+                        },
+                        extensions: None,
+                        extras: Default::default(),
+                    });
+                }
+            }
+
+            self.root.push(json::Animation {
+                extensions: None,
+                extras: Default::default(),
+                channels,
+                name: None,
+                samplers,
+            });
+        }
+    }
+
+    /// Collects the [`EntityPath`] of `entity` and its named descendants, relative to the
+    /// animation player owning `path`.
+    #[cfg(feature = "bevy_animation")]
+    fn collect_paths(
+        &self,
+        entity: Entity,
+        mut path: EntityPath,
+        paths: &mut HashMap<EntityPath, Index<json::Node>>,
+    ) {
+        // Animations can only target entities whose whole path is named.
+        let Some(name) = self.world.get::<Name>(entity) else {
+            return;
+        };
+        path.parts.push(name.clone());
+        if let Some(children) = self.world.get::<Children>(entity) {
+            for child in children.iter() {
+                self.collect_paths(*child, path.clone(), paths);
+            }
+        }
+        paths.insert(path, self.nodes[&entity]);
+    }
+
+    /// Appends `bytes` to the buffer, aligned to 4 bytes, and returns the view to them.
+    fn push_view(&mut self, bytes: &[u8], target: Option<Target>) -> Index<json::buffer::View> {
+        self.buffer.resize(align_to_four(self.buffer.len()), 0);
+        let offset = self.buffer.len();
+        self.buffer.extend_from_slice(bytes);
+        self.root.push(json::buffer::View {
+            buffer: Index::new(0),
+            byte_length: USize64::from(bytes.len()),
+            byte_offset: Some(USize64::from(offset)),
+            byte_stride: None,
+            name: None,
+            target: target.map(Checked::Valid),
+            extensions: None,
+            extras: Default::default(),
+        })
+    }
+
+    fn push_accessor(
+        &mut self,
+        bytes: &[u8],
+        count: usize,
+        component_type: ComponentType,
+        type_: Type,
+        target: Option<Target>,
+        bounds: Option<(Value, Value)>,
+    ) -> Index<json::Accessor> {
+        let buffer_view = self.push_view(bytes, target);
+        let (min, max) = bounds.unzip();
+        self.root.push(json::Accessor {
+            buffer_view: Some(buffer_view),
+            byte_offset: None,
+            count: USize64::from(count),
+            component_type: Checked::Valid(GenericComponentType(component_type)),
+            extensions: None,
+            extras: Default::default(),
+            type_: Checked::Valid(type_),
+            min,
+            max,
+            name: None,
+            normalized: false,
+            sparse: None,
+        })
+    }
+
+    /// Writes the buffer and the lights, and serializes the file.
+    fn finish(mut self, format: GltfExportFormat) -> Result<Vec<u8>, GltfExportError> {
+        if !self.lights.is_empty() {
+            self.root.extensions = Some(extensions::root::Root {
+                khr_lights_punctual: Some(extensions::root::KhrLightsPunctual {
+                    lights: std::mem::take(&mut self.lights),
+                }),
+                ..Default::default()
+            });
+        }
+
+        self.buffer.resize(align_to_four(self.buffer.len()), 0);
+        if !self.buffer.is_empty() {
+            let uri = match format {
+                GltfExportFormat::Binary => None,
+                GltfExportFormat::Embedded => Some(format!(
+                    "data:application/octet-stream;base64,{}",
+                    base64::encode(&self.buffer)
+                )),
+            };
+            self.root.push(json::Buffer {
+                byte_length: USize64::from(self.buffer.len()),
+                name: None,
+                uri,
+                extensions: None,
+                extras: Default::default(),
+            });
+        }
+
+        match format {
+            GltfExportFormat::Binary => {
+                let json = json::serialize::to_vec(&self.root)?;
+                let glb = gltf::binary::Glb {
+                    header: gltf::binary::Header {
+                        magic: *b"glTF",
+                        version: 2,
+                        // Computed when writing.
+                        length: 0,
+                    },
+                    json: json.into(),
+                    bin: (!self.buffer.is_empty()).then(|| self.buffer.into()),
+                };
+                Ok(glb.to_vec()?)
+            }
+            GltfExportFormat::Embedded => Ok(json::serialize::to_vec_pretty(&self.root)?),
+        }
+    }
+}
+
+/// Decomposes a UV transform into a `KHR_texture_transform`, if it isn't the identity.
+fn texture_transform(uv_transform: Affine2) -> Option<TextureTransform> {
+    if uv_transform == Affine2::IDENTITY {
+        return None;
+    }
+    let x_axis = uv_transform.matrix2.x_axis;
+    let y_axis = uv_transform.matrix2.y_axis;
+    Some(TextureTransform {
+        offset: TextureTransformOffset(uv_transform.translation.to_array()),
+        // glTF rotates UVs clockwise.
+        rotation: TextureTransformRotation(-x_axis.y.atan2(x_axis.x)),
+        scale: TextureTransformScale([x_axis.length(), y_axis.length()]),
+        tex_coord: None,
+        extras: Default::default(),
+    })
+}
+
+fn linear_rgb(color: Color) -> [f32; 3] {
+    let [red, green, blue, _] = color.as_linear_rgba_f32();
+    [red, green, blue]
+}
+
+fn f32_bytes(values: impl IntoIterator<Item = f32>) -> Vec<u8> {
+    values
+        .into_iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect()
+}
+
+fn align_to_four(length: usize) -> usize {
+    (length + 3) & !3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Gltf, GltfMesh, GltfNode, GltfPlugin};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, AssetServer, LoadState};
+    use bevy_core::TaskPoolPlugin;
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::{Quat, Vec3};
+    use bevy_render::mesh::shape;
+    use bevy_scene::Scene;
+    use std::path::PathBuf;
+
+    fn app(asset_folder: &Path) -> App {
+        let mut app = App::new();
+        app.add_plugin(TaskPoolPlugin::default())
+            .add_plugin(AssetPlugin {
+                asset_folder: asset_folder.to_string_lossy().into_owned(),
+                watch_for_changes: false,
+            })
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .add_asset::<Image>()
+            .add_asset::<Scene>()
+            .add_asset::<SkinnedMeshInverseBindposes>()
+            .add_plugin(GltfPlugin);
+        #[cfg(feature = "bevy_animation")]
+        app.add_asset::<AnimationClip>();
+        app
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bevy_gltf_{name}_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// Spawns a small hierarchy with a skinned mesh, a light and a camera, and returns its root.
+    fn spawn_hierarchy(app: &mut App) -> Entity {
+        let mut mesh = Mesh::from(shape::Cube { size: 1.0 });
+        let vertex_count = mesh.count_vertices();
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0; 4]; vertex_count]),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[1.0f32, 0.0, 0.0, 0.0]; vertex_count],
+        );
+        let mesh = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+        let material = app
+            .world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::rgba_linear(0.2, 0.4, 0.6, 1.0),
+                metallic: 0.25,
+                perceptual_roughness: 0.75,
+                emissive: Color::rgb_linear(4.0, 2.0, 0.0),
+                ..Default::default()
+            });
+        let inverse_bindposes = app
+            .world
+            .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .add(vec![Mat4::from_translation(Vec3::new(0.0, -1.0, 0.0))].into());
+
+        let root = app
+            .world
+            .spawn((
+                Name::new("root"),
+                Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(Quat::from_rotation_y(0.5)),
+            ))
+            .id();
+        let joint = app
+            .world
+            .spawn((Name::new("joint"), Transform::from_xyz(0.0, 1.0, 0.0)))
+            .id();
+        let cube = app
+            .world
+            .spawn((
+                Name::new("cube"),
+                Transform::from_scale(Vec3::splat(2.0)),
+                mesh,
+                material,
+                SkinnedMesh {
+                    inverse_bindposes,
+                    joints: vec![joint],
+                },
+            ))
+            .id();
+        let light = app
+            .world
+            .spawn((
+                Name::new("light"),
+                PointLight {
+                    intensity: 1000.0,
+                    range: 10.0,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        let camera = app
+            .world
+            .spawn((
+                Name::new("camera"),
+                Camera::default(),
+                Projection::Perspective(PerspectiveProjection {
+                    fov: 1.0,
+                    ..Default::default()
+                }),
+            ))
+            .id();
+        app.world
+            .entity_mut(root)
+            .push_children(&[joint, cube, light, camera]);
+        root
+    }
+
+    #[test]
+    fn export_embedded_is_valid() {
+        let dir = temp_dir("embedded");
+        let mut app = app(&dir);
+        let root = spawn_hierarchy(&mut app);
+
+        let bytes = GltfExporter::new(&app.world)
+            .with_format(GltfExportFormat::Embedded)
+            .export(root)
+            .unwrap();
+        let gltf = gltf::Gltf::from_slice(&bytes).unwrap();
+
+        assert!(gltf.blob.is_none());
+        assert_eq!(gltf.nodes().count(), 5);
+        assert_eq!(gltf.meshes().count(), 1);
+        assert_eq!(gltf.skins().count(), 1);
+        assert_eq!(gltf.cameras().count(), 1);
+        assert!(gltf
+            .buffers()
+            .all(|buffer| matches!(buffer.source(), gltf::buffer::Source::Uri(_))));
+    }
+
+    #[test]
+    fn export_missing_entity() {
+        let world = World::new();
+        let entity = Entity::from_raw(42);
+        assert!(matches!(
+            GltfExporter::new(&world).export(entity),
+            Err(GltfExportError::NoSuchEntity(e)) if e == entity
+        ));
+    }
+
+    #[test]
+    fn round_trip_through_loader() {
+        let dir = temp_dir("round_trip");
+        let mut app = app(&dir);
+        let root = spawn_hierarchy(&mut app);
+
+        #[cfg(feature = "bevy_animation")]
+        let exporter = {
+            let mut clip = AnimationClip::default();
+            clip.add_curve_to_path(
+                EntityPath {
+                    parts: vec![Name::new("root"), Name::new("cube")],
+                },
+                bevy_animation::VariableCurve {
+                    keyframe_timestamps: vec![0.0, 1.5],
+                    keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+                },
+            );
+            let clip = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+            GltfExporter::new(&app.world).with_animation(clip)
+        };
+        #[cfg(not(feature = "bevy_animation"))]
+        let exporter = GltfExporter::new(&app.world);
+        exporter.save(root, dir.join("round_trip.glb")).unwrap();
+
+        let mut app = self::app(&dir);
+        let handle: Handle<Gltf> = app.world.resource::<AssetServer>().load("round_trip.glb");
+        for _ in 0..1000 {
+            app.update();
+            match app.world.resource::<AssetServer>().get_load_state(&handle) {
+                LoadState::Loaded | LoadState::Failed => break,
+                _ => std::thread::sleep(std::time::Duration::from_millis(1)),
+            }
+        }
+        assert_eq!(
+            app.world.resource::<AssetServer>().get_load_state(&handle),
+            LoadState::Loaded
+        );
+        let gltf = app.world.resource::<Assets<Gltf>>().get(&handle).unwrap();
+
+        let root_node = app
+            .world
+            .resource::<Assets<GltfNode>>()
+            .get(&gltf.named_nodes["root"])
+            .unwrap();
+        assert!(root_node
+            .transform
+            .translation
+            .abs_diff_eq(Vec3::new(1.0, 2.0, 3.0), 1e-6));
+        assert!(root_node
+            .transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_y(0.5), 1e-6));
+        assert_eq!(root_node.children.len(), 4);
+
+        let gltf_mesh = app
+            .world
+            .resource::<Assets<GltfMesh>>()
+            .get(&gltf.meshes[0])
+            .unwrap();
+        let primitive = &gltf_mesh.primitives[0];
+        let mesh = app
+            .world
+            .resource::<Assets<Mesh>>()
+            .get(&primitive.mesh)
+            .unwrap();
+        let original = Mesh::from(shape::Cube { size: 1.0 });
+        assert_eq!(mesh.count_vertices(), original.count_vertices());
+        assert_eq!(
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3(),
+            original
+                .attribute(Mesh::ATTRIBUTE_POSITION)
+                .unwrap()
+                .as_float3()
+        );
+        assert_eq!(
+            mesh.indices().unwrap().iter().collect::<Vec<_>>(),
+            original.indices().unwrap().iter().collect::<Vec<_>>()
+        );
+        assert!(mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX).is_some());
+
+        let material = app
+            .world
+            .resource::<Assets<StandardMaterial>>()
+            .get(primitive.material.as_ref().unwrap())
+            .unwrap();
+        assert_eq!(
+            material.base_color.as_linear_rgba_f32(),
+            [0.2, 0.4, 0.6, 1.0]
+        );
+        assert_eq!(material.metallic, 0.25);
+        assert_eq!(material.perceptual_roughness, 0.75);
+        assert_eq!(linear_rgb(material.emissive), [4.0, 2.0, 0.0]);
+        assert!((material.reflectance - 0.5).abs() < 1e-4);
+
+        #[cfg(feature = "bevy_animation")]
+        assert_eq!(gltf.animations.len(), 1);
+
+        let scene = gltf.scenes[0].clone();
+        let mut scenes = app.world.resource_mut::<Assets<Scene>>();
+        let world = &mut scenes.get_mut(&scene).unwrap().world;
+
+        let lights = world.query::<&PointLight>().iter(world).collect::<Vec<_>>();
+        assert_eq!(lights.len(), 1);
+        assert!((lights[0].intensity - 1000.0).abs() < 1e-2);
+        assert_eq!(lights[0].range, 10.0);
+
+        let projections = world
+            .query::<(&Camera, &Projection)>()
+            .iter(world)
+            .map(|(_, projection)| projection.clone())
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            projections[..],
+            [Projection::Perspective(PerspectiveProjection { fov, .. })] if fov == 1.0
+        ));
+
+        let skins = world
+            .query::<&SkinnedMesh>()
+            .iter(world)
+            .collect::<Vec<_>>();
+        assert_eq!(skins.len(), 1);
+        assert_eq!(skins[0].joints.len(), 1);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -4,7 +4,9 @@
 use bevy_animation::AnimationClip;
 use bevy_utils::HashMap;
 
+mod exporter;
 mod loader;
+pub use exporter::*;
 pub use loader::*;
 
 use bevy_app::prelude::*;
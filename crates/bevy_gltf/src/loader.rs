@@ -7,7 +7,7 @@ use bevy_core_pipeline::prelude::Camera3dBundle;
 use bevy_ecs::{entity::Entity, prelude::FromWorld, world::World};
 use bevy_hierarchy::{BuildWorldChildren, WorldChildBuilder};
 use bevy_log::warn;
-use bevy_math::{Mat4, Vec3};
+use bevy_math::{Affine2, Mat4, Vec2, Vec3};
 use bevy_pbr::{
     AlphaMode, DirectionalLight, DirectionalLightBundle, PbrBundle, PointLight, PointLightBundle,
     SpotLight, SpotLightBundle, StandardMaterial,
@@ -64,8 +64,24 @@ pub enum GltfError {
     MissingAnimationSampler(usize),
     #[error("failed to generate tangents: {0}")]
     GenerateTangentsError(#[from] bevy_render::mesh::GenerateTangentsError),
+    #[error("unsupported required glTF extension: {0}")]
+    UnsupportedExtension(String),
 }
 
+/// glTF extensions that can be listed in `extensionsRequired` by the files this loader loads.
+pub const SUPPORTED_EXTENSIONS: &[&str] = &[
+    "KHR_lights_punctual",
+    "KHR_materials_unlit",
+    "KHR_materials_emissive_strength",
+    "KHR_texture_transform",
+    "KHR_materials_transmission",
+    "KHR_materials_volume",
+    "KHR_materials_ior",
+    "KHR_materials_specular",
+    "KHR_materials_clearcoat",
+    "KHR_materials_sheen",
+];
+
 /// Loads glTF files with all of their data as their corresponding bevy representations.
 pub struct GltfLoader {
     supported_compressed_formats: CompressedImageFormats,
@@ -106,7 +122,7 @@ async fn load_gltf<'a, 'b>(
     load_context: &'a mut LoadContext<'b>,
     supported_compressed_formats: CompressedImageFormats,
 ) -> Result<(), GltfError> {
-    let gltf = gltf::Gltf::from_slice(bytes)?;
+    let gltf = validate_gltf(gltf::Gltf::from_slice_without_validation(bytes)?)?;
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
 
     let mut materials = vec![];
@@ -627,6 +643,24 @@ async fn load_texture<'a>(
     Ok((texture, texture_label(&gltf_texture)))
 }
 
+/// Checks that every extension required by a glTF file is supported, then validates the file.
+fn validate_gltf(gltf: gltf::Gltf) -> Result<gltf::Gltf, GltfError> {
+    let gltf::Gltf { document, blob } = gltf;
+    let mut json = document.into_json();
+    if let Some(extension) = json
+        .extensions_required
+        .iter()
+        .find(|extension| !SUPPORTED_EXTENSIONS.contains(&extension.as_str()))
+    {
+        return Err(GltfError::UnsupportedExtension(extension.clone()));
+    }
+    // The `gltf` crate rejects required extensions it doesn't parse itself, like clearcoat and
+    // sheen, even though they are read from the raw extension values below.
+    json.extensions_required.clear();
+    let document = gltf::Document::from_json(json)?;
+    Ok(gltf::Gltf { document, blob })
+}
+
 /// Loads a glTF material as a bevy [`StandardMaterial`] and returns it.
 fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<StandardMaterial> {
     let material_label = material_label(material);
@@ -665,7 +699,6 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
         load_context.get_handle(path)
     });
 
-    let emissive = material.emissive_factor();
     let emissive_texture = material.emissive_texture().map(|info| {
         // TODO: handle occlusion_texture.tex_coord() (the *set* index for the right texcoords)
         // TODO: handle occlusion_texture.strength() (a scalar multiplier for occlusion strength)
@@ -690,15 +723,168 @@ fn load_material(material: &Material, load_context: &mut LoadContext) -> Handle<
                 Some(Face::Back)
             },
             occlusion_texture,
-            emissive: Color::rgb_linear(emissive[0], emissive[1], emissive[2]),
             emissive_texture,
             unlit: material.unlit(),
-            alpha_mode: alpha_mode(material),
-            ..Default::default()
+            ..material_extensions(material)
         }),
     )
 }
 
+/// Reads the emissive color, the alpha mode and the `KHR_materials_*` and
+/// `KHR_texture_transform` extensions of a glTF material into the matching [`StandardMaterial`]
+/// fields.
+fn material_extensions(material: &Material) -> StandardMaterial {
+    let pbr = material.pbr_metallic_roughness();
+    let emissive = material.emissive_factor();
+
+    // Bevy only supports a single UV transform per material, so the transform of the base color
+    // texture is used for every texture.
+    let uv_transform = pbr
+        .base_color_texture()
+        .and_then(|info| info.texture_transform())
+        .map(|transform| {
+            Affine2::from_scale_angle_translation(
+                Vec2::from(transform.scale()),
+                -transform.rotation(),
+                Vec2::from(transform.offset()),
+            )
+        })
+        .unwrap_or_default();
+
+    let emissive_strength = material.emissive_strength().unwrap_or(1.0);
+
+    let specular_transmission = material
+        .transmission()
+        .map_or(0.0, |transmission| transmission.transmission_factor());
+
+    let (thickness, attenuation_distance, attenuation_color) =
+        material
+            .volume()
+            .map_or((0.0, f32::INFINITY, [1.0, 1.0, 1.0]), |volume| {
+                (
+                    volume.thickness_factor(),
+                    volume.attenuation_distance(),
+                    volume.attenuation_color(),
+                )
+            });
+
+    let ior = material.ior().unwrap_or(1.5);
+
+    // glTF derives the reflectance of dielectrics from the index of refraction, scaled by the
+    // specular factor, while bevy maps `reflectance` to `0.16 * reflectance^2`.
+    let (specular_factor, specular_tint) = material
+        .specular()
+        .map_or((1.0, [1.0, 1.0, 1.0]), |specular| {
+            (specular.specular_factor(), specular.specular_color_factor())
+        });
+    let f0 = ((ior - 1.0) / (ior + 1.0)).powi(2) * specular_factor;
+    let reflectance = (f0 / 0.16).sqrt().clamp(0.0, 1.0);
+
+    let clearcoat = material.extension_value("KHR_materials_clearcoat");
+    let sheen = material.extension_value("KHR_materials_sheen");
+    for (name, extension, textures) in [
+        (
+            "KHR_materials_clearcoat",
+            clearcoat,
+            &[
+                "clearcoatTexture",
+                "clearcoatRoughnessTexture",
+                "clearcoatNormalTexture",
+            ][..],
+        ),
+        (
+            "KHR_materials_sheen",
+            sheen,
+            &["sheenColorTexture", "sheenRoughnessTexture"][..],
+        ),
+    ] {
+        if let Some(texture) = textures
+            .iter()
+            .find(|texture| extension.and_then(|value| value.get(**texture)).is_some())
+        {
+            warn!("{texture} of {name} is not supported, only its factors are used");
+        }
+    }
+    for (name, texture) in [
+        (
+            "KHR_materials_transmission",
+            material
+                .transmission()
+                .and_then(|transmission| transmission.transmission_texture())
+                .is_some(),
+        ),
+        (
+            "KHR_materials_volume",
+            material
+                .volume()
+                .and_then(|volume| volume.thickness_texture())
+                .is_some(),
+        ),
+        (
+            "KHR_materials_specular",
+            material.specular().into_iter().any(|specular| {
+                specular.specular_texture().is_some() || specular.specular_color_texture().is_some()
+            }),
+        ),
+    ] {
+        if texture {
+            warn!("textures of {name} are not supported, only its factors are used");
+        }
+    }
+
+    let clearcoat_factor = |key| {
+        clearcoat
+            .and_then(|value| value.get(key))
+            .and_then(gltf::json::Value::as_f64)
+            .unwrap_or(0.0) as f32
+    };
+    let sheen_color = sheen
+        .and_then(|value| value.get("sheenColorFactor"))
+        .and_then(gltf::json::Value::as_array)
+        .map(|color| {
+            let channel = |i: usize| {
+                color
+                    .get(i)
+                    .and_then(gltf::json::Value::as_f64)
+                    .unwrap_or(0.0)
+            };
+            Color::rgb_linear(channel(0) as f32, channel(1) as f32, channel(2) as f32)
+        })
+        .unwrap_or(Color::BLACK);
+    let sheen_perceptual_roughness = sheen
+        .and_then(|value| value.get("sheenRoughnessFactor"))
+        .and_then(gltf::json::Value::as_f64)
+        .unwrap_or(0.0) as f32;
+
+    // Transmission is approximated by blending with what is behind the mesh.
+    let alpha_mode = match alpha_mode(material) {
+        AlphaMode::Opaque if specular_transmission > 0.0 => AlphaMode::Premultiplied,
+        alpha_mode => alpha_mode,
+    };
+
+    StandardMaterial {
+        emissive: Color::rgb_linear(emissive[0], emissive[1], emissive[2]) * emissive_strength,
+        alpha_mode,
+        reflectance,
+        uv_transform,
+        clearcoat: clearcoat_factor("clearcoatFactor"),
+        clearcoat_perceptual_roughness: clearcoat_factor("clearcoatRoughnessFactor"),
+        specular_transmission,
+        thickness,
+        ior,
+        attenuation_distance,
+        attenuation_color: Color::rgb_linear(
+            attenuation_color[0],
+            attenuation_color[1],
+            attenuation_color[2],
+        ),
+        specular_tint: Color::rgb_linear(specular_tint[0], specular_tint[1], specular_tint[2]),
+        sheen_color,
+        sheen_perceptual_roughness,
+        ..Default::default()
+    }
+}
+
 /// Loads a glTF node.
 fn load_node(
     gltf_node: &gltf::Node,
@@ -1161,8 +1347,102 @@ impl<'a> DataUri<'a> {
 mod test {
     use std::path::PathBuf;
 
-    use super::resolve_node_hierarchy;
+    use super::{material_extensions, resolve_node_hierarchy, validate_gltf, GltfError};
     use crate::GltfNode;
+    use bevy_pbr::{AlphaMode, StandardMaterial};
+    use bevy_render::color::Color;
+
+    fn load_material(json: &str) -> Result<StandardMaterial, GltfError> {
+        let gltf = validate_gltf(gltf::Gltf::from_slice_without_validation(json.as_bytes())?)?;
+        let material = gltf.materials().next().unwrap();
+        Ok(material_extensions(&material))
+    }
+
+    #[test]
+    fn material_without_extensions() {
+        let material = load_material(r#"{"asset":{"version":"2.0"},"materials":[{}]}"#).unwrap();
+        assert_eq!(material.emissive, Color::rgb_linear(0.0, 0.0, 0.0));
+        assert_eq!(material.alpha_mode, AlphaMode::Opaque);
+        assert_eq!(material.ior, 1.5);
+        assert!((material.reflectance - 0.5).abs() < 1e-6);
+        assert_eq!(material.specular_transmission, 0.0);
+        assert_eq!(material.clearcoat, 0.0);
+        assert_eq!(material.sheen_color, Color::BLACK);
+    }
+
+    #[test]
+    fn material_khr_extensions() {
+        let material = load_material(
+            r#"{
+                "asset": {"version": "2.0"},
+                "extensionsUsed": ["KHR_materials_clearcoat", "KHR_materials_sheen"],
+                "extensionsRequired": ["KHR_materials_clearcoat"],
+                "materials": [{
+                    "emissiveFactor": [1.0, 0.5, 0.0],
+                    "extensions": {
+                        "KHR_materials_emissive_strength": {"emissiveStrength": 2.0},
+                        "KHR_materials_transmission": {"transmissionFactor": 0.5},
+                        "KHR_materials_volume": {
+                            "thicknessFactor": 0.1,
+                            "attenuationDistance": 2.0,
+                            "attenuationColor": [1.0, 0.5, 0.25]
+                        },
+                        "KHR_materials_ior": {"ior": 2.0},
+                        "KHR_materials_specular": {
+                            "specularFactor": 0.5,
+                            "specularColorFactor": [1.0, 0.0, 0.0]
+                        },
+                        "KHR_materials_clearcoat": {
+                            "clearcoatFactor": 0.8,
+                            "clearcoatRoughnessFactor": 0.2
+                        },
+                        "KHR_materials_sheen": {
+                            "sheenColorFactor": [0.5, 0.25, 0.5],
+                            "sheenRoughnessFactor": 0.3
+                        }
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(material.emissive, Color::rgb_linear(2.0, 1.0, 0.0));
+        // Transmissive materials are blended with what is behind them.
+        assert_eq!(material.alpha_mode, AlphaMode::Premultiplied);
+        assert_eq!(material.specular_transmission, 0.5);
+        assert_eq!(material.thickness, 0.1);
+        assert_eq!(material.attenuation_distance, 2.0);
+        assert_eq!(
+            material.attenuation_color,
+            Color::rgb_linear(1.0, 0.5, 0.25)
+        );
+        assert_eq!(material.ior, 2.0);
+        // f0 = ((2 - 1) / (2 + 1))^2 * 0.5 = 0.16 * reflectance^2
+        let reflectance = (1.0f32 / 18.0 / 0.16).sqrt();
+        assert!((material.reflectance - reflectance).abs() < 1e-6);
+        assert_eq!(material.specular_tint, Color::rgb_linear(1.0, 0.0, 0.0));
+        assert!((material.clearcoat - 0.8).abs() < 1e-6);
+        assert!((material.clearcoat_perceptual_roughness - 0.2).abs() < 1e-6);
+        assert_eq!(material.sheen_color, Color::rgb_linear(0.5, 0.25, 0.5));
+        assert!((material.sheen_perceptual_roughness - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unsupported_required_extension() {
+        let result = load_material(
+            r#"{
+                "asset": {"version": "2.0"},
+                "extensionsUsed": ["KHR_draco_mesh_compression"],
+                "extensionsRequired": ["KHR_draco_mesh_compression"],
+                "materials": [{}]
+            }"#,
+        );
+        assert!(matches!(
+            result,
+            Err(GltfError::UnsupportedExtension(extension))
+                if extension == "KHR_draco_mesh_compression"
+        ));
+    }
 
     impl GltfNode {
         fn empty() -> Self {
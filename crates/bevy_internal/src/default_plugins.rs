@@ -27,6 +27,10 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`AudioPlugin`](crate::audio::AudioPlugin) - with feature `bevy_audio`
 /// * [`GilrsPlugin`](crate::gilrs::GilrsPlugin) - with feature `bevy_gilrs`
 /// * [`AnimationPlugin`](crate::animation::AnimationPlugin) - with feature `bevy_animation`
+/// * [`PickingPlugin`](crate::picking::PickingPlugin) - with feature `bevy_picking`
+/// * [`MeshPickingPlugin`](crate::picking::backend::mesh::MeshPickingPlugin) - with feature `bevy_picking`
+/// * [`SpritePickingPlugin`](crate::picking::backend::sprite::SpritePickingPlugin) - with features `bevy_picking` and `bevy_sprite`
+/// * [`UiPickingPlugin`](crate::picking::backend::ui::UiPickingPlugin) - with features `bevy_picking` and `bevy_ui`
 ///
 /// [`DefaultPlugins`] obeys *Cargo* *feature* flags. Users may exert control over this plugin group
 /// by disabling `default-features` in their `Cargo.toml` and enabling only those features
@@ -140,6 +144,23 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_gizmos::GizmoPlugin);
         }
 
+        #[cfg(feature = "bevy_picking")]
+        {
+            group = group
+                .add(bevy_picking::PickingPlugin)
+                .add(bevy_picking::backend::mesh::MeshPickingPlugin);
+
+            #[cfg(feature = "bevy_sprite")]
+            {
+                group = group.add(bevy_picking::backend::sprite::SpritePickingPlugin);
+            }
+
+            #[cfg(feature = "bevy_ui")]
+            {
+                group = group.add(bevy_picking::backend::ui::UiPickingPlugin);
+            }
+        }
+
         group
     }
 }
@@ -192,6 +192,30 @@ pub mod gizmos {
     pub use bevy_gizmos::*;
 }
 
+#[cfg(feature = "bevy_picking")]
+pub mod picking {
+    //! Entity picking with pointer events, for meshes, sprites and UI.
+    pub use bevy_picking::*;
+}
+
+#[cfg(feature = "bevy_remote")]
+pub mod remote {
+    //! Remote inspection and editing of the app over JSON-RPC.
+    pub use bevy_remote::*;
+}
+
+#[cfg(feature = "bevy_replication")]
+pub mod replication {
+    //! Replication of entities from a server world to clients.
+    pub use bevy_replication::*;
+}
+
+#[cfg(feature = "bevy_console")]
+pub mod console {
+    //! In-game console displaying the logs and running commands.
+    pub use bevy_console::*;
+}
+
 #[cfg(feature = "bevy_dynamic_plugin")]
 pub mod dynamic_plugin {
     //! Dynamic linking of plugins
@@ -55,6 +55,10 @@ pub use crate::dynamic_plugin::*;
 #[cfg(feature = "bevy_gizmos")]
 pub use crate::gizmos::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_picking")]
+pub use crate::picking::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_gilrs")]
 pub use crate::gilrs::*;
@@ -0,0 +1,344 @@
+//! Captures log records into the ECS, for them to be displayed in the app, like in an in-game
+//! console.
+
+use bevy_ecs::prelude::*;
+use bevy_utils::{
+    tracing::{
+        field::Field,
+        span::{Attributes, Record},
+        Event, Id, Level, Subscriber,
+    },
+    Instant,
+};
+use crossbeam_channel::{Receiver, Sender};
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug},
+};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{field::Visit, layer::Context, registry::LookupSpan, Layer};
+
+/// A log record captured by a [`CaptureLayer`].
+///
+/// Records are stored in the [`LogRecords`] resource and sent as events by
+/// [`capture_log_records`].
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    /// The level of the record.
+    pub level: Level,
+    /// The target of the record, the module path of the call site by default.
+    pub target: String,
+    /// The message of the record.
+    pub message: String,
+    /// The other fields of the record, formatted with [`Debug`].
+    pub fields: Vec<(String, String)>,
+    /// The spans the record was emitted in, from the root to the innermost one.
+    pub spans: Vec<LogSpan>,
+    /// The instant the record was emitted at.
+    pub timestamp: Instant,
+}
+
+impl LogRecord {
+    /// Creates a record emitted now, outside of any span.
+    pub fn new(level: Level, target: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            target: target.into(),
+            message: message.into(),
+            fields: Vec::new(),
+            spans: Vec::new(),
+            timestamp: Instant::now(),
+        }
+    }
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5} ", self.level)?;
+        for span in &self.spans {
+            write!(f, "{span}:")?;
+        }
+        write!(f, " {}: {}", self.target, self.message)?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A span a [`LogRecord`] was emitted in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogSpan {
+    /// The name of the span.
+    pub name: String,
+    /// The fields of the span, formatted with [`Debug`].
+    pub fields: Vec<(String, String)>,
+}
+
+impl fmt::Display for LogSpan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.fields.is_empty() {
+            write!(f, "{{")?;
+            for (i, (name, value)) in self.fields.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{name}={value}")?;
+            }
+            write!(f, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The latest [`LogRecord`]s captured by the [`CaptureLayer`] it was created with.
+///
+/// Once [`capacity`](LogRecords::capacity) records are stored, the oldest ones are dropped.
+#[derive(Resource)]
+pub struct LogRecords {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    receiver: Receiver<LogRecord>,
+}
+
+impl LogRecords {
+    /// The number of records stored by default.
+    pub const DEFAULT_CAPACITY: usize = 1000;
+
+    /// Returns the stored records, from the oldest to the latest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogRecord> + ExactSizeIterator {
+        self.records.iter()
+    }
+
+    /// Returns the number of stored records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no record is stored.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the maximum number of stored records.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of stored records, dropping the oldest ones over it.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Stores a record that was not emitted through `tracing`, like the output of a command.
+    pub fn push(&mut self, record: LogRecord) {
+        self.records.push_back(record);
+        self.truncate();
+    }
+
+    /// Drops all the stored records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    fn truncate(&mut self) {
+        let excess = self.records.len().saturating_sub(self.capacity);
+        self.records.drain(..excess);
+    }
+}
+
+/// A [`Layer`] sending the log records to a [`LogRecords`] resource.
+///
+/// The [`LogPlugin`](crate::LogPlugin) adds it when [`capture`](crate::LogPlugin::capture) is
+/// enabled. Custom subscribers can add it too, and insert the resource and
+/// [`capture_log_records`] themselves.
+pub struct CaptureLayer {
+    sender: Sender<LogRecord>,
+}
+
+impl CaptureLayer {
+    /// Creates a layer and the resource receiving its records.
+    pub fn new() -> (Self, LogRecords) {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let records = LogRecords {
+            records: VecDeque::new(),
+            capacity: LogRecords::DEFAULT_CAPACITY,
+            receiver,
+        };
+        (Self { sender }, records)
+    }
+}
+
+/// The fields of a span, stored in its extensions.
+struct SpanFields(Vec<(String, String)>);
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            // Metadata of the records of the `log` crate, normalized by `tracing_log`.
+            name if name.starts_with("log.") => {}
+            name => self.fields.push((name.to_string(), format!("{value:?}"))),
+        }
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(visitor.fields));
+        }
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut visitor = FieldVisitor::default();
+            values.record(&mut visitor);
+            if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+                fields.0.extend(visitor.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let spans = ctx
+            .event_scope(event)
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+            .map(|span| LogSpan {
+                name: span.name().to_string(),
+                fields: span
+                    .extensions()
+                    .get::<SpanFields>()
+                    .map(|fields| fields.0.clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        // The resource may have been dropped with its app.
+        let _ = self.sender.send(LogRecord {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+            spans,
+            timestamp: Instant::now(),
+        });
+    }
+}
+
+/// Stores the records captured since the last run in [`LogRecords`], and sends them as
+/// [`LogRecord`] events.
+pub fn capture_log_records(mut records: ResMut<LogRecords>, mut events: EventWriter<LogRecord>) {
+    while let Ok(record) = records.receiver.try_recv() {
+        records.push(record.clone());
+        events.send(record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::event::Events;
+    use bevy_utils::tracing::{self, field, info, info_span, warn};
+    use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+    fn capture(f: impl FnOnce()) -> (LogRecords, Vec<LogRecord>) {
+        let (layer, records) = CaptureLayer::new();
+        tracing::subscriber::with_default(Registry::default().with(layer), f);
+
+        let mut world = World::new();
+        world.insert_resource(records);
+        world.init_resource::<Events<LogRecord>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(capture_log_records);
+        schedule.run(&mut world);
+        let events = world.resource_mut::<Events<LogRecord>>().drain().collect();
+        (world.remove_resource::<LogRecords>().unwrap(), events)
+    }
+
+    fn fields(fields: &[(&str, &str)]) -> Vec<(String, String)> {
+        fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn captures_fields_and_spans() {
+        let (records, events) = capture(|| {
+            let span = info_span!("frame", index = 1, late = field::Empty);
+            let _entered = span.enter();
+            span.record("late", 5);
+            info!(log.file = "main.rs", count = 3, "hello {}", "world");
+        });
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(events.len(), 1);
+        let record = &events[0];
+        assert_eq!(record.level, Level::INFO);
+        assert_eq!(record.target, module_path!());
+        assert_eq!(record.message, "hello world");
+        // The `log.` fields are metadata of the `log` crate, not fields of the record.
+        assert_eq!(record.fields, fields(&[("count", "3")]));
+        assert_eq!(
+            record.spans,
+            vec![LogSpan {
+                name: "frame".to_string(),
+                fields: fields(&[("index", "1"), ("late", "5")]),
+            }]
+        );
+        assert_eq!(
+            record.to_string(),
+            format!(
+                " INFO frame{{index=1 late=5}}: {}: hello world count=3",
+                module_path!()
+            )
+        );
+    }
+
+    #[test]
+    fn capacity_drops_oldest_records() {
+        let (mut records, _) = capture(|| {
+            for i in 0..5 {
+                warn!("record {}", i);
+            }
+        });
+        assert_eq!(records.capacity(), LogRecords::DEFAULT_CAPACITY);
+        assert_eq!(records.len(), 5);
+
+        records.set_capacity(2);
+        assert_eq!(records.capacity(), 2);
+        let messages: Vec<_> = records.iter().map(|record| &record.message).collect();
+        assert_eq!(messages, ["record 3", "record 4"]);
+
+        records.push(LogRecord::new(Level::ERROR, "command", "failed"));
+        let messages: Vec<_> = records.iter().map(|record| &record.message).collect();
+        assert_eq!(messages, ["record 4", "failed"]);
+
+        records.set_capacity(0);
+        assert!(records.is_empty());
+    }
+}
@@ -17,6 +17,9 @@ use std::panic;
 
 #[cfg(target_os = "android")]
 mod android_tracing;
+mod capture;
+
+pub use capture::*;
 
 pub mod prelude {
     //! The Bevy Log Prelude.
@@ -31,7 +34,7 @@ pub use bevy_utils::tracing::{
     Level,
 };
 
-use bevy_app::{App, Plugin};
+use bevy_app::{App, First, Plugin};
 use tracing_log::LogTracer;
 #[cfg(feature = "tracing-chrome")]
 use tracing_subscriber::fmt::{format::DefaultFields, FormattedFields};
@@ -56,6 +59,7 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 ///         .add_plugins(DefaultPlugins.set(LogPlugin {
 ///             level: Level::DEBUG,
 ///             filter: "wgpu=error,bevy_render=info,bevy_ecs=trace".to_string(),
+///             ..Default::default()
 ///         }))
 ///         .run();
 /// }
@@ -68,6 +72,9 @@ use tracing_subscriber::{prelude::*, registry::Registry, EnvFilter};
 /// If you define the `RUST_LOG` environment variable, the [`LogPlugin`] settings
 /// will be ignored.
 ///
+/// Logs can also be captured into the ECS by enabling [`LogPlugin::capture`], to display them
+/// in the app, where `stdout` may not be visible, like in a headset.
+///
 /// If you want to setup your own tracing collector, you should disable this
 /// plugin from `DefaultPlugins`:
 /// ```no_run
@@ -92,6 +99,10 @@ pub struct LogPlugin {
     /// Filters out logs that are "less than" the given level.
     /// This can be further filtered using the `filter` setting.
     pub level: Level,
+
+    /// Captures the logs into the [`LogRecords`] resource, and sends them as [`LogRecord`]
+    /// events, in addition to the platform specific output.
+    pub capture: bool,
 }
 
 impl Default for LogPlugin {
@@ -99,12 +110,12 @@ impl Default for LogPlugin {
         Self {
             filter: "wgpu=error".to_string(),
             level: Level::INFO,
+            capture: false,
         }
     }
 }
 
 impl Plugin for LogPlugin {
-    #[cfg_attr(not(feature = "tracing-chrome"), allow(unused_variables))]
     fn build(&self, app: &mut App) {
         #[cfg(feature = "trace")]
         {
@@ -122,6 +133,15 @@ impl Plugin for LogPlugin {
             .unwrap();
         let subscriber = Registry::default().with(filter_layer);
 
+        let capture_layer = self.capture.then(|| {
+            let (capture_layer, records) = CaptureLayer::new();
+            app.insert_resource(records)
+                .add_event::<LogRecord>()
+                .add_systems(First, capture_log_records);
+            capture_layer
+        });
+        let subscriber = subscriber.with(capture_layer);
+
         #[cfg(feature = "trace")]
         let subscriber = subscriber.with(tracing_error::ErrorLayer::default());
 
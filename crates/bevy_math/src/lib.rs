@@ -8,10 +8,11 @@
 #![warn(missing_docs)]
 
 pub mod cubic_splines;
+pub mod primitives;
 mod ray;
 mod rect;
 
-pub use ray::Ray;
+pub use ray::{Ray, Ray2d};
 pub use rect::Rect;
 
 /// The `bevy_math` prelude.
@@ -19,8 +20,8 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         cubic_splines::{BSpline, Bezier, CardinalSpline, CubicGenerator, CubicSegment, Hermite},
-        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, Ray, Rect,
-        UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
+        BVec2, BVec3, BVec4, EulerRot, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, Quat, Ray, Ray2d,
+        Rect, UVec2, UVec3, UVec4, Vec2, Vec3, Vec4,
     };
 }
 
@@ -0,0 +1,590 @@
+use super::{nearest_hit, quadratic_roots, Primitive2d, CONTAINS_EPSILON};
+use crate::{Mat2, Ray2d, Rect, Vec2};
+
+/// A circle, the 2D counterpart of a [`Sphere`](super::Sphere).
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Circle {
+    /// The center of the circle.
+    pub center: Vec2,
+    /// The radius of the circle.
+    pub radius: f32,
+}
+
+impl Circle {
+    /// Create a new circle from its center and radius.
+    #[inline]
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Computes a circle containing all `points`, or `None` if there are none.
+    ///
+    /// The circle is found with Ritter's algorithm, so it is close to but not always the
+    /// smallest one.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let first = *points.first()?;
+        let furthest_from = |from: Vec2| {
+            points
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    a.distance_squared(from)
+                        .total_cmp(&b.distance_squared(from))
+                })
+                .unwrap_or(from)
+        };
+        let x = furthest_from(first);
+        let y = furthest_from(x);
+        let mut circle = Self::new((x + y) / 2.0, x.distance(y) / 2.0);
+        for point in points {
+            let distance = point.distance(circle.center);
+            if distance > circle.radius {
+                let radius = (circle.radius + distance) / 2.0;
+                circle.center += (*point - circle.center) * ((radius - circle.radius) / distance);
+                circle.radius = radius;
+            }
+        }
+        Some(circle)
+    }
+}
+
+impl Primitive2d for Circle {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        let offset = point - self.center;
+        if offset.length_squared() <= self.radius * self.radius {
+            point
+        } else {
+            self.center + offset.normalize() * self.radius
+        }
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        let offset = ray.origin - self.center;
+        let c = offset.length_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+        let (t0, _) =
+            quadratic_roots(ray.direction.length_squared(), offset.dot(ray.direction), c)?;
+        (t0 >= 0.0).then_some(t0)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.center + direction.normalize_or_zero() * self.radius
+    }
+
+    fn aabb(&self) -> Rect {
+        Rect::from_center_half_size(self.center, Vec2::splat(self.radius))
+    }
+
+    fn intersects(&self, other: &impl Primitive2d) -> bool {
+        other
+            .closest_point(self.center)
+            .distance_squared(self.center)
+            <= self.radius * self.radius + CONTAINS_EPSILON
+    }
+}
+
+impl Rect {
+    /// Computes the smallest rectangle containing all `points`, or `None` if there are none.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        let first = *points.first()?;
+        Some(points.iter().fold(
+            Rect {
+                min: first,
+                max: first,
+            },
+            |rect, point| rect.union_point(*point),
+        ))
+    }
+}
+
+impl Primitive2d for Rect {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        point.clamp(self.min, self.max)
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        if Rect::contains(self, ray.origin) {
+            return Some(0.0);
+        }
+        let inverse = ray.direction.recip();
+        let t0 = (self.min - ray.origin) * inverse;
+        let t1 = (self.max - ray.origin) * inverse;
+        let near = t0.min(t1).max_element();
+        let far = t0.max(t1).min_element();
+        (near <= far && near >= 0.0).then_some(near)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        Vec2::select(direction.cmpge(Vec2::ZERO), self.max, self.min)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        Rect::contains(self, point)
+    }
+
+    fn aabb(&self) -> Rect {
+        *self
+    }
+}
+
+/// A rectangle rotated by `rotation` radians around its center.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb2d {
+    /// The center of the rectangle.
+    pub center: Vec2,
+    /// Half the size of the rectangle along its local axes.
+    pub half_extents: Vec2,
+    /// The counterclockwise rotation of the rectangle, in radians.
+    pub rotation: f32,
+}
+
+impl Obb2d {
+    /// Create a new oriented rectangle.
+    #[inline]
+    pub fn new(center: Vec2, half_extents: Vec2, rotation: f32) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Computes an oriented rectangle containing all `points`, or `None` if there are none.
+    ///
+    /// The rectangle is aligned with the principal axes of the points, which gives a tight fit
+    /// for elongated point sets.
+    pub fn from_points(points: &[Vec2]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let mean = points.iter().copied().sum::<Vec2>() / points.len() as f32;
+        let (mut xx, mut xy, mut yy) = (0.0, 0.0, 0.0);
+        for point in points {
+            let offset = *point - mean;
+            xx += offset.x * offset.x;
+            xy += offset.x * offset.y;
+            yy += offset.y * offset.y;
+        }
+        let rotation = 0.5 * f32::atan2(2.0 * xy, xx - yy);
+
+        let to_local = Mat2::from_angle(-rotation);
+        let local = points
+            .iter()
+            .map(|point| to_local * *point)
+            .collect::<Vec<_>>();
+        let bounds = Rect::from_points(&local)?;
+        Some(Self::new(
+            Mat2::from_angle(rotation) * bounds.center(),
+            bounds.half_size(),
+            rotation,
+        ))
+    }
+
+    fn local_point(&self, point: Vec2) -> Vec2 {
+        Mat2::from_angle(-self.rotation) * (point - self.center)
+    }
+
+    fn world_point(&self, point: Vec2) -> Vec2 {
+        Mat2::from_angle(self.rotation) * point + self.center
+    }
+
+    fn local_rect(&self) -> Rect {
+        Rect::from_center_half_size(Vec2::ZERO, self.half_extents)
+    }
+}
+
+impl Primitive2d for Obb2d {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        self.world_point(self.local_rect().closest_point(self.local_point(point)))
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        let local = Ray2d {
+            origin: self.local_point(ray.origin),
+            direction: Mat2::from_angle(-self.rotation) * ray.direction,
+        };
+        self.local_rect().ray_cast(&local)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        let direction = Mat2::from_angle(-self.rotation) * direction;
+        self.world_point(self.local_rect().support(direction))
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.local_point(point).abs().cmple(self.half_extents).all()
+    }
+}
+
+/// A capsule: the points within `radius` of the segment from `start` to `end`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule2d {
+    /// One end of the inner segment.
+    pub start: Vec2,
+    /// The other end of the inner segment.
+    pub end: Vec2,
+    /// The radius of the capsule.
+    pub radius: f32,
+}
+
+impl Capsule2d {
+    /// Create a new capsule around the segment from `start` to `end`.
+    #[inline]
+    pub fn new(start: Vec2, end: Vec2, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    fn segment(&self) -> Segment2d {
+        Segment2d::new(self.start, self.end)
+    }
+}
+
+impl Primitive2d for Capsule2d {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        Circle::new(self.segment().closest_point(point), self.radius).closest_point(point)
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+        let side = (self.end - self.start).perp().normalize_or_zero() * self.radius;
+        nearest_hit([
+            Circle::new(self.start, self.radius).ray_cast(ray),
+            Circle::new(self.end, self.radius).ray_cast(ray),
+            Segment2d::new(self.start + side, self.end + side).ray_cast(ray),
+            Segment2d::new(self.start - side, self.end - side).ray_cast(ray),
+        ])
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        self.segment().support(direction) + direction.normalize_or_zero() * self.radius
+    }
+}
+
+/// A solid triangle.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle2d {
+    /// The first vertex.
+    pub a: Vec2,
+    /// The second vertex.
+    pub b: Vec2,
+    /// The third vertex.
+    pub c: Vec2,
+}
+
+impl Triangle2d {
+    /// Create a new triangle from its vertices.
+    #[inline]
+    pub fn new(a: Vec2, b: Vec2, c: Vec2) -> Self {
+        Self { a, b, c }
+    }
+
+    fn edges(&self) -> [Segment2d; 3] {
+        [
+            Segment2d::new(self.a, self.b),
+            Segment2d::new(self.b, self.c),
+            Segment2d::new(self.c, self.a),
+        ]
+    }
+
+    fn contains_exactly(&self, point: Vec2) -> bool {
+        let ab = (self.b - self.a).perp_dot(point - self.a);
+        let bc = (self.c - self.b).perp_dot(point - self.b);
+        let ca = (self.a - self.c).perp_dot(point - self.c);
+        (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+    }
+}
+
+impl Primitive2d for Triangle2d {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        if self.contains_exactly(point) {
+            return point;
+        }
+        self.edges()
+            .map(|edge| edge.closest_point(point))
+            .into_iter()
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+            .unwrap_or(point)
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        if self.contains_exactly(ray.origin) {
+            return Some(0.0);
+        }
+        nearest_hit(self.edges().map(|edge| edge.ray_cast(ray)))
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        [self.a, self.b, self.c]
+            .into_iter()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap_or(self.a)
+    }
+}
+
+/// A line segment from `start` to `end`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment2d {
+    /// The start of the segment.
+    pub start: Vec2,
+    /// The end of the segment.
+    pub end: Vec2,
+}
+
+impl Segment2d {
+    /// Create a new segment from its end points.
+    #[inline]
+    pub fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+}
+
+impl Primitive2d for Segment2d {
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        let segment = self.end - self.start;
+        let length_squared = segment.length_squared();
+        if length_squared <= f32::EPSILON {
+            return self.start;
+        }
+        let t = ((point - self.start).dot(segment) / length_squared).clamp(0.0, 1.0);
+        self.start + segment * t
+    }
+
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        let segment = self.end - self.start;
+        let offset = self.start - ray.origin;
+        let denominator = ray.direction.perp_dot(segment);
+        if denominator.abs() <= f32::EPSILON {
+            // Parallel: only a collinear segment can be hit, at its nearest end.
+            if offset.perp_dot(ray.direction).abs() > CONTAINS_EPSILON {
+                return None;
+            }
+            let length_squared = ray.direction.length_squared();
+            let start = offset.dot(ray.direction) / length_squared;
+            let end = (self.end - ray.origin).dot(ray.direction) / length_squared;
+            return if start.min(end) <= 0.0 && start.max(end) >= 0.0 {
+                Some(0.0)
+            } else {
+                nearest_hit([Some(start), Some(end)])
+            };
+        }
+        let t = offset.perp_dot(segment) / denominator;
+        let s = offset.perp_dot(ray.direction) / denominator;
+        (t >= 0.0 && (0.0..=1.0).contains(&s)).then_some(t)
+    }
+
+    fn support(&self, direction: Vec2) -> Vec2 {
+        if self.end.dot(direction) > self.start.dot(direction) {
+            self.end
+        } else {
+            self.start
+        }
+    }
+}
+
+/// An infinite line, made of the points `p` where `normal.dot(p) == distance`.
+///
+/// Since it is unbounded, this doesn't implement [`Primitive2d`], but provides the queries that
+/// make sense for it.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line2d {
+    /// The normalized normal of the line.
+    pub normal: Vec2,
+    /// The signed distance of the line from the origin, along `normal`.
+    pub distance: f32,
+}
+
+impl Line2d {
+    /// Create the line going through `point` with the given `normal`, which is normalized.
+    #[inline]
+    pub fn from_point_normal(point: Vec2, normal: Vec2) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            distance: normal.dot(point),
+        }
+    }
+
+    /// Returns the distance from the line to `point`, positive on the side of the normal.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec2) -> f32 {
+        self.normal.dot(point) - self.distance
+    }
+
+    /// Returns the point of the line closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// Returns the distance along `ray` at which it crosses the line, if it does.
+    pub fn ray_cast(&self, ray: &Ray2d) -> Option<f32> {
+        let distance = self.signed_distance(ray.origin);
+        if distance.abs() <= CONTAINS_EPSILON {
+            return Some(0.0);
+        }
+        let denominator = self.normal.dot(ray.direction);
+        if denominator.abs() <= f32::EPSILON {
+            return None;
+        }
+        let t = -distance / denominator;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Returns `true` if `shape` crosses or touches the line.
+    pub fn intersects(&self, shape: &impl Primitive2d) -> bool {
+        self.signed_distance(shape.support(-self.normal)) <= CONTAINS_EPSILON
+            && self.signed_distance(shape.support(self.normal)) >= -CONTAINS_EPSILON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray(origin: Vec2, direction: Vec2) -> Ray2d {
+        Ray2d::new(origin, direction)
+    }
+
+    #[test]
+    fn circle_queries() {
+        let circle = Circle::new(Vec2::new(4.0, 0.0), 1.0);
+        assert_eq!(circle.ray_cast(&ray(Vec2::ZERO, Vec2::X)), Some(3.0));
+        assert_eq!(circle.ray_cast(&ray(Vec2::ZERO, Vec2::NEG_X)), None);
+        assert_eq!(
+            circle.ray_cast(&ray(Vec2::new(4.5, 0.0), Vec2::Y)),
+            Some(0.0)
+        );
+        assert_eq!(circle.closest_point(Vec2::ZERO), Vec2::new(3.0, 0.0));
+        assert!(circle.contains(Vec2::new(4.0, 1.0)));
+        assert!(!circle.contains(Vec2::new(4.0, 1.1)));
+        assert_eq!(circle.aabb(), Rect::new(3.0, -1.0, 5.0, 1.0));
+    }
+
+    #[test]
+    fn circle_from_points() {
+        let points = [
+            Vec2::new(-1.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(0.2, -0.3),
+        ];
+        let circle = Circle::from_points(&points).unwrap();
+        assert!(points
+            .iter()
+            .all(|point| point.distance(circle.center) <= circle.radius + 1e-5));
+        assert!(Circle::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn rect_queries() {
+        let rect = Rect::new(1.0, -1.0, 3.0, 1.0);
+        assert_eq!(rect.ray_cast(&ray(Vec2::ZERO, Vec2::X)), Some(1.0));
+        assert_eq!(rect.ray_cast(&ray(Vec2::ZERO, Vec2::Y)), None);
+        assert_eq!(rect.ray_cast(&ray(Vec2::new(2.0, 0.0), Vec2::Y)), Some(0.0));
+        assert_eq!(rect.closest_point(Vec2::new(5.0, 5.0)), Vec2::new(3.0, 1.0));
+        assert_eq!(rect.support(Vec2::new(-1.0, 1.0)), Vec2::new(1.0, 1.0));
+    }
+
+    #[test]
+    fn obb_queries() {
+        let obb = Obb2d::new(Vec2::ZERO, Vec2::new(2.0, 1.0), std::f32::consts::FRAC_PI_2);
+        assert!(obb.contains(Vec2::new(0.0, 1.9)));
+        assert!(!obb.contains(Vec2::new(1.9, 0.0)));
+        let hit = obb.ray_cast(&ray(Vec2::new(0.0, -5.0), Vec2::Y)).unwrap();
+        assert!((hit - 3.0).abs() < 1e-5);
+
+        let points = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(1.0, 1.2),
+            Vec2::new(2.0, 1.8),
+        ];
+        let obb = Obb2d::from_points(&points).unwrap();
+        assert!(points.iter().all(|point| obb
+            .local_point(*point)
+            .abs()
+            .cmple(obb.half_extents + 1e-4)
+            .all()));
+        assert!(obb.half_extents.min_element() < 0.2);
+    }
+
+    #[test]
+    fn capsule_queries() {
+        let capsule = Capsule2d::new(Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0), 0.5);
+        let hit = capsule
+            .ray_cast(&ray(Vec2::new(0.0, 2.0), Vec2::NEG_Y))
+            .unwrap();
+        assert!((hit - 1.5).abs() < 1e-5);
+        let hit = capsule
+            .ray_cast(&ray(Vec2::new(-3.0, 0.0), Vec2::X))
+            .unwrap();
+        assert!((hit - 1.5).abs() < 1e-5);
+        assert_eq!(
+            capsule.closest_point(Vec2::new(3.0, 0.0)),
+            Vec2::new(1.5, 0.0)
+        );
+    }
+
+    #[test]
+    fn triangle_queries() {
+        let triangle = Triangle2d::new(Vec2::ZERO, Vec2::new(2.0, 0.0), Vec2::new(0.0, 2.0));
+        assert!(triangle.contains(Vec2::new(0.5, 0.5)));
+        assert!(!triangle.contains(Vec2::new(1.5, 1.5)));
+        assert_eq!(
+            triangle.closest_point(Vec2::new(2.0, 2.0)),
+            Vec2::new(1.0, 1.0)
+        );
+        assert_eq!(
+            triangle.ray_cast(&ray(Vec2::new(-1.0, 0.5), Vec2::X)),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn segment_and_line() {
+        let segment = Segment2d::new(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0));
+        assert_eq!(segment.ray_cast(&ray(Vec2::ZERO, Vec2::X)), Some(1.0));
+        assert_eq!(segment.ray_cast(&ray(Vec2::new(0.0, 2.0), Vec2::X)), None);
+        let collinear = Segment2d::new(Vec2::new(2.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(collinear.ray_cast(&ray(Vec2::ZERO, Vec2::X)), Some(2.0));
+
+        let line = Line2d::from_point_normal(Vec2::new(0.0, 1.0), Vec2::Y);
+        assert_eq!(line.signed_distance(Vec2::new(5.0, 3.0)), 2.0);
+        assert_eq!(line.ray_cast(&ray(Vec2::ZERO, Vec2::Y)), Some(1.0));
+        assert!(line.intersects(&Circle::new(Vec2::ZERO, 1.0)));
+        assert!(!line.intersects(&Circle::new(Vec2::ZERO, 0.5)));
+    }
+
+    #[test]
+    fn intersections() {
+        let circle = Circle::new(Vec2::ZERO, 1.0);
+        let rect = Rect::new(0.5, 0.5, 2.0, 2.0);
+        let triangle = Triangle2d::new(
+            Vec2::new(2.0, 0.0),
+            Vec2::new(3.0, 0.0),
+            Vec2::new(2.0, 1.0),
+        );
+        assert!(circle.intersects(&rect));
+        assert!(!circle.intersects(&triangle));
+        assert!(rect.intersects(&triangle));
+        assert!(triangle.intersects(&rect));
+        assert!(!triangle.intersects(&Segment2d::new(Vec2::new(0.0, 3.0), Vec2::new(5.0, 3.0))));
+        let capsule = Capsule2d::new(Vec2::new(-3.0, 0.0), Vec2::new(3.0, 0.0), 0.1);
+        let obb = Obb2d::new(Vec2::new(0.0, 1.0), Vec2::new(2.0, 0.1), 0.5);
+        assert!(capsule.intersects(&obb));
+        assert!(!capsule.intersects(&Obb2d::new(Vec2::new(0.0, 3.0), Vec2::ONE, 0.5)));
+    }
+}
@@ -0,0 +1,1050 @@
+use super::{nearest_hit, quadratic_roots, Primitive2d, Primitive3d, Triangle2d, CONTAINS_EPSILON};
+use crate::{Mat3, Quat, Ray, Vec2, Vec3};
+
+/// A sphere.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sphere {
+    /// The center of the sphere.
+    pub center: Vec3,
+    /// The radius of the sphere.
+    pub radius: f32,
+}
+
+impl Sphere {
+    /// Create a new sphere from its center and radius.
+    #[inline]
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    /// Computes a sphere containing all `points`, or `None` if there are none.
+    ///
+    /// The sphere is found with Ritter's algorithm, so it is close to but not always the
+    /// smallest one.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let first = *points.first()?;
+        let furthest_from = |from: Vec3| {
+            points
+                .iter()
+                .copied()
+                .max_by(|a, b| {
+                    a.distance_squared(from)
+                        .total_cmp(&b.distance_squared(from))
+                })
+                .unwrap_or(from)
+        };
+        let x = furthest_from(first);
+        let y = furthest_from(x);
+        let mut sphere = Self::new((x + y) / 2.0, x.distance(y) / 2.0);
+        for point in points {
+            let distance = point.distance(sphere.center);
+            if distance > sphere.radius {
+                let radius = (sphere.radius + distance) / 2.0;
+                sphere.center += (*point - sphere.center) * ((radius - sphere.radius) / distance);
+                sphere.radius = radius;
+            }
+        }
+        Some(sphere)
+    }
+}
+
+impl Primitive3d for Sphere {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        let offset = point - self.center;
+        if offset.length_squared() <= self.radius * self.radius {
+            point
+        } else {
+            self.center + offset.normalize() * self.radius
+        }
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        let offset = ray.origin - self.center;
+        let c = offset.length_squared() - self.radius * self.radius;
+        if c <= 0.0 {
+            return Some(0.0);
+        }
+        let (t0, _) =
+            quadratic_roots(ray.direction.length_squared(), offset.dot(ray.direction), c)?;
+        (t0 >= 0.0).then_some(t0)
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.center + direction.normalize_or_zero() * self.radius
+    }
+
+    fn aabb(&self) -> Aabb3d {
+        Aabb3d::from_center_half_size(self.center, Vec3::splat(self.radius))
+    }
+
+    fn intersects(&self, other: &impl Primitive3d) -> bool {
+        other
+            .closest_point(self.center)
+            .distance_squared(self.center)
+            <= self.radius * self.radius + CONTAINS_EPSILON
+    }
+}
+
+/// An axis-aligned box.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb3d {
+    /// The minimum corner of the box.
+    pub min: Vec3,
+    /// The maximum corner of the box.
+    pub max: Vec3,
+}
+
+impl Aabb3d {
+    /// Create a new box from its corners.
+    ///
+    /// The corners don't need to be ordered.
+    #[inline]
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// Create a new box from its center and half its size.
+    #[inline]
+    pub fn from_center_half_size(center: Vec3, half_size: Vec3) -> Self {
+        Self {
+            min: center - half_size,
+            max: center + half_size,
+        }
+    }
+
+    /// Computes the smallest box containing all `points`, or `None` if there are none.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let first = *points.first()?;
+        Some(points.iter().fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |aabb, point| Self {
+                min: aabb.min.min(*point),
+                max: aabb.max.max(*point),
+            },
+        ))
+    }
+
+    /// The center of the box.
+    #[inline]
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    /// Half the size of the box.
+    #[inline]
+    pub fn half_size(&self) -> Vec3 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// The area of the surface of the box.
+    #[inline]
+    pub fn surface_area(&self) -> f32 {
+        let size = self.max - self.min;
+        2.0 * (size.x * size.y + size.y * size.z + size.z * size.x)
+    }
+
+    /// Returns the smallest box containing both `self` and `other`.
+    #[inline]
+    pub fn union(&self, other: Aabb3d) -> Aabb3d {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Returns `true` if the two boxes overlap.
+    ///
+    /// This is equivalent to, but faster than [`Primitive3d::intersects`].
+    #[inline]
+    pub fn overlaps(&self, other: &Aabb3d) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+impl Primitive3d for Aabb3d {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        point.clamp(self.min, self.max)
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+        let inverse = ray.direction.recip();
+        let t0 = (self.min - ray.origin) * inverse;
+        let t1 = (self.max - ray.origin) * inverse;
+        let near = t0.min(t1).max_element();
+        let far = t0.max(t1).min_element();
+        (near <= far && near >= 0.0).then_some(near)
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        Vec3::select(direction.cmpge(Vec3::ZERO), self.max, self.min)
+    }
+
+    fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+
+    fn aabb(&self) -> Aabb3d {
+        *self
+    }
+}
+
+/// A box rotated by `rotation` around its center.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Obb3d {
+    /// The center of the box.
+    pub center: Vec3,
+    /// Half the size of the box along its local axes.
+    pub half_extents: Vec3,
+    /// The rotation of the box.
+    pub rotation: Quat,
+}
+
+impl Obb3d {
+    /// Create a new oriented box.
+    #[inline]
+    pub fn new(center: Vec3, half_extents: Vec3, rotation: Quat) -> Self {
+        Self {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// Computes an oriented box containing all `points`, or `None` if there are none.
+    ///
+    /// The box is aligned with the principal axes of the points, which gives a tight fit for
+    /// elongated point sets.
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let mean = points.iter().copied().sum::<Vec3>() / points.len() as f32;
+        let mut covariance = [[0.0; 3]; 3];
+        for point in points {
+            let offset = (*point - mean).to_array();
+            for (i, row) in covariance.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    *value += offset[i] * offset[j];
+                }
+            }
+        }
+        let rotation = Quat::from_mat3(&symmetric_eigenvectors(covariance)).normalize();
+
+        let inverse = rotation.inverse();
+        let local = points
+            .iter()
+            .map(|point| inverse * *point)
+            .collect::<Vec<_>>();
+        let bounds = Aabb3d::from_points(&local)?;
+        Some(Self::new(
+            rotation * bounds.center(),
+            bounds.half_size(),
+            rotation,
+        ))
+    }
+
+    fn local_point(&self, point: Vec3) -> Vec3 {
+        self.rotation.inverse() * (point - self.center)
+    }
+
+    fn world_point(&self, point: Vec3) -> Vec3 {
+        self.rotation * point + self.center
+    }
+
+    fn local_aabb(&self) -> Aabb3d {
+        Aabb3d::from_center_half_size(Vec3::ZERO, self.half_extents)
+    }
+}
+
+impl Primitive3d for Obb3d {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.world_point(self.local_aabb().closest_point(self.local_point(point)))
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        let local = Ray {
+            origin: self.local_point(ray.origin),
+            direction: self.rotation.inverse() * ray.direction,
+        };
+        self.local_aabb().ray_cast(&local)
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let direction = self.rotation.inverse() * direction;
+        self.world_point(self.local_aabb().support(direction))
+    }
+
+    fn contains(&self, point: Vec3) -> bool {
+        self.local_aabb().contains(self.local_point(point))
+    }
+}
+
+/// Returns a rotation whose columns are the eigenvectors of the symmetric `matrix`, using the
+/// Jacobi eigenvalue algorithm.
+fn symmetric_eigenvectors(mut matrix: [[f32; 3]; 3]) -> Mat3 {
+    let mut vectors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..32 {
+        // Find the largest off-diagonal element.
+        let (p, q) = [(0, 1), (0, 2), (1, 2)]
+            .into_iter()
+            .max_by(|a, b| matrix[a.0][a.1].abs().total_cmp(&matrix[b.0][b.1].abs()))
+            .unwrap_or((0, 1));
+        if matrix[p][q].abs() <= 1e-9 {
+            break;
+        }
+
+        // Rotate in the (p, q) plane to zero it.
+        let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+        let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+        let cos = 1.0 / (t * t + 1.0).sqrt();
+        let sin = t * cos;
+        for row in &mut matrix {
+            let (kp, kq) = (row[p], row[q]);
+            row[p] = cos * kp - sin * kq;
+            row[q] = sin * kp + cos * kq;
+        }
+        let (row_p, row_q) = (matrix[p], matrix[q]);
+        matrix[p] = [0, 1, 2].map(|k| cos * row_p[k] - sin * row_q[k]);
+        matrix[q] = [0, 1, 2].map(|k| sin * row_p[k] + cos * row_q[k]);
+        for row in &mut vectors {
+            let (kp, kq) = (row[p], row[q]);
+            row[p] = cos * kp - sin * kq;
+            row[q] = sin * kp + cos * kq;
+        }
+    }
+
+    let column = |i: usize| Vec3::new(vectors[0][i], vectors[1][i], vectors[2][i]);
+    let (x, y) = (column(0), column(1));
+    // Rebuild the last axis so that the result is a proper rotation.
+    Mat3::from_cols(x, y, x.cross(y))
+}
+
+/// A capsule: the points within `radius` of the segment from `start` to `end`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule3d {
+    /// One end of the inner segment.
+    pub start: Vec3,
+    /// The other end of the inner segment.
+    pub end: Vec3,
+    /// The radius of the capsule.
+    pub radius: f32,
+}
+
+impl Capsule3d {
+    /// Create a new capsule around the segment from `start` to `end`.
+    #[inline]
+    pub fn new(start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+
+    fn segment(&self) -> Segment3d {
+        Segment3d::new(self.start, self.end)
+    }
+}
+
+impl Primitive3d for Capsule3d {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        Sphere::new(self.segment().closest_point(point), self.radius).closest_point(point)
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+        nearest_hit([
+            Sphere::new(self.start, self.radius).ray_cast(ray),
+            Sphere::new(self.end, self.radius).ray_cast(ray),
+            ray_cast_tube(ray, self.start, self.end, self.radius),
+        ])
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        self.segment().support(direction) + direction.normalize_or_zero() * self.radius
+    }
+}
+
+/// A solid cylinder whose caps are centered on `start` and `end`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cylinder {
+    /// The center of one cap.
+    pub start: Vec3,
+    /// The center of the other cap.
+    pub end: Vec3,
+    /// The radius of the cylinder.
+    pub radius: f32,
+}
+
+impl Cylinder {
+    /// Create a new cylinder between `start` and `end`.
+    #[inline]
+    pub fn new(start: Vec3, end: Vec3, radius: f32) -> Self {
+        Self { start, end, radius }
+    }
+}
+
+impl Primitive3d for Cylinder {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        let axis = self.end - self.start;
+        let length = axis.length();
+        let axis = axis.normalize_or_zero();
+        let offset = point - self.start;
+        let height = offset.dot(axis);
+        let radial = offset - axis * height;
+        let radial = if radial.length_squared() > self.radius * self.radius {
+            radial.normalize() * self.radius
+        } else {
+            radial
+        };
+        self.start + axis * height.clamp(0.0, length) + radial
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+        let axis = (self.end - self.start).normalize_or_zero();
+        let cap = |center: Vec3| {
+            let t = Plane3d::from_point_normal(center, axis).ray_cast(ray)?;
+            (ray.get_point(t).distance_squared(center) <= self.radius * self.radius).then_some(t)
+        };
+        nearest_hit([
+            ray_cast_tube(ray, self.start, self.end, self.radius),
+            cap(self.start),
+            cap(self.end),
+        ])
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let axis = (self.end - self.start).normalize_or_zero();
+        let radial = direction - axis * direction.dot(axis);
+        let cap = if direction.dot(axis) >= 0.0 {
+            self.end
+        } else {
+            self.start
+        };
+        cap + radial.normalize_or_zero() * self.radius
+    }
+}
+
+/// Returns the distance along `ray` at which it enters the open tube of `radius` around the
+/// segment from `start` to `end`.
+fn ray_cast_tube(ray: &Ray, start: Vec3, end: Vec3, radius: f32) -> Option<f32> {
+    let axis = end - start;
+    let length_squared = axis.length_squared();
+    if length_squared <= f32::EPSILON {
+        return None;
+    }
+    let axis = axis / length_squared.sqrt();
+    // Project out the axis to get a 2D circle test.
+    let offset = ray.origin - start;
+    let offset_radial = offset - axis * offset.dot(axis);
+    let direction_radial = ray.direction - axis * ray.direction.dot(axis);
+    let (t0, t1) = quadratic_roots(
+        direction_radial.length_squared(),
+        offset_radial.dot(direction_radial),
+        offset_radial.length_squared() - radius * radius,
+    )?;
+    let on_tube = |t: f32| {
+        let height = (ray.get_point(t) - start).dot(axis);
+        (height >= 0.0 && height * height <= length_squared).then_some(t)
+    };
+    nearest_hit([on_tube(t0), on_tube(t1)])
+}
+
+/// A solid cone with its tip at `apex` and a disk of `radius` around `base_center` as its base.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cone {
+    /// The tip of the cone.
+    pub apex: Vec3,
+    /// The center of the base of the cone.
+    pub base_center: Vec3,
+    /// The radius of the base of the cone.
+    pub radius: f32,
+}
+
+impl Cone {
+    /// Create a new cone from its apex, the center of its base and the radius of its base.
+    #[inline]
+    pub fn new(apex: Vec3, base_center: Vec3, radius: f32) -> Self {
+        Self {
+            apex,
+            base_center,
+            radius,
+        }
+    }
+}
+
+impl Primitive3d for Cone {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        let axis = self.base_center - self.apex;
+        let height = axis.length();
+        let axis = axis.normalize_or_zero();
+        let offset = point - self.apex;
+        let along = offset.dot(axis);
+        let radial = offset - axis * along;
+
+        // The cone is symmetric around its axis, so the problem reduces to the half of its cross
+        // section containing `point`.
+        let section = Triangle2d::new(
+            Vec2::ZERO,
+            Vec2::new(self.radius, height),
+            Vec2::new(0.0, height),
+        );
+        let closest = section.closest_point(Vec2::new(radial.length(), along));
+        self.apex + axis * closest.y + radial.normalize_or_zero() * closest.x
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        if self.contains(ray.origin) {
+            return Some(0.0);
+        }
+        let axis = self.base_center - self.apex;
+        let height = axis.length();
+        if height <= f32::EPSILON {
+            return None;
+        }
+        let axis = axis / height;
+        let cos_squared = height * height / (height * height + self.radius * self.radius);
+
+        let offset = ray.origin - self.apex;
+        let (direction_along, offset_along) = (ray.direction.dot(axis), offset.dot(axis));
+        let side = quadratic_roots(
+            direction_along * direction_along - cos_squared * ray.direction.length_squared(),
+            direction_along * offset_along - cos_squared * ray.direction.dot(offset),
+            offset_along * offset_along - cos_squared * offset.length_squared(),
+        );
+        // Only keep hits on the nappe of the double cone between the apex and the base.
+        let on_side = |t: f32| {
+            let along = (ray.get_point(t) - self.apex).dot(axis);
+            (0.0..=height).contains(&along).then_some(t)
+        };
+        let base = Plane3d::from_point_normal(self.base_center, axis)
+            .ray_cast(ray)
+            .filter(|t| {
+                ray.get_point(*t).distance_squared(self.base_center) <= self.radius * self.radius
+            });
+        nearest_hit([
+            side.and_then(|(t0, _)| on_side(t0)),
+            side.and_then(|(_, t1)| on_side(t1)),
+            base,
+        ])
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        let axis = (self.base_center - self.apex).normalize_or_zero();
+        let radial = direction - axis * direction.dot(axis);
+        let rim = self.base_center + radial.normalize_or_zero() * self.radius;
+        if rim.dot(direction) >= self.apex.dot(direction) {
+            rim
+        } else {
+            self.apex
+        }
+    }
+}
+
+/// A solid triangle.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Triangle3d {
+    /// The first vertex.
+    pub a: Vec3,
+    /// The second vertex.
+    pub b: Vec3,
+    /// The third vertex.
+    pub c: Vec3,
+}
+
+impl Triangle3d {
+    /// Create a new triangle from its vertices.
+    #[inline]
+    pub fn new(a: Vec3, b: Vec3, c: Vec3) -> Self {
+        Self { a, b, c }
+    }
+
+    /// The normal of the triangle, following the right hand rule with the vertices in order.
+    ///
+    /// Returns zero for degenerate triangles.
+    #[inline]
+    pub fn normal(&self) -> Vec3 {
+        (self.b - self.a).cross(self.c - self.a).normalize_or_zero()
+    }
+
+    /// Returns the distance along `ray` at which it hits the triangle, and the barycentric
+    /// coordinates of the hit for `b` and `c`.
+    ///
+    /// Both faces of the triangle are hit.
+    pub fn ray_cast_barycentric(&self, ray: &Ray) -> Option<(f32, Vec2)> {
+        // Möller–Trumbore
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let p = ray.direction.cross(ac);
+        let determinant = ab.dot(p);
+        if determinant.abs() <= f32::EPSILON {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+        let offset = ray.origin - self.a;
+        let u = offset.dot(p) * inverse;
+        if !(0.0..=1.0).contains(&u) {
+            return None;
+        }
+        let q = offset.cross(ab);
+        let v = ray.direction.dot(q) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = ac.dot(q) * inverse;
+        (t >= 0.0).then_some((t, Vec2::new(u, v)))
+    }
+}
+
+impl Primitive3d for Triangle3d {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        // From Real-Time Collision Detection by Christer Ericson, section 5.1.5.
+        let (a, b, c) = (self.a, self.b, self.c);
+        let ab = b - a;
+        let ac = c - a;
+        let ap = point - a;
+        let d1 = ab.dot(ap);
+        let d2 = ac.dot(ap);
+        if d1 <= 0.0 && d2 <= 0.0 {
+            return a;
+        }
+
+        let bp = point - b;
+        let d3 = ab.dot(bp);
+        let d4 = ac.dot(bp);
+        if d3 >= 0.0 && d4 <= d3 {
+            return b;
+        }
+
+        let vc = d1 * d4 - d3 * d2;
+        if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+            return a + ab * (d1 / (d1 - d3));
+        }
+
+        let cp = point - c;
+        let d5 = ab.dot(cp);
+        let d6 = ac.dot(cp);
+        if d6 >= 0.0 && d5 <= d6 {
+            return c;
+        }
+
+        let vb = d5 * d2 - d1 * d6;
+        if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+            return a + ac * (d2 / (d2 - d6));
+        }
+
+        let va = d3 * d6 - d5 * d4;
+        if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+            return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+        }
+
+        let denominator = 1.0 / (va + vb + vc);
+        a + ab * (vb * denominator) + ac * (vc * denominator)
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        self.ray_cast_barycentric(ray).map(|(t, _)| t)
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        [self.a, self.b, self.c]
+            .into_iter()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap_or(self.a)
+    }
+}
+
+/// A line segment from `start` to `end`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment3d {
+    /// The start of the segment.
+    pub start: Vec3,
+    /// The end of the segment.
+    pub end: Vec3,
+}
+
+impl Segment3d {
+    /// Create a new segment from its end points.
+    #[inline]
+    pub fn new(start: Vec3, end: Vec3) -> Self {
+        Self { start, end }
+    }
+}
+
+impl Primitive3d for Segment3d {
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        let segment = self.end - self.start;
+        let length_squared = segment.length_squared();
+        if length_squared <= f32::EPSILON {
+            return self.start;
+        }
+        let t = ((point - self.start).dot(segment) / length_squared).clamp(0.0, 1.0);
+        self.start + segment * t
+    }
+
+    fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        let segment = self.end - self.start;
+        let offset = self.start - ray.origin;
+        let normal = ray.direction.cross(segment);
+        let normal_squared = normal.length_squared();
+        if normal_squared <= f32::EPSILON * f32::EPSILON {
+            // Parallel: only a collinear segment can be hit, at its nearest end.
+            if offset.cross(ray.direction).length_squared() > CONTAINS_EPSILON * CONTAINS_EPSILON {
+                return None;
+            }
+            let length_squared = ray.direction.length_squared();
+            let start = offset.dot(ray.direction) / length_squared;
+            let end = (self.end - ray.origin).dot(ray.direction) / length_squared;
+            return if start.min(end) <= 0.0 && start.max(end) >= 0.0 {
+                Some(0.0)
+            } else {
+                nearest_hit([Some(start), Some(end)])
+            };
+        }
+        // The lines must be coplanar to cross.
+        if offset.dot(normal).abs() > CONTAINS_EPSILON * normal_squared.sqrt() {
+            return None;
+        }
+        let t = offset.cross(segment).dot(normal) / normal_squared;
+        let s = offset.cross(ray.direction).dot(normal) / normal_squared;
+        (t >= 0.0 && (0.0..=1.0).contains(&s)).then_some(t)
+    }
+
+    fn support(&self, direction: Vec3) -> Vec3 {
+        if self.end.dot(direction) > self.start.dot(direction) {
+            self.end
+        } else {
+            self.start
+        }
+    }
+}
+
+/// An infinite plane, made of the points `p` where `normal.dot(p) == distance`.
+///
+/// Since it is unbounded, this doesn't implement [`Primitive3d`], but provides the queries that
+/// make sense for it.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Plane3d {
+    /// The normalized normal of the plane.
+    pub normal: Vec3,
+    /// The signed distance of the plane from the origin, along `normal`.
+    pub distance: f32,
+}
+
+impl Plane3d {
+    /// Create the plane going through `point` with the given `normal`, which is normalized.
+    #[inline]
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Self {
+        let normal = normal.normalize();
+        Self {
+            normal,
+            distance: normal.dot(point),
+        }
+    }
+
+    /// Returns the distance from the plane to `point`, positive on the side of the normal.
+    #[inline]
+    pub fn signed_distance(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) - self.distance
+    }
+
+    /// Returns the point of the plane closest to `point`.
+    #[inline]
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        point - self.normal * self.signed_distance(point)
+    }
+
+    /// Returns the distance along `ray` at which it crosses the plane, if it does.
+    pub fn ray_cast(&self, ray: &Ray) -> Option<f32> {
+        let distance = self.signed_distance(ray.origin);
+        if distance.abs() <= CONTAINS_EPSILON {
+            return Some(0.0);
+        }
+        let denominator = self.normal.dot(ray.direction);
+        if denominator.abs() <= f32::EPSILON {
+            return None;
+        }
+        let t = -distance / denominator;
+        (t >= 0.0).then_some(t)
+    }
+
+    /// Returns `true` if `shape` crosses or touches the plane.
+    pub fn intersects(&self, shape: &impl Primitive3d) -> bool {
+        self.signed_distance(shape.support(-self.normal)) <= CONTAINS_EPSILON
+            && self.signed_distance(shape.support(self.normal)) >= -CONTAINS_EPSILON
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray(origin: Vec3, direction: Vec3) -> Ray {
+        Ray {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    fn assert_hit(hit: Option<f32>, expected: f32) {
+        let hit = hit.expect("expected a hit");
+        assert!((hit - expected).abs() < 1e-4, "{hit} != {expected}");
+    }
+
+    #[test]
+    fn sphere_queries() {
+        let sphere = Sphere::new(Vec3::new(0.0, 0.0, -5.0), 1.0);
+        assert_hit(sphere.ray_cast(&ray(Vec3::ZERO, Vec3::NEG_Z)), 4.0);
+        assert_eq!(sphere.ray_cast(&ray(Vec3::ZERO, Vec3::Z)), None);
+        assert_eq!(sphere.ray_cast(&ray(Vec3::ZERO, Vec3::X)), None);
+        assert_eq!(sphere.ray_cast(&ray(sphere.center, Vec3::X)), Some(0.0));
+        assert_eq!(sphere.closest_point(Vec3::ZERO), Vec3::new(0.0, 0.0, -4.0));
+        assert!(sphere.contains(Vec3::new(0.0, 0.9, -5.0)));
+        assert_eq!(
+            sphere.aabb(),
+            Aabb3d::new(Vec3::new(-1.0, -1.0, -6.0), Vec3::new(1.0, 1.0, -4.0))
+        );
+
+        let points = [Vec3::X, Vec3::NEG_X, Vec3::Y, Vec3::new(0.3, 0.3, -0.9)];
+        let bounds = Sphere::from_points(&points).unwrap();
+        assert!(points.iter().all(|point| bounds.contains(*point)));
+    }
+
+    #[test]
+    fn aabb_queries() {
+        let aabb = Aabb3d::new(Vec3::new(1.0, -1.0, -1.0), Vec3::new(3.0, 1.0, 1.0));
+        assert_hit(aabb.ray_cast(&ray(Vec3::ZERO, Vec3::X)), 1.0);
+        assert_eq!(aabb.ray_cast(&ray(Vec3::ZERO, Vec3::Y)), None);
+        assert_eq!(
+            aabb.ray_cast(&ray(Vec3::new(2.0, 0.0, 0.0), Vec3::Y)),
+            Some(0.0)
+        );
+        assert_eq!(
+            aabb.closest_point(Vec3::splat(5.0)),
+            Vec3::new(3.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            aabb.support(Vec3::new(-1.0, 1.0, -1.0)),
+            Vec3::new(1.0, 1.0, -1.0)
+        );
+        assert_eq!(aabb.surface_area(), 24.0);
+        assert!(aabb.overlaps(&Aabb3d::new(Vec3::ZERO, Vec3::ONE)));
+        assert!(!aabb.overlaps(&Aabb3d::new(Vec3::ZERO, Vec3::splat(0.5))));
+        assert_eq!(
+            Aabb3d::from_points(&[Vec3::X, Vec3::NEG_Y, Vec3::Z]),
+            Some(Aabb3d::new(Vec3::NEG_Y, Vec3::new(1.0, 0.0, 1.0)))
+        );
+    }
+
+    #[test]
+    fn obb_queries() {
+        let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        let obb = Obb3d::new(Vec3::ZERO, Vec3::new(2.0, 1.0, 1.0), rotation);
+        assert!(obb.contains(Vec3::new(0.0, 1.9, 0.0)));
+        assert!(!obb.contains(Vec3::new(1.9, 0.0, 0.0)));
+        assert_hit(obb.ray_cast(&ray(Vec3::new(0.0, -5.0, 0.0), Vec3::Y)), 3.0);
+
+        let direction = Vec3::new(1.0, 2.0, 3.0).normalize();
+        let points = (0..20)
+            .map(|i| {
+                let i = i as f32;
+                direction * i + Vec3::new(0.1, -0.1, 0.05) * (i % 3.0)
+            })
+            .collect::<Vec<_>>();
+        let obb = Obb3d::from_points(&points).unwrap();
+        assert!(points.iter().all(|point| obb
+            .local_point(*point)
+            .abs()
+            .cmple(obb.half_extents + 1e-3)
+            .all()));
+        assert!(obb.half_extents.max_element() > 9.0);
+        assert!(obb.half_extents.min_element() < 0.2);
+    }
+
+    #[test]
+    fn capsule_and_cylinder_queries() {
+        let capsule = Capsule3d::new(Vec3::NEG_Y, Vec3::Y, 0.5);
+        assert_hit(
+            capsule.ray_cast(&ray(Vec3::new(-3.0, 0.0, 0.0), Vec3::X)),
+            2.5,
+        );
+        assert_hit(
+            capsule.ray_cast(&ray(Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y)),
+            3.5,
+        );
+        assert_eq!(
+            capsule.closest_point(Vec3::new(0.0, 5.0, 0.0)),
+            Vec3::new(0.0, 1.5, 0.0)
+        );
+
+        let cylinder = Cylinder::new(Vec3::NEG_Y, Vec3::Y, 0.5);
+        assert_hit(
+            cylinder.ray_cast(&ray(Vec3::new(-3.0, 0.0, 0.0), Vec3::X)),
+            2.5,
+        );
+        assert_hit(
+            cylinder.ray_cast(&ray(Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y)),
+            4.0,
+        );
+        assert_eq!(
+            cylinder.ray_cast(&ray(Vec3::new(-3.0, 1.5, 0.0), Vec3::X)),
+            None
+        );
+        assert_eq!(
+            cylinder.closest_point(Vec3::new(2.0, 3.0, 0.0)),
+            Vec3::new(0.5, 1.0, 0.0)
+        );
+        assert_eq!(
+            cylinder.support(Vec3::new(1.0, 1.0, 0.0)),
+            Vec3::new(0.5, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn cone_queries() {
+        let cone = Cone::new(Vec3::Y, Vec3::ZERO, 1.0);
+        assert_hit(cone.ray_cast(&ray(Vec3::new(-3.0, 0.5, 0.0), Vec3::X)), 2.5);
+        assert_hit(cone.ray_cast(&ray(Vec3::new(0.0, -2.0, 0.0), Vec3::Y)), 2.0);
+        assert_hit(
+            cone.ray_cast(&ray(Vec3::new(0.0, 3.0, 0.0), Vec3::NEG_Y)),
+            2.0,
+        );
+        // The other nappe of the double cone must be ignored.
+        assert_eq!(
+            cone.ray_cast(&ray(Vec3::new(-3.0, 1.5, 0.0), Vec3::X)),
+            None
+        );
+        assert!(cone.contains(Vec3::new(0.2, 0.5, 0.2)));
+        assert!(!cone.contains(Vec3::new(0.6, 0.5, 0.0)));
+        assert_eq!(cone.closest_point(Vec3::new(0.0, 2.0, 0.0)), Vec3::Y);
+        assert_eq!(cone.support(Vec3::Y), Vec3::Y);
+    }
+
+    #[test]
+    fn triangle_and_segment_queries() {
+        let triangle = Triangle3d::new(Vec3::ZERO, Vec3::X, Vec3::Y);
+        assert_eq!(triangle.normal(), Vec3::Z);
+        let (t, barycentric) = triangle
+            .ray_cast_barycentric(&ray(Vec3::new(0.25, 0.5, 2.0), Vec3::NEG_Z))
+            .unwrap();
+        assert_eq!(t, 2.0);
+        assert_eq!(barycentric, Vec2::new(0.25, 0.5));
+        assert_hit(
+            triangle.ray_cast(&ray(Vec3::new(0.25, 0.25, -1.0), Vec3::Z)),
+            1.0,
+        );
+        assert_eq!(
+            triangle.ray_cast(&ray(Vec3::new(1.0, 1.0, 1.0), Vec3::NEG_Z)),
+            None
+        );
+        assert_eq!(
+            triangle.closest_point(Vec3::new(0.25, 0.25, 3.0)),
+            Vec3::new(0.25, 0.25, 0.0)
+        );
+        assert_eq!(triangle.closest_point(Vec3::new(2.0, -1.0, 0.0)), Vec3::X);
+
+        let segment = Segment3d::new(Vec3::new(2.0, -1.0, 0.0), Vec3::new(2.0, 1.0, 0.0));
+        assert_hit(segment.ray_cast(&ray(Vec3::ZERO, Vec3::X)), 2.0);
+        assert_eq!(
+            segment.ray_cast(&ray(Vec3::new(0.0, 0.0, 0.1), Vec3::X)),
+            None
+        );
+    }
+
+    #[test]
+    fn plane_queries() {
+        let plane = Plane3d::from_point_normal(Vec3::Y, Vec3::Y);
+        assert_eq!(plane.signed_distance(Vec3::new(5.0, 3.0, 1.0)), 2.0);
+        assert_eq!(
+            plane.closest_point(Vec3::new(5.0, 3.0, 1.0)),
+            Vec3::new(5.0, 1.0, 1.0)
+        );
+        assert_eq!(plane.ray_cast(&ray(Vec3::ZERO, Vec3::Y)), Some(1.0));
+        assert_eq!(plane.ray_cast(&ray(Vec3::ZERO, Vec3::NEG_Y)), None);
+        assert!(plane.intersects(&Sphere::new(Vec3::ZERO, 1.0)));
+        assert!(!plane.intersects(&Sphere::new(Vec3::ZERO, 0.5)));
+    }
+
+    #[test]
+    fn intersections() {
+        let sphere = Sphere::new(Vec3::ZERO, 1.0);
+        let aabb = Aabb3d::new(Vec3::splat(0.5), Vec3::splat(2.0));
+        let far_aabb = Aabb3d::new(Vec3::splat(0.7), Vec3::splat(2.0));
+        assert!(sphere.intersects(&aabb));
+        assert!(!sphere.intersects(&far_aabb));
+
+        let capsule = Capsule3d::new(Vec3::new(-3.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0), 0.2);
+        let cylinder = Cylinder::new(Vec3::new(0.0, 0.3, -1.0), Vec3::new(0.0, 0.3, 1.0), 0.2);
+        assert!(capsule.intersects(&cylinder));
+        assert!(cylinder.intersects(&capsule));
+        assert!(!capsule.intersects(&Cylinder::new(
+            Vec3::new(0.0, 0.5, -1.0),
+            Vec3::new(0.0, 0.5, 1.0),
+            0.2
+        )));
+
+        let cone = Cone::new(Vec3::new(0.0, 3.0, 0.0), Vec3::new(0.0, 1.5, 0.0), 1.0);
+        assert!(cone.intersects(&aabb));
+        assert!(!cone.intersects(&sphere));
+
+        let triangle = Triangle3d::new(
+            Vec3::new(-1.0, -1.0, 0.5),
+            Vec3::new(1.0, -1.0, 0.5),
+            Vec3::new(0.0, 1.0, 0.5),
+        );
+        let obb = Obb3d::new(Vec3::ZERO, Vec3::splat(0.6), Quat::from_rotation_y(0.7));
+        assert!(triangle.intersects(&obb));
+        assert!(obb.intersects(&triangle));
+        assert!(!triangle.intersects(&Obb3d::new(
+            Vec3::new(0.0, 0.0, -2.0),
+            Vec3::splat(0.6),
+            Quat::from_rotation_y(0.7)
+        )));
+        assert!(
+            Segment3d::new(Vec3::new(0.0, 0.0, -3.0), Vec3::new(0.0, 0.0, 3.0))
+                .intersects(&triangle)
+        );
+    }
+
+    #[test]
+    fn touching_intersections() {
+        let capsule = Capsule3d::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), 0.5);
+        let touching = Capsule3d::new(Vec3::new(-1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0), 0.5);
+        let separated =
+            Capsule3d::new(Vec3::new(-1.0, 1.001, 0.0), Vec3::new(1.0, 1.001, 0.0), 0.5);
+        assert!(capsule.intersects(&touching));
+        assert!(!capsule.intersects(&separated));
+
+        // Curved surfaces only approach each other, without a face to stop the search on.
+        let cylinder = Cylinder::new(Vec3::new(0.0, 1.0, -1.0), Vec3::new(0.0, 1.0, 1.0), 0.5);
+        let separated_cylinder =
+            Cylinder::new(Vec3::new(0.0, 1.001, -1.0), Vec3::new(0.0, 1.001, 1.0), 0.5);
+        assert!(capsule.intersects(&cylinder));
+        assert!(!capsule.intersects(&separated_cylinder));
+        let cone = Cone::new(Vec3::new(0.3, 0.5, 0.0), Vec3::new(0.3, 2.0, 0.0), 1.0);
+        assert!(cone.intersects(&capsule));
+        assert!(
+            !Cone::new(Vec3::new(0.3, 0.501, 0.0), Vec3::new(0.3, 2.0, 0.0), 1.0)
+                .intersects(&capsule)
+        );
+    }
+}
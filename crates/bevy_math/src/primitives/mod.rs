@@ -0,0 +1,300 @@
+//! Geometric primitives and the queries between them.
+//!
+//! Bounded shapes implement [`Primitive3d`] or [`Primitive2d`], which provide ray casting,
+//! closest point, containment and overlap tests. Overlap tests work between any two bounded
+//! shapes of the same dimension, using the [GJK] algorithm where no simpler test exists.
+//!
+//! The unbounded [`Plane3d`] and [`Line2d`] have their own methods, since most of these queries
+//! are meaningless for them.
+//!
+//! ```
+//! # use bevy_math::{primitives::*, Ray, Vec3};
+//! let sphere = Sphere::new(Vec3::new(0.0, 0.0, -5.0), 1.0);
+//! let ray = Ray {
+//!     origin: Vec3::ZERO,
+//!     direction: Vec3::NEG_Z,
+//! };
+//! assert_eq!(sphere.ray_cast(&ray), Some(4.0));
+//!
+//! let aabb = Aabb3d::new(Vec3::new(-1.0, -1.0, -4.5), Vec3::new(1.0, 1.0, -3.5));
+//! assert!(sphere.intersects(&aabb));
+//! ```
+//!
+//! [GJK]: https://en.wikipedia.org/wiki/Gilbert%E2%80%93Johnson%E2%80%93Keerthi_distance_algorithm
+
+mod dim2;
+mod dim3;
+
+pub use dim2::*;
+pub use dim3::*;
+
+use crate::{Ray, Ray2d, Rect, Vec2, Vec3};
+
+/// Tolerance used by containment and overlap tests.
+const CONTAINS_EPSILON: f32 = 1e-5;
+
+/// A bounded shape in 3D space.
+pub trait Primitive3d {
+    /// Returns the point of the shape closest to `point`.
+    ///
+    /// For solid shapes, points inside the shape are returned unchanged.
+    fn closest_point(&self, point: Vec3) -> Vec3;
+
+    /// Returns the distance along `ray` at which it enters the shape, if it does.
+    ///
+    /// Returns `Some(0.0)` if the origin of the ray is inside the shape. Distances are in units
+    /// of the length of the direction of the ray.
+    fn ray_cast(&self, ray: &Ray) -> Option<f32>;
+
+    /// Returns the point of the shape furthest along `direction`.
+    fn support(&self, direction: Vec3) -> Vec3;
+
+    /// Returns `true` if `point` is inside or on the surface of the shape.
+    fn contains(&self, point: Vec3) -> bool {
+        self.closest_point(point).distance_squared(point) <= CONTAINS_EPSILON * CONTAINS_EPSILON
+    }
+
+    /// Returns the smallest [`Aabb3d`] containing the shape.
+    fn aabb(&self) -> Aabb3d {
+        Aabb3d {
+            min: Vec3::new(
+                self.support(Vec3::NEG_X).x,
+                self.support(Vec3::NEG_Y).y,
+                self.support(Vec3::NEG_Z).z,
+            ),
+            max: Vec3::new(
+                self.support(Vec3::X).x,
+                self.support(Vec3::Y).y,
+                self.support(Vec3::Z).z,
+            ),
+        }
+    }
+
+    /// Returns `true` if the shape and `other` overlap, touching included.
+    fn intersects(&self, other: &impl Primitive3d) -> bool
+    where
+        Self: Sized,
+    {
+        gjk(|direction| self.support(direction) - other.support(-direction))
+    }
+}
+
+/// A bounded shape in 2D space.
+pub trait Primitive2d {
+    /// Returns the point of the shape closest to `point`.
+    ///
+    /// For solid shapes, points inside the shape are returned unchanged.
+    fn closest_point(&self, point: Vec2) -> Vec2;
+
+    /// Returns the distance along `ray` at which it enters the shape, if it does.
+    ///
+    /// Returns `Some(0.0)` if the origin of the ray is inside the shape. Distances are in units
+    /// of the length of the direction of the ray.
+    fn ray_cast(&self, ray: &Ray2d) -> Option<f32>;
+
+    /// Returns the point of the shape furthest along `direction`.
+    fn support(&self, direction: Vec2) -> Vec2;
+
+    /// Returns `true` if `point` is inside or on the boundary of the shape.
+    fn contains(&self, point: Vec2) -> bool {
+        self.closest_point(point).distance_squared(point) <= CONTAINS_EPSILON * CONTAINS_EPSILON
+    }
+
+    /// Returns the smallest [`Rect`] containing the shape.
+    fn aabb(&self) -> Rect {
+        Rect {
+            min: Vec2::new(self.support(Vec2::NEG_X).x, self.support(Vec2::NEG_Y).y),
+            max: Vec2::new(self.support(Vec2::X).x, self.support(Vec2::Y).y),
+        }
+    }
+
+    /// Returns `true` if the shape and `other` overlap, touching included.
+    fn intersects(&self, other: &impl Primitive2d) -> bool
+    where
+        Self: Sized,
+    {
+        // 2D shapes are lifted to the z = 0 plane, where the 3D algorithm handles them.
+        gjk(|direction| {
+            let direction = direction.truncate();
+            (self.support(direction) - other.support(-direction)).extend(0.0)
+        })
+    }
+}
+
+/// Maximum number of iterations of [`gjk`]. Shapes still undecided after that many iterations
+/// are reported as not overlapping.
+const GJK_MAX_ITERATIONS: usize = 64;
+
+/// Returns `true` if the Minkowski difference described by `support` contains the origin.
+///
+/// The origin counts as contained when it is within [`CONTAINS_EPSILON`] of the boundary of
+/// the difference, which is where the search stops making progress for touching shapes.
+fn gjk(support: impl Fn(Vec3) -> Vec3) -> bool {
+    let first = support(Vec3::X);
+    let mut simplex = Simplex {
+        points: [first; 4],
+        len: 1,
+    };
+    let mut direction = -first;
+
+    for _ in 0..GJK_MAX_ITERATIONS {
+        if direction.length_squared() <= f32::EPSILON * f32::EPSILON {
+            // The origin is on the simplex.
+            return true;
+        }
+        let point = support(direction);
+        let distance = point.dot(direction);
+        let tolerance = CONTAINS_EPSILON * direction.length();
+        if distance < -tolerance {
+            // The origin is beyond the furthest point of the difference.
+            return false;
+        }
+        let furthest = simplex.points[..simplex.len]
+            .iter()
+            .map(|simplex_point| simplex_point.dot(direction))
+            .fold(f32::NEG_INFINITY, f32::max);
+        if distance - furthest <= tolerance {
+            // The simplex already reaches the boundary of the difference, and the origin is
+            // between it and the furthest point, so on the boundary up to the tolerance.
+            return true;
+        }
+        simplex.push(point);
+        if simplex.contains_origin(&mut direction) {
+            return true;
+        }
+    }
+    false
+}
+
+/// The simplex of [`gjk`], with the most recently added point last.
+struct Simplex {
+    points: [Vec3; 4],
+    len: usize,
+}
+
+impl Simplex {
+    fn push(&mut self, point: Vec3) {
+        self.points[self.len] = point;
+        self.len += 1;
+    }
+
+    fn set(&mut self, points: &[Vec3]) {
+        self.points[..points.len()].copy_from_slice(points);
+        self.len = points.len();
+    }
+
+    /// Reduces the simplex to the feature closest to the origin and updates `direction` to
+    /// point from it toward the origin. Returns `true` if the simplex contains the origin.
+    fn contains_origin(&mut self, direction: &mut Vec3) -> bool {
+        match self.len {
+            2 => self.line(direction),
+            3 => self.triangle(direction),
+            _ => self.tetrahedron(direction),
+        }
+    }
+
+    fn line(&mut self, direction: &mut Vec3) -> bool {
+        let [b, a, ..] = self.points;
+        let ab = b - a;
+        let ao = -a;
+        if ab.dot(ao) > 0.0 {
+            *direction = ab.cross(ao).cross(ab);
+            // The origin is on the segment.
+            direction.length_squared() <= f32::EPSILON * f32::EPSILON
+                && ao.length_squared() <= ab.length_squared()
+        } else {
+            self.set(&[a]);
+            *direction = ao;
+            false
+        }
+    }
+
+    fn triangle(&mut self, direction: &mut Vec3) -> bool {
+        let [c, b, a, _] = self.points;
+        let ab = b - a;
+        let ac = c - a;
+        let ao = -a;
+        let abc = ab.cross(ac);
+        if abc.length_squared() <= f32::EPSILON * f32::EPSILON {
+            // Degenerate triangle, keep its newest edge.
+            self.set(&[b, a]);
+            return self.line(direction);
+        }
+
+        if abc.cross(ac).dot(ao) > 0.0 {
+            if ac.dot(ao) > 0.0 {
+                self.set(&[c, a]);
+                *direction = ac.cross(ao).cross(ac);
+                false
+            } else {
+                self.set(&[b, a]);
+                self.line(direction)
+            }
+        } else if ab.cross(abc).dot(ao) > 0.0 {
+            self.set(&[b, a]);
+            self.line(direction)
+        } else {
+            let side = abc.dot(ao);
+            if side > 0.0 {
+                *direction = abc;
+                false
+            } else if side < 0.0 {
+                self.set(&[b, c, a]);
+                *direction = -abc;
+                false
+            } else {
+                // The origin is in the triangle, which is always the case for 2D shapes.
+                true
+            }
+        }
+    }
+
+    fn tetrahedron(&mut self, direction: &mut Vec3) -> bool {
+        let [d, c, b, a] = self.points;
+        let ab = b - a;
+        let ac = c - a;
+        let ad = d - a;
+        let ao = -a;
+
+        if ab.cross(ac).dot(ao) > 0.0 {
+            self.set(&[c, b, a]);
+            self.triangle(direction)
+        } else if ac.cross(ad).dot(ao) > 0.0 {
+            self.set(&[d, c, a]);
+            self.triangle(direction)
+        } else if ad.cross(ab).dot(ao) > 0.0 {
+            self.set(&[b, d, a]);
+            self.triangle(direction)
+        } else {
+            true
+        }
+    }
+}
+
+/// Returns the real roots of `a * t^2 + 2 * half_b * t + c`, smallest first.
+///
+/// Linear equations have their single root returned twice.
+fn quadratic_roots(a: f32, half_b: f32, c: f32) -> Option<(f32, f32)> {
+    if a.abs() <= f32::EPSILON {
+        if half_b.abs() <= f32::EPSILON {
+            return None;
+        }
+        let t = -c / (2.0 * half_b);
+        return Some((t, t));
+    }
+    let discriminant = half_b * half_b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let sqrt = discriminant.sqrt();
+    let (t0, t1) = ((-half_b - sqrt) / a, (-half_b + sqrt) / a);
+    Some((t0.min(t1), t0.max(t1)))
+}
+
+/// Returns the smallest of the given distances, ignoring the negative ones.
+fn nearest_hit(hits: impl IntoIterator<Item = Option<f32>>) -> Option<f32> {
+    hits.into_iter()
+        .flatten()
+        .filter(|t| *t >= 0.0)
+        .min_by(|a, b| a.total_cmp(b))
+}
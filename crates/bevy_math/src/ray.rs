@@ -1,4 +1,4 @@
-use crate::Vec3;
+use crate::{Vec2, Vec3};
 
 /// A ray is an infinite line starting at `origin`, going in `direction`.
 #[derive(Default, Clone, Copy, Debug, PartialEq)]
@@ -31,6 +31,33 @@ impl Ray {
     }
 }
 
+/// A ray in 2D space, starting at `origin` and going in `direction`.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray2d {
+    /// The origin of the ray.
+    pub origin: Vec2,
+    /// A normalized vector representing the direction of the ray.
+    pub direction: Vec2,
+}
+
+impl Ray2d {
+    /// Create a new ray from its origin and its direction, which is normalized.
+    #[inline]
+    pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self {
+            origin,
+            direction: direction.normalize(),
+        }
+    }
+
+    /// Retrieve a point at the given distance along the ray.
+    #[inline]
+    pub fn get_point(&self, distance: f32) -> Vec2 {
+        self.origin + self.direction * distance
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
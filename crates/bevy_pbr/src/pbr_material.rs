@@ -3,7 +3,7 @@ use crate::{
     PBR_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
-use bevy_math::Vec4;
+use bevy_math::{Affine2, Mat3, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect, TypeUuid};
 use bevy_render::{
     color::Color, mesh::MeshVertexBufferLayout, render_asset::RenderAssets, render_resource::*,
@@ -231,6 +231,83 @@ pub struct StandardMaterial {
     ///
     /// [z-fighting]: https://en.wikipedia.org/wiki/Z-fighting
     pub depth_bias: f32,
+
+    /// Transform applied to the UV coordinates of the mesh before sampling any texture.
+    ///
+    /// Can be used to offset, scale or rotate the textures of the material, for example to tile
+    /// a texture or to pick a region of a texture atlas.
+    ///
+    /// Defaults to [`Affine2::IDENTITY`].
+    pub uv_transform: Affine2,
+
+    /// Strength of a thin, glossy dielectric layer on top of the material, within `[0.0, 1.0]`.
+    ///
+    /// This is typically used for car paint or varnished wood. The layer adds its own specular
+    /// highlight, and dims the layer below at grazing angles.
+    ///
+    /// Defaults to `0.0`, for no clearcoat.
+    pub clearcoat: f32,
+
+    /// Linear perceptual roughness of the clearcoat layer, clamped to `[0.089, 1.0]` in the shader.
+    ///
+    /// Defaults to `0.0`.
+    pub clearcoat_perceptual_roughness: f32,
+
+    /// Amount of light transmitted through the surface, within `[0.0, 1.0]`.
+    ///
+    /// This is typically used for glass or thin plastic. Transmitted light is approximated with
+    /// blending, so it isn't refracted, and it is only visible with an [`AlphaMode`] that blends
+    /// with what is behind the mesh, such as [`AlphaMode::Premultiplied`].
+    ///
+    /// Defaults to `0.0`, for opaque.
+    #[doc(alias = "transmission")]
+    pub specular_transmission: f32,
+
+    /// Thickness of the volume below the surface, in the mesh's local units.
+    ///
+    /// Used with [`attenuation_distance`] and [`attenuation_color`] to tint transmitted light.
+    /// `0.0` means the material is thin-walled.
+    ///
+    /// Defaults to `0.0`.
+    ///
+    /// [`attenuation_distance`]: StandardMaterial::attenuation_distance
+    /// [`attenuation_color`]: StandardMaterial::attenuation_color
+    pub thickness: f32,
+
+    /// Index of refraction of the volume below the surface.
+    ///
+    /// Defaults to `1.5`, which is typical of glass and plastic.
+    pub ior: f32,
+
+    /// Average distance light travels in the volume before being attenuated to
+    /// [`attenuation_color`].
+    ///
+    /// Defaults to [`f32::INFINITY`], for no attenuation.
+    ///
+    /// [`attenuation_color`]: StandardMaterial::attenuation_color
+    pub attenuation_distance: f32,
+
+    /// Color that white light turns into after traveling [`attenuation_distance`] in the volume.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    ///
+    /// [`attenuation_distance`]: StandardMaterial::attenuation_distance
+    pub attenuation_color: Color,
+
+    /// Tint of the specular highlight of non-metals.
+    ///
+    /// Defaults to [`Color::WHITE`], for an untinted highlight.
+    pub specular_tint: Color,
+
+    /// Color of the sheen layer, a soft highlight at grazing angles typical of cloth and velvet.
+    ///
+    /// Defaults to [`Color::BLACK`], for no sheen.
+    pub sheen_color: Color,
+
+    /// Linear perceptual roughness of the sheen layer, within `[0.0, 1.0]`.
+    ///
+    /// Defaults to `0.0`.
+    pub sheen_perceptual_roughness: f32,
 }
 
 impl Default for StandardMaterial {
@@ -260,6 +337,18 @@ impl Default for StandardMaterial {
             fog_enabled: true,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
+            uv_transform: Affine2::IDENTITY,
+            clearcoat: 0.0,
+            clearcoat_perceptual_roughness: 0.0,
+            specular_transmission: 0.0,
+            thickness: 0.0,
+            // Matches the glTF default, typical of glass and plastic.
+            ior: 1.5,
+            attenuation_distance: f32::INFINITY,
+            attenuation_color: Color::WHITE,
+            specular_tint: Color::WHITE,
+            sheen_color: Color::BLACK,
+            sheen_perceptual_roughness: 0.0,
         }
     }
 }
@@ -341,6 +430,28 @@ pub struct StandardMaterialUniform {
     /// When the alpha mode mask flag is set, any base color alpha above this cutoff means fully opaque,
     /// and any below means fully transparent.
     pub alpha_cutoff: f32,
+    /// Transform applied to the UV coordinates before sampling textures.
+    pub uv_transform: Mat3,
+    /// Strength of the clearcoat layer, from [0.0, 1.0]
+    pub clearcoat: f32,
+    /// Linear perceptual roughness of the clearcoat layer
+    pub clearcoat_perceptual_roughness: f32,
+    /// Amount of light transmitted through the surface, from [0.0, 1.0]
+    pub specular_transmission: f32,
+    /// Thickness of the volume below the surface
+    pub thickness: f32,
+    /// Index of refraction of the volume below the surface
+    pub ior: f32,
+    /// Distance light travels in the volume before being attenuated to `attenuation_color`
+    pub attenuation_distance: f32,
+    /// Color of white light after traveling `attenuation_distance` in the volume
+    pub attenuation_color: Vec4,
+    /// Tint of the specular highlight of non-metals
+    pub specular_tint: Vec4,
+    /// Color of the sheen layer
+    pub sheen_color: Vec4,
+    /// Linear perceptual roughness of the sheen layer
+    pub sheen_perceptual_roughness: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -407,6 +518,17 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             reflectance: self.reflectance,
             flags: flags.bits(),
             alpha_cutoff,
+            uv_transform: self.uv_transform.into(),
+            clearcoat: self.clearcoat,
+            clearcoat_perceptual_roughness: self.clearcoat_perceptual_roughness,
+            specular_transmission: self.specular_transmission,
+            thickness: self.thickness,
+            ior: self.ior,
+            attenuation_distance: self.attenuation_distance,
+            attenuation_color: self.attenuation_color.as_linear_rgba_f32().into(),
+            specular_tint: self.specular_tint.as_linear_rgba_f32().into(),
+            sheen_color: self.sheen_color.as_linear_rgba_f32().into(),
+            sheen_perceptual_roughness: self.sheen_perceptual_roughness,
         }
     }
 }
@@ -21,12 +21,15 @@ struct FragmentInput {
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
     var output_color: vec4<f32> = material.base_color;
+#ifdef VERTEX_UVS
+    let uv = (material.uv_transform * vec3<f32>(in.uv, 1.0)).xy;
+#endif
 #ifdef VERTEX_COLORS
     output_color = output_color * in.color;
 #endif
 #ifdef VERTEX_UVS
     if ((material.flags & STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u) {
-        output_color = output_color * textureSample(base_color_texture, base_color_sampler, in.uv);
+        output_color = output_color * textureSample(base_color_texture, base_color_sampler, uv);
     }
 #endif
 
@@ -40,12 +43,22 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         pbr_input.material.reflectance = material.reflectance;
         pbr_input.material.flags = material.flags;
         pbr_input.material.alpha_cutoff = material.alpha_cutoff;
+        pbr_input.material.clearcoat = material.clearcoat;
+        pbr_input.material.clearcoat_perceptual_roughness = material.clearcoat_perceptual_roughness;
+        pbr_input.material.specular_transmission = material.specular_transmission;
+        pbr_input.material.thickness = material.thickness;
+        pbr_input.material.ior = material.ior;
+        pbr_input.material.attenuation_distance = material.attenuation_distance;
+        pbr_input.material.attenuation_color = material.attenuation_color;
+        pbr_input.material.specular_tint = material.specular_tint;
+        pbr_input.material.sheen_color = material.sheen_color;
+        pbr_input.material.sheen_perceptual_roughness = material.sheen_perceptual_roughness;
 
         // TODO use .a for exposure compensation in HDR
         var emissive: vec4<f32> = material.emissive;
 #ifdef VERTEX_UVS
         if ((material.flags & STANDARD_MATERIAL_FLAGS_EMISSIVE_TEXTURE_BIT) != 0u) {
-            emissive = vec4<f32>(emissive.rgb * textureSample(emissive_texture, emissive_sampler, in.uv).rgb, 1.0);
+            emissive = vec4<f32>(emissive.rgb * textureSample(emissive_texture, emissive_sampler, uv).rgb, 1.0);
         }
 #endif
         pbr_input.material.emissive = emissive;
@@ -54,7 +67,7 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         var perceptual_roughness: f32 = material.perceptual_roughness;
 #ifdef VERTEX_UVS
         if ((material.flags & STANDARD_MATERIAL_FLAGS_METALLIC_ROUGHNESS_TEXTURE_BIT) != 0u) {
-            let metallic_roughness = textureSample(metallic_roughness_texture, metallic_roughness_sampler, in.uv);
+            let metallic_roughness = textureSample(metallic_roughness_texture, metallic_roughness_sampler, uv);
             // Sampling from GLTF standard channels for now
             metallic = metallic * metallic_roughness.b;
             perceptual_roughness = perceptual_roughness * metallic_roughness.g;
@@ -66,7 +79,7 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         var occlusion: f32 = 1.0;
 #ifdef VERTEX_UVS
         if ((material.flags & STANDARD_MATERIAL_FLAGS_OCCLUSION_TEXTURE_BIT) != 0u) {
-            occlusion = textureSample(occlusion_texture, occlusion_sampler, in.uv).r;
+            occlusion = textureSample(occlusion_texture, occlusion_sampler, uv).r;
         }
 #endif
         pbr_input.frag_coord = in.frag_coord;
@@ -93,7 +106,7 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
 #endif
 #endif
 #ifdef VERTEX_UVS
-            in.uv,
+            uv,
 #endif
         );
         pbr_input.V = calculate_view(in.world_position, pbr_input.is_orthographic);
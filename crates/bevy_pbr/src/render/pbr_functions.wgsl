@@ -159,49 +159,20 @@ fn pbr_input_new() -> PbrInput {
 }
 
 #ifndef PREPASS_FRAGMENT
-fn pbr(
+// Sums the contributions of every direct light affecting the fragment, with shadows.
+fn direct_lighting(
     in: PbrInput,
-) -> vec4<f32> {
-    var output_color: vec4<f32> = in.material.base_color;
-
-    // TODO use .a for exposure compensation in HDR
-    let emissive = in.material.emissive;
-
-    // calculate non-linear roughness from linear perceptualRoughness
-    let metallic = in.material.metallic;
-    let perceptual_roughness = in.material.perceptual_roughness;
-    let roughness = perceptualRoughnessToRoughness(perceptual_roughness);
-
-    let occlusion = in.occlusion;
-
-    output_color = alpha_discard(in.material, output_color);
-
-    // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
-    let NdotV = max(dot(in.N, in.V), 0.0001);
-
-    // Remapping [0,1] reflectance to F0
-    // See https://google.github.io/filament/Filament.html#materialsystem/parameterization/remapping
-    let reflectance = in.material.reflectance;
-    let F0 = 0.16 * reflectance * reflectance * (1.0 - metallic) + output_color.rgb * metallic;
-
-    // Diffuse strength inversely related to metallicity
-    let diffuse_color = output_color.rgb * (1.0 - metallic);
-
-    let R = reflect(-in.V, in.N);
-
-    let f_ab = F_AB(perceptual_roughness, NdotV);
-
+    roughness: f32,
+    NdotV: f32,
+    R: vec3<f32>,
+    F0: vec3<f32>,
+    f_ab: vec2<f32>,
+    diffuse_color: vec3<f32>,
+    view_z: f32,
+    offset_and_counts: vec3<u32>,
+) -> vec3<f32> {
     var direct_light: vec3<f32> = vec3<f32>(0.0);
 
-    let view_z = dot(vec4<f32>(
-        view.inverse_view[0].z,
-        view.inverse_view[1].z,
-        view.inverse_view[2].z,
-        view.inverse_view[3].z
-    ), in.world_position);
-    let cluster_index = fragment_cluster_index(in.frag_coord.xy, view_z, in.is_orthographic);
-    let offset_and_counts = unpack_offset_and_counts(cluster_index);
-
     // Point lights (direct)
     for (var i: u32 = offset_and_counts[0]; i < offset_and_counts[0] + offset_and_counts[1]; i = i + 1u) {
         let light_id = get_light_id(i);
@@ -241,6 +212,55 @@ fn pbr(
         direct_light += light_contrib * shadow;
     }
 
+    return direct_light;
+}
+
+fn pbr(
+    in: PbrInput,
+) -> vec4<f32> {
+    var output_color: vec4<f32> = in.material.base_color;
+
+    // TODO use .a for exposure compensation in HDR
+    let emissive = in.material.emissive;
+
+    // calculate non-linear roughness from linear perceptualRoughness
+    let metallic = in.material.metallic;
+    let perceptual_roughness = in.material.perceptual_roughness;
+    let roughness = perceptualRoughnessToRoughness(perceptual_roughness);
+
+    let occlusion = in.occlusion;
+
+    output_color = alpha_discard(in.material, output_color);
+
+    // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
+    let NdotV = max(dot(in.N, in.V), 0.0001);
+
+    // Remapping [0,1] reflectance to F0
+    // See https://google.github.io/filament/Filament.html#materialsystem/parameterization/remapping
+    let reflectance = in.material.reflectance;
+    let F0 = 0.16 * reflectance * reflectance * (1.0 - metallic) * in.material.specular_tint.rgb
+        + output_color.rgb * metallic;
+
+    // Diffuse strength inversely related to metallicity, and to the light transmitted through the
+    // surface
+    let transmission = in.material.specular_transmission * (1.0 - metallic);
+    let diffuse_color = output_color.rgb * (1.0 - metallic) * (1.0 - transmission);
+
+    let R = reflect(-in.V, in.N);
+
+    let f_ab = F_AB(perceptual_roughness, NdotV);
+
+    let view_z = dot(vec4<f32>(
+        view.inverse_view[0].z,
+        view.inverse_view[1].z,
+        view.inverse_view[2].z,
+        view.inverse_view[3].z
+    ), in.world_position);
+    let cluster_index = fragment_cluster_index(in.frag_coord.xy, view_z, in.is_orthographic);
+    let offset_and_counts = unpack_offset_and_counts(cluster_index);
+
+    var direct_light = direct_lighting(in, roughness, NdotV, R, F0, f_ab, diffuse_color, view_z, offset_and_counts);
+
     // Ambient light (indirect)
     var indirect_light = ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, occlusion);
 
@@ -250,8 +270,50 @@ fn pbr(
     indirect_light += (environment_light.diffuse * occlusion) + environment_light.specular;
 #endif
 
+    // Sheen layer, approximated as a tinted rim of the diffuse lighting that narrows as the sheen
+    // gets smoother
+    if any(in.material.sheen_color.rgb > vec3<f32>(0.0)) {
+        let sheen_irradiance = direct_lighting(in, roughness, NdotV, R, vec3<f32>(0.0), f_ab, vec3<f32>(1.0), view_z, offset_and_counts)
+            + ambient_light(in.world_position, in.N, in.V, NdotV, vec3<f32>(1.0), vec3<f32>(0.0), 1.0, occlusion);
+        let sheen_exponent = mix(1.0, 5.0, 1.0 - in.material.sheen_perceptual_roughness);
+        let sheen = in.material.sheen_color.rgb * pow(1.0 - NdotV, sheen_exponent);
+        direct_light += sheen * sheen_irradiance;
+    }
+
+    // Clearcoat layer, a dielectric with a fixed 4% reflectance on top of the base layer
+    let clearcoat = in.material.clearcoat;
+    if clearcoat > 0.0 {
+        let clearcoat_F0 = vec3<f32>(0.04);
+        let clearcoat_perceptual_roughness = clamp(in.material.clearcoat_perceptual_roughness, 0.089, 1.0);
+        let clearcoat_roughness = perceptualRoughnessToRoughness(clearcoat_perceptual_roughness);
+        let clearcoat_f_ab = F_AB(clearcoat_perceptual_roughness, NdotV);
+
+        // Light reflected by the clearcoat doesn't reach the base layer
+        let clearcoat_fresnel = clearcoat * F_Schlick(0.04, 1.0, NdotV);
+        direct_light *= 1.0 - clearcoat_fresnel;
+        indirect_light *= 1.0 - clearcoat_fresnel;
+
+        direct_light += clearcoat * direct_lighting(in, clearcoat_roughness, NdotV, R, clearcoat_F0, clearcoat_f_ab, vec3<f32>(0.0), view_z, offset_and_counts);
+        indirect_light += clearcoat * ambient_light(in.world_position, in.N, in.V, NdotV, vec3<f32>(0.0), clearcoat_F0, clearcoat_perceptual_roughness, occlusion);
+#ifdef ENVIRONMENT_MAP
+        let clearcoat_environment_light = environment_map_light(clearcoat_perceptual_roughness, clearcoat_roughness, vec3<f32>(0.0), NdotV, clearcoat_f_ab, in.N, R, clearcoat_F0);
+        indirect_light += clearcoat * clearcoat_environment_light.specular;
+#endif
+    }
+
     let emissive_light = emissive.rgb * output_color.a;
 
+    // Light transmitted through the surface is approximated by letting what is behind the mesh
+    // show through, attenuated by the volume following the Beer-Lambert law
+    if transmission > 0.0 {
+        let attenuation = pow(
+            in.material.attenuation_color.rgb,
+            vec3<f32>(in.material.thickness / max(in.material.attenuation_distance, 0.0001))
+        );
+        let transmitted = transmission * (1.0 - F_Schlick(F0.g, 1.0, NdotV)) * dot(attenuation, vec3<f32>(0.2126, 0.7152, 0.0722));
+        output_color.a = output_color.a * (1.0 - transmitted);
+    }
+
     // Total light
     output_color = vec4<f32>(
         direct_light + indirect_light + emissive_light,
@@ -47,7 +47,8 @@ fn prepass_alpha_discard(in: FragmentInput) {
 
 #ifdef VERTEX_UVS
     if (material.flags & STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
-        output_color = output_color * textureSample(base_color_texture, base_color_sampler, in.uv);
+        let uv = (material.uv_transform * vec3<f32>(in.uv, 1.0)).xy;
+        output_color = output_color * textureSample(base_color_texture, base_color_sampler, uv);
     }
 #endif // VERTEX_UVS
 
@@ -104,7 +105,7 @@ fn fragment(in: FragmentInput) -> FragmentOutput {
 #endif // STANDARDMATERIAL_NORMAL_MAP
 #endif // VERTEX_TANGENTS
 #ifdef VERTEX_UVS
-            in.uv,
+            (material.uv_transform * vec3<f32>(in.uv, 1.0)).xy,
 #endif // VERTEX_UVS
         );
 
@@ -9,6 +9,17 @@ struct StandardMaterial {
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
     alpha_cutoff: f32,
+    uv_transform: mat3x3<f32>,
+    clearcoat: f32,
+    clearcoat_perceptual_roughness: f32,
+    specular_transmission: f32,
+    thickness: f32,
+    ior: f32,
+    attenuation_distance: f32,
+    attenuation_color: vec4<f32>,
+    specular_tint: vec4<f32>,
+    sheen_color: vec4<f32>,
+    sheen_perceptual_roughness: f32,
 };
 
 const STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT: u32         = 1u;
@@ -42,6 +53,21 @@ fn standard_material_new() -> StandardMaterial {
     material.reflectance = 0.5;
     material.flags = STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE;
     material.alpha_cutoff = 0.5;
+    material.uv_transform = mat3x3<f32>(
+        vec3<f32>(1.0, 0.0, 0.0),
+        vec3<f32>(0.0, 1.0, 0.0),
+        vec3<f32>(0.0, 0.0, 1.0),
+    );
+    material.clearcoat = 0.0;
+    material.clearcoat_perceptual_roughness = 0.0;
+    material.specular_transmission = 0.0;
+    material.thickness = 0.0;
+    material.ior = 1.5;
+    material.attenuation_distance = 1.0e38; // ~f32::INFINITY, which isn't allowed in WGSL literals
+    material.attenuation_color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    material.specular_tint = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    material.sheen_color = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    material.sheen_perceptual_roughness = 0.0;
 
     return material;
 }
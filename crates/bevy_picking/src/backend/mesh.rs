@@ -0,0 +1,52 @@
+//! Picks entities with a [`Mesh`](bevy_render::mesh::Mesh) by ray casting against their
+//! triangles with [`MeshRayCast`].
+
+use super::{HitData, PointerHits, PointerRays};
+use crate::{pointer::PointerId, PickingSet};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_render::mesh::{MeshRayCast, MeshRayCastSettings};
+
+/// Adds [`mesh_picking`] to pick the meshes under pointers.
+#[derive(Default)]
+pub struct MeshPickingPlugin;
+
+impl Plugin for MeshPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, mesh_picking.in_set(PickingSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the meshes along the ray of each pointer through each camera.
+///
+/// Only meshes on the [`RenderLayers`](bevy_render::view::RenderLayers) of the camera are hit,
+/// and pointers never hit their own mesh.
+pub fn mesh_picking(
+    pointer_rays: PointerRays,
+    pointers: Query<(), With<PointerId>>,
+    mut ray_cast: MeshRayCast,
+    mut hits: EventWriter<PointerHits>,
+) {
+    let not_pointer = |entity: Entity| !pointers.contains(entity);
+    for pointer_ray in pointer_rays.rays() {
+        let settings = MeshRayCastSettings::default()
+            .with_layers(pointer_ray.layers)
+            .with_filter(&not_pointer);
+        let picks = ray_cast
+            .cast_ray(pointer_ray.ray, &settings)
+            .into_iter()
+            .map(|(entity, hit)| {
+                let data = HitData::new(pointer_ray.camera, hit.distance)
+                    .with_position(hit.point, hit.normal);
+                (entity, data)
+            })
+            .collect::<Vec<_>>();
+        if !picks.is_empty() {
+            hits.send(PointerHits::new(
+                pointer_ray.pointer,
+                picks,
+                pointer_ray.order,
+            ));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Backends find the entities under each pointer and report them with [`PointerHits`].
+//!
+//! A backend is a system in [`PickingSet::Backend`](crate::PickingSet::Backend) that reads the
+//! [`PointerLocation`] of pointers, tests them against the entities it knows about, and sends
+//! a [`PointerHits`] event per pointer and camera. Backends don't decide what is hovered: hits of
+//! every backend are merged and sorted by the focus systems, see [`HoverMap`](crate::focus::HoverMap).
+//!
+//! Backends that ray cast into the world can use [`PointerRays`] to get the ray of each pointer
+//! through each camera.
+
+pub mod mesh;
+#[cfg(feature = "bevy_sprite")]
+pub mod sprite;
+#[cfg(feature = "bevy_ui")]
+pub mod ui;
+
+use crate::pointer::{Location, PointerId, PointerLocation};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Ray, Vec2, Vec3};
+use bevy_render::{
+    camera::{Camera, NormalizedRenderTarget},
+    view::RenderLayers,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_window::PrimaryWindow;
+
+/// The entities a backend found under a pointer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointerHits {
+    /// The pointer the entities are under.
+    pub pointer: PointerId,
+    /// The entities under the pointer, with where they were hit.
+    ///
+    /// They don't need to be sorted, but only entities with a [`HitData::depth`] lower than the
+    /// first blocking one will be hovered.
+    pub picks: Vec<(Entity, HitData)>,
+    /// The order of these hits relative to the hits of other cameras and backends, higher being
+    /// on top.
+    ///
+    /// This is usually the [`Camera::order`] of the camera the entities were seen through.
+    pub order: f32,
+}
+
+impl PointerHits {
+    /// Creates the hits of `pointer`.
+    pub fn new(pointer: PointerId, picks: Vec<(Entity, HitData)>, order: f32) -> Self {
+        Self {
+            pointer,
+            picks,
+            order,
+        }
+    }
+}
+
+/// Where an entity was hit by a pointer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HitData {
+    /// The camera the entity was seen through, `None` for pointers not tied to a camera.
+    pub camera: Option<Entity>,
+    /// How far from the camera or pointer the entity is. Entities with a lower depth are on top
+    /// of entities with a higher depth in the same [`PointerHits`].
+    pub depth: f32,
+    /// The hit point, in world space, if the backend computes it.
+    pub position: Option<Vec3>,
+    /// The normal of the entity at the hit point, in world space, if the backend computes it.
+    pub normal: Option<Vec3>,
+}
+
+impl HitData {
+    /// Creates a hit at `depth`, without a position or normal.
+    pub fn new(camera: Option<Entity>, depth: f32) -> Self {
+        Self {
+            camera,
+            depth,
+            position: None,
+            normal: None,
+        }
+    }
+
+    /// Sets the hit point and the normal at it.
+    pub fn with_position(mut self, position: Vec3, normal: Vec3) -> Self {
+        self.position = Some(position);
+        self.normal = Some(normal);
+        self
+    }
+}
+
+/// The ray of a pointer through a camera, see [`PointerRays`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointerRay {
+    /// The pointer.
+    pub pointer: PointerId,
+    /// The ray, in world space.
+    pub ray: Ray,
+    /// The camera the ray goes through, `None` for pointers with a [`Location::Ray`].
+    pub camera: Option<Entity>,
+    /// The [`PointerHits::order`] of hits along this ray.
+    pub order: f32,
+    /// The layers of the entities the ray can hit.
+    pub layers: RenderLayers,
+}
+
+/// A [`SystemParam`] giving the ray of each pointer through each active camera it is on.
+///
+/// Pointers on the screen get a ray through every active camera that renders to their target and
+/// whose viewport contains them. Pointers with a [`Location::Ray`] get their own ray, which hits
+/// entities on every layer.
+#[derive(SystemParam)]
+pub struct PointerRays<'w, 's> {
+    pointers: Query<'w, 's, (&'static PointerId, &'static PointerLocation)>,
+    cameras: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Camera,
+            &'static GlobalTransform,
+            Option<&'static RenderLayers>,
+        ),
+    >,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> PointerRays<'w, 's> {
+    /// Returns the ray of each pointer through each camera.
+    pub fn rays(&self) -> Vec<PointerRay> {
+        let primary_window = self.primary_window.get_single().ok();
+        let mut rays = Vec::new();
+        for (pointer, location) in &self.pointers {
+            match &location.location {
+                Some(Location::Screen { target, position }) => {
+                    for (entity, camera, transform, layers) in &self.cameras {
+                        let Some(viewport_position) =
+                            viewport_position(camera, primary_window, target, *position)
+                        else {
+                            continue;
+                        };
+                        let Some(ray) = camera.viewport_to_world(transform, viewport_position)
+                        else {
+                            continue;
+                        };
+                        rays.push(PointerRay {
+                            pointer: *pointer,
+                            ray,
+                            camera: Some(entity),
+                            order: camera.order as f32,
+                            layers: layers.copied().unwrap_or_default(),
+                        });
+                    }
+                }
+                Some(Location::Ray(ray)) => rays.push(PointerRay {
+                    pointer: *pointer,
+                    ray: *ray,
+                    camera: None,
+                    order: 0.0,
+                    layers: RenderLayers::all(),
+                }),
+                None => {}
+            }
+        }
+        rays
+    }
+}
+
+/// Returns the position of a pointer at `position` on `target` relative to the viewport of
+/// `camera`, or `None` if the camera is inactive, renders elsewhere, or the pointer is outside of
+/// its viewport.
+pub fn viewport_position(
+    camera: &Camera,
+    primary_window: Option<Entity>,
+    target: &NormalizedRenderTarget,
+    position: Vec2,
+) -> Option<Vec2> {
+    if !camera.is_active || camera.target.normalize(primary_window).as_ref() != Some(target) {
+        return None;
+    }
+    let (min, max) = camera.logical_viewport_rect()?;
+    let inside = position.cmpge(min).all() && position.cmple(max).all();
+    inside.then_some(position - min)
+}
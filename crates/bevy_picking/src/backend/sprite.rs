@@ -0,0 +1,144 @@
+//! Picks [`Sprite`]s and [`TextureAtlasSprite`]s by intersecting the ray of pointers with their
+//! rectangle.
+
+use super::{HitData, PointerHits, PointerRays};
+use crate::PickingSet;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{Ray, Rect, Vec2, Vec3};
+use bevy_render::{
+    texture::Image,
+    view::{ComputedVisibility, RenderLayers},
+};
+use bevy_sprite::{Anchor, Sprite, TextureAtlas, TextureAtlasSprite};
+use bevy_transform::components::GlobalTransform;
+
+/// Adds [`sprite_picking`] to pick the sprites under pointers.
+#[derive(Default)]
+pub struct SpritePickingPlugin;
+
+impl Plugin for SpritePickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, sprite_picking.in_set(PickingSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the sprites along the ray of each pointer through each camera.
+///
+/// Sprites are hit anywhere in their rectangle, including on transparent pixels.
+pub fn sprite_picking(
+    pointer_rays: PointerRays,
+    images: Res<Assets<Image>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    sprites: Query<(
+        Entity,
+        &Sprite,
+        &Handle<Image>,
+        &GlobalTransform,
+        Option<&RenderLayers>,
+        Option<&ComputedVisibility>,
+    )>,
+    atlas_sprites: Query<(
+        Entity,
+        &TextureAtlasSprite,
+        &Handle<TextureAtlas>,
+        &GlobalTransform,
+        Option<&RenderLayers>,
+        Option<&ComputedVisibility>,
+    )>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    let sprites =
+        sprites
+            .iter()
+            .filter_map(|(entity, sprite, image, transform, layers, visibility)| {
+                let size = sprite.custom_size.or_else(|| {
+                    sprite
+                        .rect
+                        .map(|rect| rect.size())
+                        .or_else(|| Some(images.get(image)?.size()))
+                })?;
+                Some((entity, size, &sprite.anchor, transform, layers, visibility))
+            });
+    let atlas_sprites = atlas_sprites.iter().filter_map(
+        |(entity, sprite, atlas, transform, layers, visibility)| {
+            let size = sprite.custom_size.or_else(|| {
+                let rect = atlases.get(atlas)?.textures.get(sprite.index)?;
+                Some(rect.size())
+            })?;
+            Some((entity, size, &sprite.anchor, transform, layers, visibility))
+        },
+    );
+    let targets = sprites
+        .chain(atlas_sprites)
+        .filter(|(.., visibility)| {
+            visibility
+                .map(ComputedVisibility::is_visible_in_hierarchy)
+                .unwrap_or(true)
+        })
+        .collect::<Vec<_>>();
+    if targets.is_empty() {
+        return;
+    }
+
+    for pointer_ray in pointer_rays.rays() {
+        let picks = targets
+            .iter()
+            .filter(|(.., layers, _)| {
+                layers
+                    .copied()
+                    .unwrap_or_default()
+                    .intersects(&pointer_ray.layers)
+            })
+            .filter_map(|(entity, size, anchor, transform, ..)| {
+                let (position, normal) = cast_sprite(&pointer_ray.ray, *size, anchor, transform)?;
+                let depth = (position - pointer_ray.ray.origin).dot(pointer_ray.ray.direction);
+                let data = HitData::new(pointer_ray.camera, depth).with_position(position, normal);
+                Some((*entity, data))
+            })
+            .collect::<Vec<_>>();
+        if !picks.is_empty() {
+            hits.send(PointerHits::new(
+                pointer_ray.pointer,
+                picks,
+                pointer_ray.order,
+            ));
+        }
+    }
+}
+
+/// Returns the point where `ray` hits a sprite of the given `size`, and its normal facing the ray.
+fn cast_sprite(
+    ray: &Ray,
+    size: Vec2,
+    anchor: &Anchor,
+    transform: &GlobalTransform,
+) -> Option<(Vec3, Vec3)> {
+    let world_to_local = transform.affine().inverse();
+    let origin = world_to_local.transform_point3(ray.origin);
+    let direction = world_to_local.transform_vector3(ray.direction);
+    // Sprites lie on the local XY plane.
+    if direction.z == 0.0 {
+        return None;
+    }
+    let distance = -origin.z / direction.z;
+    if distance < 0.0 {
+        return None;
+    }
+    let point = (origin + direction * distance).truncate();
+    let rect = Rect::from_center_size(-anchor.as_vec() * size, size);
+    if !rect.contains(point) {
+        return None;
+    }
+
+    let position = transform.transform_point(point.extend(0.0));
+    let mut normal = transform
+        .affine()
+        .transform_vector3(Vec3::Z)
+        .normalize_or_zero();
+    if normal.dot(ray.direction) > 0.0 {
+        normal = -normal;
+    }
+    Some((position, normal))
+}
@@ -0,0 +1,88 @@
+//! Picks UI nodes under pointers on the screen, from the top of the [`UiStack`] down.
+
+use super::{HitData, PointerHits};
+use crate::{
+    pointer::{Location, PointerId, PointerLocation},
+    PickingSet,
+};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::Rect;
+use bevy_render::{camera::Camera, view::ComputedVisibility};
+use bevy_transform::components::GlobalTransform;
+use bevy_ui::{camera_config::UiCameraConfig, CalculatedClip, Node, UiStack};
+use bevy_window::PrimaryWindow;
+
+/// Adds [`ui_picking`] to pick the UI nodes under pointers.
+#[derive(Default)]
+pub struct UiPickingPlugin;
+
+impl Plugin for UiPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, ui_picking.in_set(PickingSet::Backend));
+    }
+}
+
+/// Sends the [`PointerHits`] of the UI nodes under each pointer on a window.
+///
+/// The UI is picked through the active camera with the highest [`Camera::order`] that renders it
+/// to the window, and is on top of everything else seen through that camera. The depth of a node
+/// is its rank from the top of the [`UiStack`].
+pub fn ui_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, Option<&UiCameraConfig>)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    ui_stack: Res<UiStack>,
+    nodes: Query<(
+        &Node,
+        &GlobalTransform,
+        Option<&CalculatedClip>,
+        Option<&ComputedVisibility>,
+    )>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    let primary_window = primary_window.get_single().ok();
+    for (pointer, location) in &pointers {
+        let Some(Location::Screen { target, position }) = &location.location else {
+            continue;
+        };
+        let Some((camera_entity, camera)) = cameras
+            .iter()
+            .filter(|(_, camera, config)| {
+                camera.is_active
+                    && config.map(|config| config.show_ui).unwrap_or(true)
+                    && camera.target.normalize(primary_window).as_ref() == Some(target)
+            })
+            .max_by_key(|(_, camera, _)| camera.order)
+            .map(|(entity, camera, _)| (entity, camera))
+        else {
+            continue;
+        };
+
+        let picks = ui_stack
+            .uinodes
+            .iter()
+            .rev()
+            .filter_map(|entity| {
+                let (node, transform, clip, visibility) = nodes.get(*entity).ok()?;
+                if !visibility
+                    .map(ComputedVisibility::is_visible)
+                    .unwrap_or(true)
+                {
+                    return None;
+                }
+                let mut rect =
+                    Rect::from_center_size(transform.translation().truncate(), node.size());
+                if let Some(clip) = clip {
+                    rect = rect.intersect(clip.clip);
+                }
+                rect.contains(*position).then_some(*entity)
+            })
+            .enumerate()
+            .map(|(depth, entity)| (entity, HitData::new(Some(camera_entity), depth as f32)))
+            .collect::<Vec<_>>();
+        if !picks.is_empty() {
+            hits.send(PointerHits::new(*pointer, picks, camera.order as f32 + 0.5));
+        }
+    }
+}
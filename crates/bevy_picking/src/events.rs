@@ -0,0 +1,362 @@
+//! Pointer events sent to the entities pointers interact with, and bubbled up to their ancestors.
+//!
+//! Every event is a [`Pointer<E>`], sent once to its [`target`](Pointer::target) and once more
+//! to each of its ancestors, the [`listener`](Pointer::listener) of each copy. Listening for a
+//! [`Pointer<Click>`] on a parent entity thus catches clicks on any of its children.
+//!
+//! Within a frame and for a given pointer, events are sent in this order:
+//! [`Out`], [`Over`], then for each [`PointerButton`] [`Down`], or [`Up`], [`Click`],
+//! [`DragDrop`] and [`DragEnd`], or [`DragStart`] and [`Drag`].
+
+use crate::{
+    backend::HitData,
+    focus::{HoverMap, PreviousHoverMap},
+    pointer::{PointerButton, PointerId, PointerLocation, PointerPress},
+};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_hierarchy::Parent;
+use bevy_math::Vec2;
+use bevy_utils::HashMap;
+use std::ops::Deref;
+
+/// An event of a pointer on an entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pointer<E> {
+    /// The entity this copy of the event is sent to: the target or one of its ancestors.
+    pub listener: Entity,
+    /// The entity the pointer interacted with.
+    pub target: Entity,
+    /// The pointer.
+    pub pointer_id: PointerId,
+    /// The event.
+    pub event: E,
+}
+
+impl<E> Deref for Pointer<E> {
+    type Target = E;
+
+    fn deref(&self) -> &Self::Target {
+        &self.event
+    }
+}
+
+/// The pointer started hovering the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Over {
+    /// Where the entity is hit.
+    pub hit: HitData,
+}
+
+/// The pointer stopped hovering the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Out {
+    /// Where the entity was last hit.
+    pub hit: HitData,
+}
+
+/// A button was pressed while hovering the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Down {
+    /// The pressed button.
+    pub button: PointerButton,
+    /// Where the entity is hit.
+    pub hit: HitData,
+}
+
+/// A button was released while hovering the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Up {
+    /// The released button.
+    pub button: PointerButton,
+    /// Where the entity is hit.
+    pub hit: HitData,
+}
+
+/// A button was pressed then released while hovering the entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Click {
+    /// The clicked button.
+    pub button: PointerButton,
+    /// Where the entity is hit.
+    pub hit: HitData,
+}
+
+/// The pointer moved while a button pressed on the entity is held.
+///
+/// Only pointers on the screen can drag entities.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragStart {
+    /// The held button.
+    pub button: PointerButton,
+    /// Where the entity was hit when the button was pressed.
+    pub hit: HitData,
+}
+
+/// The pointer dragging the entity moved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drag {
+    /// The held button.
+    pub button: PointerButton,
+    /// How far the pointer moved since the button was pressed, in logical pixels.
+    pub distance: Vec2,
+    /// How far the pointer moved since the previous [`Drag`], in logical pixels.
+    pub delta: Vec2,
+}
+
+/// The button dragging the entity was released.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragEnd {
+    /// The released button.
+    pub button: PointerButton,
+    /// How far the pointer moved since the button was pressed, in logical pixels.
+    pub distance: Vec2,
+}
+
+/// An entity dragged by the pointer was dropped on the entity.
+///
+/// The dragged entity usually stays under the pointer and blocks the entities below it: make it
+/// [not hoverable](crate::Pickable::is_hoverable) while dragging it to drop it on them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragDrop {
+    /// The released button.
+    pub button: PointerButton,
+    /// The dragged entity.
+    pub dropped: Entity,
+    /// Where the entity is hit.
+    pub hit: HitData,
+}
+
+/// The state of a button of a pointer, kept by [`send_pointer_events`].
+#[derive(Debug, Default)]
+pub struct ButtonState {
+    /// The entities the button was pressed on.
+    pressed_on: HashMap<Entity, HitData>,
+    /// The position of the pointer when the button was pressed.
+    press_position: Option<Vec2>,
+    /// The latest position of the pointer while dragging, `None` if not dragging.
+    drag_position: Option<Vec2>,
+}
+
+/// The state of every pointer, kept by [`send_pointer_events`].
+#[derive(Debug, Default)]
+pub struct PointerStates {
+    presses: HashMap<PointerId, PointerPress>,
+    buttons: HashMap<(PointerId, PointerButton), ButtonState>,
+}
+
+/// The writers of every pointer event, and the hierarchy to bubble them up.
+#[derive(SystemParam)]
+pub struct PointerEventWriters<'w, 's> {
+    parents: Query<'w, 's, &'static Parent>,
+    over: EventWriter<'w, Pointer<Over>>,
+    out: EventWriter<'w, Pointer<Out>>,
+    down: EventWriter<'w, Pointer<Down>>,
+    up: EventWriter<'w, Pointer<Up>>,
+    click: EventWriter<'w, Pointer<Click>>,
+    drag_start: EventWriter<'w, Pointer<DragStart>>,
+    drag: EventWriter<'w, Pointer<Drag>>,
+    drag_end: EventWriter<'w, Pointer<DragEnd>>,
+    drop: EventWriter<'w, Pointer<DragDrop>>,
+}
+
+/// Sends `event` to `target` and each of its ancestors.
+fn bubble<E: Clone + Send + Sync + 'static>(
+    writer: &mut EventWriter<Pointer<E>>,
+    parents: &Query<&Parent>,
+    target: Entity,
+    pointer_id: PointerId,
+    event: E,
+) {
+    let mut listener = Some(target);
+    while let Some(entity) = listener {
+        writer.send(Pointer {
+            listener: entity,
+            target,
+            pointer_id,
+            event: event.clone(),
+        });
+        listener = parents.get(entity).ok().map(Parent::get);
+    }
+}
+
+/// Sends the [`Pointer`] events of every pointer from the [`HoverMap`] and their
+/// [`PointerPress`].
+pub fn send_pointer_events(
+    mut states: Local<PointerStates>,
+    hover_map: Res<HoverMap>,
+    previous_hover_map: Res<PreviousHoverMap>,
+    pointers: Query<(&PointerId, &PointerLocation, &PointerPress)>,
+    mut writers: PointerEventWriters,
+) {
+    let empty = HashMap::default();
+    let writers = &mut writers;
+
+    // Pointers that were removed stop hovering anything.
+    for (pointer_id, previous) in &previous_hover_map.0 {
+        if hover_map.0.contains_key(pointer_id) {
+            continue;
+        }
+        for (entity, hit) in previous {
+            bubble(
+                &mut writers.out,
+                &writers.parents,
+                *entity,
+                *pointer_id,
+                Out { hit: *hit },
+            );
+        }
+    }
+    states
+        .presses
+        .retain(|pointer_id, _| hover_map.0.contains_key(pointer_id));
+    states
+        .buttons
+        .retain(|(pointer_id, _), _| hover_map.0.contains_key(pointer_id));
+
+    for (pointer_id, location, press) in &pointers {
+        let hovered = hover_map.get(pointer_id).unwrap_or(&empty);
+        let previously_hovered = previous_hover_map.0.get(pointer_id).unwrap_or(&empty);
+
+        for (entity, hit) in previously_hovered {
+            if !hovered.contains_key(entity) {
+                bubble(
+                    &mut writers.out,
+                    &writers.parents,
+                    *entity,
+                    *pointer_id,
+                    Out { hit: *hit },
+                );
+            }
+        }
+        for (entity, hit) in hovered {
+            if !previously_hovered.contains_key(entity) {
+                bubble(
+                    &mut writers.over,
+                    &writers.parents,
+                    *entity,
+                    *pointer_id,
+                    Over { hit: *hit },
+                );
+            }
+        }
+
+        let previous_press = states
+            .presses
+            .insert(*pointer_id, *press)
+            .unwrap_or_default();
+        let position = location
+            .location
+            .as_ref()
+            .and_then(|location| location.position());
+        for button in PointerButton::ALL {
+            let state = states.buttons.entry((*pointer_id, button)).or_default();
+            let pressed = press.is_pressed(button);
+            let was_pressed = previous_press.is_pressed(button);
+
+            if pressed && !was_pressed {
+                for (entity, hit) in hovered {
+                    bubble(
+                        &mut writers.down,
+                        &writers.parents,
+                        *entity,
+                        *pointer_id,
+                        Down { button, hit: *hit },
+                    );
+                }
+                state.pressed_on = hovered.clone();
+                state.press_position = position;
+                state.drag_position = None;
+            } else if !pressed && was_pressed {
+                for (entity, hit) in hovered {
+                    bubble(
+                        &mut writers.up,
+                        &writers.parents,
+                        *entity,
+                        *pointer_id,
+                        Up { button, hit: *hit },
+                    );
+                }
+                for (entity, hit) in hovered {
+                    if state.pressed_on.contains_key(entity) {
+                        bubble(
+                            &mut writers.click,
+                            &writers.parents,
+                            *entity,
+                            *pointer_id,
+                            Click { button, hit: *hit },
+                        );
+                    }
+                }
+                if let (Some(start), Some(latest)) = (state.press_position, state.drag_position) {
+                    for dropped in state.pressed_on.keys() {
+                        for (entity, hit) in hovered {
+                            if entity == dropped {
+                                continue;
+                            }
+                            bubble(
+                                &mut writers.drop,
+                                &writers.parents,
+                                *entity,
+                                *pointer_id,
+                                DragDrop {
+                                    button,
+                                    dropped: *dropped,
+                                    hit: *hit,
+                                },
+                            );
+                        }
+                    }
+                    for entity in state.pressed_on.keys() {
+                        bubble(
+                            &mut writers.drag_end,
+                            &writers.parents,
+                            *entity,
+                            *pointer_id,
+                            DragEnd {
+                                button,
+                                distance: latest - start,
+                            },
+                        );
+                    }
+                }
+                *state = ButtonState::default();
+            } else if pressed {
+                let (Some(start), Some(position)) = (state.press_position, position) else {
+                    continue;
+                };
+                let latest = match state.drag_position {
+                    Some(latest) => latest,
+                    None if position != start => {
+                        for (entity, hit) in &state.pressed_on {
+                            bubble(
+                                &mut writers.drag_start,
+                                &writers.parents,
+                                *entity,
+                                *pointer_id,
+                                DragStart { button, hit: *hit },
+                            );
+                        }
+                        start
+                    }
+                    None => continue,
+                };
+                if position != latest {
+                    for entity in state.pressed_on.keys() {
+                        bubble(
+                            &mut writers.drag,
+                            &writers.parents,
+                            *entity,
+                            *pointer_id,
+                            Drag {
+                                button,
+                                distance: position - start,
+                                delta: position - latest,
+                            },
+                        );
+                    }
+                }
+                state.drag_position = Some(position);
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Decides which entities each pointer hovers, from the [`PointerHits`] of every backend.
+
+use crate::{backend::HitData, backend::PointerHits, pointer::PointerId, Pickable};
+use bevy_ecs::prelude::*;
+use bevy_utils::HashMap;
+
+/// The entities hovered by each pointer, with where they were hit.
+///
+/// Hits of every backend are sorted from the highest [`PointerHits::order`] down, then from the
+/// lowest [`HitData::depth`] up. Entities are hovered in that order until one that
+/// [blocks](Pickable::should_block_lower) the entities below it.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct HoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
+
+/// The [`HoverMap`] of the previous frame, used to find which entities started or stopped being
+/// hovered.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct PreviousHoverMap(pub HashMap<PointerId, HashMap<Entity, HitData>>);
+
+impl HoverMap {
+    /// Returns the entities hovered by `pointer`.
+    pub fn get(&self, pointer: &PointerId) -> Option<&HashMap<Entity, HitData>> {
+        self.0.get(pointer)
+    }
+
+    /// Returns `true` if `entity` is hovered by any pointer.
+    pub fn is_hovered(&self, entity: Entity) -> bool {
+        self.0.values().any(|hovered| hovered.contains_key(&entity))
+    }
+}
+
+/// Moves the [`HoverMap`] to the [`PreviousHoverMap`] and rebuilds it from the [`PointerHits`]
+/// sent this frame.
+///
+/// Every pointer gets an entry, even when it hovers nothing.
+pub fn update_hover_map(
+    mut hover_map: ResMut<HoverMap>,
+    mut previous_hover_map: ResMut<PreviousHoverMap>,
+    mut pointer_hits: EventReader<PointerHits>,
+    pointers: Query<&PointerId>,
+    pickables: Query<&Pickable>,
+) {
+    previous_hover_map.0 = std::mem::take(&mut hover_map.0);
+    for pointer in &pointers {
+        hover_map.0.insert(*pointer, HashMap::default());
+    }
+
+    let mut hits_by_pointer: HashMap<PointerId, Vec<(f32, Entity, HitData)>> = HashMap::default();
+    for hits in pointer_hits.iter() {
+        hits_by_pointer.entry(hits.pointer).or_default().extend(
+            hits.picks
+                .iter()
+                .map(|(entity, hit)| (hits.order, *entity, *hit)),
+        );
+    }
+
+    for (pointer, mut hits) in hits_by_pointer {
+        hits.sort_by(|(order_a, _, hit_a), (order_b, _, hit_b)| {
+            order_b
+                .total_cmp(order_a)
+                .then(hit_a.depth.total_cmp(&hit_b.depth))
+        });
+        let hovered = hover_map.0.entry(pointer).or_default();
+        for (_, entity, hit) in hits {
+            let pickable = pickables.get(entity).copied().unwrap_or_default();
+            if pickable.is_hoverable {
+                hovered.entry(entity).or_insert(hit);
+            }
+            if pickable.should_block_lower {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,484 @@
+#![allow(clippy::type_complexity)]
+#![warn(missing_docs)]
+
+//! This crate finds the entities under pointers, such as the mouse, touches or the rays of XR
+//! controllers, and sends them [pointer events](crate::events).
+//!
+//! Picking happens in [`PreUpdate`], in the [`PickingSet`]s:
+//! 1. [`PickingSet::Input`] updates the location and buttons of [pointers](crate::pointer).
+//! 2. [`PickingSet::Backend`] finds the entities under each pointer, see [`backend`].
+//! 3. [`PickingSet::Focus`] decides which of them are hovered, see [`HoverMap`](focus::HoverMap).
+//! 4. [`PickingSet::Events`] sends [`Pointer`](events::Pointer) events to the hovered entities
+//!    and their ancestors.
+//!
+//! # Example
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! fn on_click(mut clicks: EventReader<Pointer<Click>>) {
+//!     for click in clicks.iter() {
+//!         println!("{:?} was clicked with {:?}", click.target, click.button);
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(on_click);
+//! ```
+
+pub mod backend;
+pub mod events;
+pub mod focus;
+pub mod pointer;
+
+/// The `bevy_picking` prelude.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        backend::mesh::MeshPickingPlugin,
+        events::{Click, Down, Drag, DragDrop, DragEnd, DragStart, Out, Over, Pointer, Up},
+        pointer::{PointerButton, PointerId, RayPointer},
+        Pickable, PickingPlugin,
+    };
+
+    #[doc(hidden)]
+    #[cfg(feature = "bevy_sprite")]
+    pub use crate::backend::sprite::SpritePickingPlugin;
+
+    #[doc(hidden)]
+    #[cfg(feature = "bevy_ui")]
+    pub use crate::backend::ui::UiPickingPlugin;
+}
+
+use backend::PointerHits;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_input::InputSystem;
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use events::{Click, Down, Drag, DragDrop, DragEnd, DragStart, Out, Over, Pointer, Up};
+use focus::{HoverMap, PreviousHoverMap};
+use pointer::{PointerBundle, PointerButton, PointerId, PointerPress, RayPointer};
+
+/// How an entity interacts with pointers.
+///
+/// Entities without this component are hoverable and block the entities below them.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Pickable {
+    /// Whether entities below this one can be hovered by the same pointer.
+    ///
+    /// This is checked even when the entity isn't hoverable, so an entity can shield the entities
+    /// below it without being hovered itself.
+    pub should_block_lower: bool,
+    /// Whether this entity can be hovered, and thus receive pointer events.
+    pub is_hoverable: bool,
+}
+
+impl Pickable {
+    /// An entity that is hoverable but lets pointers hover the entities below it.
+    pub const PASS: Self = Self {
+        should_block_lower: false,
+        is_hoverable: true,
+    };
+
+    /// An entity that pointers ignore: it isn't hovered and doesn't block the entities below it.
+    pub const IGNORE: Self = Self {
+        should_block_lower: false,
+        is_hoverable: false,
+    };
+}
+
+impl Default for Pickable {
+    fn default() -> Self {
+        Self {
+            should_block_lower: true,
+            is_hoverable: true,
+        }
+    }
+}
+
+/// The steps of picking, run in order in [`PreUpdate`] after [`InputSystem`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PickingSet {
+    /// Updates the [`PointerLocation`](pointer::PointerLocation) and [`PointerPress`] of pointers.
+    Input,
+    /// Backends send the [`PointerHits`] of each pointer.
+    Backend,
+    /// Builds the [`HoverMap`] from the [`PointerHits`].
+    Focus,
+    /// Sends the [`Pointer`] events.
+    Events,
+}
+
+/// Adds the pointers, the [`HoverMap`] and the [`Pointer`] events.
+///
+/// This doesn't pick anything on its own: add the plugins of the [`backend`]s to use as well.
+/// The mouse pointer is spawned when the plugin is built, touch pointers are spawned and
+/// despawned as fingers touch the screen, and other pointers can be spawned with a
+/// [`PointerBundle`].
+#[derive(Default)]
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.world.spawn(PointerBundle::new(PointerId::Mouse));
+
+        app.init_resource::<HoverMap>()
+            .init_resource::<PreviousHoverMap>()
+            .add_event::<PointerHits>()
+            .add_event::<Pointer<Over>>()
+            .add_event::<Pointer<Out>>()
+            .add_event::<Pointer<Down>>()
+            .add_event::<Pointer<Up>>()
+            .add_event::<Pointer<Click>>()
+            .add_event::<Pointer<DragStart>>()
+            .add_event::<Pointer<Drag>>()
+            .add_event::<Pointer<DragEnd>>()
+            .add_event::<Pointer<DragDrop>>()
+            .register_type::<Pickable>()
+            .register_type::<PointerId>()
+            .register_type::<PointerButton>()
+            .register_type::<PointerPress>()
+            .register_type::<RayPointer>()
+            .configure_sets(
+                PreUpdate,
+                (
+                    PickingSet::Input,
+                    PickingSet::Backend,
+                    PickingSet::Focus,
+                    PickingSet::Events,
+                )
+                    .chain()
+                    .after(InputSystem),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    (
+                        pointer::update_mouse_pointer,
+                        pointer::update_touch_pointers,
+                        pointer::update_ray_pointers,
+                    )
+                        .in_set(PickingSet::Input),
+                    // Touch pointers are spawned with commands.
+                    apply_system_buffers
+                        .after(PickingSet::Input)
+                        .before(PickingSet::Backend),
+                    focus::update_hover_map.in_set(PickingSet::Focus),
+                    events::send_pointer_events.in_set(PickingSet::Events),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::{HitData, PointerHits},
+        events::{Click, Down, Drag, DragDrop, DragEnd, DragStart, Out, Over, Pointer, Up},
+        pointer::{Location, PointerButton, PointerId, PointerLocation},
+        Pickable, PickingPlugin, PickingSet,
+    };
+    use bevy_app::{App, PreUpdate};
+    use bevy_ecs::prelude::*;
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_input::{mouse::MouseButton, Input, InputPlugin};
+    use bevy_math::{Vec2, Vec3};
+
+    /// The hits the test backend sends every frame.
+    #[derive(Resource, Default)]
+    struct TestHits(Vec<PointerHits>);
+
+    fn test_backend(hits: Res<TestHits>, mut writer: EventWriter<PointerHits>) {
+        writer.send_batch(hits.0.iter().cloned());
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(InputPlugin)
+            .add_plugin(PickingPlugin)
+            .init_resource::<TestHits>()
+            .add_systems(PreUpdate, test_backend.in_set(PickingSet::Backend));
+        app
+    }
+
+    fn hit(depth: f32) -> HitData {
+        HitData::new(None, depth)
+    }
+
+    fn set_hits(app: &mut App, hits: Vec<PointerHits>) {
+        app.world.resource_mut::<TestHits>().0 = hits;
+    }
+
+    fn set_mouse_button(app: &mut App, pressed: bool) {
+        let mut input = app.world.resource_mut::<Input<MouseButton>>();
+        if pressed {
+            input.press(MouseButton::Left);
+        } else {
+            input.release(MouseButton::Left);
+        }
+    }
+
+    fn set_mouse_position(app: &mut App, position: Vec2) {
+        app.world.resource_mut::<MousePosition>().0 = Some(position);
+    }
+
+    /// Overrides the location of the mouse pointer, which has none without a window.
+    #[derive(Resource, Default)]
+    struct MousePosition(Option<Vec2>);
+
+    fn override_mouse_position(
+        position: Res<MousePosition>,
+        mut pointers: Query<(&PointerId, &mut PointerLocation)>,
+    ) {
+        let Some(position) = position.0 else {
+            return;
+        };
+        for (id, mut location) in &mut pointers {
+            if *id == PointerId::Mouse {
+                location.location = Some(Location::Screen {
+                    target: bevy_render::camera::NormalizedRenderTarget::Image(Default::default()),
+                    position,
+                });
+            }
+        }
+    }
+
+    fn drain<E: Clone + Send + Sync + 'static>(app: &mut App) -> Vec<Pointer<E>> {
+        app.world
+            .resource_mut::<Events<Pointer<E>>>()
+            .drain()
+            .collect()
+    }
+
+    #[test]
+    fn over_out_and_bubbling() {
+        let mut app = app();
+        let parent = app.world.spawn_empty().id();
+        let child = app.world.spawn_empty().set_parent(parent).id();
+
+        set_hits(
+            &mut app,
+            vec![PointerHits::new(
+                PointerId::Mouse,
+                vec![(child, hit(1.0))],
+                0.0,
+            )],
+        );
+        app.update();
+        let over = drain::<Over>(&mut app);
+        assert_eq!(
+            over.iter()
+                .map(|event| (event.listener, event.target))
+                .collect::<Vec<_>>(),
+            vec![(child, child), (parent, child)]
+        );
+
+        // Still hovered: no new events.
+        app.update();
+        assert!(drain::<Over>(&mut app).is_empty());
+        assert!(drain::<Out>(&mut app).is_empty());
+
+        set_hits(&mut app, Vec::new());
+        app.update();
+        let out = drain::<Out>(&mut app);
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|event| event.target == child));
+    }
+
+    #[test]
+    fn down_up_click() {
+        let mut app = app();
+        let entity = app.world.spawn_empty().id();
+        let other = app.world.spawn_empty().id();
+        set_hits(
+            &mut app,
+            vec![PointerHits::new(
+                PointerId::Mouse,
+                vec![(entity, hit(1.0))],
+                0.0,
+            )],
+        );
+
+        set_mouse_button(&mut app, true);
+        app.update();
+        let down = drain::<Down>(&mut app);
+        assert_eq!(down.len(), 1);
+        assert_eq!(down[0].button, PointerButton::Primary);
+
+        set_mouse_button(&mut app, false);
+        app.update();
+        assert_eq!(drain::<Up>(&mut app).len(), 1);
+        assert_eq!(drain::<Click>(&mut app).len(), 1);
+
+        // Pressing on one entity and releasing on another isn't a click.
+        set_mouse_button(&mut app, true);
+        app.update();
+        set_hits(
+            &mut app,
+            vec![PointerHits::new(
+                PointerId::Mouse,
+                vec![(other, hit(1.0))],
+                0.0,
+            )],
+        );
+        set_mouse_button(&mut app, false);
+        app.update();
+        let up = drain::<Up>(&mut app);
+        assert_eq!(up.last().map(|event| event.target), Some(other));
+        assert!(drain::<Click>(&mut app).is_empty());
+    }
+
+    #[test]
+    fn blocking_and_order() {
+        let mut app = app();
+        let front = app.world.spawn_empty().id();
+        let back = app.world.spawn_empty().id();
+        let overlay = app.world.spawn(Pickable::PASS).id();
+        let ignored = app.world.spawn(Pickable::IGNORE).id();
+
+        // `overlay` is seen through a camera on top, and lets entities below it be hovered.
+        // `ignored` is in front of everything else but is neither hovered nor blocking.
+        set_hits(
+            &mut app,
+            vec![
+                PointerHits::new(
+                    PointerId::Mouse,
+                    vec![(back, hit(2.0)), (front, hit(1.0)), (ignored, hit(0.0))],
+                    0.0,
+                ),
+                PointerHits::new(PointerId::Mouse, vec![(overlay, hit(5.0))], 1.0),
+            ],
+        );
+        app.update();
+        let mut over = drain::<Over>(&mut app)
+            .into_iter()
+            .map(|event| event.target)
+            .collect::<Vec<_>>();
+        over.sort();
+        let mut expected = vec![overlay, front];
+        expected.sort();
+        assert_eq!(over, expected);
+
+        // An opaque entity on a camera on top blocks everything below it.
+        let blocker = app.world.spawn_empty().id();
+        set_hits(
+            &mut app,
+            vec![
+                PointerHits::new(PointerId::Mouse, vec![(front, hit(1.0))], 0.0),
+                PointerHits::new(PointerId::Mouse, vec![(blocker, hit(5.0))], 2.0),
+            ],
+        );
+        app.update();
+        let out = drain::<Out>(&mut app)
+            .into_iter()
+            .map(|event| event.target)
+            .collect::<Vec<_>>();
+        assert!(out.contains(&front) && out.contains(&overlay));
+        assert_eq!(
+            drain::<Over>(&mut app)
+                .into_iter()
+                .map(|event| event.target)
+                .collect::<Vec<_>>(),
+            vec![blocker]
+        );
+    }
+
+    #[test]
+    fn drag_and_drop() {
+        let mut app = app();
+        app.init_resource::<MousePosition>().add_systems(
+            PreUpdate,
+            override_mouse_position
+                .after(PickingSet::Input)
+                .before(PickingSet::Backend),
+        );
+        let dragged = app.world.spawn(Pickable::PASS).id();
+        let target = app.world.spawn_empty().id();
+        set_hits(
+            &mut app,
+            vec![PointerHits::new(
+                PointerId::Mouse,
+                vec![(dragged, hit(1.0)), (target, hit(2.0))],
+                0.0,
+            )],
+        );
+
+        set_mouse_position(&mut app, Vec2::new(10.0, 10.0));
+        set_mouse_button(&mut app, true);
+        app.update();
+        assert!(drain::<DragStart>(&mut app).is_empty());
+
+        set_mouse_position(&mut app, Vec2::new(15.0, 12.0));
+        app.update();
+        set_mouse_position(&mut app, Vec2::new(20.0, 20.0));
+        app.update();
+        let drag_start = drain::<DragStart>(&mut app);
+        assert_eq!(
+            drag_start
+                .iter()
+                .map(|event| event.target)
+                .collect::<Vec<_>>()
+                .len(),
+            2
+        );
+        let drag = drain::<Drag>(&mut app)
+            .into_iter()
+            .filter(|event| event.target == dragged)
+            .collect::<Vec<_>>();
+        assert_eq!(drag.len(), 2);
+        assert_eq!(drag[0].delta, Vec2::new(5.0, 2.0));
+        assert_eq!(drag[1].delta, Vec2::new(5.0, 8.0));
+        assert_eq!(drag[1].distance, Vec2::new(10.0, 10.0));
+
+        set_mouse_button(&mut app, false);
+        app.update();
+        let drop = drain::<DragDrop>(&mut app);
+        assert!(drop
+            .iter()
+            .any(|event| event.target == target && event.dropped == dragged));
+        let drag_end = drain::<DragEnd>(&mut app);
+        assert_eq!(drag_end.len(), 2);
+        assert_eq!(drag_end[0].distance, Vec2::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn ray_pointer_hits_meshes() {
+        use crate::{
+            backend::mesh::MeshPickingPlugin,
+            pointer::{PointerBundle, RayPointer},
+        };
+        use bevy_asset::{AssetPlugin, Assets};
+        use bevy_core::TaskPoolPlugin;
+        use bevy_render::mesh::{shape, Mesh, MeshPlugin};
+        use bevy_transform::prelude::{GlobalTransform, Transform};
+
+        let mut app = app();
+        app.add_plugin(TaskPoolPlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(MeshPlugin)
+            .add_plugin(MeshPickingPlugin);
+        let mesh = app
+            .world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::Cube::new(1.0).into());
+        let cube = app
+            .world
+            .spawn((
+                mesh,
+                GlobalTransform::from(Transform::from_xyz(0.0, 0.0, -5.0)),
+            ))
+            .id();
+        let pointer = PointerId::Custom(0);
+        app.world.spawn((
+            PointerBundle::new(pointer),
+            RayPointer,
+            GlobalTransform::IDENTITY,
+        ));
+
+        app.update();
+        let over = drain::<Over>(&mut app);
+        assert_eq!(over.len(), 1);
+        assert_eq!(over[0].target, cube);
+        assert_eq!(over[0].pointer_id, pointer);
+        let position = over[0].hit.position.unwrap();
+        assert!(position.abs_diff_eq(Vec3::new(0.0, 0.0, -4.5), 1e-4));
+    }
+}
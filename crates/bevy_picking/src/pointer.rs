@@ -0,0 +1,223 @@
+//! Pointers, and the systems updating them from the mouse, touches and rays.
+
+use bevy_ecs::prelude::*;
+use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+use bevy_math::{Ray, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_render::camera::NormalizedRenderTarget;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{PrimaryWindow, Window, WindowRef};
+
+/// Identifies a pointer: something that can point at entities and press on them.
+///
+/// Every pointer is an entity with this component, a [`PointerLocation`] and a [`PointerPress`],
+/// see [`PointerBundle`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect, FromReflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum PointerId {
+    /// The mouse cursor.
+    #[default]
+    Mouse,
+    /// A finger on a touch screen, with the id of its [`Touch`](bevy_input::touch::Touch).
+    Touch(u64),
+    /// Any other pointer, such as the ray of an XR controller.
+    Custom(u64),
+}
+
+/// Where a pointer is pointing.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Location {
+    /// A position on a render target.
+    Screen {
+        /// The render target the pointer is on.
+        target: NormalizedRenderTarget,
+        /// The position of the pointer in logical pixels, from the top left corner of the target.
+        position: Vec2,
+    },
+    /// A ray in world space, for pointers that aren't tied to a screen such as XR controllers.
+    Ray(Ray),
+}
+
+impl Location {
+    /// The position of the pointer on its render target, if it is on one.
+    pub fn position(&self) -> Option<Vec2> {
+        match self {
+            Location::Screen { position, .. } => Some(*position),
+            Location::Ray(_) => None,
+        }
+    }
+}
+
+/// The current [`Location`] of a pointer, if it has one.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct PointerLocation {
+    /// The location of the pointer, `None` if it isn't pointing at anything.
+    pub location: Option<Location>,
+}
+
+/// A button of a pointer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect, FromReflect)]
+pub enum PointerButton {
+    /// The main button, like the left mouse button or a finger touching the screen.
+    Primary,
+    /// The secondary button, like the right mouse button.
+    Secondary,
+    /// The middle button, like the mouse wheel.
+    Middle,
+}
+
+impl PointerButton {
+    /// Every button, in a consistent order.
+    pub const ALL: [PointerButton; 3] = [Self::Primary, Self::Secondary, Self::Middle];
+}
+
+/// Which buttons of a pointer are pressed.
+///
+/// Pointers that aren't driven by this crate, like XR controllers, should update this from their
+/// input in [`PickingSet::Input`](crate::PickingSet::Input).
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct PointerPress {
+    /// Whether the primary button is pressed.
+    pub primary: bool,
+    /// Whether the secondary button is pressed.
+    pub secondary: bool,
+    /// Whether the middle button is pressed.
+    pub middle: bool,
+}
+
+impl PointerPress {
+    /// Returns `true` if `button` is pressed.
+    pub fn is_pressed(&self, button: PointerButton) -> bool {
+        match button {
+            PointerButton::Primary => self.primary,
+            PointerButton::Secondary => self.secondary,
+            PointerButton::Middle => self.middle,
+        }
+    }
+}
+
+/// Marks a pointer whose [`Location`] is the forward ray of its [`GlobalTransform`], such as the
+/// laser of an XR controller.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct RayPointer;
+
+/// The components of a pointer.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct PointerBundle {
+    /// The identifier of the pointer.
+    pub id: PointerId,
+    /// Where the pointer is.
+    pub location: PointerLocation,
+    /// Which buttons of the pointer are pressed.
+    pub press: PointerPress,
+}
+
+impl PointerBundle {
+    /// Creates a pointer with the given id, not pointing at anything.
+    pub fn new(id: PointerId) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+}
+
+/// Updates the [`PointerId::Mouse`] pointer from the cursor of the windows and the mouse buttons.
+pub fn update_mouse_pointer(
+    windows: Query<(Entity, &Window)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    buttons: Res<Input<MouseButton>>,
+    mut pointers: Query<(&PointerId, &mut PointerLocation, &mut PointerPress)>,
+) {
+    let primary_window = primary_window.get_single().ok();
+    // Prefer the primary window if the cursor is somehow on several windows.
+    let location = windows
+        .iter()
+        .filter_map(|(entity, window)| Some((entity, window.cursor_position()?)))
+        .max_by_key(|(entity, _)| Some(*entity) == primary_window)
+        .and_then(|(entity, position)| {
+            Some(Location::Screen {
+                target: NormalizedRenderTarget::Window(WindowRef::Entity(entity).normalize(None)?),
+                position,
+            })
+        });
+    let press = PointerPress {
+        primary: buttons.pressed(MouseButton::Left),
+        secondary: buttons.pressed(MouseButton::Right),
+        middle: buttons.pressed(MouseButton::Middle),
+    };
+
+    for (id, mut pointer_location, mut pointer_press) in &mut pointers {
+        if *id == PointerId::Mouse {
+            pointer_location.set_if_neq(PointerLocation {
+                location: location.clone(),
+            });
+            pointer_press.set_if_neq(press);
+        }
+    }
+}
+
+/// Spawns a [`PointerId::Touch`] pointer for each finger touching the primary window, and
+/// despawns it the frame after the finger is lifted.
+pub fn update_touch_pointers(
+    mut commands: Commands,
+    touches: Res<Touches>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    mut pointers: Query<(Entity, &PointerId, &mut PointerLocation, &mut PointerPress)>,
+) {
+    let target = primary_window
+        .get_single()
+        .ok()
+        .and_then(|window| WindowRef::Entity(window).normalize(None))
+        .map(NormalizedRenderTarget::Window);
+
+    for (entity, id, mut location, mut press) in &mut pointers {
+        let PointerId::Touch(touch_id) = *id else {
+            continue;
+        };
+        if let Some(touch) = touches.get_pressed(touch_id) {
+            location.location = target.clone().map(|target| Location::Screen {
+                target,
+                position: touch.position(),
+            });
+            press.primary = true;
+        } else if touches.just_released(touch_id) || touches.just_canceled(touch_id) {
+            // Keep the pointer one more frame so that releasing it is noticed.
+            press.primary = false;
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for touch in touches.iter_just_pressed() {
+        commands.spawn(PointerBundle {
+            id: PointerId::Touch(touch.id()),
+            location: PointerLocation {
+                location: target.clone().map(|target| Location::Screen {
+                    target,
+                    position: touch.position(),
+                }),
+            },
+            press: PointerPress {
+                primary: true,
+                ..Default::default()
+            },
+        });
+    }
+}
+
+/// Sets the [`Location`] of each [`RayPointer`] to the forward ray of its [`GlobalTransform`].
+pub fn update_ray_pointers(
+    mut pointers: Query<(&GlobalTransform, &mut PointerLocation), With<RayPointer>>,
+) {
+    for (transform, mut location) in &mut pointers {
+        location.set_if_neq(PointerLocation {
+            location: Some(Location::Ray(Ray {
+                origin: transform.translation(),
+                direction: transform.forward(),
+            })),
+        });
+    }
+}
@@ -4,6 +4,9 @@
 //! as opposed to a particular field or variant. An example of such an attribute is
 //! the derive helper attribute for `Reflect`, which looks like:
 //! `#[reflect(PartialEq, Default, ...)]` and `#[reflect_value(PartialEq, Default, ...)]`.
+//!
+//! The same attributes also hold key-value settings of the type, such as
+//! `#[reflect(type_path = "my_crate::MyType")]` or `#[reflect(version = 2)]`.
 
 use crate::fq_std::{FQAny, FQOption};
 use crate::utility;
@@ -13,7 +16,7 @@ use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Meta, NestedMeta, Path};
+use syn::{Lit, LitInt, LitStr, Meta, NestedMeta, Path};
 
 // The "special" trait idents that are used internally for reflection.
 // Received via attributes like `#[reflect(PartialEq, Hash, ...)]`
@@ -21,6 +24,11 @@ const DEBUG_ATTR: &str = "Debug";
 const PARTIAL_EQ_ATTR: &str = "PartialEq";
 const HASH_ATTR: &str = "Hash";
 
+// The key-value settings of the type.
+// Received via attributes like `#[reflect(type_path = "my_crate::MyType")]`
+const TYPE_PATH_ATTR: &str = "type_path";
+const VERSION_ATTR: &str = "version";
+
 // The traits listed below are not considered "special" (i.e. they use the `ReflectMyTrait` syntax)
 // but useful to know exist nonetheless
 pub(crate) const REFLECT_DEFAULT: &str = "ReflectDefault";
@@ -119,12 +127,31 @@ impl TraitImpl {
 ///
 /// > __Note:__ Registering a custom function only works for special traits.
 ///
+/// Setting the type path of the type, which otherwise is its module path followed by its name:
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(type_path = "my_game::Player")]
+/// struct Player;
+/// ```
+///
+/// Setting the version of the type, recorded in serialized data to migrate it when the type
+/// changes:
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(version = 2)]
+/// struct Player;
+/// ```
+///
 #[derive(Default, Clone)]
 pub(crate) struct ReflectTraits {
     debug: TraitImpl,
     hash: TraitImpl,
     partial_eq: TraitImpl,
     idents: Vec<Ident>,
+    type_path: Option<LitStr>,
+    version: Option<LitInt>,
 }
 
 impl ReflectTraits {
@@ -199,6 +226,27 @@ impl ReflectTraits {
                         }
                     }
                 }
+                // Handles `#[reflect( type_path = "my_crate::MyType" )]`
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident(TYPE_PATH_ATTR) => {
+                    let Lit::Str(lit) = &pair.lit else {
+                        return Err(syn::Error::new(
+                            pair.lit.span(),
+                            format_args!("expected a string literal for `{TYPE_PATH_ATTR}`"),
+                        ));
+                    };
+                    traits.type_path = merge_setting(traits.type_path, Some(lit.clone()))?;
+                }
+                // Handles `#[reflect( version = 2 )]`
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident(VERSION_ATTR) => {
+                    let Lit::Int(lit) = &pair.lit else {
+                        return Err(syn::Error::new(
+                            pair.lit.span(),
+                            format_args!("expected an integer literal for `{VERSION_ATTR}`"),
+                        ));
+                    };
+                    lit.base10_parse::<u32>()?;
+                    traits.version = merge_setting(traits.version, Some(lit.clone()))?;
+                }
                 _ => {}
             }
         }
@@ -217,6 +265,16 @@ impl ReflectTraits {
         &self.idents
     }
 
+    /// The stable type path set with `#[reflect(type_path = "...")]`, if any.
+    pub fn type_path(&self) -> Option<&LitStr> {
+        self.type_path.as_ref()
+    }
+
+    /// The version set with `#[reflect(version = ...)]`, if any.
+    pub fn version(&self) -> Option<&LitInt> {
+        self.version.as_ref()
+    }
+
     /// Returns the implementation of `Reflect::reflect_hash` as a `TokenStream`.
     ///
     /// If `Hash` was not registered, returns `None`.
@@ -301,6 +359,8 @@ impl ReflectTraits {
                 }
                 idents
             },
+            type_path: merge_setting(self.type_path, other.type_path)?,
+            version: merge_setting(self.version, other.version)?,
         })
     }
 }
@@ -324,3 +384,13 @@ fn add_unique_ident(idents: &mut Vec<Ident>, ident: Ident) -> Result<(), syn::Er
     idents.push(ident);
     Ok(())
 }
+
+/// Merges two values of a key-value setting.
+///
+/// Returns an error if both are set.
+fn merge_setting<T: Spanned>(this: Option<T>, other: Option<T>) -> Result<Option<T>, syn::Error> {
+    match (this, other) {
+        (Some(_), Some(other)) => Err(syn::Error::new(other.span(), CONFLICTING_TYPE_DATA_MESSAGE)),
+        (this, other) => Ok(this.or(other)),
+    }
+}
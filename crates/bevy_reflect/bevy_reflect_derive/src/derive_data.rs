@@ -40,6 +40,9 @@ pub(crate) struct ReflectMeta<'a> {
     generics: &'a Generics,
     /// A cached instance of the path to the `bevy_reflect` crate.
     bevy_reflect_path: Path,
+    /// Whether the type is defined where the macro is invoked, so that its default type path is
+    /// its module path followed by its name.
+    module_type_path: bool,
     /// The documentation for this type, if any
     #[cfg(feature = "documentation")]
     docs: crate::documentation::Documentation,
@@ -128,7 +131,9 @@ enum ReflectMode {
 }
 
 impl<'a> ReflectDerive<'a> {
-    pub fn from_input(input: &'a DeriveInput) -> Result<Self, syn::Error> {
+    /// Parses the input of a derive macro, or of `impl_reflect_struct!` for foreign types when
+    /// `is_derive` is `false`.
+    pub fn from_input(input: &'a DeriveInput, is_derive: bool) -> Result<Self, syn::Error> {
         let mut traits = ReflectTraits::default();
         // Should indicate whether `#[reflect_value]` was used
         let mut reflect_mode = None;
@@ -182,7 +187,21 @@ impl<'a> ReflectDerive<'a> {
             }
         }
 
+        if let Some(type_path) = traits.type_path() {
+            if input.generics.type_params().next().is_some() {
+                return Err(syn::Error::new(
+                    type_path.span(),
+                    "generic types can't have a stable type path",
+                ));
+            }
+        }
+
         let meta = ReflectMeta::new(&input.ident, &input.generics, traits);
+        let meta = if is_derive {
+            meta.with_module_type_path()
+        } else {
+            meta
+        };
 
         #[cfg(feature = "documentation")]
         let meta = meta.with_docs(doc);
@@ -285,11 +304,20 @@ impl<'a> ReflectMeta<'a> {
             type_name,
             generics,
             bevy_reflect_path: utility::get_bevy_reflect_path(),
+            module_type_path: false,
             #[cfg(feature = "documentation")]
             docs: Default::default(),
         }
     }
 
+    /// Defaults the type path to the module path of the type followed by its name.
+    pub fn with_module_type_path(self) -> Self {
+        Self {
+            module_type_path: true,
+            ..self
+        }
+    }
+
     /// Sets the documentation for this type.
     #[cfg(feature = "documentation")]
     pub fn with_docs(self, docs: crate::documentation::Documentation) -> Self {
@@ -316,6 +344,21 @@ impl<'a> ReflectMeta<'a> {
         &self.bevy_reflect_path
     }
 
+    /// Returns the type path set with `#[reflect(type_path = "...")]`, or for non-generic types
+    /// defined where the macro is invoked, their module path followed by their name.
+    ///
+    /// Other types keep their type name as their type path.
+    pub fn type_path(&self) -> Option<proc_macro2::TokenStream> {
+        let type_name = self.type_name;
+        match self.traits.type_path() {
+            Some(type_path) => Some(quote!(#type_path)),
+            None if self.module_type_path && self.generics.params.is_empty() => Some(quote! {
+                ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#type_name))
+            }),
+            None => None,
+        }
+    }
+
     /// Returns the `GetTypeRegistration` impl as a `TokenStream`.
     pub fn get_type_registration(
         &self,
@@ -328,6 +371,8 @@ impl<'a> ReflectMeta<'a> {
             self.generics,
             where_clause_options,
             None,
+            self.type_path(),
+            self.traits.version(),
         )
     }
 
@@ -368,6 +413,8 @@ impl<'a> ReflectStruct<'a> {
             self.meta.generics(),
             where_clause_options,
             Some(&self.serialization_denylist),
+            self.meta.type_path(),
+            self.meta.traits().version(),
         )
     }
 
@@ -89,6 +89,21 @@ pub(crate) static REFLECT_VALUE_ATTRIBUTE_NAME: &str = "reflect_value";
 ///   where adding this attribute will cause the `FromReflect` implementation to create
 ///   a base value using its [`Default`] implementation avoiding issues with ignored fields.
 ///
+/// ### Type Path
+///
+/// Serialized data identifies a type by its type path, which defaults to the module path of the
+/// type followed by its name. Unlike [`std::any::type_name`], it is stable across compiler
+/// versions, but it still changes when the type is moved or renamed, so another path can be set
+/// with `#[reflect(type_path = "my_crate::MyType")]`.
+/// Generic types can't have a stable type path, and are identified by their type name.
+///
+/// ### Version
+///
+/// `#[reflect(version = 2)]` sets the version of the type, which is recorded alongside its
+/// type path in serialized data. When data of an older version is deserialized, the migrations
+/// registered with `TypeRegistry::register_migration` are run to update it to the current
+/// version of the type.
+///
 /// ## `#[reflect_value]`
 ///
 /// The `#[reflect_value]` attribute (which may also take the form `#[reflect_value(Ident)]`),
@@ -127,7 +142,7 @@ pub(crate) static REFLECT_VALUE_ATTRIBUTE_NAME: &str = "reflect_value";
 pub fn derive_reflect(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
-    let derive_data = match ReflectDerive::from_input(&ast) {
+    let derive_data = match ReflectDerive::from_input(&ast, true) {
         Ok(data) => data,
         Err(err) => return err.into_compile_error().into(),
     };
@@ -172,7 +187,7 @@ pub fn derive_reflect(input: TokenStream) -> TokenStream {
 pub fn derive_from_reflect(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
 
-    let derive_data = match ReflectDerive::from_input(&ast) {
+    let derive_data = match ReflectDerive::from_input(&ast, true) {
         Ok(data) => data,
         Err(err) => return err.into_compile_error().into(),
     };
@@ -315,7 +330,7 @@ pub fn impl_reflect_value(input: TokenStream) -> TokenStream {
 #[proc_macro]
 pub fn impl_reflect_struct(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
-    let derive_data = match ReflectDerive::from_input(&ast) {
+    let derive_data = match ReflectDerive::from_input(&ast, false) {
         Ok(data) => data,
         Err(err) => return err.into_compile_error().into(),
     };
@@ -4,7 +4,7 @@ use crate::utility::{extend_where_clause, WhereClauseOptions};
 use bit_set::BitSet;
 use proc_macro2::Ident;
 use quote::quote;
-use syn::{Generics, Path};
+use syn::{Generics, LitInt, Path};
 
 /// Creates the `GetTypeRegistration` impl for the given type data.
 #[allow(clippy::too_many_arguments)]
@@ -15,6 +15,8 @@ pub(crate) fn impl_get_type_registration(
     generics: &Generics,
     where_clause_options: &WhereClauseOptions,
     serialization_denylist: Option<&BitSet<u32>>,
+    type_path: Option<proc_macro2::TokenStream>,
+    version: Option<&LitInt>,
 ) -> proc_macro2::TokenStream {
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let serialization_data = serialization_denylist.map(|denylist| {
@@ -25,6 +27,18 @@ pub(crate) fn impl_get_type_registration(
         }
     });
 
+    let type_path = type_path.map(|type_path| {
+        quote! {
+            registration.set_type_path(#type_path);
+        }
+    });
+
+    let version = version.map(|version| {
+        quote! {
+            registration.set_version(#version);
+        }
+    });
+
     let where_reflect_clause = extend_where_clause(where_clause, where_clause_options);
 
     quote! {
@@ -32,6 +46,8 @@ pub(crate) fn impl_get_type_registration(
         impl #impl_generics #bevy_reflect_path::GetTypeRegistration for #type_name #ty_generics #where_reflect_clause {
             fn get_type_registration() -> #bevy_reflect_path::TypeRegistration {
                 let mut registration = #bevy_reflect_path::TypeRegistration::of::<#type_name #ty_generics>();
+                #type_path
+                #version
                 registration.insert::<#bevy_reflect_path::ReflectFromPtr>(#bevy_reflect_path::FromType::<#type_name #ty_generics>::from_type());
                 #serialization_data
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<#type_name #ty_generics>::from_type());)*
@@ -1,4 +1,4 @@
-use crate::serde::SerializationData;
+use crate::serde::{ReflectMigrations, SerializationData};
 use crate::{
     ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
     DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField, Reflect,
@@ -223,7 +223,8 @@ impl<'de> Visitor<'de> for U32Visitor {
 ///
 /// Because the type isn't known ahead of time, the serialized data must take the form of
 /// a map containing the following entries (in order):
-/// 1. `type`: The _full_ [type name]
+/// 1. `type`: The [type path], an alias of it, or the _full_ [type name], optionally followed
+///    by `@` and the [version] of the type the data was serialized with
 /// 2. `value`: The serialized value of the reflected type
 ///
 /// If the type is already known and the [`TypeInfo`] for it can be retrieved,
@@ -233,7 +234,9 @@ impl<'de> Visitor<'de> for U32Visitor {
 /// [`DynamicStruct`]: crate::DynamicStruct
 /// [`DynamicList`]: crate::DynamicList
 /// [`FromReflect`]: crate::FromReflect
+/// [type path]: crate::TypeRegistration::type_path
 /// [type name]: std::any::type_name
+/// [version]: crate::TypeRegistration::version
 pub struct UntypedReflectDeserializer<'a> {
     registry: &'a TypeRegistry,
 }
@@ -260,11 +263,11 @@ impl<'a, 'de> DeserializeSeed<'de> for UntypedReflectDeserializer<'a> {
 /// A deserializer for type registrations.
 ///
 /// This will return a [`&TypeRegistration`] corresponding to the given type.
-/// This deserializer expects a string containing the _full_ [type name] of the
-/// type to find the `TypeRegistration` of.
+/// This deserializer expects a string containing the [type path] of the type to find the
+/// `TypeRegistration` of, as found by [`TypeRegistry::get_with_type_path`].
 ///
 /// [`&TypeRegistration`]: crate::TypeRegistration
-/// [type name]: std::any::type_name
+/// [type path]: crate::TypeRegistration::type_path
 pub struct TypeRegistrationDeserializer<'a> {
     registry: &'a TypeRegistry,
 }
@@ -291,12 +294,12 @@ impl<'a, 'de> DeserializeSeed<'de> for TypeRegistrationDeserializer<'a> {
                 formatter.write_str("string containing `type` entry for the reflected value")
             }
 
-            fn visit_str<E>(self, type_name: &str) -> Result<Self::Value, E>
+            fn visit_str<E>(self, type_path: &str) -> Result<Self::Value, E>
             where
                 E: Error,
             {
-                self.0.get_with_name(type_name).ok_or_else(|| {
-                    Error::custom(format_args!("No registration found for `{type_name}`"))
+                self.0.get_with_type_path(type_path).ok_or_else(|| {
+                    Error::custom(format_args!("No registration found for `{type_path}`"))
                 })
             }
         }
@@ -305,6 +308,56 @@ impl<'a, 'de> DeserializeSeed<'de> for TypeRegistrationDeserializer<'a> {
     }
 }
 
+/// A deserializer for type registrations and the version of the serialized data.
+///
+/// This will return a [`&TypeRegistration`] corresponding to the given type, along with the
+/// [version] of the type the data was serialized with.
+/// This deserializer expects a string containing the [type path] of the type, optionally
+/// followed by `@` and the version, as found by [`TypeRegistry::get_with_versioned_type_path`].
+///
+/// [`&TypeRegistration`]: crate::TypeRegistration
+/// [version]: crate::TypeRegistration::version
+/// [type path]: crate::TypeRegistration::type_path
+pub struct VersionedTypeRegistrationDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> VersionedTypeRegistrationDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for VersionedTypeRegistrationDeserializer<'a> {
+    type Value = (&'a TypeRegistration, u32);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VersionedTypeRegistrationVisitor<'a>(&'a TypeRegistry);
+
+        impl<'de, 'a> Visitor<'de> for VersionedTypeRegistrationVisitor<'a> {
+            type Value = (&'a TypeRegistration, u32);
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("string containing `type` entry for the reflected value")
+            }
+
+            fn visit_str<E>(self, key: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                self.0
+                    .get_with_versioned_type_path(key)
+                    .ok_or_else(|| Error::custom(format_args!("No registration found for `{key}`")))
+            }
+        }
+
+        deserializer.deserialize_str(VersionedTypeRegistrationVisitor(self.registry))
+    }
+}
+
 struct UntypedReflectDeserializerVisitor<'a> {
     registry: &'a TypeRegistry,
 }
@@ -320,13 +373,12 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
     where
         A: MapAccess<'de>,
     {
-        let registration = map
-            .next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        let (registration, version) = map
+            .next_key_seed(VersionedTypeRegistrationDeserializer::new(self.registry))?
             .ok_or_else(|| Error::invalid_length(0, &"at least one entry"))?;
-        let value = map.next_value_seed(TypedReflectDeserializer {
-            registration,
-            registry: self.registry,
-        })?;
+        let value = map.next_value_seed(
+            TypedReflectDeserializer::new(registration, self.registry).with_version(version),
+        )?;
         Ok(value)
     }
 }
@@ -344,7 +396,20 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
 ///
 /// If the type is not known ahead of time, use [`UntypedReflectDeserializer`] instead.
 ///
+/// If the data was serialized with an older [version] of the type, set with
+/// [`with_version`](Self::with_version), the [`ReflectMigrations`] of the type are run on the
+/// deserialized value to update it. The data of an older version can only be migrated:
+/// - from self-describing formats, which name the fields of structs and the variants of enums,
+///   since the fields and variants of older versions are unknown,
+/// - for types without [`ReflectDeserialize`], which are deserialized without reflection.
+///
+/// The version only applies to the deserialized type: the fields, items and entries of the
+/// value are deserialized with the current version of their types, since serialized data only
+/// records the version of its top-level values. Changes to the types of nested values must be
+/// handled by the migrations of the top-level types.
+///
 /// [`TypeInfo`]: crate::TypeInfo
+/// [version]: crate::TypeRegistration::version
 /// [`Box<dyn Reflect>`]: crate::Reflect
 /// [`DynamicStruct`]: crate::DynamicStruct
 /// [`DynamicList`]: crate::DynamicList
@@ -352,6 +417,7 @@ impl<'a, 'de> Visitor<'de> for UntypedReflectDeserializerVisitor<'a> {
 pub struct TypedReflectDeserializer<'a> {
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    version: u32,
 }
 
 impl<'a> TypedReflectDeserializer<'a> {
@@ -359,8 +425,16 @@ impl<'a> TypedReflectDeserializer<'a> {
         Self {
             registration,
             registry,
+            version: registration.version(),
         }
     }
+
+    /// Sets the version of the type the data was serialized with, which defaults to the
+    /// current version of the type.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
 }
 
 impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
@@ -372,13 +446,38 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
     {
         let type_name = self.registration.type_name();
 
+        let current_version = self.registration.version();
+        if self.version > current_version {
+            return Err(de::Error::custom(format_args!(
+                "data of {type_name} has version {}, newer than its current version {current_version}",
+                self.version,
+            )));
+        }
+        let old_version = if self.version < current_version {
+            self.registration
+                .data::<ReflectMigrations>()
+                .map(|migrations| OldVersion {
+                    migrations,
+                    version: self.version,
+                    type_name,
+                })
+        } else {
+            None
+        };
+
         // Handle both Value case and types that have a custom `ReflectDeserialize`
         if let Some(deserialize_reflect) = self.registration.data::<ReflectDeserialize>() {
+            if let Some(old_version) = old_version {
+                return Err(de::Error::custom(format_args!(
+                    "cannot migrate {type_name} from version {}, it has `ReflectDeserialize`",
+                    old_version.version,
+                )));
+            }
             let value = deserialize_reflect.deserialize(deserializer)?;
             return Ok(value);
         }
 
-        match self.registration.type_info() {
+        let mut value: Box<dyn Reflect> = match self.registration.type_info() {
             TypeInfo::Struct(struct_info) => {
                 let mut dynamic_struct = deserializer.deserialize_struct(
                     struct_info.name(),
@@ -387,10 +486,11 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        old_version,
                     },
                 )?;
                 dynamic_struct.set_name(struct_info.type_name().to_string());
-                Ok(Box::new(dynamic_struct))
+                Box::new(dynamic_struct)
             }
             TypeInfo::TupleStruct(tuple_struct_info) => {
                 let mut dynamic_tuple_struct = deserializer.deserialize_tuple_struct(
@@ -403,7 +503,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     },
                 )?;
                 dynamic_tuple_struct.set_name(tuple_struct_info.type_name().to_string());
-                Ok(Box::new(dynamic_tuple_struct))
+                Box::new(dynamic_tuple_struct)
             }
             TypeInfo::List(list_info) => {
                 let mut dynamic_list = deserializer.deserialize_seq(ListVisitor {
@@ -411,7 +511,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     registry: self.registry,
                 })?;
                 dynamic_list.set_name(list_info.type_name().to_string());
-                Ok(Box::new(dynamic_list))
+                Box::new(dynamic_list)
             }
             TypeInfo::Array(array_info) => {
                 let mut dynamic_array = deserializer.deserialize_tuple(
@@ -422,7 +522,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     },
                 )?;
                 dynamic_array.set_name(array_info.type_name().to_string());
-                Ok(Box::new(dynamic_array))
+                Box::new(dynamic_array)
             }
             TypeInfo::Map(map_info) => {
                 let mut dynamic_map = deserializer.deserialize_map(MapVisitor {
@@ -430,7 +530,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     registry: self.registry,
                 })?;
                 dynamic_map.set_name(map_info.type_name().to_string());
-                Ok(Box::new(dynamic_map))
+                Box::new(dynamic_map)
             }
             TypeInfo::Tuple(tuple_info) => {
                 let mut dynamic_tuple = deserializer.deserialize_tuple(
@@ -441,7 +541,7 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                     },
                 )?;
                 dynamic_tuple.set_name(tuple_info.type_name().to_string());
-                Ok(Box::new(dynamic_tuple))
+                Box::new(dynamic_tuple)
             }
             TypeInfo::Enum(enum_info) => {
                 let type_name = enum_info.type_name();
@@ -458,26 +558,70 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                             enum_info,
                             registration: self.registration,
                             registry: self.registry,
+                            old_version,
                         },
                     )?
                 };
                 dynamic_enum.set_name(type_name.to_string());
-                Ok(Box::new(dynamic_enum))
+                Box::new(dynamic_enum)
             }
             TypeInfo::Value(_) => {
                 // This case should already be handled
-                Err(de::Error::custom(format_args!(
+                return Err(de::Error::custom(format_args!(
                     "the TypeRegistration for {type_name} doesn't have ReflectDeserialize",
-                )))
+                )));
             }
             TypeInfo::Dynamic(_) => {
                 // We could potentially allow this but we'd have no idea what the actual types of the
                 // fields are and would rely on the deserializer to determine them (e.g. `i32` vs `i64`)
-                Err(de::Error::custom(format_args!(
+                return Err(de::Error::custom(format_args!(
                     "cannot deserialize arbitrary dynamic type {type_name}",
-                )))
+                )));
             }
+        };
+
+        if let Some(old_version) = old_version {
+            old_version
+                .migrations
+                .migrate(old_version.version, &mut *value)
+                .map_err(|err| {
+                    de::Error::custom(format_args!(
+                        "failed to migrate {type_name} from version {}: {err}",
+                        old_version.version,
+                    ))
+                })?;
         }
+        Ok(value)
+    }
+}
+
+/// The migrations to run on data serialized with an older version of a type.
+#[derive(Clone, Copy)]
+struct OldVersion<'a> {
+    migrations: &'a ReflectMigrations,
+    version: u32,
+    type_name: &'a str,
+}
+
+impl<'a> OldVersion<'a> {
+    /// Returns the registration of the field named `name` that the version of the data had,
+    /// as declared by the migrations.
+    fn field_registration<E: Error>(
+        &self,
+        name: &str,
+        registry: &'a TypeRegistry,
+    ) -> Option<Result<&'a TypeRegistration, E>> {
+        let type_id = self.migrations.field_type_id(self.version, name)?;
+        Some(get_registration(type_id, name, registry))
+    }
+
+    /// The error returned when the data is in a format that isn't self-describing, whose
+    /// fields or variants can't be matched to those of the older version.
+    fn not_self_describing<E: Error>(&self) -> E {
+        Error::custom(format_args!(
+            "cannot migrate {} from version {}, its data must be in a self-describing format",
+            self.type_name, self.version,
+        ))
     }
 }
 
@@ -485,6 +629,7 @@ struct StructVisitor<'a> {
     struct_info: &'static StructInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    old_version: Option<OldVersion<'a>>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
@@ -498,13 +643,16 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registry)
+        visit_struct(&mut map, self.struct_info, self.registry, self.old_version)
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
+        if let Some(old_version) = self.old_version {
+            return Err(old_version.not_self_describing());
+        }
         let mut index = 0usize;
         let mut output = DynamicStruct::default();
 
@@ -520,12 +668,11 @@ impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
             return Ok(output);
         }
 
-        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-            registration: self
-                .struct_info
+        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer::new(
+            self.struct_info
                 .get_field_registration(index, self.registry)?,
-            registry: self.registry,
-        })? {
+            self.registry,
+        ))? {
             let name = self.struct_info.field_at(index).unwrap().name();
             output.insert_boxed(name, value);
             index += 1;
@@ -584,10 +731,10 @@ impl<'a, 'de> Visitor<'de> for TupleStructVisitor<'a> {
             get_registration(field.type_id(), field.type_name(), self.registry)
         };
 
-        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-            registration: get_field_registration(index)?,
-            registry: self.registry,
-        })? {
+        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer::new(
+            get_field_registration(index)?,
+            self.registry,
+        ))? {
             tuple_struct.insert_boxed(value);
             index += 1;
             if index >= self.tuple_struct_info.field_len() {
@@ -653,10 +800,9 @@ impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
             self.array_info.item_type_name(),
             self.registry,
         )?;
-        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-            registration,
-            registry: self.registry,
-        })? {
+        while let Some(value) =
+            seq.next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+        {
             vec.push(value);
         }
 
@@ -693,10 +839,9 @@ impl<'a, 'de> Visitor<'de> for ListVisitor<'a> {
             self.list_info.item_type_name(),
             self.registry,
         )?;
-        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-            registration,
-            registry: self.registry,
-        })? {
+        while let Some(value) =
+            seq.next_element_seed(TypedReflectDeserializer::new(registration, self.registry))?
+        {
             list.push_box(value);
         }
         Ok(list)
@@ -730,14 +875,14 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
             self.map_info.value_type_name(),
             self.registry,
         )?;
-        while let Some(key) = map.next_key_seed(TypedReflectDeserializer {
-            registration: key_registration,
-            registry: self.registry,
-        })? {
-            let value = map.next_value_seed(TypedReflectDeserializer {
-                registration: value_registration,
-                registry: self.registry,
-            })?;
+        while let Some(key) = map.next_key_seed(TypedReflectDeserializer::new(
+            key_registration,
+            self.registry,
+        ))? {
+            let value = map.next_value_seed(TypedReflectDeserializer::new(
+                value_registration,
+                self.registry,
+            ))?;
             dynamic_map.insert_boxed(key, value);
         }
 
@@ -749,6 +894,7 @@ struct EnumVisitor<'a> {
     enum_info: &'static EnumInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    old_version: Option<OldVersion<'a>>,
 }
 
 impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
@@ -765,6 +911,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
         let mut dynamic_enum = DynamicEnum::default();
         let (variant_info, variant) = data.variant_seed(VariantDeserializer {
             enum_info: self.enum_info,
+            old_version: self.old_version,
         })?;
 
         let value: DynamicVariant = match variant_info {
@@ -776,6 +923,7 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                         struct_info,
                         registration: self.registration,
                         registry: self.registry,
+                        old_version: self.old_version,
                     },
                 )?
                 .into(),
@@ -783,10 +931,10 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                 let field = tuple_info.field_at(0).unwrap();
                 let registration =
                     get_registration(field.type_id(), field.type_name(), self.registry)?;
-                let value = variant.newtype_variant_seed(TypedReflectDeserializer {
+                let value = variant.newtype_variant_seed(TypedReflectDeserializer::new(
                     registration,
-                    registry: self.registry,
-                })?;
+                    self.registry,
+                ))?;
                 let mut dynamic_tuple = DynamicTuple::default();
                 dynamic_tuple.insert_boxed(value);
                 dynamic_tuple.into()
@@ -808,20 +956,21 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
     }
 }
 
-struct VariantDeserializer {
+struct VariantDeserializer<'a> {
     enum_info: &'static EnumInfo,
+    old_version: Option<OldVersion<'a>>,
 }
 
-impl<'de> DeserializeSeed<'de> for VariantDeserializer {
+impl<'a, 'de> DeserializeSeed<'de> for VariantDeserializer<'a> {
     type Value = &'static VariantInfo;
 
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        struct VariantVisitor(&'static EnumInfo);
+        struct VariantVisitor<'a>(&'static EnumInfo, Option<OldVersion<'a>>);
 
-        impl<'de> Visitor<'de> for VariantVisitor {
+        impl<'a, 'de> Visitor<'de> for VariantVisitor<'a> {
             type Value = &'static VariantInfo;
 
             fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
@@ -846,6 +995,10 @@ impl<'de> DeserializeSeed<'de> for VariantDeserializer {
             where
                 E: Error,
             {
+                // The variants may have been reordered since the older version.
+                if let Some(old_version) = self.1 {
+                    return Err(old_version.not_self_describing());
+                }
                 self.0.variant_at(variant_index as usize).ok_or_else(|| {
                     Error::custom(format_args!(
                         "no variant found at index `{}` on enum `{}`",
@@ -856,7 +1009,7 @@ impl<'de> DeserializeSeed<'de> for VariantDeserializer {
             }
         }
 
-        deserializer.deserialize_identifier(VariantVisitor(self.enum_info))
+        deserializer.deserialize_identifier(VariantVisitor(self.enum_info, self.old_version))
     }
 }
 
@@ -864,6 +1017,7 @@ struct StructVariantVisitor<'a> {
     struct_info: &'static StructVariantInfo,
     registration: &'a TypeRegistration,
     registry: &'a TypeRegistry,
+    old_version: Option<OldVersion<'a>>,
 }
 
 impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
@@ -877,13 +1031,16 @@ impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
     where
         V: MapAccess<'de>,
     {
-        visit_struct(&mut map, self.struct_info, self.registry)
+        visit_struct(&mut map, self.struct_info, self.registry, self.old_version)
     }
 
     fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
     where
         A: SeqAccess<'de>,
     {
+        if let Some(old_version) = self.old_version {
+            return Err(old_version.not_self_describing());
+        }
         let mut index = 0usize;
         let mut output = DynamicStruct::default();
 
@@ -899,12 +1056,11 @@ impl<'a, 'de> Visitor<'de> for StructVariantVisitor<'a> {
             return Ok(output);
         }
 
-        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-            registration: self
-                .struct_info
+        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer::new(
+            self.struct_info
                 .get_field_registration(index, self.registry)?,
-            registry: self.registry,
-        })? {
+            self.registry,
+        ))? {
             let name = self.struct_info.field_at(index).unwrap().name();
             output.insert_boxed(name, value);
             index += 1;
@@ -973,10 +1129,7 @@ impl<'a, 'de> Visitor<'de> for OptionVisitor<'a> {
                 let field = tuple_info.field_at(0).unwrap();
                 let registration =
                     get_registration(field.type_id(), field.type_name(), self.registry)?;
-                let de = TypedReflectDeserializer {
-                    registration,
-                    registry: self.registry,
-                };
+                let de = TypedReflectDeserializer::new(registration, self.registry);
                 let mut value = DynamicTuple::default();
                 value.insert_boxed(de.deserialize(deserializer)?);
                 let mut option = DynamicEnum::default();
@@ -1004,6 +1157,7 @@ fn visit_struct<'de, T, V>(
     map: &mut V,
     info: &'static T,
     registry: &TypeRegistry,
+    old_version: Option<OldVersion>,
 ) -> Result<DynamicStruct, V::Error>
 where
     T: StructLikeInfo,
@@ -1011,19 +1165,20 @@ where
 {
     let mut dynamic_struct = DynamicStruct::default();
     while let Some(Ident(key)) = map.next_key::<Ident>()? {
-        let field = info.get_field(&key).ok_or_else(|| {
-            let fields = info.iter_fields().map(|field| field.name());
-            Error::custom(format_args!(
-                "unknown field `{}`, expected one of {:?}",
-                key,
-                ExpectedValues(fields.collect())
-            ))
-        })?;
-        let registration = get_registration(field.type_id(), field.type_name(), registry)?;
-        let value = map.next_value_seed(TypedReflectDeserializer {
-            registration,
-            registry,
-        })?;
+        let registration = match info.get_field(&key) {
+            Some(field) => get_registration(field.type_id(), field.type_name(), registry)?,
+            None => old_version
+                .and_then(|old_version| old_version.field_registration(&key, registry))
+                .unwrap_or_else(|| {
+                    let fields = info.iter_fields().map(|field| field.name());
+                    Err(Error::custom(format_args!(
+                        "unknown field `{}`, expected one of {:?}",
+                        key,
+                        ExpectedValues(fields.collect())
+                    )))
+                })?,
+        };
+        let value = map.next_value_seed(TypedReflectDeserializer::new(registration, registry))?;
         dynamic_struct.insert_boxed(&key, value);
     }
 
@@ -1049,10 +1204,10 @@ where
         get_registration(field.type_id(), field.type_name(), registry)
     };
 
-    while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
-        registration: get_field_registration(index)?,
+    while let Some(value) = seq.next_element_seed(TypedReflectDeserializer::new(
+        get_field_registration(index)?,
         registry,
-    })? {
+    ))? {
         tuple.insert_boxed(value);
         index += 1;
         if index >= info.get_field_len() {
@@ -0,0 +1,343 @@
+use crate::{DynamicEnum, DynamicStruct, GetTypeRegistration, Reflect, TypeRegistration};
+use std::any::TypeId;
+
+/// A function updating the value of a type deserialized from data of an older version.
+#[derive(Clone, Copy)]
+pub enum MigrationFn {
+    /// Updates the value of a struct.
+    Struct(fn(&mut DynamicStruct)),
+    /// Updates the value of an enum.
+    Enum(fn(&mut DynamicEnum)),
+}
+
+/// A field of an older version of a type that the type no longer has.
+#[derive(Clone)]
+struct MigrationField {
+    name: String,
+    type_id: TypeId,
+    get_registration: fn() -> TypeRegistration,
+}
+
+/// Updates the data of a reflected type serialized with a given [version] to the next version.
+///
+/// Migrations are registered with [`TypeRegistry::register_migration`], and run by the
+/// [`TypedReflectDeserializer`] when deserializing data of an older version, in order of the
+/// version they migrate from.
+///
+/// Since the data is first deserialized with the fields of the current version of the type,
+/// the fields that were renamed or removed since the version of the data must be declared with
+/// [`with_field`](Self::with_field) for the data to be deserialized. Fields missing from the
+/// data are left unset, to be added by the migration or filled by [`FromReflect`].
+///
+/// Only the version of top-level values is recorded in serialized data, so migrations only run
+/// for those: values nested in the fields of other types are deserialized with their current
+/// version. Data of older versions must also be in a self-describing format. See
+/// [`TypedReflectDeserializer`] for the details.
+///
+/// [version]: crate::TypeRegistration::version
+/// [`TypeRegistry::register_migration`]: crate::TypeRegistry::register_migration
+/// [`TypedReflectDeserializer`]: crate::serde::TypedReflectDeserializer
+/// [`FromReflect`]: crate::FromReflect
+#[derive(Clone)]
+pub struct Migration {
+    from_version: u32,
+    fields: Vec<MigrationField>,
+    migrate: MigrationFn,
+}
+
+impl Migration {
+    /// Creates a migration updating the [`DynamicStruct`] deserialized from data of version
+    /// `from_version` of a struct.
+    pub fn new_struct(from_version: u32, migrate: fn(&mut DynamicStruct)) -> Self {
+        Self {
+            from_version,
+            fields: Vec::new(),
+            migrate: MigrationFn::Struct(migrate),
+        }
+    }
+
+    /// Creates a migration updating the [`DynamicEnum`] deserialized from data of version
+    /// `from_version` of an enum.
+    pub fn new_enum(from_version: u32, migrate: fn(&mut DynamicEnum)) -> Self {
+        Self {
+            from_version,
+            fields: Vec::new(),
+            migrate: MigrationFn::Enum(migrate),
+        }
+    }
+
+    /// Declares a field of type `T` that the migrated version of the type had, and that the
+    /// current version doesn't have.
+    ///
+    /// For enums, this declares a field of the struct variants.
+    /// Declared fields are only found in the data of self-describing formats, which name the
+    /// fields of structs.
+    pub fn with_field<T: GetTypeRegistration>(mut self, name: impl Into<String>) -> Self {
+        self.fields.push(MigrationField {
+            name: name.into(),
+            type_id: T::get_type_registration().type_id(),
+            get_registration: T::get_type_registration,
+        });
+        self
+    }
+
+    /// Returns the version this migration updates data from.
+    pub fn from_version(&self) -> u32 {
+        self.from_version
+    }
+
+    /// Returns the function updating the deserialized value.
+    pub fn migrate_fn(&self) -> MigrationFn {
+        self.migrate
+    }
+
+    /// Returns the functions creating the registrations of the declared fields.
+    pub(crate) fn field_registrations(
+        &self,
+    ) -> impl Iterator<Item = fn() -> TypeRegistration> + '_ {
+        self.fields.iter().map(|field| field.get_registration)
+    }
+
+    /// Runs the migration on `value`.
+    ///
+    /// Returns an error if `value` isn't of the kind the migration expects.
+    pub fn migrate(&self, value: &mut dyn Reflect) -> Result<(), String> {
+        match self.migrate {
+            MigrationFn::Struct(migrate) => match value.downcast_mut::<DynamicStruct>() {
+                Some(value) => migrate(value),
+                None => return Err(format!("expected a struct, found {}", value.type_name())),
+            },
+            MigrationFn::Enum(migrate) => match value.downcast_mut::<DynamicEnum>() {
+                Some(value) => migrate(value),
+                None => return Err(format!("expected an enum, found {}", value.type_name())),
+            },
+        }
+        Ok(())
+    }
+}
+
+/// Type data holding the [`Migration`]s of a versioned type.
+///
+/// This is inserted in the [`TypeRegistration`] of a type by
+/// [`TypeRegistry::register_migration`].
+///
+/// [`TypeRegistry::register_migration`]: crate::TypeRegistry::register_migration
+#[derive(Clone, Default)]
+pub struct ReflectMigrations {
+    migrations: Vec<Migration>,
+}
+
+impl ReflectMigrations {
+    /// Adds a migration, keeping the migrations sorted by the version they update data from.
+    pub fn add(&mut self, migration: Migration) {
+        let index = self
+            .migrations
+            .partition_point(|other| other.from_version <= migration.from_version);
+        self.migrations.insert(index, migration);
+    }
+
+    /// Returns an iterator over the migrations, in order of the version they update data from.
+    pub fn iter(&self) -> impl Iterator<Item = &Migration> {
+        self.migrations.iter()
+    }
+
+    /// Returns the [`TypeId`] of the field named `name` declared by the migrations updating
+    /// data of `version`, if any.
+    pub fn field_type_id(&self, version: u32, name: &str) -> Option<TypeId> {
+        self.migrations
+            .iter()
+            .filter(|migration| migration.from_version >= version)
+            .flat_map(|migration| &migration.fields)
+            .find(|field| field.name == name)
+            .map(|field| field.type_id)
+    }
+
+    /// Runs the migrations updating `value`, deserialized from data of `version`, in order.
+    pub fn migrate(&self, version: u32, value: &mut dyn Reflect) -> Result<(), String> {
+        self.migrations
+            .iter()
+            .filter(|migration| migration.from_version >= version)
+            .try_for_each(|migration| migration.migrate(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_reflect,
+        serde::{
+            Migration, ReflectMigrations, ReflectSerializer, TypedReflectDeserializer,
+            TypedReflectSerializer, UntypedReflectDeserializer,
+        },
+        DynamicStruct, FromReflect, GetTypeRegistration, Reflect, ReflectDeserialize, Struct,
+        TypeRegistry,
+    };
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    /// Appends the version a migration updates from to the `log` field.
+    fn log(value: &mut DynamicStruct, version: &str) {
+        let mut log = value
+            .field("log")
+            .and_then(|log| log.downcast_ref::<String>())
+            .cloned()
+            .unwrap_or_default();
+        log.push_str(version);
+        value.insert("log", log);
+    }
+
+    #[test]
+    fn chained_migrations() {
+        let mut migrations = ReflectMigrations::default();
+        migrations.add(Migration::new_struct(2, |value| log(value, "2")));
+        migrations.add(Migration::new_struct(0, |value| log(value, "0")));
+        migrations.add(Migration::new_struct(1, |value| log(value, "1")));
+        assert_eq!(
+            migrations
+                .iter()
+                .map(Migration::from_version)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        for (version, expected) in [(0, "012"), (1, "12"), (2, "2"), (3, "")] {
+            let mut value = DynamicStruct::default();
+            value.insert("log", String::new());
+            migrations.migrate(version, &mut value).unwrap();
+            assert_eq!(
+                value
+                    .field("log")
+                    .unwrap()
+                    .downcast_ref::<String>()
+                    .unwrap(),
+                expected
+            );
+        }
+
+        let mut value = 0u32;
+        assert!(migrations.migrate(0, &mut value).is_err());
+    }
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    #[reflect(version = 1)]
+    struct Inner {
+        health: u32,
+    }
+
+    #[derive(Reflect, FromReflect, Debug, PartialEq)]
+    #[reflect(version = 1)]
+    struct Outer {
+        inner: Inner,
+        lives: u8,
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        // Version 0 of `Inner` named `health` `hp`, and version 0 of `Outer` had no `lives`.
+        registry.register_migration::<Inner>(
+            Migration::new_struct(0, |inner| {
+                if let Some(hp) = inner.remove("hp") {
+                    inner.insert_boxed("health", hp);
+                }
+            })
+            .with_field::<u32>("hp"),
+        );
+        registry.register_migration::<Outer>(Migration::new_struct(0, |outer| {
+            if outer.field("lives").is_none() {
+                outer.insert("lives", 3u8);
+            }
+        }));
+        registry
+    }
+
+    #[test]
+    fn nested_versioned_fields() {
+        let registry = registry();
+        let deserialize = |data: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(data).unwrap();
+            UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer)
+        };
+        let outer = concat!(module_path!(), "::Outer");
+        let inner = concat!(module_path!(), "::Inner");
+
+        let value = deserialize(&format!("{{\"{inner}\":(hp:5)}}")).unwrap();
+        assert_eq!(Inner::from_reflect(&*value), Some(Inner { health: 5 }));
+
+        // Only the version of the top-level value is known, nested values have the current
+        // version of their type.
+        let value = deserialize(&format!("{{\"{outer}\":(inner:(health:5))}}")).unwrap();
+        assert_eq!(
+            Outer::from_reflect(&*value),
+            Some(Outer {
+                inner: Inner { health: 5 },
+                lives: 3,
+            })
+        );
+        assert!(deserialize(&format!("{{\"{outer}\":(inner:(hp:5))}}")).is_err());
+
+        let serialized = ron::to_string(&ReflectSerializer::new(
+            &Outer {
+                inner: Inner { health: 5 },
+                lives: 1,
+            },
+            &registry,
+        ))
+        .unwrap();
+        assert_eq!(
+            serialized,
+            format!("{{\"{outer}@1\":(inner:(health:5),lives:1)}}")
+        );
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    #[reflect(version = 1)]
+    struct Id(u32);
+
+    impl<'de> serde::Deserialize<'de> for Id {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            u32::deserialize(deserializer).map(Id)
+        }
+    }
+
+    #[test]
+    fn non_self_describing_format() {
+        let mut registry = registry();
+        let registration = registry
+            .get(Outer::get_type_registration().type_id())
+            .unwrap();
+        let value = Outer {
+            inner: Inner { health: 5 },
+            lives: 1,
+        };
+        let data = bincode::serialize(&TypedReflectSerializer::new(&value, &registry)).unwrap();
+
+        let deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let deserialized = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(deserializer, &data)
+            .unwrap();
+        assert_eq!(Outer::from_reflect(&*deserialized), Some(value));
+
+        // The fields of older versions are unknown, the data can't be deserialized.
+        let deserializer = TypedReflectDeserializer::new(registration, &registry).with_version(0);
+        let err = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(deserializer, &data)
+            .unwrap_err();
+        assert!(err.to_string().contains("self-describing"), "{err}");
+
+        // Types with `ReflectDeserialize` aren't deserialized through reflection.
+        registry.register_migration::<Id>(Migration::new_struct(0, |_| {}));
+        registry.register_type_data::<Id, ReflectDeserialize>();
+        let registration = registry.get(Id::get_type_registration().type_id()).unwrap();
+        let deserialize = |version| {
+            let mut deserializer = ron::de::Deserializer::from_str("7").unwrap();
+            TypedReflectDeserializer::new(registration, &registry)
+                .with_version(version)
+                .deserialize(&mut deserializer)
+        };
+        let id = deserialize(1).unwrap();
+        assert_eq!(id.downcast_ref::<Id>(), Some(&Id(7)));
+        assert!(deserialize(0).is_err());
+    }
+}
@@ -1,8 +1,10 @@
 mod de;
+mod migration;
 mod ser;
 mod type_data;
 
 pub use de::*;
+pub use migration::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -92,4 +94,87 @@ mod tests {
             "Expected {expected:?} found {deserialized:?}"
         );
     }
+
+    #[test]
+    fn test_migration() {
+        use crate::serde::Migration;
+        use crate::{DynamicEnum, Enum, FromReflect, Struct};
+
+        #[derive(Debug, Reflect, FromReflect, PartialEq)]
+        #[reflect(version = 2)]
+        struct Player {
+            health: u32,
+            lives: u8,
+        }
+
+        #[derive(Debug, Reflect, FromReflect, PartialEq)]
+        #[reflect(version = 1)]
+        enum Event {
+            Hit { amount: u32 },
+        }
+
+        let mut registry = TypeRegistry::default();
+        // Version 0 named `health` `hp`, and version 1 had no `lives`.
+        registry.register_migration::<Player>(
+            Migration::new_struct(0, |player: &mut DynamicStruct| {
+                if let Some(hp) = player.remove("hp") {
+                    player.insert_boxed("health", hp);
+                }
+            })
+            .with_field::<u32>("hp"),
+        );
+        registry.register_migration::<Player>(Migration::new_struct(
+            1,
+            |player: &mut DynamicStruct| {
+                if player.field("lives").is_none() {
+                    player.insert("lives", 3u8);
+                }
+            },
+        ));
+        // Version 0 named `amount` `damage`.
+        registry.register_migration::<Event>(
+            Migration::new_enum(0, |event: &mut DynamicEnum| {
+                if let Some(damage) = event.field("damage") {
+                    let mut hit = DynamicStruct::default();
+                    hit.insert_boxed("amount", damage.clone_value());
+                    event.set_variant("Hit", hit);
+                }
+            })
+            .with_field::<u32>("damage"),
+        );
+
+        let player = Player {
+            health: 5,
+            lives: 1,
+        };
+        let serialized = ron::to_string(&ReflectSerializer::new(&player, &registry)).unwrap();
+        let type_path = concat!(module_path!(), "::Player");
+        assert_eq!(
+            serialized,
+            format!("{{\"{type_path}@2\":(health:5,lives:1)}}")
+        );
+
+        let deserialize = |data: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(data).unwrap();
+            UntypedReflectDeserializer::new(&registry).deserialize(&mut deserializer)
+        };
+        for data in [
+            serialized.clone(),
+            format!("{{\"{type_path}@1\":(health:5)}}"),
+            format!("{{\"{type_path}\":(hp:5)}}"),
+        ] {
+            let value = deserialize(&data).unwrap();
+            let lives = if data == serialized { 1 } else { 3 };
+            assert_eq!(
+                Player::from_reflect(&*value),
+                Some(Player { health: 5, lives })
+            );
+        }
+        assert!(deserialize(&format!("{{\"{type_path}@3\":(health:5)}}")).is_err());
+        assert!(deserialize(&format!("{{\"{type_path}@2\":(hp:5)}}")).is_err());
+
+        let data = format!("{{\"{}\":Hit(damage:2)}}", std::any::type_name::<Event>());
+        let value = deserialize(&data).unwrap();
+        assert_eq!(Event::from_reflect(&*value), Some(Event::Hit { amount: 2 }));
+    }
 }
@@ -1,10 +1,13 @@
-use crate::{serde::Serializable, Reflect, TypeInfo, Typed};
+use crate::{
+    serde::{Migration, ReflectMigrations, Serializable},
+    Reflect, TypeInfo, Typed,
+};
 use bevy_ptr::{Ptr, PtrMut};
 use bevy_utils::{HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use serde::Deserialize;
-use std::{any::TypeId, fmt::Debug, sync::Arc};
+use std::{any::TypeId, borrow::Cow, fmt::Debug, sync::Arc};
 
 /// A registry of [reflected] types.
 ///
@@ -22,7 +25,9 @@ pub struct TypeRegistry {
     registrations: HashMap<TypeId, TypeRegistration>,
     short_name_to_id: HashMap<String, TypeId>,
     full_name_to_id: HashMap<String, TypeId>,
+    type_path_to_id: HashMap<String, TypeId>,
     ambiguous_names: HashSet<String>,
+    ambiguous_type_paths: HashSet<String>,
 }
 
 // TODO:  remove this wrapper once we migrate to Atelier Assets and the Scene AssetLoader doesn't
@@ -65,7 +70,9 @@ impl TypeRegistry {
             registrations: Default::default(),
             short_name_to_id: Default::default(),
             full_name_to_id: Default::default(),
+            type_path_to_id: Default::default(),
             ambiguous_names: Default::default(),
+            ambiguous_type_paths: Default::default(),
         }
     }
 
@@ -105,6 +112,11 @@ impl TypeRegistry {
     }
 
     /// Registers the type described by `registration`.
+    ///
+    /// If another type was registered with the same [type path](TypeRegistration::type_path),
+    /// for example a type with the same name declared in another function of the same module,
+    /// the path becomes ambiguous and can no longer be looked up, see
+    /// [`TypeRegistry::get_with_type_path`].
     pub fn add_registration(&mut self, registration: TypeRegistration) {
         if self.registrations.contains_key(&registration.type_id()) {
             return;
@@ -123,6 +135,7 @@ impl TypeRegistry {
         }
         self.full_name_to_id
             .insert(registration.type_name().to_string(), registration.type_id());
+        self.insert_type_path(registration.type_path().to_string(), registration.type_id());
         self.registrations
             .insert(registration.type_id(), registration);
     }
@@ -154,6 +167,20 @@ impl TypeRegistry {
         data.insert(D::from_type());
     }
 
+    /// Removes the [`TypeRegistration`] of the type with the given [`TypeId`], along with its
+    /// names and aliases, returning it.
+    ///
+    /// If the specified type has not been registered, returns `None`.
+    ///
+    /// [`TypeId`]: std::any::TypeId
+    pub fn remove(&mut self, type_id: TypeId) -> Option<TypeRegistration> {
+        let registration = self.registrations.remove(&type_id)?;
+        self.short_name_to_id.retain(|_, id| *id != type_id);
+        self.full_name_to_id.retain(|_, id| *id != type_id);
+        self.type_path_to_id.retain(|_, id| *id != type_id);
+        Some(registration)
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with the
     /// given [`TypeId`].
     ///
@@ -195,6 +222,174 @@ impl TypeRegistry {
             .and_then(move |id| self.get_mut(id))
     }
 
+    /// Registers `alias` as another [type path] of the type `T`, registering `T` if needed.
+    ///
+    /// This allows data serialized with a former path of a renamed or moved type to still be
+    /// deserialized. If `alias` is the path or alias of another type, it becomes ambiguous and can
+    /// no longer be looked up.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_reflect::{Reflect, TypeRegistry};
+    ///
+    /// #[derive(Reflect)]
+    /// struct Player;
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register_type_alias::<Player>("my_game::entities::Player");
+    /// assert!(type_registry.get_with_type_path("my_game::entities::Player").is_some());
+    /// ```
+    ///
+    /// [type path]: TypeRegistration::type_path
+    pub fn register_type_alias<T>(&mut self, alias: impl Into<String>)
+    where
+        T: GetTypeRegistration,
+    {
+        let registration = T::get_type_registration();
+        let type_id = registration.type_id();
+        self.add_registration(registration);
+        self.insert_type_path(alias.into(), type_id);
+    }
+
+    /// Maps `type_path` to the type with the given [`TypeId`], unless it is used by another type,
+    /// in which case the path is marked as ambiguous.
+    fn insert_type_path(&mut self, type_path: String, type_id: TypeId) {
+        if self.ambiguous_type_paths.contains(&type_path) {
+            return;
+        }
+        match self.type_path_to_id.get(&type_path) {
+            Some(id) if *id != type_id => {
+                // Looking the path up could deserialize data into the wrong type.
+                self.type_path_to_id.remove(&type_path);
+                self.ambiguous_type_paths.insert(type_path);
+            }
+            _ => {
+                self.type_path_to_id.insert(type_path, type_id);
+            }
+        }
+    }
+
+    /// Returns a reference to the [`TypeRegistration`] of the type with the
+    /// given [type path] or alias.
+    ///
+    /// Falls back to the [type name], which data serialized before type paths were introduced
+    /// uses. If the path is ambiguous, because several types were registered with it, or if no
+    /// type with the given path or name has been registered, returns `None`.
+    ///
+    /// [type path]: TypeRegistration::type_path
+    /// [type name]: TypeRegistration::type_name
+    pub fn get_with_type_path(&self, type_path: &str) -> Option<&TypeRegistration> {
+        if self.is_ambiguous_type_path(type_path) {
+            return None;
+        }
+        self.type_path_to_id
+            .get(type_path)
+            .and_then(|id| self.get(*id))
+            .or_else(|| self.get_with_name(type_path))
+    }
+
+    /// Returns `true` if several types were registered with the given [type path] or alias.
+    ///
+    /// [type path]: TypeRegistration::type_path
+    pub fn is_ambiguous_type_path(&self, type_path: &str) -> bool {
+        self.ambiguous_type_paths.contains(type_path)
+    }
+
+    /// Returns the [type path] identifying the type of `value` in serialized data.
+    ///
+    /// This is the type path of the registration of the type of `value`, or of the type it
+    /// represents if `value` is a dynamic type. Falls back to the [type name] of `value` if the
+    /// type is not registered.
+    ///
+    /// [type path]: TypeRegistration::type_path
+    /// [type name]: Reflect::type_name
+    pub fn type_path_of<'a>(&'a self, value: &'a dyn Reflect) -> &'a str {
+        self.get(value.type_id())
+            .or_else(|| self.get_with_name(value.type_name()))
+            .map(TypeRegistration::type_path)
+            .unwrap_or_else(|| value.type_name())
+    }
+
+    /// Returns the key identifying the type of `value` and its [version] in serialized data.
+    ///
+    /// This is the [type path](Self::type_path_of) of the type, followed by `@` and the version
+    /// of the type if it isn't `0`.
+    ///
+    /// [version]: TypeRegistration::version
+    pub fn versioned_type_path_of<'a>(&'a self, value: &'a dyn Reflect) -> Cow<'a, str> {
+        match self
+            .get(value.type_id())
+            .or_else(|| self.get_with_name(value.type_name()))
+        {
+            Some(registration) => registration.versioned_type_path(),
+            None => Cow::Borrowed(value.type_name()),
+        }
+    }
+
+    /// Returns a reference to the [`TypeRegistration`] of the type identified by `key` in
+    /// serialized data, along with the version of the type the data was serialized with.
+    ///
+    /// `key` is a [type path], optionally followed by `@` and a version, as returned by
+    /// [`versioned_type_path_of`](Self::versioned_type_path_of). Keys without a version are
+    /// of version `0`.
+    ///
+    /// [type path]: TypeRegistration::type_path
+    pub fn get_with_versioned_type_path(&self, key: &str) -> Option<(&TypeRegistration, u32)> {
+        if let Some(registration) = self.get_with_type_path(key) {
+            return Some((registration, 0));
+        }
+        let (type_path, version) = key.rsplit_once('@')?;
+        let version = version.parse().ok()?;
+        Some((self.get_with_type_path(type_path)?, version))
+    }
+
+    /// Registers a [`Migration`] updating data serialized with an older [version] of the type
+    /// `T`, registering `T` and the types of the fields declared by the migration if needed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy_reflect::{serde::Migration, DynamicStruct, Reflect, TypeRegistry};
+    ///
+    /// #[derive(Reflect)]
+    /// #[reflect(version = 1)]
+    /// struct Player {
+    ///     health: u32,
+    /// }
+    ///
+    /// let mut type_registry = TypeRegistry::default();
+    /// type_registry.register_migration::<Player>(
+    ///     // Version 0 named the field `hp`.
+    ///     Migration::new_struct(0, |player: &mut DynamicStruct| {
+    ///         if let Some(hp) = player.remove("hp") {
+    ///             player.insert_boxed("health", hp);
+    ///         }
+    ///     })
+    ///     .with_field::<u32>("hp"),
+    /// );
+    /// ```
+    ///
+    /// [version]: TypeRegistration::version
+    pub fn register_migration<T>(&mut self, migration: Migration)
+    where
+        T: GetTypeRegistration,
+    {
+        let registration = T::get_type_registration();
+        let type_id = registration.type_id();
+        self.add_registration(registration);
+        for get_registration in migration.field_registrations() {
+            self.add_registration(get_registration());
+        }
+        let registration = self.get_mut(type_id).unwrap();
+        match registration.data_mut::<ReflectMigrations>() {
+            Some(migrations) => migrations.add(migration),
+            None => {
+                let mut migrations = ReflectMigrations::default();
+                migrations.add(migration);
+                registration.insert(migrations);
+            }
+        }
+    }
+
     /// Returns a reference to the [`TypeRegistration`] of the type with
     /// the given short name.
     ///
@@ -304,6 +499,8 @@ impl TypeRegistryArc {
 /// [crate-level documentation]: crate
 pub struct TypeRegistration {
     short_name: String,
+    type_path: &'static str,
+    version: u32,
     data: HashMap<TypeId, Box<dyn TypeData>>,
     type_info: &'static TypeInfo,
 }
@@ -312,6 +509,8 @@ impl Debug for TypeRegistration {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TypeRegistration")
             .field("short_name", &self.short_name)
+            .field("type_path", &self.type_path)
+            .field("version", &self.version)
             .field("type_info", &self.type_info)
             .finish()
     }
@@ -364,10 +563,24 @@ impl TypeRegistration {
         Self {
             data: HashMap::default(),
             short_name: bevy_utils::get_short_name(type_name),
+            type_path: type_name,
+            version: 0,
             type_info: T::type_info(),
         }
     }
 
+    /// Sets the [type path](Self::type_path) of the type.
+    ///
+    /// This must be called before the registration is added to a [`TypeRegistry`].
+    pub fn set_type_path(&mut self, type_path: &'static str) {
+        self.type_path = type_path;
+    }
+
+    /// Sets the [version](Self::version) of the type.
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
     /// Returns the [short name] of the type.
     ///
     /// [short name]: bevy_utils::get_short_name
@@ -381,6 +594,38 @@ impl TypeRegistration {
     pub fn type_name(&self) -> &'static str {
         self.type_info.type_name()
     }
+
+    /// Returns the stable path identifying the type in serialized data.
+    ///
+    /// When [deriving `Reflect`], this is the path set with `#[reflect(type_path = "...")]`, or
+    /// the module path of the type followed by its name, which unlike the
+    /// [type name](Self::type_name) is stable across compiler versions. Generic types and types
+    /// that don't derive `Reflect` use their type name.
+    ///
+    /// [deriving `Reflect`]: derive@crate::Reflect
+    pub fn type_path(&self) -> &'static str {
+        self.type_path
+    }
+
+    /// Returns the current version of the type, set with `#[reflect(version = ...)]` when
+    /// [deriving `Reflect`], or `0`.
+    ///
+    /// Data serialized with an older version is updated by the [`ReflectMigrations`] of the
+    /// type when it is deserialized.
+    ///
+    /// [deriving `Reflect`]: derive@crate::Reflect
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Returns the key identifying the type and its [version](Self::version) in serialized
+    /// data: the [type path](Self::type_path), followed by `@` and the version if it isn't `0`.
+    pub fn versioned_type_path(&self) -> Cow<'static, str> {
+        match self.version {
+            0 => Cow::Borrowed(self.type_path),
+            version => Cow::Owned(format!("{}@{version}", self.type_path)),
+        }
+    }
 }
 
 impl Clone for TypeRegistration {
@@ -393,6 +638,8 @@ impl Clone for TypeRegistration {
         TypeRegistration {
             data,
             short_name: self.short_name.clone(),
+            type_path: self.type_path,
+            version: self.version,
             type_info: self.type_info,
         }
     }
@@ -655,4 +902,173 @@ mod test {
             "Option<HashMap<Option<String>, (String, Option<String>)>>"
         );
     }
+
+    #[test]
+    fn test_type_path() {
+        use crate::serde::{ReflectSerializer, UntypedReflectDeserializer};
+        use crate::{FromReflect, TypeRegistry};
+        use serde::de::DeserializeSeed;
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        struct Local(u32);
+
+        #[derive(Reflect, FromReflect, Debug, PartialEq)]
+        #[reflect(type_path = "my_game::Player")]
+        struct Player {
+            health: u32,
+        }
+
+        #[derive(Reflect)]
+        struct Generic<T>(T);
+
+        mod nested {
+            use crate as bevy_reflect;
+            use crate::Reflect;
+
+            #[derive(Reflect)]
+            pub struct Nested;
+        }
+
+        // The type name also contains the function the types are declared in.
+        assert_eq!(
+            Local::get_type_registration().type_path(),
+            concat!(module_path!(), "::Local")
+        );
+        assert_ne!(
+            Local::get_type_registration().type_path(),
+            std::any::type_name::<Local>()
+        );
+        assert_eq!(
+            nested::Nested::get_type_registration().type_path(),
+            concat!(module_path!(), "::nested::Nested")
+        );
+        assert_ne!(
+            nested::Nested::get_type_registration().type_path(),
+            std::any::type_name::<nested::Nested>()
+        );
+        assert_eq!(
+            Player::get_type_registration().type_path(),
+            "my_game::Player"
+        );
+        assert_eq!(
+            Generic::<u32>::get_type_registration().type_path(),
+            std::any::type_name::<Generic<u32>>()
+        );
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register_type_alias::<Player>("my_game::entities::Hero");
+
+        let player = Player { health: 3 };
+        let serialized = ron::to_string(&ReflectSerializer::new(&player, &registry)).unwrap();
+        assert_eq!(serialized, r#"{"my_game::Player":(health:3)}"#);
+
+        // Dynamic values are serialized with the path of the type they represent.
+        let dynamic = player.clone_value();
+        assert_eq!(
+            ron::to_string(&ReflectSerializer::new(&*dynamic, &registry)).unwrap(),
+            serialized
+        );
+
+        for data in [
+            serialized.clone(),
+            r#"{"my_game::entities::Hero":(health:3)}"#.to_string(),
+            format!("{{\"{}\":(health:3)}}", std::any::type_name::<Player>()),
+        ] {
+            let mut deserializer = ron::de::Deserializer::from_str(&data).unwrap();
+            let value = UntypedReflectDeserializer::new(&registry)
+                .deserialize(&mut deserializer)
+                .unwrap();
+            assert_eq!(Player::from_reflect(&*value), Some(Player { health: 3 }));
+        }
+    }
+
+    #[test]
+    fn test_remove() {
+        use crate::TypeRegistry;
+        use std::any::TypeId;
+
+        #[derive(Reflect)]
+        #[reflect(type_path = "my_game::Player")]
+        struct Player;
+
+        let mut registry = TypeRegistry::default();
+        registry.register_type_alias::<Player>("my_game::entities::Hero");
+        assert!(registry.remove(TypeId::of::<Player>()).is_some());
+        assert!(registry.get(TypeId::of::<Player>()).is_none());
+        assert!(registry.get_with_short_name("Player").is_none());
+        assert!(registry.get_with_type_path("my_game::Player").is_none());
+        assert!(registry
+            .get_with_type_path("my_game::entities::Hero")
+            .is_none());
+        assert!(registry.remove(TypeId::of::<Player>()).is_none());
+    }
+
+    #[test]
+    fn test_ambiguous_type_path() {
+        use crate::TypeRegistry;
+        use std::any::TypeId;
+
+        // Both are declared in this module with the same name, so they get the same type path.
+        fn first() -> TypeRegistration {
+            #[derive(Reflect)]
+            struct Settings;
+            Settings::get_type_registration()
+        }
+        fn second() -> TypeRegistration {
+            #[derive(Reflect)]
+            struct Settings(u32);
+            Settings::get_type_registration()
+        }
+
+        #[derive(Reflect)]
+        #[reflect(type_path = "my_game::Player")]
+        struct Player;
+
+        #[derive(Reflect)]
+        #[reflect(type_path = "my_game::Enemy")]
+        struct Enemy;
+
+        let (first, second) = (first(), second());
+        let (first_id, second_id) = (first.type_id(), second.type_id());
+        let type_path = first.type_path().to_string();
+        assert_eq!(type_path, second.type_path());
+
+        let mut registry = TypeRegistry::default();
+        registry.add_registration(first);
+        assert_eq!(
+            registry.get_with_type_path(&type_path).map(|r| r.type_id()),
+            Some(first_id)
+        );
+        registry.add_registration(second);
+        assert!(registry.is_ambiguous_type_path(&type_path));
+        assert!(registry.get_with_type_path(&type_path).is_none());
+        // Both types are still registered, and found with their type name.
+        assert!(registry.get(first_id).is_some());
+        assert_eq!(
+            registry
+                .get_with_type_path(registry.get(second_id).unwrap().type_name())
+                .map(|r| r.type_id()),
+            Some(second_id)
+        );
+
+        // An alias shadowing the path of another type is ambiguous too.
+        registry.register::<Player>();
+        registry.register_type_alias::<Player>("my_game::entities::Player");
+        registry.register_type_alias::<Enemy>("my_game::Player");
+        assert!(registry.is_ambiguous_type_path("my_game::Player"));
+        assert!(registry.get_with_type_path("my_game::Player").is_none());
+        assert_eq!(
+            registry
+                .get_with_type_path("my_game::entities::Player")
+                .map(|r| r.type_id()),
+            Some(TypeId::of::<Player>())
+        );
+        assert_eq!(
+            registry
+                .get_with_type_path("my_game::Enemy")
+                .map(|r| r.type_id()),
+            Some(TypeId::of::<Enemy>())
+        );
+    }
 }
@@ -0,0 +1,439 @@
+//! Exports [JSON Schemas](https://json-schema.org) of the types registered for reflection, to
+//! validate or autocomplete the data serialized by `bevy_reflect` in other tools.
+//!
+//! This is a separate crate so that only the crates using it depend on `serde_json`.
+
+use bevy_reflect::serde::{SerializationData, TypedReflectSerializer};
+use bevy_reflect::{
+    std_traits::ReflectDefault, NamedField, ReflectSerialize, TypeInfo, TypeRegistration,
+    TypeRegistry, UnnamedField, VariantInfo,
+};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// The JSON Schema dialect of the exported schemas.
+const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Exports [JSON Schemas] describing the JSON data of the types of a [`TypeRegistry`], as
+/// serialized by [`ReflectSerializer`] and [`TypedReflectSerializer`].
+///
+/// Every registered type is described by a definition named after its [type path], including
+/// its documentation when the `documentation` feature is enabled, and its default value if it
+/// registered [`ReflectDefault`].
+///
+/// Value types are described by their JSON type when they are primitives or strings, and accept
+/// any value otherwise, as their serialized form isn't known.
+///
+/// # Example
+/// ```rust
+/// use bevy_reflect::{Reflect, TypeRegistry};
+/// use bevy_reflect_json_schema::JsonSchemaExporter;
+/// use std::any::TypeId;
+///
+/// #[derive(Reflect)]
+/// struct Player {
+///     health: u32,
+/// }
+///
+/// let mut type_registry = TypeRegistry::default();
+/// type_registry.register::<Player>();
+///
+/// let schema = JsonSchemaExporter::new(&type_registry).export();
+/// let type_path = type_registry.get(TypeId::of::<Player>()).unwrap().type_path();
+/// assert!(schema["definitions"][type_path].is_object());
+/// ```
+///
+/// [JSON Schemas]: https://json-schema.org
+/// [`ReflectSerializer`]: bevy_reflect::serde::ReflectSerializer
+/// [type path]: bevy_reflect::TypeRegistration::type_path
+pub struct JsonSchemaExporter<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> JsonSchemaExporter<'a> {
+    /// Creates an exporter of the schemas of the types registered in `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+
+    /// Returns the schema of the data serialized by [`ReflectSerializer`], a map from the
+    /// [versioned type path] of any registered type to its value.
+    ///
+    /// [`ReflectSerializer`]: bevy_reflect::serde::ReflectSerializer
+    /// [versioned type path]: bevy_reflect::TypeRegistration::versioned_type_path
+    pub fn export(&self) -> Value {
+        let mut registrations = self
+            .registry
+            .iter()
+            .filter(|registration| is_serializable(registration))
+            .collect::<Vec<_>>();
+        registrations.sort_by_key(|registration| registration.type_path());
+        let entries = registrations
+            .into_iter()
+            .map(|registration| {
+                let key = registration.versioned_type_path();
+                json!({
+                    "properties": { key.as_ref(): self.reference(registration.type_id()) },
+                    "required": [key],
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "$schema": SCHEMA_DIALECT,
+            "type": "object",
+            "minProperties": 1,
+            "maxProperties": 1,
+            "oneOf": entries,
+            "definitions": self.definitions(),
+        })
+    }
+
+    /// Returns the schema of the data serialized by [`TypedReflectSerializer`] for values of
+    /// the type of `registration`.
+    pub fn export_type(&self, registration: &TypeRegistration) -> Value {
+        json!({
+            "$schema": SCHEMA_DIALECT,
+            "$ref": definition_ref(registration.type_path()),
+            "definitions": self.definitions(),
+        })
+    }
+
+    /// Returns the definitions of every registered type, by type path.
+    fn definitions(&self) -> Map<String, Value> {
+        self.registry
+            .iter()
+            .map(|registration| {
+                (
+                    registration.type_path().to_string(),
+                    self.type_schema(registration),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the schema of the values of the type of `registration`.
+    fn type_schema(&self, registration: &TypeRegistration) -> Value {
+        let type_info = registration.type_info();
+        let mut schema = match type_info {
+            TypeInfo::Struct(info) => {
+                let fields = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_ignored_field(registration, *index));
+                self.struct_schema(fields.map(|(_, field)| field))
+            }
+            TypeInfo::TupleStruct(info) => {
+                let fields = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| !is_ignored_field(registration, *index));
+                self.tuple_schema(fields.map(|(_, field)| field))
+            }
+            TypeInfo::Tuple(info) => self.tuple_schema(info.iter()),
+            TypeInfo::List(info) => json!({
+                "type": "array",
+                "items": self.reference(info.item_type_id()),
+            }),
+            TypeInfo::Array(info) => json!({
+                "type": "array",
+                "items": self.reference(info.item_type_id()),
+                "minItems": info.capacity(),
+                "maxItems": info.capacity(),
+            }),
+            TypeInfo::Map(info) => json!({
+                "type": "object",
+                "additionalProperties": self.reference(info.value_type_id()),
+            }),
+            TypeInfo::Enum(info) if info.type_name().starts_with("core::option::Option") => {
+                let some = match info.variant("Some") {
+                    Some(VariantInfo::Tuple(variant)) => variant
+                        .field_at(0)
+                        .map(|field| self.reference(field.type_id())),
+                    _ => None,
+                };
+                json!({ "anyOf": [{ "type": "null" }, some.unwrap_or_else(|| json!({}))] })
+            }
+            TypeInfo::Enum(info) => {
+                let variants = info
+                    .iter()
+                    .map(|variant| self.variant_schema(variant))
+                    .collect::<Vec<_>>();
+                json!({ "oneOf": variants })
+            }
+            TypeInfo::Value(_) => value_schema(registration.type_id()),
+            TypeInfo::Dynamic(_) => json!({}),
+        };
+
+        let schema_map = schema.as_object_mut().unwrap();
+        schema_map.insert("title".into(), registration.short_name().into());
+        #[cfg(feature = "documentation")]
+        insert_docs(schema_map, type_info.docs());
+        if let Some(default) = registration.data::<ReflectDefault>() {
+            let default = default.default();
+            if let Ok(default) =
+                serde_json::to_value(TypedReflectSerializer::new(&*default, self.registry))
+            {
+                schema_map.insert("default".into(), default);
+            }
+        }
+        schema
+    }
+
+    /// Returns the schema of a struct, or of the data of a struct variant.
+    fn struct_schema<'f>(&self, fields: impl Iterator<Item = &'f NamedField>) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            #[allow(unused_mut)]
+            let mut field_schema = self.reference(field.type_id());
+            #[cfg(feature = "documentation")]
+            if let Some(docs) = field.docs() {
+                // Keywords next to `$ref` are ignored by draft 7, so the reference is wrapped.
+                field_schema = json!({ "allOf": [field_schema] });
+                insert_docs(field_schema.as_object_mut().unwrap(), Some(docs));
+            }
+            properties.insert(field.name().to_string(), field_schema);
+            required.push(field.name());
+        }
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Returns the schema of a tuple or tuple struct, or of the data of a tuple variant.
+    fn tuple_schema<'f>(&self, fields: impl Iterator<Item = &'f UnnamedField>) -> Value {
+        let items = fields
+            .map(|field| self.reference(field.type_id()))
+            .collect::<Vec<_>>();
+        json!({
+            "type": "array",
+            "minItems": items.len(),
+            "items": items,
+            "additionalItems": false,
+        })
+    }
+
+    /// Returns the schema of a variant of an enum, which is serialized as the name of unit
+    /// variants, or as a map from the name of the variant to its data.
+    fn variant_schema(&self, variant: &VariantInfo) -> Value {
+        let data = match variant {
+            VariantInfo::Unit(_) => json!({ "const": variant.name() }),
+            VariantInfo::Struct(info) => self.struct_schema(info.iter()),
+            VariantInfo::Tuple(info) if info.field_len() == 1 => {
+                self.reference(info.field_at(0).unwrap().type_id())
+            }
+            VariantInfo::Tuple(info) => self.tuple_schema(info.iter()),
+        };
+        #[allow(unused_mut)]
+        let mut schema = match variant {
+            VariantInfo::Unit(_) => data,
+            _ => json!({
+                "type": "object",
+                "properties": { variant.name(): data },
+                "required": [variant.name()],
+                "additionalProperties": false,
+            }),
+        };
+        #[cfg(feature = "documentation")]
+        insert_docs(schema.as_object_mut().unwrap(), variant.docs());
+        schema
+    }
+
+    /// Returns a reference to the definition of the type with the given [`TypeId`], or a schema
+    /// accepting any value if the type isn't registered.
+    fn reference(&self, type_id: TypeId) -> Value {
+        match self.registry.get(type_id) {
+            Some(registration) => json!({ "$ref": definition_ref(registration.type_path()) }),
+            None => json!({}),
+        }
+    }
+}
+
+/// Returns `true` if values of the type of `registration` can be serialized.
+fn is_serializable(registration: &TypeRegistration) -> bool {
+    match registration.type_info() {
+        TypeInfo::Value(_) => registration.data::<ReflectSerialize>().is_some(),
+        TypeInfo::Dynamic(_) => false,
+        _ => true,
+    }
+}
+
+/// Returns `true` if the field at `index` of the type of `registration` isn't serialized.
+fn is_ignored_field(registration: &TypeRegistration, index: usize) -> bool {
+    matches!(
+        registration.data::<SerializationData>(),
+        Some(data) if data.is_ignored_field(index)
+    )
+}
+
+/// Returns the schema of the serialized values of primitives and strings, or a schema accepting
+/// any value for other value types.
+fn value_schema(type_id: TypeId) -> Value {
+    macro_rules! is_any {
+        ($($ty:ty),*) => {
+            false $(|| type_id == TypeId::of::<$ty>())*
+        };
+    }
+
+    if is_any!(bool) {
+        json!({ "type": "boolean" })
+    } else if is_any!(u8, u16, u32, u64, u128, usize) {
+        json!({ "type": "integer", "minimum": 0 })
+    } else if is_any!(i8, i16, i32, i64, i128, isize) {
+        json!({ "type": "integer" })
+    } else if is_any!(f32, f64) {
+        json!({ "type": "number" })
+    } else if is_any!(char) {
+        json!({ "type": "string", "minLength": 1, "maxLength": 1 })
+    } else if is_any!(String, &'static str, Cow<'static, str>, PathBuf) {
+        json!({ "type": "string" })
+    } else {
+        json!({})
+    }
+}
+
+/// Returns the reference to the definition of the type with the given type path.
+///
+/// The path is escaped as a JSON pointer, and percent-encoded to be a valid URI fragment.
+fn definition_ref(type_path: &str) -> String {
+    let pointer = type_path.replace('~', "~0").replace('/', "~1");
+    let mut reference = String::from("#/definitions/");
+    for byte in pointer.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~:@!$&'()*+,;=/?".contains(&byte) {
+            reference.push(byte as char);
+        } else {
+            reference.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    reference
+}
+
+#[cfg(feature = "documentation")]
+fn insert_docs(schema: &mut Map<String, Value>, docs: Option<&str>) {
+    if let Some(docs) = docs {
+        let docs = docs.lines().map(str::trim).collect::<Vec<_>>().join("\n");
+        schema.insert("description".into(), docs.trim().into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonSchemaExporter;
+    use bevy_reflect::serde::{ReflectSerializer, TypedReflectSerializer};
+    use bevy_reflect::std_traits::ReflectDefault;
+    use bevy_reflect::{FromReflect, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use jsonschema::JSONSchema;
+
+    /// A player.
+    #[derive(Reflect, Default)]
+    #[reflect(Default)]
+    struct Player {
+        name: String,
+        health: u32,
+        #[reflect(skip_serializing)]
+        cached: f32,
+        position: (f32, f32),
+        inventory: Vec<Item>,
+        stats: HashMap<String, i32>,
+        companion: Option<String>,
+        slots: [u8; 2],
+        state: State,
+    }
+
+    #[derive(Reflect, FromReflect, Default)]
+    struct Item(String, u8);
+
+    #[derive(Reflect, Default)]
+    enum State {
+        #[default]
+        Idle,
+        Walking(f32),
+        Attacking {
+            target: u32,
+        },
+        Stunned(f32, f32),
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Item>();
+        registry.register::<State>();
+        registry.register::<String>();
+        registry.register::<u32>();
+        registry.register::<i32>();
+        registry.register::<u8>();
+        registry.register::<f32>();
+        registry.register::<(f32, f32)>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, i32>>();
+        registry.register::<[u8; 2]>();
+        registry.register::<Option<String>>();
+        registry
+    }
+
+    #[test]
+    fn schema_validates_serialized_values() {
+        let registry = registry();
+        let exporter = JsonSchemaExporter::new(&registry);
+        let schema = exporter.export();
+        let compiled = JSONSchema::compile(&schema).unwrap();
+
+        let player = Player {
+            name: "Ferris".into(),
+            health: 10,
+            cached: 1.0,
+            position: (1.0, 2.0),
+            inventory: vec![Item("sword".into(), 1), Item("shield".into(), 2)],
+            stats: [("strength".to_string(), 3)].into_iter().collect(),
+            companion: None,
+            slots: [1, 2],
+            state: State::Attacking { target: 4 },
+        };
+        let states = [
+            State::Idle,
+            State::Walking(1.5),
+            State::Attacking { target: 1 },
+            State::Stunned(1.0, 2.0),
+        ];
+        for value in std::iter::once(&player as &dyn Reflect)
+            .chain(states.iter().map(|state| state as &dyn Reflect))
+        {
+            let json = serde_json::to_value(ReflectSerializer::new(value, &registry)).unwrap();
+            assert!(compiled.is_valid(&json), "{json} is invalid");
+        }
+
+        let player_schema = exporter.export_type(
+            registry
+                .get_with_name(std::any::type_name::<Player>())
+                .unwrap(),
+        );
+        let compiled = JSONSchema::compile(&player_schema).unwrap();
+        let json = serde_json::to_value(TypedReflectSerializer::new(&player, &registry)).unwrap();
+        assert!(compiled.is_valid(&json));
+
+        let mut invalid = json.clone();
+        invalid["health"] = (-1).into();
+        assert!(!compiled.is_valid(&invalid));
+        let mut invalid = json.clone();
+        invalid["cached"] = 1.0.into();
+        assert!(!compiled.is_valid(&invalid));
+        let mut invalid = json;
+        invalid["state"] = "Running".into();
+        assert!(!compiled.is_valid(&invalid));
+
+        // The default value is serialized in the schema.
+        let definition = &schema["definitions"][std::any::type_name::<Player>()];
+        assert_eq!(definition["default"]["health"], 0);
+        assert_eq!(definition["title"], "Player");
+        #[cfg(feature = "documentation")]
+        assert_eq!(definition["description"], "A player.");
+    }
+}
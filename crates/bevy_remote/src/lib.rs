@@ -0,0 +1,536 @@
+#![allow(clippy::type_complexity)]
+#![warn(missing_docs)]
+
+//! This crate serves a [JSON-RPC 2.0](https://www.jsonrpc.org/specification) protocol to inspect
+//! and edit a running app from external tools.
+//!
+//! The [`RemotePlugin`] listens for clients on a local socket. Clients either keep a TCP
+//! connection open and exchange messages, one per line, or send each request in the body of an
+//! HTTP `POST` request. The requests are processed by [`process_remote_requests`], an exclusive
+//! system running in [`Last`].
+//!
+//! The builtin [methods] list entities, get, insert and remove their components, get resources
+//! and watch the changes of components. Components and resources are identified by their
+//! [type path](bevy_reflect::TypeRegistration::type_path), must be registered in the
+//! [`AppTypeRegistry`] with [`ReflectComponent`](bevy_ecs::reflect::ReflectComponent) or
+//! [`ReflectResource`](bevy_ecs::reflect::ReflectResource), and their values are serialized
+//! with reflection. Entities are identified by [`Entity::to_bits`].
+//!
+//! # Example
+//! A request and its response:
+//! ```text
+//! {"jsonrpc":"2.0","id":1,"method":"bevy/get","params":{"entity":4294967296,"components":["bevy_transform::components::transform::Transform"]}}
+//! {"jsonrpc":"2.0","id":1,"result":{"bevy_transform::components::transform::Transform":{"translation":[0.0,1.0,0.0],...}}}
+//! ```
+
+pub mod methods;
+mod server;
+mod watch;
+
+pub use server::RemoteServer;
+
+use bevy_app::{App, AppTypeRegistry, Last, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_log::error;
+use methods::{RemoteMethods, WATCH_METHOD};
+use server::{response, RemoteMessage};
+use std::net::{Ipv4Addr, SocketAddr};
+use thiserror::Error;
+use watch::RemoteWatches;
+
+/// The port the [`RemotePlugin`] listens at by default, on [`Ipv4Addr::LOCALHOST`].
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// Serves the remote protocol, see the [crate documentation](crate).
+///
+/// If the server can't listen at its address, an error is logged and no request is served.
+pub struct RemotePlugin {
+    address: SocketAddr,
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        Self {
+            address: (Ipv4Addr::LOCALHOST, DEFAULT_PORT).into(),
+        }
+    }
+}
+
+impl RemotePlugin {
+    /// Sets the address to listen at, `127.0.0.1:15702` by default.
+    ///
+    /// Use port `0` to listen at any free port, found with [`RemoteServer::local_addr`].
+    pub fn with_address(mut self, address: impl Into<SocketAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        match RemoteServer::bind(self.address) {
+            Ok(server) => {
+                app.insert_resource(server);
+            }
+            Err(err) => error!(
+                "failed to serve the remote protocol at {}: {err}",
+                self.address
+            ),
+        }
+        app.init_resource::<AppTypeRegistry>()
+            .init_resource::<RemoteMethods>()
+            .init_resource::<RemoteWatches>()
+            .add_systems(Last, process_remote_requests);
+    }
+}
+
+/// An error serving a remote request.
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    /// The request isn't valid JSON.
+    #[error("parse error: {0}")]
+    Parse(serde_json::Error),
+    /// The request isn't a valid JSON-RPC request.
+    #[error("invalid request: {0}")]
+    InvalidRequest(serde_json::Error),
+    /// No method has the requested name.
+    #[error("method `{0}` not found")]
+    MethodNotFound(String),
+    /// The params of the request are invalid.
+    #[error("invalid params: {0}")]
+    InvalidParams(String),
+    /// The entity doesn't exist.
+    #[error("entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    /// No component is registered with the type path.
+    #[error("no component is registered with type path `{0}`")]
+    UnregisteredComponent(String),
+    /// The entity doesn't have the component.
+    #[error("entity {entity:?} has no component `{component}`")]
+    MissingComponent {
+        /// The entity.
+        entity: Entity,
+        /// The type path of the component.
+        component: String,
+    },
+    /// No resource is registered with the type path.
+    #[error("no resource is registered with type path `{0}`")]
+    UnregisteredResource(String),
+    /// The resource doesn't exist in the world.
+    #[error("resource `{0}` does not exist")]
+    MissingResource(String),
+    /// A value couldn't be serialized.
+    #[error("failed to serialize `{type_path}`: {error}")]
+    Serialize {
+        /// The type path of the value.
+        type_path: String,
+        /// The serialization error.
+        error: serde_json::Error,
+    },
+}
+
+impl RemoteError {
+    /// Returns the JSON-RPC error code of this error.
+    ///
+    /// Errors specific to the app have codes from `-32000` down.
+    pub fn code(&self) -> i64 {
+        match self {
+            RemoteError::Parse(_) => -32700,
+            RemoteError::InvalidRequest(_) => -32600,
+            RemoteError::MethodNotFound(_) => -32601,
+            RemoteError::InvalidParams(_) => -32602,
+            RemoteError::Serialize { .. } => -32603,
+            RemoteError::NoSuchEntity(_) => -32000,
+            RemoteError::UnregisteredComponent(_) => -32001,
+            RemoteError::MissingComponent { .. } => -32002,
+            RemoteError::UnregisteredResource(_) => -32003,
+            RemoteError::MissingResource(_) => -32004,
+        }
+    }
+}
+
+/// Serves the requests received by the [`RemoteServer`] since the last run, then notifies the
+/// clients of the changes of the components they watch.
+pub fn process_remote_requests(world: &mut World) {
+    let Some(server) = world.get_resource::<RemoteServer>() else {
+        return;
+    };
+    let messages: Vec<RemoteMessage> = server.receiver().try_iter().collect();
+    for RemoteMessage { request, sender } in messages {
+        let result = if request.method == WATCH_METHOD {
+            watch::watch(world, request.params, &sender)
+        } else {
+            match world.resource::<RemoteMethods>().get(&request.method) {
+                Some(method) => method(world, request.params),
+                None => Err(RemoteError::MethodNotFound(request.method)),
+            }
+        };
+        if let Some(id) = request.id {
+            // The client may have disconnected.
+            let _ = sender.send(response(id, result));
+        }
+    }
+    watch::send_watched_changes(world);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_reflect::Reflect;
+    use crossbeam_channel::Receiver;
+    use serde_json::{json, Value};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        value: f32,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name {
+        name: String,
+    }
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Score(u32);
+
+    struct Client {
+        stream: TcpStream,
+        lines: Receiver<Value>,
+        next_id: u64,
+    }
+
+    impl Client {
+        fn connect(app: &App) -> Self {
+            let address = app.world.resource::<RemoteServer>().local_addr();
+            let stream = TcpStream::connect(address).unwrap();
+            let reader = BufReader::new(stream.try_clone().unwrap());
+            let (sender, lines) = crossbeam_channel::unbounded();
+            thread::spawn(move || {
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    if sender.send(serde_json::from_str(&line).unwrap()).is_err() {
+                        break;
+                    }
+                }
+            });
+            Self {
+                stream,
+                lines,
+                next_id: 0,
+            }
+        }
+
+        fn send(&mut self, message: &str) {
+            writeln!(self.stream, "{message}").unwrap();
+        }
+
+        fn receive(&self, app: &mut App) -> Value {
+            receive(app, &self.lines)
+        }
+
+        /// Sends a request and returns its response.
+        fn request(&mut self, app: &mut App, method: &str, params: Value) -> Value {
+            self.next_id += 1;
+            let request =
+                json!({ "jsonrpc": "2.0", "id": self.next_id, "method": method, "params": params });
+            self.send(&request.to_string());
+            let response = self.receive(app);
+            assert_eq!(response["id"], self.next_id);
+            response
+        }
+    }
+
+    /// Updates the app until a message is received.
+    fn receive(app: &mut App, messages: &Receiver<Value>) -> Value {
+        for _ in 0..1000 {
+            app.update();
+            if let Ok(message) = messages.try_recv() {
+                return message;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        panic!("no message received");
+    }
+
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.register_type::<Health>()
+            .register_type::<Name>()
+            .register_type::<Score>()
+            .insert_resource(Score(3))
+            .add_plugin(RemotePlugin::default().with_address((Ipv4Addr::LOCALHOST, 0)));
+        let entity = app.world.spawn(Health { value: 10.0 }).id();
+        (app, entity)
+    }
+
+    fn type_path<T: 'static>() -> &'static str {
+        std::any::type_name::<T>()
+    }
+
+    #[test]
+    fn edit_components() {
+        let (mut app, entity) = setup();
+        let mut client = Client::connect(&app);
+        let bits = entity.to_bits();
+
+        let response = client.request(
+            &mut app,
+            "bevy/list",
+            json!({ "components": [type_path::<Health>()] }),
+        );
+        assert_eq!(
+            response["result"],
+            json!([{ "entity": bits, "components": [type_path::<Health>()] }])
+        );
+
+        let response = client.request(
+            &mut app,
+            "bevy/get",
+            json!({ "entity": bits, "components": [type_path::<Health>()] }),
+        );
+        assert_eq!(
+            response["result"],
+            json!({ type_path::<Health>(): { "value": 10.0 } })
+        );
+
+        client.request(
+            &mut app,
+            "bevy/insert",
+            json!({
+                "entity": bits,
+                "components": {
+                    type_path::<Health>(): { "value": 5.0 },
+                    type_path::<Name>(): { "name": "player" },
+                },
+            }),
+        );
+        assert_eq!(
+            app.world.get::<Health>(entity),
+            Some(&Health { value: 5.0 })
+        );
+        assert_eq!(
+            app.world.get::<Name>(entity).unwrap().name,
+            "player".to_string()
+        );
+
+        client.request(
+            &mut app,
+            "bevy/remove",
+            json!({ "entity": bits, "components": [type_path::<Health>()] }),
+        );
+        assert!(app.world.get::<Health>(entity).is_none());
+
+        let response = client.request(
+            &mut app,
+            "bevy/spawn",
+            json!({ "components": { type_path::<Health>(): { "value": 1.0 } } }),
+        );
+        let spawned = Entity::from_bits(response["result"]["entity"].as_u64().unwrap());
+        assert_eq!(
+            app.world.get::<Health>(spawned),
+            Some(&Health { value: 1.0 })
+        );
+
+        client.request(
+            &mut app,
+            "bevy/despawn",
+            json!({ "entity": spawned.to_bits() }),
+        );
+        assert!(app.world.get_entity(spawned).is_none());
+
+        let response = client.request(
+            &mut app,
+            "bevy/get_resource",
+            json!({ "resource": type_path::<Score>() }),
+        );
+        assert_eq!(response["result"], json!([3]));
+    }
+
+    #[test]
+    fn errors() {
+        let (mut app, entity) = setup();
+        let mut client = Client::connect(&app);
+
+        client.send("{ not json");
+        let response = client.receive(&mut app);
+        assert_eq!(response["error"]["code"], -32700);
+        assert_eq!(response["id"], Value::Null);
+
+        let response = client.request(&mut app, "bevy/unknown", Value::Null);
+        assert_eq!(response["error"]["code"], -32601);
+
+        let response = client.request(&mut app, "bevy/get", json!({ "entity": "player" }));
+        assert_eq!(response["error"]["code"], -32602);
+
+        let response = client.request(
+            &mut app,
+            "bevy/get",
+            json!({ "entity": entity.to_bits(), "components": [type_path::<Name>()] }),
+        );
+        assert_eq!(response["error"]["code"], -32002);
+
+        let response = client.request(
+            &mut app,
+            "bevy/insert",
+            json!({ "entity": entity.to_bits(), "components": { type_path::<Health>(): "full" } }),
+        );
+        assert_eq!(response["error"]["code"], -32602);
+        assert_eq!(
+            app.world.get::<Health>(entity),
+            Some(&Health { value: 10.0 })
+        );
+
+        app.world.despawn(entity);
+        let response = client.request(
+            &mut app,
+            "bevy/get",
+            json!({ "entity": entity.to_bits(), "components": [] }),
+        );
+        assert_eq!(response["error"]["code"], -32000);
+    }
+
+    #[test]
+    fn watch_changes() {
+        let (mut app, entity) = setup();
+        let mut client = Client::connect(&app);
+
+        let response = client.request(
+            &mut app,
+            "bevy/watch",
+            json!({ "entity": entity.to_bits(), "component": type_path::<Health>() }),
+        );
+        assert_eq!(response["result"], json!({ "value": 10.0 }));
+
+        // Unchanged components aren't notified.
+        app.update();
+        assert!(client.lines.try_recv().is_err());
+
+        app.world.get_mut::<Health>(entity).unwrap().value = 7.0;
+        let notification = client.receive(&mut app);
+        assert_eq!(notification["method"], "bevy/changed");
+        assert_eq!(
+            notification["params"],
+            json!({
+                "entity": entity.to_bits(),
+                "component": type_path::<Health>(),
+                "value": { "value": 7.0 },
+            })
+        );
+
+        app.world.entity_mut(entity).remove::<Health>();
+        let notification = client.receive(&mut app);
+        assert_eq!(notification["params"]["value"], Value::Null);
+
+        app.world.entity_mut(entity).insert(Health { value: 1.0 });
+        // The watch ended with the removal.
+        app.update();
+        assert!(client.lines.try_recv().is_err());
+    }
+
+    #[test]
+    fn http_request() {
+        let (mut app, entity) = setup();
+        let address = app.world.resource::<RemoteServer>().local_addr();
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": "health",
+            "method": "bevy/get",
+            "params": { "entity": entity.to_bits(), "components": [type_path::<Health>()] },
+        })
+        .to_string();
+
+        let (sender, responses) = crossbeam_channel::unbounded();
+        thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\nHost: {address}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            let (head, body) = response.split_once("\r\n\r\n").unwrap();
+            assert!(head.starts_with("HTTP/1.1 200 OK"));
+            sender.send(serde_json::from_str(body).unwrap()).unwrap();
+        });
+
+        let response = receive(&mut app, &responses);
+        assert_eq!(response["id"], "health");
+        assert_eq!(
+            response["result"],
+            json!({ type_path::<Health>(): { "value": 10.0 } })
+        );
+
+        // Bodies over the limit are rejected without being read.
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            usize::MAX
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 413 Payload Too Large"));
+    }
+
+    #[test]
+    fn http_request_from_foreign_site() {
+        let (app, _) = setup();
+        let address = app.world.resource::<RemoteServer>().local_addr();
+        let request = |headers: &str| {
+            let mut stream = TcpStream::connect(address).unwrap();
+            write!(
+                stream,
+                "POST / HTTP/1.1\r\n{headers}Content-Length: 0\r\n\r\n"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        for headers in [
+            "Host: example.com\r\n".to_string(),
+            format!("Host: {address}\r\nOrigin: https://example.com\r\n"),
+            format!("Host: {address}\r\nOrigin: null\r\n"),
+        ] {
+            assert!(request(&headers).starts_with("HTTP/1.1 403 Forbidden"));
+        }
+        // The server stops reading at the limit, so closing the connection may reset it before
+        // the response is read.
+        let mut stream = TcpStream::connect(address).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nX-Padding: {}\r\n\r\n",
+            "a".repeat(16 * 1024)
+        )
+        .unwrap();
+        let mut response = String::new();
+        if stream.read_to_string(&mut response).is_ok() {
+            assert!(response.starts_with("HTTP/1.1 431"));
+        }
+    }
+
+    #[test]
+    fn too_long_line() {
+        let (app, _) = setup();
+        let address = app.world.resource::<RemoteServer>().local_addr();
+        let mut stream = TcpStream::connect(address).unwrap();
+        // The server closes the connection once the limit is reached, so the write can fail.
+        let _ = stream.write_all(&vec![b'['; 17 * 1024 * 1024]);
+        let mut response = Vec::new();
+        let _ = stream.read_to_end(&mut response);
+        assert!(response.is_empty());
+    }
+}
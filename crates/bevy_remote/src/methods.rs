@@ -0,0 +1,352 @@
+//! The methods of the remote protocol.
+
+use crate::RemoteError;
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    prelude::*,
+    reflect::{ReflectComponent, ReflectResource},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::HashMap;
+use serde::{de::DeserializeOwned, de::DeserializeSeed, Deserialize};
+use serde_json::{json, Map, Value};
+
+/// Lists the entities and the components they have.
+///
+/// Params: `{ "components"?: [type path] }`, only listing the entities having all the given
+/// components.
+///
+/// Result: `[{ "entity": entity, "components": [type path] }]`.
+pub const LIST_METHOD: &str = "bevy/list";
+
+/// Gets the values of components of an entity.
+///
+/// Params: `{ "entity": entity, "components": [type path] }`.
+///
+/// Result: `{ type path: value }`.
+pub const GET_METHOD: &str = "bevy/get";
+
+/// Inserts components into an entity, or sets their values if the entity already has them.
+///
+/// Params: `{ "entity": entity, "components": { type path: value } }`.
+///
+/// Result: `null`.
+pub const INSERT_METHOD: &str = "bevy/insert";
+
+/// Removes components from an entity.
+///
+/// Params: `{ "entity": entity, "components": [type path] }`.
+///
+/// Result: `null`.
+pub const REMOVE_METHOD: &str = "bevy/remove";
+
+/// Spawns an entity with the given components.
+///
+/// Params: `{ "components"?: { type path: value } }`.
+///
+/// Result: `{ "entity": entity }`.
+pub const SPAWN_METHOD: &str = "bevy/spawn";
+
+/// Despawns an entity.
+///
+/// Params: `{ "entity": entity }`.
+///
+/// Result: `null`.
+pub const DESPAWN_METHOD: &str = "bevy/despawn";
+
+/// Gets the value of a resource.
+///
+/// Params: `{ "resource": type path }`.
+///
+/// Result: the value of the resource.
+pub const GET_RESOURCE_METHOD: &str = "bevy/get_resource";
+
+/// Watches the changes of a component of an entity.
+///
+/// Params: `{ "entity": entity, "component": type path }`.
+///
+/// Result: the current value of the component. Each time the component changes, a
+/// [`CHANGED_NOTIFICATION`] is then sent to the client, until the component is removed or the
+/// client disconnects.
+pub const WATCH_METHOD: &str = "bevy/watch";
+
+/// The notification sent to clients when a watched component changes.
+///
+/// Params: `{ "entity": entity, "component": type path, "value": value }`, where the value is
+/// `null` if the component was removed.
+pub const CHANGED_NOTIFICATION: &str = "bevy/changed";
+
+/// A method of the remote protocol, getting the params of a request and returning its result.
+pub type RemoteMethod = fn(&mut World, Value) -> Result<Value, RemoteError>;
+
+/// The methods the remote protocol serves, by name.
+///
+/// This holds the builtin methods by default, and custom methods can be inserted.
+/// Entities are identified by [`Entity::to_bits`], and components and resources by their
+/// [type path](bevy_reflect::TypeRegistration::type_path).
+///
+/// [`WATCH_METHOD`] is served separately, since its results are sent over time.
+#[derive(Resource)]
+pub struct RemoteMethods(HashMap<String, RemoteMethod>);
+
+impl Default for RemoteMethods {
+    fn default() -> Self {
+        let mut methods = Self(HashMap::default());
+        methods.insert(LIST_METHOD, list);
+        methods.insert(GET_METHOD, get);
+        methods.insert(INSERT_METHOD, insert);
+        methods.insert(REMOVE_METHOD, remove);
+        methods.insert(SPAWN_METHOD, spawn);
+        methods.insert(DESPAWN_METHOD, despawn);
+        methods.insert(GET_RESOURCE_METHOD, get_resource);
+        methods
+    }
+}
+
+impl RemoteMethods {
+    /// Adds a method, replacing any method with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, method: RemoteMethod) {
+        self.0.insert(name.into(), method);
+    }
+
+    /// Returns the method with the given name.
+    pub fn get(&self, name: &str) -> Option<RemoteMethod> {
+        self.0.get(name).copied()
+    }
+}
+
+#[derive(Deserialize)]
+struct ListParams {
+    #[serde(default)]
+    components: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct GetParams {
+    entity: u64,
+    components: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct InsertParams {
+    entity: u64,
+    components: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct SpawnParams {
+    #[serde(default)]
+    components: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct DespawnParams {
+    entity: u64,
+}
+
+#[derive(Deserialize)]
+struct GetResourceParams {
+    resource: String,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct WatchParams {
+    pub entity: u64,
+    pub component: String,
+}
+
+/// Deserializes the params of a request, missing params being an empty object.
+pub(crate) fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RemoteError> {
+    let params = match params {
+        Value::Null => Value::Object(Map::new()),
+        params => params,
+    };
+    serde_json::from_value(params).map_err(|error| RemoteError::InvalidParams(error.to_string()))
+}
+
+/// Returns the registration of the component with the given type path.
+pub(crate) fn component_registration<'a>(
+    registry: &'a TypeRegistry,
+    type_path: &str,
+) -> Result<(&'a TypeRegistration, &'a ReflectComponent), RemoteError> {
+    registry
+        .get_with_type_path(type_path)
+        .and_then(|registration| {
+            registration
+                .data::<ReflectComponent>()
+                .map(|reflect_component| (registration, reflect_component))
+        })
+        .ok_or_else(|| RemoteError::UnregisteredComponent(type_path.to_string()))
+}
+
+/// Serializes a reflected value into JSON.
+pub(crate) fn serialize(
+    value: &dyn Reflect,
+    registry: &TypeRegistry,
+) -> Result<Value, RemoteError> {
+    serde_json::to_value(TypedReflectSerializer::new(value, registry)).map_err(|error| {
+        RemoteError::Serialize {
+            type_path: registry.type_path_of(value).to_string(),
+            error,
+        }
+    })
+}
+
+/// Deserializes a value of the registered type from JSON.
+fn deserialize(
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+    value: Value,
+) -> Result<Box<dyn Reflect>, RemoteError> {
+    TypedReflectDeserializer::new(registration, registry)
+        .deserialize(value)
+        .map_err(|error| {
+            RemoteError::InvalidParams(format!(
+                "invalid value for `{}`: {error}",
+                registration.type_path()
+            ))
+        })
+}
+
+/// Deserializes the given components, to be inserted into an entity.
+fn deserialize_components(
+    registry: &TypeRegistry,
+    components: Map<String, Value>,
+) -> Result<Vec<(&ReflectComponent, Box<dyn Reflect>)>, RemoteError> {
+    components
+        .into_iter()
+        .map(|(type_path, value)| {
+            let (registration, reflect_component) = component_registration(registry, &type_path)?;
+            Ok((
+                reflect_component,
+                deserialize(registration, registry, value)?,
+            ))
+        })
+        .collect()
+}
+
+fn list(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let ListParams { components } = parse_params(params)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut filter = Vec::with_capacity(components.len());
+    for type_path in &components {
+        let (registration, _) = component_registration(&registry, type_path)?;
+        match world.components().get_id(registration.type_id()) {
+            Some(component_id) => filter.push(component_id),
+            // No entity ever had the component.
+            None => return Ok(Value::Array(Vec::new())),
+        }
+    }
+
+    let entities = world
+        .iter_entities()
+        .filter(|entity| filter.iter().all(|&id| entity.contains_id(id)))
+        .map(|entity| {
+            let components: Vec<&str> = entity
+                .archetype()
+                .components()
+                .filter_map(|id| world.components().get_info(id))
+                .map(|info| {
+                    info.type_id()
+                        .and_then(|type_id| registry.get(type_id))
+                        .map_or(info.name(), |registration| registration.type_path())
+                })
+                .collect();
+            json!({ "entity": entity.id().to_bits(), "components": components })
+        })
+        .collect();
+    Ok(Value::Array(entities))
+}
+
+fn get(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let GetParams { entity, components } = parse_params(params)?;
+    let entity = Entity::from_bits(entity);
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(RemoteError::NoSuchEntity(entity))?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut values = Map::new();
+    for type_path in components {
+        let (_, reflect_component) = component_registration(&registry, &type_path)?;
+        let value =
+            reflect_component
+                .reflect(entity_ref)
+                .ok_or_else(|| RemoteError::MissingComponent {
+                    entity,
+                    component: type_path.clone(),
+                })?;
+        values.insert(type_path, serialize(value, &registry)?);
+    }
+    Ok(Value::Object(values))
+}
+
+fn insert(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let InsertParams { entity, components } = parse_params(params)?;
+    let entity = Entity::from_bits(entity);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = deserialize_components(&registry, components)?;
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .ok_or(RemoteError::NoSuchEntity(entity))?;
+    for (reflect_component, value) in components {
+        reflect_component.apply_or_insert(&mut entity_mut, &*value);
+    }
+    Ok(Value::Null)
+}
+
+fn remove(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let GetParams { entity, components } = parse_params(params)?;
+    let entity = Entity::from_bits(entity);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = components
+        .iter()
+        .map(|type_path| component_registration(&registry, type_path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .ok_or(RemoteError::NoSuchEntity(entity))?;
+    for (_, reflect_component) in components {
+        reflect_component.remove(&mut entity_mut);
+    }
+    Ok(Value::Null)
+}
+
+fn spawn(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let SpawnParams { components } = parse_params(params)?;
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let components = deserialize_components(&registry, components)?;
+    let mut entity_mut = world.spawn_empty();
+    for (reflect_component, value) in components {
+        reflect_component.insert(&mut entity_mut, &*value);
+    }
+    Ok(json!({ "entity": entity_mut.id().to_bits() }))
+}
+
+fn despawn(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let DespawnParams { entity } = parse_params(params)?;
+    let entity = Entity::from_bits(entity);
+    if world.despawn(entity) {
+        Ok(Value::Null)
+    } else {
+        Err(RemoteError::NoSuchEntity(entity))
+    }
+}
+
+fn get_resource(world: &mut World, params: Value) -> Result<Value, RemoteError> {
+    let GetResourceParams { resource } = parse_params(params)?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect_resource = registry
+        .get_with_type_path(&resource)
+        .and_then(|registration| registration.data::<ReflectResource>())
+        .ok_or_else(|| RemoteError::UnregisteredResource(resource.clone()))?;
+    let value = reflect_resource
+        .reflect(world)
+        .ok_or(RemoteError::MissingResource(resource))?;
+    serialize(value, &registry)
+}
@@ -0,0 +1,359 @@
+//! The server receiving remote requests on its own threads and forwarding them to the app.
+
+use crate::RemoteError;
+use bevy_ecs::system::Resource;
+use bevy_log::{debug, warn};
+use crossbeam_channel::{Receiver, Sender};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{IpAddr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+/// How long the listener thread sleeps when there is no connection to accept.
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The largest message the server accepts, as a line or as the body of an HTTP request, in bytes.
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// The largest header line of an HTTP request the server accepts, in bytes.
+const MAX_HTTP_HEADER_LEN: usize = 8 * 1024;
+
+/// The largest number of headers of an HTTP request the server accepts.
+const MAX_HTTP_HEADERS: usize = 64;
+
+/// The largest number of connections served at the same time, each on its own thread.
+const MAX_CONNECTIONS: usize = 64;
+
+/// A JSON-RPC request received from a client.
+#[derive(Debug, Deserialize)]
+pub(crate) struct RemoteRequest {
+    /// The id of the request, or `None` for notifications, which get no response.
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A request, along with the channel sending messages back to the client that sent it.
+pub(crate) struct RemoteMessage {
+    pub request: RemoteRequest,
+    pub sender: Sender<Value>,
+}
+
+/// The server listening for remote clients, added by the [`RemotePlugin`](crate::RemotePlugin).
+///
+/// Connections are served on their own threads, and their requests are forwarded to
+/// [`process_remote_requests`](crate::process_remote_requests). The threads stop when this
+/// resource is dropped.
+///
+/// Clients either keep a TCP connection open and exchange JSON-RPC messages, one per line, or
+/// send each request in the body of an HTTP `POST` request.
+///
+/// Messages are limited to 16 MiB, and at most 64 connections are served at the same time,
+/// further connections being closed right away. To keep web pages from sending requests to the
+/// server through the browser, HTTP requests are rejected if their `Host` header is a domain
+/// name other than `localhost`, or if they have an `Origin` header that isn't on `localhost`
+/// or a loopback address.
+#[derive(Resource)]
+pub struct RemoteServer {
+    local_addr: SocketAddr,
+    receiver: Receiver<RemoteMessage>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl RemoteServer {
+    /// Starts listening for clients at `address`.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let listener_shutdown = shutdown.clone();
+        thread::Builder::new()
+            .name("remote server".to_string())
+            .spawn(move || listen(listener, sender, listener_shutdown))?;
+        Ok(Self {
+            local_addr,
+            receiver,
+            shutdown,
+        })
+    }
+
+    /// Returns the address the server listens at.
+    ///
+    /// This is the address to connect to when the server was bound to port `0`.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub(crate) fn receiver(&self) -> &Receiver<RemoteMessage> {
+        &self.receiver
+    }
+}
+
+impl Drop for RemoteServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Builds the response to the request with the given `id`.
+pub(crate) fn response(id: Value, result: Result<Value, RemoteError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": error.code(), "message": error.to_string() },
+        }),
+    }
+}
+
+fn listen(listener: TcpListener, sender: Sender<RemoteMessage>, shutdown: Arc<AtomicBool>) {
+    let connections = Arc::new(AtomicUsize::new(0));
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                if connections.load(Ordering::Relaxed) >= MAX_CONNECTIONS {
+                    warn!("too many remote connections, closing the connection of {peer}");
+                    continue;
+                }
+                let connection = ConnectionGuard::new(connections.clone());
+                let sender = sender.clone();
+                let spawned = thread::Builder::new()
+                    .name(format!("remote connection {peer}"))
+                    .spawn(move || {
+                        let _connection = connection;
+                        if let Err(error) = serve_connection(stream, &sender) {
+                            debug!("remote connection with {peer} failed: {error}");
+                        }
+                    });
+                if let Err(error) = spawned {
+                    warn!("failed to spawn the thread of remote connection {peer}: {error}");
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+            Err(error) => {
+                warn!("failed to accept a remote connection: {error}");
+                thread::sleep(ACCEPT_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Counts a connection as served for as long as it is alive.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    fn new(connections: Arc<AtomicUsize>) -> Self {
+        connections.fetch_add(1, Ordering::Relaxed);
+        Self(connections)
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Reads a line into `line` like [`BufRead::read_line`], failing if it is longer than `limit`
+/// bytes.
+fn read_line(reader: &mut impl BufRead, line: &mut String, limit: usize) -> io::Result<usize> {
+    let read = reader.take(limit as u64).read_line(line)?;
+    if read == limit && !line.ends_with('\n') {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long"));
+    }
+    Ok(read)
+}
+
+fn serve_connection(stream: TcpStream, sender: &Sender<RemoteMessage>) -> io::Result<()> {
+    // Accepted streams may inherit the non-blocking mode of the listener on some platforms.
+    stream.set_nonblocking(false)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    if read_line(&mut reader, &mut line, MAX_MESSAGE_LEN)? == 0 {
+        return Ok(());
+    }
+    if line.starts_with("POST ") {
+        return serve_http(reader, stream, sender);
+    }
+
+    let (response_sender, responses) = crossbeam_channel::unbounded::<Value>();
+    let mut writer = stream;
+    thread::Builder::new()
+        .name("remote connection writer".to_string())
+        .spawn(move || {
+            for message in responses {
+                if writeln!(writer, "{message}").is_err() {
+                    break;
+                }
+            }
+        })?;
+
+    loop {
+        if !line.trim().is_empty()
+            && !forward_request(line.as_bytes(), sender, response_sender.clone())
+        {
+            break;
+        }
+        line.clear();
+        if read_line(&mut reader, &mut line, MAX_MESSAGE_LEN)? == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Serves a single request sent as the body of an HTTP `POST` request.
+///
+/// The request line has already been read from `reader`.
+fn serve_http(
+    mut reader: BufReader<TcpStream>,
+    mut stream: TcpStream,
+    sender: &Sender<RemoteMessage>,
+) -> io::Result<()> {
+    let mut content_length = 0;
+    let mut allowed = true;
+    let mut line = String::new();
+    for headers in 0.. {
+        line.clear();
+        let read = match read_line(&mut reader, &mut line, MAX_HTTP_HEADER_LEN) {
+            Ok(read) if headers < MAX_HTTP_HEADERS => read,
+            Ok(_) | Err(_) => {
+                return write!(
+                    stream,
+                    "HTTP/1.1 431 Request Header Fields Too Large\r\nConnection: close\r\n\r\n"
+                );
+            }
+        };
+        if read == 0 {
+            return Ok(());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            let (name, value) = (name.trim(), value.trim());
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.parse().map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "invalid Content-Length")
+                })?;
+            } else if name.eq_ignore_ascii_case("host") {
+                allowed &= is_allowed_host(value);
+            } else if name.eq_ignore_ascii_case("origin") {
+                allowed &= is_allowed_origin(value);
+            }
+        }
+    }
+    if !allowed {
+        return write!(
+            stream,
+            "HTTP/1.1 403 Forbidden\r\nConnection: close\r\n\r\n"
+        );
+    }
+    if content_length > MAX_MESSAGE_LEN {
+        return write!(
+            stream,
+            "HTTP/1.1 413 Payload Too Large\r\nConnection: close\r\n\r\n"
+        );
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let (response_sender, responses) = crossbeam_channel::unbounded();
+    forward_request(&body, sender, response_sender);
+    // Notifications drop the sender without responding.
+    match responses.recv() {
+        Ok(response) => {
+            let response = response.to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+                response.len()
+            )
+        }
+        Err(_) => write!(
+            stream,
+            "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n"
+        ),
+    }
+}
+
+/// Returns the host name or IP address of a `host[:port]` pair, without the brackets of IPv6
+/// addresses.
+fn host_name(host: &str) -> &str {
+    let name = match host.rsplit_once(':') {
+        Some((name, port))
+            if !name.is_empty() && port.bytes().all(|byte| byte.is_ascii_digit()) =>
+        {
+            name
+        }
+        _ => host,
+    };
+    name.strip_prefix('[')
+        .and_then(|name| name.strip_suffix(']'))
+        .unwrap_or(name)
+}
+
+/// Returns whether the `Host` header of a request can't have been sent by a web page through a
+/// domain name resolving to this machine, to protect against DNS rebinding.
+fn is_allowed_host(host: &str) -> bool {
+    let name = host_name(host);
+    name.eq_ignore_ascii_case("localhost") || name.parse::<IpAddr>().is_ok()
+}
+
+/// Returns whether a request with the given `Origin` header was sent by a page served from this
+/// machine, and not by any web page the user visits.
+fn is_allowed_origin(origin: &str) -> bool {
+    let Some((_scheme, host)) = origin.split_once("://") else {
+        // Opaque origins, like `null`, can be any page.
+        return false;
+    };
+    let name = host_name(host);
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false)
+}
+
+/// Parses `request` and forwards it to the app, or sends back an error if it isn't a valid
+/// request.
+///
+/// Returns `false` if the server was dropped.
+fn forward_request(
+    request: &[u8],
+    sender: &Sender<RemoteMessage>,
+    response_sender: Sender<Value>,
+) -> bool {
+    let request = serde_json::from_slice(request)
+        .map_err(RemoteError::Parse)
+        .and_then(|request| serde_json::from_value(request).map_err(RemoteError::InvalidRequest));
+    match request {
+        Ok(request) => sender
+            .send(RemoteMessage {
+                request,
+                sender: response_sender,
+            })
+            .is_ok(),
+        Err(error) => {
+            // The id of invalid requests can't be known.
+            let _ = response_sender.send(response(Value::Null, Err(error)));
+            true
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Watching the changes of components for remote clients.
+
+use crate::{
+    methods::{component_registration, parse_params, serialize, WatchParams, CHANGED_NOTIFICATION},
+    RemoteError,
+};
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    component::{ComponentId, Tick},
+    prelude::*,
+    reflect::ReflectComponent,
+};
+use bevy_log::warn;
+use crossbeam_channel::Sender;
+use serde_json::{json, Value};
+
+struct Watch {
+    entity: Entity,
+    component: String,
+    component_id: ComponentId,
+    reflect_component: ReflectComponent,
+    last_run: Tick,
+    sender: Sender<Value>,
+}
+
+/// The components watched by remote clients.
+#[derive(Resource, Default)]
+pub(crate) struct RemoteWatches(Vec<Watch>);
+
+/// Serves [`WATCH_METHOD`](crate::methods::WATCH_METHOD), sending the changes to `sender`.
+pub(crate) fn watch(
+    world: &mut World,
+    params: Value,
+    sender: &Sender<Value>,
+) -> Result<Value, RemoteError> {
+    let WatchParams { entity, component } = parse_params(params)?;
+    let entity = Entity::from_bits(entity);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (registration, reflect_component) = component_registration(&registry, &component)?;
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or(RemoteError::NoSuchEntity(entity))?;
+    let missing_component = || RemoteError::MissingComponent {
+        entity,
+        component: component.clone(),
+    };
+    let value = reflect_component
+        .reflect(entity_ref)
+        .ok_or_else(missing_component)?;
+    let value = serialize(value, &registry)?;
+    let component_id = world
+        .components()
+        .get_id(registration.type_id())
+        .ok_or_else(missing_component)?;
+
+    // Changes made from now on get a newer tick.
+    let last_run = world.increment_change_tick();
+    world.resource_mut::<RemoteWatches>().0.push(Watch {
+        entity,
+        component,
+        component_id,
+        reflect_component: reflect_component.clone(),
+        last_run,
+        sender: sender.clone(),
+    });
+    Ok(value)
+}
+
+/// Notifies the clients of the changes of the components they watch.
+///
+/// Watches end when the component is removed, or when the client disconnects.
+pub(crate) fn send_watched_changes(world: &mut World) {
+    if world.resource::<RemoteWatches>().0.is_empty() {
+        return;
+    }
+    let this_run = world.increment_change_tick();
+    world.resource_scope(|world, mut watches: Mut<RemoteWatches>| {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        watches.0.retain_mut(|watch| {
+            let entity = world.get_entity(watch.entity);
+            let ticks = entity.and_then(|entity| entity.get_change_ticks_by_id(watch.component_id));
+            let value = match (entity, ticks) {
+                (Some(entity), Some(ticks)) => {
+                    if !ticks.is_changed(watch.last_run, this_run) {
+                        return true;
+                    }
+                    let value = watch.reflect_component.reflect(entity).unwrap();
+                    match serialize(value, &registry) {
+                        Ok(value) => value,
+                        Err(error) => {
+                            warn!("stopped watching a remote component: {error}");
+                            return false;
+                        }
+                    }
+                }
+                _ => Value::Null,
+            };
+            watch.last_run = this_run;
+
+            let removed = value.is_null();
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": CHANGED_NOTIFICATION,
+                "params": {
+                    "entity": watch.entity.to_bits(),
+                    "component": watch.component,
+                    "value": value,
+                },
+            });
+            watch.sender.send(notification).is_ok() && !removed
+        });
+    });
+}
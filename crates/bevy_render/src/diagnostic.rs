@@ -0,0 +1,249 @@
+//! Diagnostics measuring the cost of the nodes of the render graph.
+
+use crate::{
+    renderer::{RenderGraphMeasurements, RenderGraphProfiler, RenderMeasurementKind},
+    RenderApp,
+};
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH};
+use bevy_ecs::{entity::Entities, prelude::*};
+use bevy_utils::HashMap;
+
+/// Adds diagnostics measuring each node of the render graph, per view for the nodes of
+/// view sub graphs.
+///
+/// The time spent encoding each node on the CPU is always measured, so the diagnostics are
+/// available on any adapter. When supported, the time spent on the GPU and the pipeline statistics
+/// of the render passes are measured too, see [`RenderGraphProfiler`]. The id of each diagnostic
+/// can be found with [`RenderDiagnostics::diagnostic_id`].
+///
+/// This plugin must be added after the [`RenderPlugin`](crate::RenderPlugin), and does nothing if
+/// there is no render app.
+pub struct RenderDiagnosticsPlugin {
+    /// The number of measurements kept by each diagnostic, to compute rolling averages.
+    pub max_history_length: usize,
+    /// The maximum number of nodes, and of render passes, measured on the GPU each frame.
+    pub max_gpu_queries: u32,
+}
+
+impl Default for RenderDiagnosticsPlugin {
+    fn default() -> Self {
+        RenderDiagnosticsPlugin {
+            max_history_length: 120,
+            max_gpu_queries: 256,
+        }
+    }
+}
+
+impl Plugin for RenderDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        let profiler = RenderGraphProfiler::new(self.max_gpu_queries);
+        let measurements = profiler.measurements();
+        render_app.insert_resource(profiler);
+
+        app.insert_resource(RenderDiagnostics {
+            measurements,
+            diagnostic_ids: HashMap::default(),
+            max_history_length: self.max_history_length,
+        })
+        .add_systems(Update, RenderDiagnostics::diagnostic_system);
+    }
+}
+
+/// The diagnostics added by the [`RenderDiagnosticsPlugin`].
+#[derive(Resource)]
+pub struct RenderDiagnostics {
+    measurements: RenderGraphMeasurements,
+    diagnostic_ids: HashMap<(String, Option<Entity>, RenderMeasurementKind), DiagnosticId>,
+    max_history_length: usize,
+}
+
+impl RenderDiagnostics {
+    /// Returns the id of the diagnostic measuring `kind` for a node, if it was measured since the
+    /// plugin was added.
+    ///
+    /// Nodes of sub graphs are named `sub_graph/node`, and nodes without a name are named after
+    /// their type. Views are the entities of the main world that the views were extracted from.
+    pub fn diagnostic_id(
+        &self,
+        node: &str,
+        view: Option<Entity>,
+        kind: RenderMeasurementKind,
+    ) -> Option<DiagnosticId> {
+        self.diagnostic_ids
+            .get(&(node.to_string(), view, kind))
+            .copied()
+    }
+
+    /// Adds the measurements taken by the [`RenderGraphProfiler`] to the [`Diagnostics`].
+    ///
+    /// Nodes running several times in a frame get a single measurement, the sum of their runs.
+    /// The diagnostics of views that were despawned are removed.
+    pub fn diagnostic_system(
+        mut render_diagnostics: ResMut<RenderDiagnostics>,
+        mut diagnostics: ResMut<Diagnostics>,
+        entities: &Entities,
+    ) {
+        for frame in render_diagnostics.measurements.drain() {
+            let mut values: HashMap<DiagnosticId, f64> = HashMap::default();
+            for measurement in frame {
+                let key = (
+                    measurement.node.into_owned(),
+                    measurement.view,
+                    measurement.kind,
+                );
+                let id = render_diagnostics.diagnostic_id_or_add(key, &mut diagnostics);
+                *values.entry(id).or_default() += measurement.value;
+            }
+            for (id, value) in values {
+                diagnostics.add_measurement(id, || value);
+            }
+        }
+
+        render_diagnostics
+            .diagnostic_ids
+            .retain(|(_, view, _), id| match view {
+                Some(view) if !entities.contains(*view) => {
+                    diagnostics.remove(*id);
+                    false
+                }
+                _ => true,
+            });
+    }
+
+    fn diagnostic_id_or_add(
+        &mut self,
+        key: (String, Option<Entity>, RenderMeasurementKind),
+        diagnostics: &mut Diagnostics,
+    ) -> DiagnosticId {
+        if let Some(id) = self.diagnostic_ids.get(&key) {
+            return *id;
+        }
+        let (node, view, kind) = &key;
+        let name = match view {
+            Some(view) => format!("{node} {view:?} {kind}"),
+            None => format!("{node} {kind}"),
+        };
+        // Keep the end of long names, which tells the view and what is measured.
+        let skipped = name
+            .chars()
+            .count()
+            .saturating_sub(MAX_DIAGNOSTIC_NAME_WIDTH);
+        let name: String = name.chars().skip(skipped).collect();
+        let id = DiagnosticId::default();
+        diagnostics
+            .add(Diagnostic::new(id, name, self.max_history_length).with_suffix(kind.suffix()));
+        self.diagnostic_ids.insert(key, id);
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderDiagnostics;
+    use crate::renderer::{RenderGraphMeasurement, RenderGraphMeasurements, RenderMeasurementKind};
+    use bevy_diagnostic::Diagnostics;
+    use bevy_ecs::prelude::*;
+    use bevy_utils::HashMap;
+
+    #[test]
+    fn publish_measurements() {
+        let mut world = World::new();
+        let measurements = RenderGraphMeasurements::default();
+        world.init_resource::<Diagnostics>();
+        world.insert_resource(RenderDiagnostics {
+            measurements: measurements.clone(),
+            diagnostic_ids: HashMap::default(),
+            max_history_length: 10,
+        });
+        let mut schedule = Schedule::new();
+        schedule.add_systems(RenderDiagnostics::diagnostic_system);
+
+        let view = world.spawn_empty().id();
+        let measurement = |node: &'static str, view, kind, value| RenderGraphMeasurement {
+            node: node.into(),
+            view,
+            kind,
+            value,
+        };
+        measurements.push(vec![
+            measurement("ui", None, RenderMeasurementKind::CpuTime, 1.0),
+            measurement(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::CpuTime,
+                2.0,
+            ),
+            measurement(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::CpuTime,
+                0.5,
+            ),
+        ]);
+        measurements.push(vec![measurement(
+            "core_3d/main_pass",
+            Some(view),
+            RenderMeasurementKind::GpuTime,
+            4.0,
+        )]);
+        schedule.run(&mut world);
+
+        let render_diagnostics = world.resource::<RenderDiagnostics>();
+        let diagnostics = world.resource::<Diagnostics>();
+        let value = |node, view, kind| {
+            let id = render_diagnostics.diagnostic_id(node, view, kind).unwrap();
+            let diagnostic = diagnostics.get(id).unwrap();
+            (diagnostic.name.to_string(), diagnostic.value().unwrap())
+        };
+        assert_eq!(
+            value("ui", None, RenderMeasurementKind::CpuTime),
+            ("ui cpu".to_string(), 1.0)
+        );
+        assert_eq!(
+            value(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::CpuTime
+            ),
+            (format!("core_3d/main_pass {view:?} cpu"), 2.5)
+        );
+        assert_eq!(
+            value(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::GpuTime
+            ),
+            (format!("core_3d/main_pass {view:?} gpu"), 4.0)
+        );
+        assert!(render_diagnostics
+            .diagnostic_id("core_3d/main_pass", None, RenderMeasurementKind::CpuTime)
+            .is_none());
+
+        // The diagnostics of despawned views are removed.
+        let id = render_diagnostics
+            .diagnostic_id(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::CpuTime,
+            )
+            .unwrap();
+        world.despawn(view);
+        schedule.run(&mut world);
+        let render_diagnostics = world.resource::<RenderDiagnostics>();
+        assert!(render_diagnostics
+            .diagnostic_id(
+                "core_3d/main_pass",
+                Some(view),
+                RenderMeasurementKind::CpuTime
+            )
+            .is_none());
+        assert!(world.resource::<Diagnostics>().get(id).is_none());
+        assert!(render_diagnostics
+            .diagnostic_id("ui", None, RenderMeasurementKind::CpuTime)
+            .is_some());
+    }
+}
@@ -1,12 +1,14 @@
 #[allow(clippy::module_inception)]
 mod mesh;
+mod ray_cast;
 /// Generation for some primitive shape meshes.
 pub mod shape;
 
 pub use mesh::*;
+pub use ray_cast::*;
 
 use crate::render_asset::RenderAssetPlugin;
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::AddAsset;
 use bevy_ecs::entity::Entity;
 
@@ -19,6 +21,8 @@ impl Plugin for MeshPlugin {
             .add_asset::<skinning::SkinnedMeshInverseBindposes>()
             .register_type::<skinning::SkinnedMesh>()
             .register_type::<Vec<Entity>>()
+            .init_resource::<MeshBvhCache>()
+            .add_systems(PostUpdate, update_mesh_bvh_cache)
             .add_plugin(RenderAssetPlugin::<Mesh>::default());
     }
 }
@@ -0,0 +1,229 @@
+use crate::mesh::Mesh;
+use bevy_math::{
+    primitives::{Aabb3d, Primitive3d, Triangle3d},
+    Ray, Vec3,
+};
+use wgpu::PrimitiveTopology;
+
+/// Maximum number of triangles in a leaf of a [`MeshBvh`].
+const MAX_LEAF_TRIANGLES: usize = 4;
+
+/// A triangle of a mesh hit by a ray.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TriangleHit {
+    /// The distance along the ray to the hit, in units of the length of its direction.
+    pub distance: f32,
+    /// The index of the triangle in the mesh, in the order of its indices.
+    pub triangle_index: usize,
+    /// The indices of the vertices of the triangle.
+    pub vertices: [usize; 3],
+    /// The barycentric coordinates of the hit, as the weights of the three vertices.
+    pub barycentric: Vec3,
+}
+
+/// A bounding volume hierarchy over the triangles of a [`Mesh`], used to ray cast against it.
+///
+/// The hierarchy is built from the positions of the mesh in its local space, and must be rebuilt
+/// whenever they change. See [`MeshBvhCache`](super::MeshBvhCache) for a cache of these built on
+/// demand.
+#[derive(Clone, Debug)]
+pub struct MeshBvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<BvhTriangle>,
+}
+
+#[derive(Clone, Debug)]
+struct BvhNode {
+    aabb: Aabb3d,
+    /// Index of the first triangle of a leaf, or of the second child of an inner node. The first
+    /// child of an inner node always directly follows it.
+    offset: usize,
+    /// Number of triangles of a leaf, `0` for inner nodes.
+    count: usize,
+}
+
+#[derive(Clone, Debug)]
+struct BvhTriangle {
+    index: usize,
+    vertices: [usize; 3],
+    shape: Triangle3d,
+}
+
+impl MeshBvh {
+    /// Builds the hierarchy of `mesh`.
+    ///
+    /// Returns `None` if the mesh doesn't use [`PrimitiveTopology::TriangleList`] or has no
+    /// [`Mesh::ATTRIBUTE_POSITION`].
+    pub fn from_mesh(mesh: &Mesh) -> Option<Self> {
+        let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+        let positions = positions
+            .iter()
+            .copied()
+            .map(Vec3::from)
+            .collect::<Vec<_>>();
+        Some(Self::new(&positions, &triangle_indices(mesh)?))
+    }
+
+    /// Builds the hierarchy of the triangles made of `positions` and `triangles`, a list of
+    /// vertex indices.
+    ///
+    /// Triangles referring to missing vertices are ignored.
+    pub fn new(positions: &[Vec3], triangles: &[[usize; 3]]) -> Self {
+        let mut triangles = triangles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, vertices)| {
+                let [a, b, c] = vertices.map(|vertex| positions.get(vertex).copied());
+                Some(BvhTriangle {
+                    index,
+                    vertices: *vertices,
+                    shape: Triangle3d::new(a?, b?, c?),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut nodes = Vec::with_capacity(2 * triangles.len() / MAX_LEAF_TRIANGLES + 1);
+        build_node(&mut nodes, &mut triangles, 0);
+        Self { nodes, triangles }
+    }
+
+    /// Returns the bounds of the whole mesh.
+    pub fn aabb(&self) -> Aabb3d {
+        self.nodes.first().map(|node| node.aabb).unwrap_or_default()
+    }
+
+    /// Returns the triangle closest to the origin of `ray` that it hits.
+    ///
+    /// Both faces of the triangles are hit.
+    pub fn cast_ray(&self, ray: &Ray) -> Option<TriangleHit> {
+        let mut nearest: Option<TriangleHit> = None;
+        let mut stack = Vec::with_capacity(32);
+        if !self.triangles.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            let Some(entry) = node.aabb.ray_cast(ray) else {
+                continue;
+            };
+            if nearest.map(|hit| entry > hit.distance).unwrap_or(false) {
+                continue;
+            }
+
+            if node.count == 0 {
+                stack.push(node.offset);
+                stack.push(index + 1);
+                continue;
+            }
+            for triangle in &self.triangles[node.offset..node.offset + node.count] {
+                let Some(hit) = cast_triangle(triangle, ray) else {
+                    continue;
+                };
+                if nearest
+                    .map(|nearest| hit.distance < nearest.distance)
+                    .unwrap_or(true)
+                {
+                    nearest = Some(hit);
+                }
+            }
+        }
+        nearest
+    }
+}
+
+/// Appends the node containing `triangles` and its descendants to `nodes`, reordering the
+/// triangles so that each leaf refers to a contiguous range of them.
+fn build_node(nodes: &mut Vec<BvhNode>, triangles: &mut [BvhTriangle], offset: usize) {
+    let index = nodes.len();
+    let aabb = triangles
+        .iter()
+        .map(|triangle| triangle.shape.aabb())
+        .reduce(|a, b| a.union(b))
+        .unwrap_or_default();
+    nodes.push(BvhNode {
+        aabb,
+        offset,
+        count: triangles.len(),
+    });
+    if triangles.len() <= MAX_LEAF_TRIANGLES {
+        return;
+    }
+
+    // Split at the median centroid along the axis where the centroids are the most spread.
+    let centroid =
+        |triangle: &BvhTriangle| (triangle.shape.a + triangle.shape.b + triangle.shape.c) / 3.0;
+    let centroids = triangles.iter().map(centroid).collect::<Vec<_>>();
+    let extent = Aabb3d::from_points(&centroids).unwrap_or_default();
+    let size = extent.max - extent.min;
+    let axis = if size.x >= size.y && size.x >= size.z {
+        0
+    } else if size.y >= size.z {
+        1
+    } else {
+        2
+    };
+    let middle = triangles.len() / 2;
+    triangles.select_nth_unstable_by(middle, |a, b| {
+        centroid(a)[axis].total_cmp(&centroid(b)[axis])
+    });
+
+    let (left, right) = triangles.split_at_mut(middle);
+    build_node(nodes, left, offset);
+    let right_index = nodes.len();
+    build_node(nodes, right, offset + middle);
+    nodes[index].offset = right_index;
+    nodes[index].count = 0;
+}
+
+fn cast_triangle(triangle: &BvhTriangle, ray: &Ray) -> Option<TriangleHit> {
+    let (distance, uv) = triangle.shape.ray_cast_barycentric(ray)?;
+    Some(TriangleHit {
+        distance,
+        triangle_index: triangle.index,
+        vertices: triangle.vertices,
+        barycentric: Vec3::new(1.0 - uv.x - uv.y, uv.x, uv.y),
+    })
+}
+
+/// Returns the vertex indices of each triangle of `mesh`, or `None` if it isn't a
+/// [`PrimitiveTopology::TriangleList`].
+pub(super) fn triangle_indices(mesh: &Mesh) -> Option<Vec<[usize; 3]>> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let indices = match mesh.indices() {
+        Some(indices) => indices.iter().collect::<Vec<_>>(),
+        None => (0..mesh.count_vertices()).collect(),
+    };
+    Some(
+        indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+            .collect(),
+    )
+}
+
+/// Returns the triangle closest to the origin of `ray` among `triangles`, without using a
+/// hierarchy.
+pub(super) fn cast_ray_brute_force(
+    positions: &[Vec3],
+    triangles: &[[usize; 3]],
+    ray: &Ray,
+) -> Option<TriangleHit> {
+    triangles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, vertices)| {
+            let [a, b, c] = vertices.map(|vertex| positions.get(vertex).copied());
+            cast_triangle(
+                &BvhTriangle {
+                    index,
+                    vertices: *vertices,
+                    shape: Triangle3d::new(a?, b?, c?),
+                },
+                ray,
+            )
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))
+}
@@ -0,0 +1,677 @@
+mod bvh;
+
+pub use bvh::*;
+
+use crate::{
+    mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Mesh, VertexAttributeValues,
+    },
+    primitives::Aabb,
+    view::{ComputedVisibility, RenderLayers},
+};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    system::{Query, Res, ResMut, Resource, SystemParam},
+};
+use bevy_math::{
+    primitives::{Aabb3d, Primitive3d},
+    Mat4, Ray, Vec2, Vec3,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// Cache of the [`MeshBvh`] of each [`Mesh`] ray cast against, built on demand.
+///
+/// Entries are dropped when their mesh is modified or removed.
+#[derive(Resource, Debug, Default)]
+pub struct MeshBvhCache {
+    bvhs: HashMap<HandleId, Option<MeshBvh>>,
+}
+
+impl MeshBvhCache {
+    /// Returns the hierarchy of `mesh`, building it if needed.
+    ///
+    /// Returns `None` if the mesh can't be ray cast against, see [`MeshBvh::from_mesh`].
+    pub fn get_or_build(&mut self, handle: &Handle<Mesh>, mesh: &Mesh) -> Option<&MeshBvh> {
+        self.bvhs
+            .entry(handle.id())
+            .or_insert_with(|| MeshBvh::from_mesh(mesh))
+            .as_ref()
+    }
+
+    /// Drops the hierarchy of the mesh with the given handle.
+    pub fn invalidate(&mut self, handle: &Handle<Mesh>) {
+        self.bvhs.remove(&handle.id());
+    }
+
+    /// Drops every hierarchy.
+    pub fn clear(&mut self) {
+        self.bvhs.clear();
+    }
+}
+
+/// Drops the cached [`MeshBvh`] of meshes that have been modified or removed.
+pub fn update_mesh_bvh_cache(
+    mut cache: ResMut<MeshBvhCache>,
+    mut events: EventReader<AssetEvent<Mesh>>,
+) {
+    for event in events.iter() {
+        match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                cache.invalidate(handle);
+            }
+            AssetEvent::Created { .. } => {}
+        }
+    }
+}
+
+/// Which entities are ray cast against, depending on their [`ComputedVisibility`].
+///
+/// Entities without [`ComputedVisibility`] are always ray cast against.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RayCastVisibility {
+    /// Ray cast against every entity.
+    Any,
+    /// Only ray cast against entities that aren't hidden, directly or by one of their ancestors.
+    #[default]
+    VisibleInHierarchy,
+    /// Only ray cast against entities that were visible in a view last frame.
+    Visible,
+}
+
+/// Settings of a [`MeshRayCast`].
+#[derive(Clone, Copy)]
+pub struct MeshRayCastSettings<'a> {
+    /// Only entities on one of these layers are ray cast against. Entities without
+    /// [`RenderLayers`] are on layer `0`.
+    pub layers: RenderLayers,
+    /// Which entities are ray cast against depending on their visibility.
+    pub visibility: RayCastVisibility,
+    /// Whether entities with a [`SkinnedMesh`] are ray cast against, in their current pose.
+    ///
+    /// Skinned meshes are skinned on the CPU for each ray cast, which is a lot more expensive
+    /// than ray casting against static meshes.
+    pub skinned: bool,
+    /// Only entities for which this returns `true` are ray cast against.
+    pub filter: &'a dyn Fn(Entity) -> bool,
+}
+
+fn any_entity(_: Entity) -> bool {
+    true
+}
+
+impl Default for MeshRayCastSettings<'static> {
+    fn default() -> Self {
+        Self {
+            layers: RenderLayers::all(),
+            visibility: RayCastVisibility::default(),
+            skinned: true,
+            filter: &any_entity,
+        }
+    }
+}
+
+impl<'a> MeshRayCastSettings<'a> {
+    /// Helper to only ray cast against entities on `layers`.
+    pub fn with_layers(mut self, layers: RenderLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    /// Helper to set which entities are ray cast against depending on their visibility.
+    pub fn with_visibility(mut self, visibility: RayCastVisibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Helper to set whether skinned meshes are ray cast against.
+    pub fn with_skinned(mut self, skinned: bool) -> Self {
+        self.skinned = skinned;
+        self
+    }
+
+    /// Helper to only ray cast against entities for which `filter` returns `true`.
+    pub fn with_filter<'b>(self, filter: &'b dyn Fn(Entity) -> bool) -> MeshRayCastSettings<'b> {
+        MeshRayCastSettings {
+            layers: self.layers,
+            visibility: self.visibility,
+            skinned: self.skinned,
+            filter,
+        }
+    }
+}
+
+/// A hit of a ray on the mesh of an entity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeshRayHit {
+    /// The hit point, in world space.
+    pub point: Vec3,
+    /// The normal of the mesh at the hit point, in world space.
+    ///
+    /// This is interpolated from [`Mesh::ATTRIBUTE_NORMAL`] if the mesh has normals, and is the
+    /// normal of the hit triangle otherwise.
+    pub normal: Vec3,
+    /// The texture coordinates of the hit point, if the mesh has [`Mesh::ATTRIBUTE_UV_0`].
+    pub uv: Option<Vec2>,
+    /// The distance along the ray to the hit, in units of the length of its direction.
+    pub distance: f32,
+    /// The index of the hit triangle in the mesh.
+    pub triangle_index: usize,
+    /// The barycentric coordinates of the hit in the triangle, as the weights of its vertices.
+    pub barycentric: Vec3,
+}
+
+/// A [`SystemParam`] to ray cast against the triangles of the meshes of entities.
+///
+/// Every entity with a [`Handle<Mesh>`] and a [`GlobalTransform`] can be hit. Static meshes are
+/// ray cast against a [`MeshBvh`] cached in the [`MeshBvhCache`], and are first tested against
+/// their [`Aabb`] if they have one.
+///
+/// ```
+/// # use bevy_math::{Ray, Vec3};
+/// # use bevy_render::mesh::{MeshRayCast, MeshRayCastSettings};
+/// fn laser_pointer(mut ray_cast: MeshRayCast) {
+///     let ray = Ray {
+///         origin: Vec3::ZERO,
+///         direction: Vec3::NEG_Z,
+///     };
+///     let settings = MeshRayCastSettings::default();
+///     if let Some((entity, hit)) = ray_cast.cast_ray_nearest(ray, &settings) {
+///         println!("{entity:?} hit at {} with normal {}", hit.point, hit.normal);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(laser_pointer);
+/// ```
+#[derive(SystemParam)]
+pub struct MeshRayCast<'w, 's> {
+    meshes: Res<'w, Assets<Mesh>>,
+    inverse_bindposes: Res<'w, Assets<SkinnedMeshInverseBindposes>>,
+    cache: ResMut<'w, MeshBvhCache>,
+    targets: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Handle<Mesh>,
+            &'static GlobalTransform,
+            Option<&'static Aabb>,
+            Option<&'static RenderLayers>,
+            Option<&'static ComputedVisibility>,
+            Option<&'static SkinnedMesh>,
+        ),
+    >,
+    joints: Query<'w, 's, &'static GlobalTransform>,
+}
+
+impl<'w, 's> MeshRayCast<'w, 's> {
+    /// Ray casts against the meshes of entities, returning every entity hit and the hit closest
+    /// to the origin of the ray on it, sorted from nearest to furthest.
+    pub fn cast_ray(
+        &mut self,
+        ray: Ray,
+        settings: &MeshRayCastSettings,
+    ) -> Vec<(Entity, MeshRayHit)> {
+        self.cast(ray, settings, false)
+    }
+
+    /// Ray casts against the meshes of entities, returning the hit closest to the origin of the
+    /// ray.
+    pub fn cast_ray_nearest(
+        &mut self,
+        ray: Ray,
+        settings: &MeshRayCastSettings,
+    ) -> Option<(Entity, MeshRayHit)> {
+        self.cast(ray, settings, true).into_iter().next()
+    }
+
+    fn cast(
+        &mut self,
+        ray: Ray,
+        settings: &MeshRayCastSettings,
+        nearest_only: bool,
+    ) -> Vec<(Entity, MeshRayHit)> {
+        let mut hits: Vec<(Entity, MeshRayHit)> = Vec::new();
+
+        // Collect the candidates with the distance at which the ray enters their bounds, so that
+        // they are tested from nearest to furthest.
+        let mut candidates = Vec::new();
+        for (entity, handle, transform, aabb, layers, visibility, skinned) in &self.targets {
+            let visible = match (settings.visibility, visibility) {
+                (RayCastVisibility::Any, _) | (_, None) => true,
+                (RayCastVisibility::VisibleInHierarchy, Some(visibility)) => {
+                    visibility.is_visible_in_hierarchy()
+                }
+                (RayCastVisibility::Visible, Some(visibility)) => visibility.is_visible(),
+            };
+            if !visible
+                || !settings
+                    .layers
+                    .intersects(&layers.copied().unwrap_or_default())
+                || (skinned.is_some() && !settings.skinned)
+                || !(settings.filter)(entity)
+            {
+                continue;
+            }
+
+            let world_to_local = transform.affine().inverse();
+            let local_ray = Ray {
+                origin: world_to_local.transform_point3(ray.origin),
+                direction: world_to_local.transform_vector3(ray.direction),
+            };
+            // Skinned meshes can move outside of their bounds.
+            let entry = match aabb {
+                Some(aabb) if skinned.is_none() => {
+                    let Some(entry) = Aabb3d::from(*aabb).ray_cast(&local_ray) else {
+                        continue;
+                    };
+                    entry
+                }
+                _ => 0.0,
+            };
+            candidates.push((entry, entity, handle, transform, skinned, local_ray));
+        }
+        candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (entry, entity, handle, transform, skinned, local_ray) in candidates {
+            if nearest_only
+                && hits
+                    .first()
+                    .map(|(_, hit)| entry > hit.distance)
+                    .unwrap_or(false)
+            {
+                break;
+            }
+            let Some(mesh) = self.meshes.get(handle) else {
+                continue;
+            };
+            let hit = match skinned {
+                Some(skinned) => {
+                    cast_skinned(&ray, mesh, skinned, &self.inverse_bindposes, &self.joints)
+                }
+                None => self
+                    .cache
+                    .get_or_build(handle, mesh)
+                    .and_then(|bvh| cast_static(&local_ray, bvh, mesh, transform.compute_matrix())),
+            };
+            let Some(hit) = hit else {
+                continue;
+            };
+            if nearest_only {
+                if hits
+                    .first()
+                    .map(|(_, nearest)| hit.distance < nearest.distance)
+                    .unwrap_or(true)
+                {
+                    hits.clear();
+                    hits.push((entity, hit));
+                }
+            } else {
+                hits.push((entity, hit));
+            }
+        }
+
+        hits.sort_by(|a, b| a.1.distance.total_cmp(&b.1.distance));
+        hits
+    }
+}
+
+fn cast_static(
+    local_ray: &Ray,
+    bvh: &MeshBvh,
+    mesh: &Mesh,
+    local_to_world: Mat4,
+) -> Option<MeshRayHit> {
+    let hit = bvh.cast_ray(local_ray)?;
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    // The hierarchy is only rebuilt once the mesh has been modified for a frame, so the mesh may
+    // no longer have the vertices of the hit triangle.
+    let [a, b, c] = triangle(positions, &hit)?.map(Vec3::from);
+    let local_normal = interpolate_normal(mesh, &hit).unwrap_or_else(|| (b - a).cross(c - a));
+    let normal_matrix = local_to_world.inverse().transpose();
+    Some(mesh_hit(
+        mesh,
+        &hit,
+        local_to_world.transform_point3(local_ray.get_point(hit.distance)),
+        normal_matrix.transform_vector3(local_normal),
+    ))
+}
+
+fn cast_skinned(
+    ray: &Ray,
+    mesh: &Mesh,
+    skinned: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joints: &Query<&GlobalTransform>,
+) -> Option<MeshRayHit> {
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let Some(VertexAttributeValues::Uint16x4(joint_indices)) =
+        mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX)
+    else {
+        return None;
+    };
+    let Some(VertexAttributeValues::Float32x4(joint_weights)) =
+        mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT)
+    else {
+        return None;
+    };
+    let inverse_bindposes = inverse_bindposes.get(&skinned.inverse_bindposes)?;
+    let joints = skinned
+        .joints
+        .iter()
+        .zip(inverse_bindposes.iter())
+        .map(|(joint, inverse_bindpose)| {
+            Some(joints.get(*joint).ok()?.compute_matrix() * *inverse_bindpose)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // Skin the vertices the same way the vertex shader does.
+    let skins = joint_indices
+        .iter()
+        .zip(joint_weights)
+        .map(|(indices, weights)| {
+            indices
+                .iter()
+                .zip(weights)
+                .filter_map(|(joint, weight)| Some(*joints.get(*joint as usize)? * *weight))
+                .fold(Mat4::ZERO, |sum, matrix| sum + matrix)
+        })
+        .collect::<Vec<_>>();
+    let positions = positions
+        .iter()
+        .zip(&skins)
+        .map(|(position, skin)| skin.transform_point3(Vec3::from(*position)))
+        .collect::<Vec<_>>();
+
+    let hit = cast_ray_brute_force(&positions, &bvh::triangle_indices(mesh)?, ray)?;
+    let [a, b, c] = hit.vertices.map(|vertex| positions[vertex]);
+    let normal = match mesh
+        .attribute(Mesh::ATTRIBUTE_NORMAL)
+        .and_then(|normals| normals.as_float3())
+    {
+        Some(normals) => hit
+            .vertices
+            .iter()
+            .zip(hit.barycentric.to_array())
+            .map(|(vertex, weight)| {
+                skins[*vertex].transform_vector3(Vec3::from(normals[*vertex])) * weight
+            })
+            .sum(),
+        None => (b - a).cross(c - a),
+    };
+    Some(mesh_hit(mesh, &hit, ray.get_point(hit.distance), normal))
+}
+
+/// Returns the values of an attribute for the vertices of the hit triangle, if the attribute has
+/// a value for each of them.
+fn triangle<T: Copy>(values: &[T], hit: &TriangleHit) -> Option<[T; 3]> {
+    let [a, b, c] = hit.vertices;
+    Some([*values.get(a)?, *values.get(b)?, *values.get(c)?])
+}
+
+fn interpolate_normal(mesh: &Mesh, hit: &TriangleHit) -> Option<Vec3> {
+    let normals = mesh.attribute(Mesh::ATTRIBUTE_NORMAL)?.as_float3()?;
+    Some(
+        triangle(normals, hit)?
+            .into_iter()
+            .zip(hit.barycentric.to_array())
+            .map(|(normal, weight)| Vec3::from(normal) * weight)
+            .sum(),
+    )
+}
+
+fn mesh_hit(mesh: &Mesh, hit: &TriangleHit, point: Vec3, normal: Vec3) -> MeshRayHit {
+    let uv = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+        Some(VertexAttributeValues::Float32x2(uvs)) => triangle(uvs, hit).map(|uvs| {
+            uvs.into_iter()
+                .zip(hit.barycentric.to_array())
+                .map(|(uv, weight)| Vec2::from(uv) * weight)
+                .sum()
+        }),
+        _ => None,
+    };
+    MeshRayHit {
+        point,
+        normal: normal.normalize_or_zero(),
+        uv,
+        distance: hit.distance,
+        triangle_index: hit.triangle_index,
+        barycentric: hit.barycentric,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{shape, skinning::SkinnedMeshInverseBindposes, Indices, MeshPlugin};
+    use bevy_app::App;
+    use bevy_asset::AssetPlugin;
+    use bevy_core::TaskPoolPlugin;
+    use bevy_ecs::system::SystemState;
+    use bevy_transform::components::Transform;
+    use wgpu::PrimitiveTopology;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(TaskPoolPlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(MeshPlugin);
+        app
+    }
+
+    fn cast(app: &mut App, ray: Ray, settings: &MeshRayCastSettings) -> Vec<(Entity, MeshRayHit)> {
+        let mut state = SystemState::<MeshRayCast>::new(&mut app.world);
+        state.get_mut(&mut app.world).cast_ray(ray, settings)
+    }
+
+    #[test]
+    fn bvh_matches_brute_force() {
+        let mesh = Mesh::from(shape::UVSphere {
+            radius: 1.0,
+            sectors: 24,
+            stacks: 16,
+        });
+        let bvh = MeshBvh::from_mesh(&mesh).unwrap();
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap()
+            .iter()
+            .copied()
+            .map(Vec3::from)
+            .collect::<Vec<_>>();
+        let triangles = bvh::triangle_indices(&mesh).unwrap();
+
+        for i in 0..64 {
+            let angle = i as f32 * 0.37;
+            let origin = Vec3::new(
+                angle.cos() * 3.0,
+                (i as f32 * 0.11).sin(),
+                angle.sin() * 3.0,
+            );
+            let target = Vec3::new((i as f32 * 0.7).sin() * 0.8, 0.2, 0.1);
+            let ray = Ray {
+                origin,
+                direction: (target - origin).normalize(),
+            };
+            let expected = cast_ray_brute_force(&positions, &triangles, &ray).unwrap();
+            let hit = bvh.cast_ray(&ray).unwrap();
+            assert_eq!(hit.triangle_index, expected.triangle_index);
+            assert!((hit.distance - expected.distance).abs() < 1e-5);
+        }
+        assert!(bvh
+            .cast_ray(&Ray {
+                origin: Vec3::new(0.0, 3.0, 0.0),
+                direction: Vec3::X,
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn cast_against_entities() {
+        let mut app = app();
+        let cube = app
+            .world
+            .resource_mut::<Assets<Mesh>>()
+            .add(shape::Cube { size: 1.0 }.into());
+        let near = app
+            .world
+            .spawn((
+                cube.clone(),
+                GlobalTransform::from(Transform::from_xyz(0.0, 0.0, -5.0)),
+                RenderLayers::layer(1),
+            ))
+            .id();
+        let far = app
+            .world
+            .spawn((
+                cube,
+                GlobalTransform::from(
+                    Transform::from_xyz(0.0, 0.0, -10.0).with_scale(Vec3::splat(2.0)),
+                ),
+            ))
+            .id();
+        let ray = Ray {
+            origin: Vec3::new(0.1, 0.2, 0.0),
+            direction: Vec3::NEG_Z,
+        };
+
+        let hits = cast(&mut app, ray, &MeshRayCastSettings::default());
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].0, near);
+        assert!((hits[0].1.distance - 4.5).abs() < 1e-5);
+        assert!((hits[0].1.point - Vec3::new(0.1, 0.2, -4.5)).length() < 1e-5);
+        assert!((hits[0].1.normal - Vec3::Z).length() < 1e-5);
+        assert!(hits[0].1.uv.is_some());
+        assert_eq!(hits[1].0, far);
+        assert!((hits[1].1.distance - 9.0).abs() < 1e-5);
+
+        let settings = MeshRayCastSettings::default().with_layers(RenderLayers::layer(0));
+        let hits = cast(&mut app, ray, &settings);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, far);
+
+        let not_far = |entity: Entity| entity != far;
+        let settings = MeshRayCastSettings::default().with_filter(&not_far);
+        let mut state = SystemState::<MeshRayCast>::new(&mut app.world);
+        let nearest = state
+            .get_mut(&mut app.world)
+            .cast_ray_nearest(ray, &settings);
+        assert_eq!(nearest.map(|(entity, _)| entity), Some(near));
+        assert!(app.world.resource::<MeshBvhCache>().bvhs.len() == 1);
+    }
+
+    #[test]
+    fn cast_against_mesh_modified_this_frame() {
+        let mut app = app();
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![
+                [-1.0, -1.0, 0.0],
+                [1.0, -1.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [2.0, -1.0, 0.0],
+                [4.0, -1.0, 0.0],
+                [3.0, 1.0, 0.0],
+            ],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 6]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 6]);
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2, 3, 4, 5])));
+        let handle = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+        app.world.spawn((
+            handle.clone(),
+            GlobalTransform::from(Transform::from_xyz(0.0, 0.0, -5.0)),
+        ));
+        let ray = Ray {
+            origin: Vec3::new(3.0, 0.0, 0.0),
+            direction: Vec3::NEG_Z,
+        };
+        let hits = cast(&mut app, ray, &MeshRayCastSettings::default());
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].1.triangle_index, 1);
+
+        // The cached hierarchy still has the second triangle until the cache is updated.
+        let mut meshes = app.world.resource_mut::<Assets<Mesh>>();
+        let mesh = meshes.get_mut(&handle).unwrap();
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[-1.0, -1.0, 0.0], [1.0, -1.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 3]);
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        assert!(cast(&mut app, ray, &MeshRayCastSettings::default()).is_empty());
+
+        app.update();
+        assert!(cast(&mut app, ray, &MeshRayCastSettings::default()).is_empty());
+        let ray = Ray {
+            origin: Vec3::new(0.0, 0.0, 0.0),
+            direction: Vec3::NEG_Z,
+        };
+        let hits = cast(&mut app, ray, &MeshRayCastSettings::default());
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].1.triangle_index, 0);
+    }
+
+    #[test]
+    fn cast_against_skinned_mesh() {
+        let mut app = app();
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[-1.0, -1.0, 0.0], [1.0, -1.0, 0.0], [0.0, 1.0, 0.0]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            VertexAttributeValues::Uint16x4(vec![[0, 0, 0, 0]; 3]),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[1.0f32, 0.0, 0.0, 0.0]; 3],
+        );
+        mesh.set_indices(Some(Indices::U16(vec![0, 1, 2])));
+        let mesh = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+        let inverse_bindposes = app
+            .world
+            .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .add(vec![Mat4::IDENTITY].into());
+
+        let joint = app
+            .world
+            .spawn(GlobalTransform::from(Transform::from_xyz(5.0, 0.0, -3.0)))
+            .id();
+        let skinned = app
+            .world
+            .spawn((
+                mesh,
+                GlobalTransform::IDENTITY,
+                SkinnedMesh {
+                    inverse_bindposes,
+                    joints: vec![joint],
+                },
+            ))
+            .id();
+
+        let ray = Ray {
+            origin: Vec3::new(5.0, 0.0, 0.0),
+            direction: Vec3::NEG_Z,
+        };
+        let hits = cast(&mut app, ray, &MeshRayCastSettings::default());
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, skinned);
+        assert!((hits[0].1.distance - 3.0).abs() < 1e-5);
+        assert_eq!(hits[0].1.normal, Vec3::Z);
+
+        let hits = cast(
+            &mut app,
+            ray,
+            &MeshRayCastSettings::default().with_skinned(false),
+        );
+        assert!(hits.is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use bevy_ecs::{component::Component, prelude::Entity, reflect::ReflectComponent};
-use bevy_math::{Mat4, Vec3, Vec3A, Vec4, Vec4Swizzles};
+use bevy_math::{primitives, Mat4, Vec3, Vec3A, Vec4, Vec4Swizzles};
 use bevy_reflect::{FromReflect, Reflect};
 use bevy_utils::HashMap;
 
@@ -59,6 +59,23 @@ impl From<Sphere> for Aabb {
     }
 }
 
+impl From<primitives::Aabb3d> for Aabb {
+    #[inline]
+    fn from(aabb: primitives::Aabb3d) -> Self {
+        Self::from_min_max(aabb.min, aabb.max)
+    }
+}
+
+impl From<Aabb> for primitives::Aabb3d {
+    #[inline]
+    fn from(aabb: Aabb) -> Self {
+        Self {
+            min: aabb.min().into(),
+            max: aabb.max().into(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Sphere {
     pub center: Vec3A,
@@ -81,6 +98,26 @@ impl Sphere {
     }
 }
 
+impl From<primitives::Sphere> for Sphere {
+    #[inline]
+    fn from(sphere: primitives::Sphere) -> Self {
+        Self {
+            center: sphere.center.into(),
+            radius: sphere.radius,
+        }
+    }
+}
+
+impl From<Sphere> for primitives::Sphere {
+    #[inline]
+    fn from(sphere: Sphere) -> Self {
+        Self {
+            center: sphere.center.into(),
+            radius: sphere.radius,
+        }
+    }
+}
+
 /// A plane defined by a unit normal and distance from the origin along the normal
 /// Any point `p` is in the plane if `n.p + d = 0`
 /// For planes defining half-spaces such as for frusta, if `n.p + d > 0` then `p` is on
@@ -397,4 +434,18 @@ mod tests {
         };
         assert!(frustum.intersects_sphere(&sphere, true));
     }
+
+    #[test]
+    fn math_primitive_conversions() {
+        let aabb = Aabb::from_min_max(Vec3::new(-1.0, 0.0, 2.0), Vec3::new(3.0, 1.0, 4.0));
+        let math_aabb = primitives::Aabb3d::from(aabb);
+        assert_eq!(math_aabb.min, Vec3::new(-1.0, 0.0, 2.0));
+        assert_eq!(math_aabb.max, Vec3::new(3.0, 1.0, 4.0));
+        let round_trip = Aabb::from(math_aabb);
+        assert_eq!(round_trip.center, aabb.center);
+        assert_eq!(round_trip.half_extents, aabb.half_extents);
+
+        let sphere = primitives::Sphere::new(Vec3::ONE, 2.0);
+        assert_eq!(primitives::Sphere::from(Sphere::from(sphere)), sphere);
+    }
 }
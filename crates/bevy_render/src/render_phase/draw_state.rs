@@ -104,6 +104,8 @@ impl DrawState {
 pub struct TrackedRenderPass<'a> {
     pass: RenderPass<'a>,
     state: DrawState,
+    /// Whether a pipeline statistics query was begun on the pass, to be ended when it is dropped.
+    pipeline_statistics_query: bool,
 }
 
 impl<'a> TrackedRenderPass<'a> {
@@ -119,9 +121,15 @@ impl<'a> TrackedRenderPass<'a> {
                 ..default()
             },
             pass,
+            pipeline_statistics_query: false,
         }
     }
 
+    /// Ends the pipeline statistics query begun on the pass when the pass is dropped.
+    pub(crate) fn end_pipeline_statistics_query_on_drop(&mut self) {
+        self.pipeline_statistics_query = true;
+    }
+
     /// Sets the active [`RenderPipeline`].
     ///
     /// Subsequent draw calls will exhibit the behavior defined by the `pipeline`.
@@ -599,3 +607,11 @@ impl<'a> TrackedRenderPass<'a> {
         self.pass.set_blend_constant(wgpu::Color::from(color));
     }
 }
+
+impl Drop for TrackedRenderPass<'_> {
+    fn drop(&mut self) {
+        if self.pipeline_statistics_query {
+            self.pass.end_pipeline_statistics_query();
+        }
+    }
+}
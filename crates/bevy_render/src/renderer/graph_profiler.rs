@@ -0,0 +1,468 @@
+use bevy_ecs::{prelude::Entity, system::Resource};
+use bevy_utils::Instant;
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoder, Features, Maintain, MapMode,
+    PipelineStatisticsTypes, QuerySet, QuerySetDescriptor, QueryType, RenderPass,
+    QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE,
+};
+
+use crate::renderer::RenderDevice;
+
+/// The number of frames whose queries can be read back at the same time.
+const MAX_FRAMES_IN_FLIGHT: usize = 3;
+
+/// The pipeline statistics recorded for each render pass, in the order the values are resolved.
+const PIPELINE_STATISTICS: PipelineStatisticsTypes =
+    PipelineStatisticsTypes::VERTEX_SHADER_INVOCATIONS
+        .union(PipelineStatisticsTypes::CLIPPER_PRIMITIVES_OUT)
+        .union(PipelineStatisticsTypes::FRAGMENT_SHADER_INVOCATIONS);
+const PIPELINE_STATISTICS_KINDS: [RenderMeasurementKind; 3] = [
+    RenderMeasurementKind::VertexShaderInvocations,
+    RenderMeasurementKind::ClipperPrimitivesOut,
+    RenderMeasurementKind::FragmentShaderInvocations,
+];
+
+/// What a [`RenderGraphMeasurement`] measures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderMeasurementKind {
+    /// The time spent encoding the commands of a node on the CPU, in milliseconds.
+    CpuTime,
+    /// The time spent executing the commands of a node on the GPU, in milliseconds.
+    ///
+    /// Requires [`Features::TIMESTAMP_QUERY`].
+    GpuTime,
+    /// The number of vertex shader invocations of the render passes of a node.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`].
+    VertexShaderInvocations,
+    /// The number of primitives output by the clipper in the render passes of a node.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`].
+    ClipperPrimitivesOut,
+    /// The number of fragment shader invocations of the render passes of a node.
+    ///
+    /// Requires [`Features::PIPELINE_STATISTICS_QUERY`].
+    FragmentShaderInvocations,
+}
+
+impl RenderMeasurementKind {
+    /// Returns the unit of the measurements, if any.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            RenderMeasurementKind::CpuTime | RenderMeasurementKind::GpuTime => "ms",
+            _ => "",
+        }
+    }
+}
+
+impl fmt::Display for RenderMeasurementKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RenderMeasurementKind::CpuTime => "cpu",
+            RenderMeasurementKind::GpuTime => "gpu",
+            RenderMeasurementKind::VertexShaderInvocations => "vertices",
+            RenderMeasurementKind::ClipperPrimitivesOut => "primitives",
+            RenderMeasurementKind::FragmentShaderInvocations => "fragments",
+        })
+    }
+}
+
+/// A measurement of a run of a render graph node.
+#[derive(Debug, Clone)]
+pub struct RenderGraphMeasurement {
+    /// The node, named `sub_graph/node` for nodes of sub graphs.
+    pub node: Cow<'static, str>,
+    /// The view the node ran for, if any.
+    pub view: Option<Entity>,
+    /// What is measured.
+    pub kind: RenderMeasurementKind,
+    /// The measured value.
+    pub value: f64,
+}
+
+/// The measurements taken by a [`RenderGraphProfiler`], grouped by frame.
+///
+/// Clones share the same measurements, so they can be read from the main world.
+#[derive(Clone, Default)]
+pub struct RenderGraphMeasurements(Arc<Mutex<Vec<Vec<RenderGraphMeasurement>>>>);
+
+impl RenderGraphMeasurements {
+    /// Adds the measurements of a frame.
+    pub fn push(&self, frame: Vec<RenderGraphMeasurement>) {
+        if !frame.is_empty() {
+            self.0.lock().unwrap().push(frame);
+        }
+    }
+
+    /// Removes and returns the measurements taken so far, grouped by frame.
+    ///
+    /// GPU measurements are read back asynchronously, so they come a few frames after the CPU
+    /// measurements of the same frame.
+    pub fn drain(&self) -> Vec<Vec<RenderGraphMeasurement>> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Measures the cost of each node the [`RenderGraph`](crate::render_graph::RenderGraph) runs,
+/// when inserted in the render world.
+///
+/// The time spent encoding each node is always measured. When the device has
+/// [`Features::TIMESTAMP_QUERY`], timestamps are written around each node to measure the time the
+/// GPU spends executing its commands. When it has [`Features::PIPELINE_STATISTICS_QUERY`], the
+/// pipeline statistics of the render passes begun through
+/// [`RenderContext::begin_tracked_render_pass`](crate::renderer::RenderContext::begin_tracked_render_pass)
+/// are collected too. These features are enabled by default when the adapter supports them,
+/// see [`WgpuSettings`](crate::settings::WgpuSettings).
+#[derive(Resource)]
+pub struct RenderGraphProfiler {
+    measurements: RenderGraphMeasurements,
+    max_nodes: u32,
+    queries: Mutex<QueryPool>,
+}
+
+#[derive(Default)]
+struct QueryPool {
+    free: Vec<QueryFrame>,
+    in_flight: VecDeque<QueryFrame>,
+    len: usize,
+}
+
+impl RenderGraphProfiler {
+    /// Creates a profiler taking GPU measurements for at most `max_nodes` nodes, and at most
+    /// `max_nodes` render passes, per frame.
+    pub fn new(max_nodes: u32) -> Self {
+        Self {
+            measurements: RenderGraphMeasurements::default(),
+            max_nodes: max_nodes.min(QUERY_SET_MAX_QUERIES / 2),
+            queries: Mutex::default(),
+        }
+    }
+
+    /// Returns a handle to the measurements taken by this profiler.
+    pub fn measurements(&self) -> RenderGraphMeasurements {
+        self.measurements.clone()
+    }
+
+    /// Reads back the GPU measurements of previous frames that are available, and starts
+    /// profiling a new frame.
+    pub(crate) fn begin_frame(&self, device: &RenderDevice, queue: &wgpu::Queue) -> FrameProfile {
+        let mut pool = self.queries.lock().unwrap();
+        if !pool.in_flight.is_empty() {
+            device.poll(Maintain::Poll);
+        }
+        let timestamp_period = queue.get_timestamp_period();
+        while let Some(mapped) = pool.in_flight.front().and_then(QueryFrame::mapped) {
+            let mut frame = pool.in_flight.pop_front().unwrap();
+            if mapped {
+                self.measurements.push(frame.read(timestamp_period));
+            }
+            frame.clear();
+            pool.free.push(frame);
+        }
+
+        let queries = pool.free.pop().or_else(|| {
+            let features = device.features();
+            let supported = features.contains(Features::TIMESTAMP_QUERY)
+                || features.contains(Features::PIPELINE_STATISTICS_QUERY);
+            (supported && pool.len < MAX_FRAMES_IN_FLIGHT).then(|| {
+                pool.len += 1;
+                QueryFrame::new(device, self.max_nodes)
+            })
+        });
+        FrameProfile {
+            queries,
+            measurements: Vec::new(),
+            current_node: None,
+        }
+    }
+
+    /// Publishes the CPU measurements of a frame, and starts reading back its GPU measurements.
+    ///
+    /// Must be called after the commands of the frame are submitted.
+    pub(crate) fn end_frame(&self, profile: FrameProfile) {
+        self.measurements.push(profile.measurements);
+        let Some(frame) = profile.queries else {
+            return;
+        };
+        let mut pool = self.queries.lock().unwrap();
+        if frame.nodes.is_empty() {
+            pool.free.push(frame);
+            return;
+        }
+        let mapped = frame.mapped.clone();
+        frame
+            .read_buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                *mapped.lock().unwrap() = Some(result.is_ok());
+            });
+        pool.in_flight.push_back(frame);
+    }
+
+    /// Drops the measurements of a frame whose commands won't be submitted, keeping its queries
+    /// for the next frames.
+    pub(crate) fn cancel_frame(&self, profile: FrameProfile) {
+        if let Some(mut frame) = profile.queries {
+            frame.clear();
+            self.queries.lock().unwrap().free.push(frame);
+        }
+    }
+}
+
+/// The queries of a frame, and the buffers their results are read back through.
+struct QueryFrame {
+    timestamps: Option<QuerySet>,
+    pipeline_statistics: Option<QuerySet>,
+    max_nodes: u32,
+    pipeline_statistics_offset: u64,
+    resolve_buffer: wgpu::Buffer,
+    read_buffer: wgpu::Buffer,
+    /// The nodes profiled in this frame, in the order of their queries.
+    nodes: Vec<(Cow<'static, str>, Option<Entity>)>,
+    /// The index of the node each render pass was begun in.
+    passes: Vec<usize>,
+    /// Set once `read_buffer` is mapped, to whether the mapping succeeded.
+    mapped: Arc<Mutex<Option<bool>>>,
+}
+
+impl QueryFrame {
+    fn new(device: &RenderDevice, max_nodes: u32) -> Self {
+        let features = device.features();
+        let timestamps = features.contains(Features::TIMESTAMP_QUERY).then(|| {
+            device.wgpu_device().create_query_set(&QuerySetDescriptor {
+                label: Some("render_graph_timestamps"),
+                ty: QueryType::Timestamp,
+                count: max_nodes * 2,
+            })
+        });
+        let pipeline_statistics =
+            features
+                .contains(Features::PIPELINE_STATISTICS_QUERY)
+                .then(|| {
+                    device.wgpu_device().create_query_set(&QuerySetDescriptor {
+                        label: Some("render_graph_pipeline_statistics"),
+                        ty: QueryType::PipelineStatistics(PIPELINE_STATISTICS),
+                        count: max_nodes,
+                    })
+                });
+
+        let timestamps_size = (max_nodes * 2 * QUERY_SIZE) as u64;
+        let pipeline_statistics_offset =
+            wgpu::util::align_to(timestamps_size, QUERY_RESOLVE_BUFFER_ALIGNMENT);
+        let pipeline_statistics_size =
+            (max_nodes * PIPELINE_STATISTICS_KINDS.len() as u32 * QUERY_SIZE) as u64;
+        let size = pipeline_statistics_offset + pipeline_statistics_size;
+        let resolve_buffer = device.wgpu_device().create_buffer(&BufferDescriptor {
+            label: Some("render_graph_query_resolve_buffer"),
+            size,
+            usage: BufferUsages::COPY_DST | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = device.wgpu_device().create_buffer(&BufferDescriptor {
+            label: Some("render_graph_query_read_buffer"),
+            size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            timestamps,
+            pipeline_statistics,
+            max_nodes,
+            pipeline_statistics_offset,
+            resolve_buffer,
+            read_buffer,
+            nodes: Vec::new(),
+            passes: Vec::new(),
+            mapped: Arc::default(),
+        }
+    }
+
+    /// Returns whether the mapping of `read_buffer` succeeded, once it is done.
+    fn mapped(&self) -> Option<bool> {
+        *self.mapped.lock().unwrap()
+    }
+
+    /// Resolves the queries of the frame and copies their results to `read_buffer`.
+    fn resolve(&self, encoder: &mut CommandEncoder) {
+        if let Some(timestamps) = &self.timestamps {
+            let count = self.nodes.len() as u32 * 2;
+            encoder.resolve_query_set(timestamps, 0..count, &self.resolve_buffer, 0);
+        }
+        if let Some(pipeline_statistics) = &self.pipeline_statistics {
+            if !self.passes.is_empty() {
+                encoder.resolve_query_set(
+                    pipeline_statistics,
+                    0..self.passes.len() as u32,
+                    &self.resolve_buffer,
+                    self.pipeline_statistics_offset,
+                );
+            }
+        }
+        encoder.copy_buffer_to_buffer(
+            &self.resolve_buffer,
+            0,
+            &self.read_buffer,
+            0,
+            self.read_buffer.size(),
+        );
+    }
+
+    /// Reads the results of the queries from the mapped `read_buffer`.
+    fn read(&self, timestamp_period: f32) -> Vec<RenderGraphMeasurement> {
+        let values: Vec<u64> = self
+            .read_buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks_exact(QUERY_SIZE as usize)
+            .map(|value| u64::from_ne_bytes(value.try_into().unwrap()))
+            .collect();
+        let mut measurements = Vec::new();
+        let mut measure = |node: usize, kind, value| {
+            let (name, view) = &self.nodes[node];
+            measurements.push(RenderGraphMeasurement {
+                node: name.clone(),
+                view: *view,
+                kind,
+                value,
+            });
+        };
+
+        if self.timestamps.is_some() {
+            for node in 0..self.nodes.len() {
+                let ticks = values[node * 2 + 1].saturating_sub(values[node * 2]);
+                let nanoseconds = ticks as f64 * timestamp_period as f64;
+                measure(
+                    node,
+                    RenderMeasurementKind::GpuTime,
+                    nanoseconds / 1_000_000.0,
+                );
+            }
+        }
+
+        if self.pipeline_statistics.is_some() {
+            let statistics =
+                &values[(self.pipeline_statistics_offset / QUERY_SIZE as u64) as usize..];
+            let mut node_statistics = vec![None; self.nodes.len()];
+            for (pass, &node) in self.passes.iter().enumerate() {
+                let totals = node_statistics[node].get_or_insert([0; 3]);
+                let pass_statistics = &statistics[pass * totals.len()..][..totals.len()];
+                for (total, value) in totals.iter_mut().zip(pass_statistics) {
+                    *total += value;
+                }
+            }
+            for (node, totals) in node_statistics.into_iter().enumerate() {
+                for (kind, total) in PIPELINE_STATISTICS_KINDS
+                    .into_iter()
+                    .zip(totals.into_iter().flatten())
+                {
+                    measure(node, kind, total as f64);
+                }
+            }
+        }
+        measurements
+    }
+
+    /// Unmaps `read_buffer` and forgets the recorded queries, to record a new frame.
+    fn clear(&mut self) {
+        if self.mapped() == Some(true) {
+            self.read_buffer.unmap();
+        }
+        *self.mapped.lock().unwrap() = None;
+        self.nodes.clear();
+        self.passes.clear();
+    }
+}
+
+/// The profiling of the frame being encoded by a [`RenderContext`](crate::renderer::RenderContext).
+pub(crate) struct FrameProfile {
+    queries: Option<QueryFrame>,
+    measurements: Vec<RenderGraphMeasurement>,
+    /// The index of the node being run in the nodes of the queries, if it is profiled on the GPU.
+    current_node: Option<usize>,
+}
+
+/// A node being profiled, to be ended with [`FrameProfile::end_node`].
+pub(crate) struct NodeProfile {
+    node: Cow<'static, str>,
+    view: Option<Entity>,
+    start: Instant,
+}
+
+impl FrameProfile {
+    /// Starts profiling a node, writing its first timestamp to `encoder`.
+    pub(crate) fn begin_node(
+        &mut self,
+        node: Cow<'static, str>,
+        view: Option<Entity>,
+        encoder: &mut CommandEncoder,
+    ) -> NodeProfile {
+        if let Some(queries) = &mut self.queries {
+            let index = queries.nodes.len();
+            if index < queries.max_nodes as usize {
+                if let Some(timestamps) = &queries.timestamps {
+                    encoder.write_timestamp(timestamps, index as u32 * 2);
+                }
+                queries.nodes.push((node.clone(), view));
+                self.current_node = Some(index);
+            }
+        }
+        NodeProfile {
+            node,
+            view,
+            start: Instant::now(),
+        }
+    }
+
+    /// Ends profiling a node, writing its last timestamp to `encoder`.
+    pub(crate) fn end_node(&mut self, node: NodeProfile, encoder: &mut CommandEncoder) {
+        self.measurements.push(RenderGraphMeasurement {
+            node: node.node,
+            view: node.view,
+            kind: RenderMeasurementKind::CpuTime,
+            value: node.start.elapsed().as_secs_f64() * 1000.0,
+        });
+        if let (Some(index), Some(queries)) = (self.current_node.take(), &self.queries) {
+            if let Some(timestamps) = &queries.timestamps {
+                encoder.write_timestamp(timestamps, index as u32 * 2 + 1);
+            }
+        }
+    }
+
+    /// Begins a pipeline statistics query on a render pass of the node being profiled.
+    ///
+    /// Returns whether a query was begun, which must then be ended before the pass is dropped.
+    pub(crate) fn begin_pass(&mut self, pass: &mut RenderPass) -> bool {
+        let (Some(node), Some(queries)) = (self.current_node, &mut self.queries) else {
+            return false;
+        };
+        let Some(pipeline_statistics) = &queries.pipeline_statistics else {
+            return false;
+        };
+        let index = queries.passes.len();
+        if index >= queries.max_nodes as usize {
+            return false;
+        }
+        pass.begin_pipeline_statistics_query(pipeline_statistics, index as u32);
+        queries.passes.push(node);
+        true
+    }
+
+    /// Returns whether queries were recorded this frame, which must then be resolved.
+    pub(crate) fn has_queries(&self) -> bool {
+        matches!(&self.queries, Some(queries) if !queries.nodes.is_empty())
+    }
+
+    /// Resolves the queries of the frame into a buffer to be read back.
+    pub(crate) fn resolve(&self, encoder: &mut CommandEncoder) {
+        if let Some(queries) = &self.queries {
+            queries.resolve(encoder);
+        }
+    }
+}
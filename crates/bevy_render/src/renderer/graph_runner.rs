@@ -1,7 +1,7 @@
 use bevy_ecs::{prelude::Entity, world::World};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
-use bevy_utils::HashMap;
+use bevy_utils::{get_short_name, HashMap};
 use smallvec::{smallvec, SmallVec};
 #[cfg(feature = "trace")]
 use std::ops::Deref;
@@ -13,7 +13,7 @@ use crate::{
         Edge, NodeId, NodeRunError, NodeState, RenderGraph, RenderGraphContext, SlotLabel,
         SlotType, SlotValue,
     },
-    renderer::{RenderContext, RenderDevice},
+    renderer::{RenderContext, RenderDevice, RenderGraphProfiler},
 };
 
 pub(crate) struct RenderGraphRunner;
@@ -59,12 +59,25 @@ impl RenderGraphRunner {
         world: &World,
     ) -> Result<(), RenderGraphRunnerError> {
         let mut render_context = RenderContext::new(render_device);
-        Self::run_graph(graph, None, &mut render_context, world, &[], None)?;
+        let profiler = world.get_resource::<RenderGraphProfiler>();
+        if let Some(profiler) = profiler {
+            render_context.begin_profile(profiler, queue);
+        }
+        if let Err(error) = Self::run_graph(graph, None, &mut render_context, world, &[], None) {
+            if let (Some(profiler), Some(profile)) = (profiler, render_context.cancel_profile()) {
+                profiler.cancel_frame(profile);
+            }
+            return Err(error);
+        }
+        let profile = render_context.finish_profile();
         {
             #[cfg(feature = "trace")]
             let _span = info_span!("submit_graph_commands").entered();
             queue.submit(render_context.finish());
         }
+        if let (Some(profiler), Some(profile)) = (profiler, profile) {
+            profiler.end_frame(profile);
+        }
         Ok(())
     }
 
@@ -184,7 +197,15 @@ impl RenderGraphRunner {
                     #[cfg(feature = "trace")]
                     let _span = info_span!("node", name = node_state.type_name).entered();
 
-                    node_state.node.run(&mut context, render_context, world)?;
+                    let node_profile = render_context.begin_node_profile(
+                        || node_path(graph_name.as_deref(), node_state),
+                        view_entity,
+                    );
+                    let result = node_state.node.run(&mut context, render_context, world);
+                    if let Some(node_profile) = node_profile {
+                        render_context.end_node_profile(node_profile);
+                    }
+                    result?;
                 }
 
                 for run_sub_graph in context.finish() {
@@ -225,3 +246,15 @@ impl RenderGraphRunner {
         Ok(())
     }
 }
+
+/// Returns the name of a node in profiling measurements, prefixed by the name of its sub graph.
+fn node_path(graph_name: Option<&str>, node_state: &NodeState) -> Cow<'static, str> {
+    let node_name = match &node_state.name {
+        Some(name) => name.clone(),
+        None => get_short_name(node_state.type_name).into(),
+    };
+    match graph_name {
+        Some(graph_name) => format!("{graph_name}/{node_name}").into(),
+        None => node_name,
+    }
+}
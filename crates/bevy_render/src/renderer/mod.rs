@@ -1,8 +1,10 @@
+mod graph_profiler;
 mod graph_runner;
 mod render_device;
 
 use bevy_derive::{Deref, DerefMut};
 use bevy_utils::tracing::{error, info, info_span};
+pub use graph_profiler::*;
 pub use graph_runner::*;
 pub use render_device::*;
 
@@ -16,7 +18,7 @@ use crate::{
 use bevy_ecs::prelude::*;
 use bevy_time::TimeSender;
 use bevy_utils::Instant;
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 use wgpu::{
     Adapter, AdapterInfo, CommandBuffer, CommandEncoder, Instance, Queue, RequestAdapterOptions,
 };
@@ -286,6 +288,7 @@ pub struct RenderContext {
     render_device: RenderDevice,
     command_encoder: Option<CommandEncoder>,
     command_buffers: Vec<CommandBuffer>,
+    profile: Option<FrameProfile>,
 }
 
 impl RenderContext {
@@ -295,6 +298,7 @@ impl RenderContext {
             render_device,
             command_encoder: None,
             command_buffers: Vec::new(),
+            profile: None,
         }
     }
 
@@ -322,8 +326,16 @@ impl RenderContext {
             self.render_device
                 .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
         });
-        let render_pass = command_encoder.begin_render_pass(&descriptor);
-        TrackedRenderPass::new(&self.render_device, render_pass)
+        let mut render_pass = command_encoder.begin_render_pass(&descriptor);
+        let pipeline_statistics_query = match &mut self.profile {
+            Some(profile) => profile.begin_pass(&mut render_pass),
+            None => false,
+        };
+        let mut tracked_pass = TrackedRenderPass::new(&self.render_device, render_pass);
+        if pipeline_statistics_query {
+            tracked_pass.end_pipeline_statistics_query_on_drop();
+        }
+        tracked_pass
     }
 
     /// Append a [`CommandBuffer`] to the queue.
@@ -336,6 +348,53 @@ impl RenderContext {
         self.command_buffers.push(command_buffer);
     }
 
+    /// Starts profiling the frame encoded by this context with `profiler`.
+    pub(crate) fn begin_profile(&mut self, profiler: &RenderGraphProfiler, queue: &Queue) {
+        self.profile = Some(profiler.begin_frame(&self.render_device, queue));
+    }
+
+    /// Starts profiling a render graph node, if the frame is profiled.
+    pub(crate) fn begin_node_profile(
+        &mut self,
+        node: impl FnOnce() -> Cow<'static, str>,
+        view: Option<Entity>,
+    ) -> Option<NodeProfile> {
+        let profile = self.profile.as_mut()?;
+        let command_encoder = self.command_encoder.get_or_insert_with(|| {
+            self.render_device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        });
+        Some(profile.begin_node(node(), view, command_encoder))
+    }
+
+    /// Ends profiling a render graph node.
+    pub(crate) fn end_node_profile(&mut self, node: NodeProfile) {
+        let Some(profile) = &mut self.profile else {
+            return;
+        };
+        let command_encoder = self.command_encoder.get_or_insert_with(|| {
+            self.render_device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        });
+        profile.end_node(node, command_encoder);
+    }
+
+    /// Resolves the queries of the profiled frame, and returns its profile to be ended once the
+    /// commands are submitted.
+    pub(crate) fn finish_profile(&mut self) -> Option<FrameProfile> {
+        let profile = self.profile.take()?;
+        if profile.has_queries() {
+            profile.resolve(self.command_encoder());
+        }
+        Some(profile)
+    }
+
+    /// Stops profiling the frame, returning its profile to be cancelled as its commands won't be
+    /// submitted.
+    pub(crate) fn cancel_profile(&mut self) -> Option<FrameProfile> {
+        self.profile.take()
+    }
+
     /// Finalizes the queue and returns the queue of [`CommandBuffer`]s.
     pub fn finish(mut self) -> Vec<CommandBuffer> {
         self.flush_encoder();
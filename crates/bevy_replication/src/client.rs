@@ -0,0 +1,301 @@
+//! The client side of replication.
+
+use crate::{
+    message::{deserialize_component, Packet, Update},
+    Channel, ClientTransport, Replicated, ReplicationError, ReplicationRules, ReplicationTick,
+};
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::EntityMap, prelude::*, reflect::ReflectMapEntities, schedule::ScheduleLabel,
+};
+use bevy_log::{error, warn};
+
+/// A message sent by the server with [`ReplicationServer::send`](crate::ReplicationServer::send).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FromServer {
+    /// The channel the message was sent on.
+    pub channel: Channel,
+    /// The message.
+    pub message: Vec<u8>,
+}
+
+/// Marks a replicated entity whose state is predicted by the client, like the avatar of the local
+/// user.
+///
+/// The replicated components of predicted entities are still overwritten by the state received
+/// from the server, which is authoritative. The [`Reconcile`] schedule then runs, to re-apply the
+/// local inputs the server had not processed yet.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Predicted;
+
+/// The schedule run by [`receive_server_updates`] after the state received from the server was
+/// applied to [`Predicted`] entities.
+///
+/// The [`Reconciliation`] resource lists these entities and the tick of the state.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Reconcile;
+
+/// The [`Predicted`] entities updated with the state of the server, available in [`Reconcile`].
+#[derive(Resource, Clone, Debug, Default)]
+pub struct Reconciliation {
+    /// The tick of the server state the entities were updated with.
+    pub tick: ReplicationTick,
+    /// The predicted entities that were updated.
+    pub entities: Vec<Entity>,
+}
+
+/// Applies the state of the [`Replicated`] entities received from a server to this [`World`].
+///
+/// Entities are spawned for the server entities, and the [`Entity`] fields of components that
+/// reflect [`MapEntities`](bevy_ecs::entity::MapEntities) are mapped to them.
+#[derive(Resource)]
+pub struct ReplicationClient {
+    transport: Box<dyn ClientTransport>,
+    entity_map: EntityMap,
+    tick: Option<ReplicationTick>,
+}
+
+impl ReplicationClient {
+    /// Creates a client receiving entities from the server of `transport`.
+    pub fn new(transport: impl ClientTransport) -> Self {
+        Self {
+            transport: Box::new(transport),
+            entity_map: EntityMap::default(),
+            tick: None,
+        }
+    }
+
+    /// Returns the map from the server entities to the entities of this client.
+    pub fn entity_map(&self) -> &EntityMap {
+        &self.entity_map
+    }
+
+    /// Returns the tick of the latest update received from the server, if any.
+    pub fn tick(&self) -> Option<ReplicationTick> {
+        self.tick
+    }
+
+    /// Sends a message to the server, received as a [`FromClient`](crate::FromClient) event.
+    pub fn send(&mut self, channel: Channel, message: Vec<u8>) {
+        self.transport.send(channel, message);
+    }
+}
+
+/// Applies the updates received by the [`ReplicationClient`], and sends the other messages as
+/// [`FromServer`] events.
+///
+/// Unreliable updates older than an update already applied are ignored. If [`Predicted`] entities
+/// were updated, the [`Reconcile`] schedule runs afterwards.
+pub fn receive_server_updates(world: &mut World) {
+    let mut reconciliation = Reconciliation::default();
+    world.resource_scope(|world, mut client: Mut<ReplicationClient>| {
+        while let Some((channel, bytes)) = client.transport.receive() {
+            match Packet::decode(&bytes) {
+                Ok(Packet::Update(update)) => {
+                    if channel == Channel::Unreliable
+                        && matches!(client.tick, Some(tick) if tick > update.tick)
+                    {
+                        continue;
+                    }
+                    reconciliation.tick = update.tick;
+                    apply_update(world, &mut client, channel, update, &mut reconciliation);
+                }
+                Ok(Packet::User(message)) => world.send_event(FromServer { channel, message }),
+                Err(err) => error!("failed to decode a message from the server: {err}"),
+            }
+        }
+    });
+
+    if !reconciliation.entities.is_empty() {
+        world.insert_resource(reconciliation);
+        // Nothing to reconcile if no system was added.
+        let _ = world.try_run_schedule(Reconcile);
+    }
+}
+
+fn apply_update(
+    world: &mut World,
+    client: &mut ReplicationClient,
+    channel: Channel,
+    update: Update,
+    reconciliation: &mut Reconciliation,
+) {
+    client.tick = Some(match client.tick {
+        Some(tick) => tick.max(update.tick),
+        None => update.tick,
+    });
+
+    // Spawn the new entities first, for the components referring to them to be mapped.
+    // Unreliable updates can't spawn entities, they could arrive after their despawn.
+    if channel == Channel::Reliable {
+        for entity_update in &update.entities {
+            let server_entity = Entity::from_bits(entity_update.entity);
+            if client.entity_map.get(server_entity).is_err() {
+                let entity = world.spawn(Replicated).id();
+                client.entity_map.insert(server_entity, entity);
+            }
+        }
+    }
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    world.resource_scope(|world, rules: Mut<ReplicationRules>| {
+        for entity_update in update.entities {
+            let Ok(entity) = client
+                .entity_map
+                .get(Entity::from_bits(entity_update.entity))
+            else {
+                continue;
+            };
+            if world.get_entity(entity).is_none() {
+                warn!("replicated entity {entity:?} was despawned by the client");
+                continue;
+            }
+
+            for (type_path, bytes) in entity_update.changed {
+                let result = registry
+                    .get_with_type_path(&type_path)
+                    .zip(rules.get_by_type_path(&registry, &type_path))
+                    .ok_or_else(|| ReplicationError::UnknownComponent(type_path.clone()))
+                    .and_then(|(registration, rule)| {
+                        let value = deserialize_component(&bytes, registration, &registry)?;
+                        rule.reflect_component
+                            .insert(&mut world.entity_mut(entity), &*value);
+                        Ok(registration.data::<ReflectMapEntities>())
+                    });
+                match result {
+                    Ok(Some(map_entities)) => {
+                        if let Err(err) =
+                            map_entities.map_specific_entities(world, &client.entity_map, &[entity])
+                        {
+                            warn!(
+                                "failed to map the entities of `{type_path}` of {entity:?}: {err}"
+                            );
+                        }
+                    }
+                    Ok(None) => {}
+                    Err(err) => error!("failed to apply `{type_path}` to {entity:?}: {err}"),
+                }
+            }
+
+            for type_path in entity_update.removed {
+                match rules.get_by_type_path(&registry, &type_path) {
+                    Some(rule) => rule.reflect_component.remove(&mut world.entity_mut(entity)),
+                    None => error!(
+                        "failed to remove `{type_path}` from {entity:?}: {}",
+                        ReplicationError::UnknownComponent(type_path.clone())
+                    ),
+                }
+            }
+
+            if world.get::<Predicted>(entity).is_some()
+                && !reconciliation.entities.contains(&entity)
+            {
+                reconciliation.entities.push(entity);
+            }
+        }
+    });
+
+    for server_entity in update.despawned {
+        if let Some(entity) = client.entity_map.remove(Entity::from_bits(server_entity)) {
+            world.despawn(entity);
+            reconciliation.entities.retain(|&other| other != entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{serialize_component, EntityUpdate},
+        AppReplicationExt, LoopbackServer, ReplicationPlugin, ServerTransport,
+    };
+    use bevy_app::App;
+    use bevy_ecs::entity::{MapEntities, MapEntitiesError};
+    use bevy_reflect::Reflect;
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn apply_packets() {
+        let mut server = LoopbackServer::default();
+        let transport = server.connector().connect();
+        let client = transport.id();
+        server.receive();
+        let mut app = App::new();
+        app.register_type::<Entity>()
+            .add_plugin(ReplicationPlugin)
+            .replicate::<Target>()
+            .insert_resource(ReplicationClient::new(transport));
+        // Offset the client entities from the server ones.
+        app.world.spawn_batch((0..3).map(|_| ()));
+
+        let target = Entity::from_raw(0);
+        let entity = Entity::from_raw(1);
+        let registry = app.world.resource::<AppTypeRegistry>().clone();
+        let mut entity_update = EntityUpdate::new(entity.to_bits());
+        entity_update.changed.push((
+            registry
+                .read()
+                .get(TypeId::of::<Target>())
+                .unwrap()
+                .type_path()
+                .to_string(),
+            serialize_component(&Target(target), &registry.read()).unwrap(),
+        ));
+        let mut update = Update::new(ReplicationTick(1));
+        update.entities.push(EntityUpdate::new(target.to_bits()));
+        update.entities.push(entity_update);
+        let bytes = Packet::Update(update).encode().unwrap();
+
+        // Malformed and truncated packets are dropped.
+        server.send(client, Channel::Reliable, vec![9, 9]);
+        server.send(client, Channel::Reliable, bytes[..bytes.len() - 1].to_vec());
+        app.update();
+        let replication = app.world.resource::<ReplicationClient>();
+        assert!(replication.entity_map().is_empty());
+        assert_eq!(replication.tick(), None);
+
+        server.send(client, Channel::Reliable, bytes);
+        app.update();
+        let entity_map = app.world.resource::<ReplicationClient>().entity_map();
+        let client_target = entity_map.get(target).unwrap();
+        let client_entity = entity_map.get(entity).unwrap();
+        assert_ne!(client_target, target);
+        assert_eq!(
+            app.world.get::<Target>(client_entity),
+            Some(&Target(client_target))
+        );
+
+        let mut update = Update::new(ReplicationTick(2));
+        update.despawned.push(target.to_bits());
+        server.send(
+            client,
+            Channel::Reliable,
+            Packet::Update(update).encode().unwrap(),
+        );
+        app.update();
+        let replication = app.world.resource::<ReplicationClient>();
+        assert!(replication.entity_map().get(target).is_err());
+        assert_eq!(replication.tick(), Some(ReplicationTick(2)));
+        assert!(app.world.get_entity(client_target).is_none());
+        assert!(app.world.get_entity(client_entity).is_some());
+    }
+}
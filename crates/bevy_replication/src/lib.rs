@@ -0,0 +1,529 @@
+#![allow(clippy::type_complexity)]
+#![warn(missing_docs)]
+
+//! This crate replicates entities from a server [`World`] to clients.
+//!
+//! Entities with the [`Replicated`] marker component are replicated, along with their components
+//! registered with [`AppReplicationExt::replicate`]. Components are serialized with reflection,
+//! and only the components changed since the previous update are sent, as detected with their
+//! change ticks.
+//!
+//! The server and clients exchange messages through a [`ServerTransport`] and a
+//! [`ClientTransport`], which carry them on [`Channel::Reliable`] or [`Channel::Unreliable`].
+//! The [`LoopbackServer`] and [`LoopbackClient`] exchange them in memory, for tests and local
+//! sessions.
+//!
+//! An app is a server when it has a [`ReplicationServer`] resource, and a client when it has a
+//! [`ReplicationClient`] resource, both served by the [`ReplicationPlugin`]. Clients can predict
+//! the state of some entities, see [`Predicted`].
+//!
+//! # Example
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::Reflect;
+//! # use bevy_replication::*;
+//! #[derive(Component, Reflect, Default)]
+//! struct Score(u32);
+//!
+//! let transport = LoopbackServer::default();
+//! let connector = transport.connector();
+//!
+//! let mut server = App::new();
+//! server
+//!     .add_plugin(ReplicationPlugin)
+//!     .replicate::<Score>()
+//!     .insert_resource(ReplicationServer::new(transport));
+//! server.world.spawn((Replicated, Score(3)));
+//!
+//! let mut client = App::new();
+//! client
+//!     .add_plugin(ReplicationPlugin)
+//!     .replicate::<Score>()
+//!     .insert_resource(ReplicationClient::new(connector.connect()));
+//!
+//! server.update();
+//! client.update();
+//! let mut scores = client.world.query::<&Score>();
+//! assert_eq!(scores.single(&client.world).0, 3);
+//! ```
+
+mod client;
+mod message;
+mod server;
+mod transport;
+
+pub use client::*;
+pub use server::*;
+pub use transport::*;
+
+use bevy_app::{App, AppTypeRegistry, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{component::ComponentId, prelude::*, reflect::ReflectComponent};
+use bevy_reflect::{FromType, GetTypeRegistration, Reflect, TypeRegistry};
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// Serves the [`ReplicationServer`] and the [`ReplicationClient`] of the app, if any.
+#[derive(Default)]
+pub struct ReplicationPlugin;
+
+impl Plugin for ReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AppTypeRegistry>()
+            .init_resource::<ReplicationRules>()
+            .register_type::<Replicated>()
+            .add_event::<ClientConnected>()
+            .add_event::<ClientDisconnected>()
+            .add_event::<FromClient>()
+            .add_event::<FromServer>()
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_client_messages.run_if(resource_exists::<ReplicationServer>()),
+                    receive_server_updates.run_if(resource_exists::<ReplicationClient>()),
+                )
+                    .in_set(ReplicationSet::Receive),
+            )
+            .add_systems(
+                PostUpdate,
+                send_server_updates
+                    .run_if(resource_exists::<ReplicationServer>())
+                    .in_set(ReplicationSet::Send),
+            );
+    }
+}
+
+/// The systems of the [`ReplicationPlugin`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReplicationSet {
+    /// Receives the messages of the transports, in [`PreUpdate`].
+    Receive,
+    /// Sends the updates of the server, in [`PostUpdate`].
+    Send,
+}
+
+/// Marks an entity replicated from the server to its clients.
+///
+/// Clients add it to the entities they spawn for the server entities.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component)]
+pub struct Replicated;
+
+/// The number of updates sent by a [`ReplicationServer`].
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub struct ReplicationTick(pub u64);
+
+/// A component replicated by the [`ReplicationPlugin`].
+#[derive(Clone)]
+pub struct ReplicationRule {
+    type_id: TypeId,
+    component_id: ComponentId,
+    reflect_component: ReflectComponent,
+    channel: Channel,
+}
+
+impl ReplicationRule {
+    /// Returns the [`TypeId`] of the component.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the [`ComponentId`] of the component.
+    pub fn component_id(&self) -> ComponentId {
+        self.component_id
+    }
+
+    /// Returns the channel the changes of the component are sent on.
+    pub fn channel(&self) -> Channel {
+        self.channel
+    }
+}
+
+/// The components replicated by the [`ReplicationPlugin`], added with
+/// [`AppReplicationExt::replicate`].
+#[derive(Resource, Default)]
+pub struct ReplicationRules {
+    rules: Vec<ReplicationRule>,
+}
+
+impl ReplicationRules {
+    /// Returns the replicated components.
+    pub fn iter(&self) -> impl Iterator<Item = &ReplicationRule> {
+        self.rules.iter()
+    }
+
+    fn get_by_component_id(&self, component_id: ComponentId) -> Option<&ReplicationRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.component_id == component_id)
+    }
+
+    fn get_by_type_path(
+        &self,
+        registry: &TypeRegistry,
+        type_path: &str,
+    ) -> Option<&ReplicationRule> {
+        let type_id = registry.get_with_type_path(type_path)?.type_id();
+        self.rules.iter().find(|rule| rule.type_id == type_id)
+    }
+}
+
+/// Adds replicated components to an [`App`].
+pub trait AppReplicationExt {
+    /// Replicates the component `C` of the [`Replicated`] entities, sending its changes on
+    /// [`Channel::Reliable`].
+    ///
+    /// The server and its clients must replicate the same components. The component is
+    /// registered in the [`AppTypeRegistry`], and identified by its
+    /// [type path](bevy_reflect::TypeRegistration::type_path).
+    fn replicate<C>(&mut self) -> &mut Self
+    where
+        C: Component + Reflect + FromWorld + GetTypeRegistration,
+    {
+        self.replicate_with_channel::<C>(Channel::Reliable)
+    }
+
+    /// Replicates the component `C` of the [`Replicated`] entities, sending its changes on
+    /// `channel`.
+    ///
+    /// The component is always sent reliably when inserted, so [`Channel::Unreliable`] only
+    /// applies to its changes.
+    fn replicate_with_channel<C>(&mut self, channel: Channel) -> &mut Self
+    where
+        C: Component + Reflect + FromWorld + GetTypeRegistration;
+}
+
+impl AppReplicationExt for App {
+    fn replicate_with_channel<C>(&mut self, channel: Channel) -> &mut Self
+    where
+        C: Component + Reflect + FromWorld + GetTypeRegistration,
+    {
+        self.register_type::<C>();
+        let component_id = self.world.init_component::<C>();
+        let mut rules = self
+            .world
+            .get_resource_or_insert_with(ReplicationRules::default);
+        rules.rules.retain(|rule| rule.type_id != TypeId::of::<C>());
+        rules.rules.push(ReplicationRule {
+            type_id: TypeId::of::<C>(),
+            component_id,
+            reflect_component: <ReflectComponent as FromType<C>>::from_type(),
+            channel,
+        });
+        self
+    }
+}
+
+/// An error replicating a component.
+#[derive(Debug, Error)]
+pub enum ReplicationError {
+    /// A message couldn't be encoded.
+    #[error("failed to encode: {0}")]
+    Encode(bincode::Error),
+    /// A message couldn't be decoded.
+    #[error("failed to decode: {0}")]
+    Decode(bincode::Error),
+    /// No replicated component has the type path.
+    #[error("no replicated component has type path `{0}`")]
+    UnknownComponent(String),
+    /// The entity doesn't have the component.
+    #[error("missing component `{0}`")]
+    MissingComponent(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::entity::{EntityMap, MapEntities, MapEntitiesError};
+    use bevy_ecs::reflect::ReflectMapEntities;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl FromWorld for Target {
+        fn from_world(_world: &mut World) -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        // Registered by the `CorePlugin` in apps.
+        app.register_type::<Entity>()
+            .add_plugin(ReplicationPlugin)
+            .replicate::<Health>()
+            .replicate_with_channel::<Position>(Channel::Unreliable)
+            .replicate::<Target>();
+        app
+    }
+
+    fn server() -> (App, LoopbackConnector) {
+        let mut app = app();
+        let transport = LoopbackServer::default();
+        let connector = transport.connector();
+        app.insert_resource(ReplicationServer::new(transport));
+        (app, connector)
+    }
+
+    fn client(transport: LoopbackClient) -> App {
+        let mut app = app();
+        app.insert_resource(ReplicationClient::new(transport));
+        app
+    }
+
+    fn client_entity(client: &App, server_entity: Entity) -> Entity {
+        client
+            .world
+            .resource::<ReplicationClient>()
+            .entity_map()
+            .get(server_entity)
+            .unwrap()
+    }
+
+    fn update(server: &mut App, client: &mut App) {
+        server.update();
+        client.update();
+    }
+
+    #[test]
+    fn replicate_changes() {
+        let (mut server, connector) = server();
+        let mut client = client(connector.connect());
+        let entity = server.world.spawn((Replicated, Health(10.0))).id();
+        update(&mut server, &mut client);
+
+        let client_entity = client_entity(&client, entity);
+        assert!(client.world.get::<Replicated>(client_entity).is_some());
+        assert_eq!(
+            client.world.get::<Health>(client_entity),
+            Some(&Health(10.0))
+        );
+
+        server.world.get_mut::<Health>(entity).unwrap().0 = 5.0;
+        server
+            .world
+            .entity_mut(entity)
+            .insert(Position { x: 1.0, y: 2.0 });
+        update(&mut server, &mut client);
+        assert_eq!(
+            client.world.get::<Health>(client_entity),
+            Some(&Health(5.0))
+        );
+        assert_eq!(
+            client.world.get::<Position>(client_entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+
+        server.world.entity_mut(entity).remove::<Health>();
+        update(&mut server, &mut client);
+        assert!(client.world.get::<Health>(client_entity).is_none());
+
+        // Unchanged entities aren't sent.
+        update(&mut server, &mut client);
+        assert_eq!(
+            server.world.resource::<ReplicationServer>().tick(),
+            ReplicationTick(4)
+        );
+        assert_eq!(
+            client.world.resource::<ReplicationClient>().tick(),
+            Some(ReplicationTick(3))
+        );
+
+        server.world.despawn(entity);
+        update(&mut server, &mut client);
+        assert!(client.world.get_entity(client_entity).is_none());
+        assert!(client
+            .world
+            .resource::<ReplicationClient>()
+            .entity_map()
+            .is_empty());
+    }
+
+    #[test]
+    fn late_client_receives_state() {
+        let (mut server, connector) = server();
+        let entity = server
+            .world
+            .spawn((Replicated, Health(10.0), Position { x: 1.0, y: 2.0 }))
+            .id();
+        server.world.spawn(Health(1.0));
+        server.update();
+        server.update();
+
+        let mut client = client(connector.connect());
+        update(&mut server, &mut client);
+        let client_entity = client_entity(&client, entity);
+        assert_eq!(
+            client.world.get::<Health>(client_entity),
+            Some(&Health(10.0))
+        );
+        assert_eq!(
+            client.world.get::<Position>(client_entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        // Entities without `Replicated` aren't replicated.
+        assert_eq!(client.world.query::<&Health>().iter(&client.world).len(), 1);
+    }
+
+    #[test]
+    fn unreliable_changes() {
+        let (mut server, connector) = server();
+        let mut client = client(connector.connect().with_unreliable_dropped());
+        let entity = server
+            .world
+            .spawn((Replicated, Health(10.0), Position { x: 1.0, y: 2.0 }))
+            .id();
+        update(&mut server, &mut client);
+        let client_entity = client_entity(&client, entity);
+
+        // Insertions are reliable, changes of unreliable components are lost.
+        server.world.get_mut::<Position>(entity).unwrap().x = 3.0;
+        server.world.get_mut::<Health>(entity).unwrap().0 = 5.0;
+        update(&mut server, &mut client);
+        assert_eq!(
+            client.world.get::<Position>(client_entity),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert_eq!(
+            client.world.get::<Health>(client_entity),
+            Some(&Health(5.0))
+        );
+    }
+
+    #[test]
+    fn map_entities() {
+        let (mut server, connector) = server();
+        let mut client = client(connector.connect());
+        // Offset the client entities from the server ones.
+        client.world.spawn_batch((0..3).map(|_| ()));
+        let target = server.world.spawn(Replicated).id();
+        let entity = server.world.spawn((Replicated, Target(target))).id();
+        update(&mut server, &mut client);
+
+        let client_target = client_entity(&client, target);
+        let client_entity = client_entity(&client, entity);
+        assert_ne!(client_target, target);
+        assert_eq!(
+            client.world.get::<Target>(client_entity),
+            Some(&Target(client_target))
+        );
+    }
+
+    #[test]
+    fn messages_and_prediction() {
+        #[derive(Resource, Default)]
+        struct Reconciled(Vec<(ReplicationTick, Vec<Entity>)>);
+
+        let (mut server, connector) = server();
+        let mut client = client(connector.connect());
+        client.init_resource::<Reconciled>().add_systems(
+            Reconcile,
+            |reconciliation: Res<Reconciliation>, mut reconciled: ResMut<Reconciled>| {
+                reconciled
+                    .0
+                    .push((reconciliation.tick, reconciliation.entities.clone()));
+            },
+        );
+        let entity = server.world.spawn((Replicated, Health(10.0))).id();
+        server.world.spawn((Replicated, Health(1.0)));
+        update(&mut server, &mut client);
+
+        let client_entity = client_entity(&client, entity);
+        client.world.entity_mut(client_entity).insert(Predicted);
+        client.world.get_mut::<Health>(client_entity).unwrap().0 = 8.0;
+        server.world.get_mut::<Health>(entity).unwrap().0 = 9.0;
+        update(&mut server, &mut client);
+        assert_eq!(
+            client.world.get::<Health>(client_entity),
+            Some(&Health(9.0))
+        );
+        assert_eq!(
+            client.world.resource::<Reconciled>().0,
+            vec![(ReplicationTick(2), vec![client_entity])]
+        );
+
+        client
+            .world
+            .resource_mut::<ReplicationClient>()
+            .send(Channel::Reliable, b"jump".to_vec());
+        server.update();
+        let events = server.world.resource::<Events<FromClient>>();
+        let message = events.iter_current_update_events().next().unwrap();
+        assert_eq!(message.message, b"jump");
+        let client_id = message.client;
+
+        server.world.resource_mut::<ReplicationServer>().send(
+            client_id,
+            Channel::Unreliable,
+            b"landed".to_vec(),
+        );
+        client.update();
+        let events = client.world.resource::<Events<FromServer>>();
+        assert_eq!(
+            events.iter_current_update_events().next(),
+            Some(&FromServer {
+                channel: Channel::Unreliable,
+                message: b"landed".to_vec(),
+            })
+        );
+    }
+
+    #[test]
+    fn connections() {
+        let (mut server, connector) = server();
+        let client = connector.connect();
+        server.update();
+        let events = server.world.resource::<Events<ClientConnected>>();
+        assert_eq!(
+            events.iter_current_update_events().next(),
+            Some(&ClientConnected(client.id()))
+        );
+        assert_eq!(
+            server
+                .world
+                .resource::<ReplicationServer>()
+                .clients()
+                .collect::<Vec<_>>(),
+            vec![client.id()]
+        );
+
+        let id = client.id();
+        drop(client);
+        server.update();
+        let events = server.world.resource::<Events<ClientDisconnected>>();
+        assert_eq!(
+            events.iter_current_update_events().next(),
+            Some(&ClientDisconnected(id))
+        );
+        assert_eq!(
+            server
+                .world
+                .resource::<ReplicationServer>()
+                .clients()
+                .count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! The messages sent by the server to its clients.
+
+use crate::{ReplicationError, ReplicationTick, MAX_MESSAGE_SIZE};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+/// The encoding of the messages, refusing to encode messages over [`MAX_MESSAGE_SIZE`] bytes.
+fn options() -> impl Options {
+    bincode::DefaultOptions::new().with_limit(MAX_MESSAGE_SIZE as u64)
+}
+
+/// Refuses to decode messages over [`MAX_MESSAGE_SIZE`] bytes, which `bincode` doesn't check when
+/// decoding slices.
+fn check_size(bytes: &[u8]) -> Result<(), ReplicationError> {
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        return Err(ReplicationError::Decode(Box::new(
+            bincode::ErrorKind::SizeLimit,
+        )));
+    }
+    Ok(())
+}
+
+/// A message sent by the server.
+#[derive(Serialize, Deserialize)]
+pub(crate) enum Packet {
+    /// Changes of the replicated entities.
+    Update(Update),
+    /// A message sent with [`ReplicationServer::send`](crate::ReplicationServer::send).
+    User(Vec<u8>),
+}
+
+impl Packet {
+    pub fn encode(&self) -> Result<Vec<u8>, ReplicationError> {
+        options().serialize(self).map_err(ReplicationError::Encode)
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ReplicationError> {
+        check_size(bytes)?;
+        options()
+            .deserialize(bytes)
+            .map_err(ReplicationError::Decode)
+    }
+}
+
+/// The changes of the replicated entities during a tick of the server, sent on one channel.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Update {
+    pub tick: ReplicationTick,
+    pub entities: Vec<EntityUpdate>,
+    /// The bits of the server entities despawned, or that stopped being replicated.
+    pub despawned: Vec<u64>,
+}
+
+impl Update {
+    pub fn new(tick: ReplicationTick) -> Self {
+        Self {
+            tick,
+            entities: Vec::new(),
+            despawned: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.despawned.is_empty()
+    }
+}
+
+/// The changes of a replicated entity.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EntityUpdate {
+    /// The bits of the server entity.
+    pub entity: u64,
+    /// The type paths and serialized values of the components inserted or changed.
+    pub changed: Vec<(String, Vec<u8>)>,
+    /// The type paths of the components removed.
+    pub removed: Vec<String>,
+}
+
+impl EntityUpdate {
+    pub fn new(entity: u64) -> Self {
+        Self {
+            entity,
+            changed: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+pub(crate) fn serialize_component(
+    value: &dyn Reflect,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, ReplicationError> {
+    options()
+        .serialize(&TypedReflectSerializer::new(value, registry))
+        .map_err(ReplicationError::Encode)
+}
+
+pub(crate) fn deserialize_component(
+    bytes: &[u8],
+    registration: &TypeRegistration,
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, ReplicationError> {
+    check_size(bytes)?;
+    options()
+        .deserialize_seed(TypedReflectDeserializer::new(registration, registry), bytes)
+        .map_err(ReplicationError::Decode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn malformed_packets() {
+        let mut update = Update::new(ReplicationTick(3));
+        let mut entity = EntityUpdate::new(7);
+        entity.changed.push(("health".to_string(), vec![1, 2, 3]));
+        entity.removed.push("position".to_string());
+        update.entities.push(entity);
+        update.despawned.push(8);
+        let bytes = Packet::Update(update).encode().unwrap();
+        let Ok(Packet::Update(decoded)) = Packet::decode(&bytes) else {
+            panic!("the update wasn't decoded");
+        };
+        assert_eq!(decoded.tick, ReplicationTick(3));
+        assert_eq!(decoded.entities[0].entity, 7);
+        assert_eq!(decoded.entities[0].changed[0].1, vec![1, 2, 3]);
+        assert_eq!(decoded.entities[0].removed, vec!["position".to_string()]);
+        assert_eq!(decoded.despawned, vec![8]);
+
+        for end in 0..bytes.len() {
+            assert!(Packet::decode(&bytes[..end]).is_err());
+        }
+        assert!(Packet::decode(&[9]).is_err());
+
+        // Messages over the limit are neither sent nor decoded.
+        let bytes = bincode::DefaultOptions::new()
+            .serialize(&Packet::User(vec![0; MAX_MESSAGE_SIZE]))
+            .unwrap();
+        assert!(matches!(
+            Packet::decode(&bytes),
+            Err(ReplicationError::Decode(err)) if matches!(*err, bincode::ErrorKind::SizeLimit)
+        ));
+        assert!(Packet::User(vec![0; MAX_MESSAGE_SIZE]).encode().is_err());
+    }
+}
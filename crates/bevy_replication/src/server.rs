@@ -0,0 +1,322 @@
+//! The server side of replication.
+
+use crate::{
+    message::{serialize_component, EntityUpdate, Packet, Update},
+    Channel, ClientId, Replicated, ReplicationError, ReplicationRules, ReplicationTick,
+    ServerTransport, TransportEvent,
+};
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    component::{ComponentId, Tick},
+    prelude::*,
+};
+use bevy_log::error;
+use bevy_utils::HashMap;
+
+/// A client connected to the [`ReplicationServer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClientConnected(pub ClientId);
+
+/// A client disconnected from the [`ReplicationServer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ClientDisconnected(pub ClientId);
+
+/// A message sent by a client with [`ReplicationClient::send`](crate::ReplicationClient::send),
+/// like its inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FromClient {
+    /// The client that sent the message.
+    pub client: ClientId,
+    /// The channel the message was sent on.
+    pub channel: Channel,
+    /// The message.
+    pub message: Vec<u8>,
+}
+
+/// Replicates the entities of this [`World`] to the clients connected to its transport.
+///
+/// After each update, [`send_server_updates`] sends the changes of the [`Replicated`] entities to
+/// the clients, and the whole state to the clients that connected since the last update.
+#[derive(Resource)]
+pub struct ReplicationServer {
+    transport: Box<dyn ServerTransport>,
+    clients: Vec<ClientId>,
+    new_clients: Vec<ClientId>,
+    tick: ReplicationTick,
+    last_run: Tick,
+    /// The components of each replicated entity during the last update.
+    replicated: HashMap<Entity, Vec<ComponentId>>,
+}
+
+impl ReplicationServer {
+    /// Creates a server replicating entities to the clients of `transport`.
+    pub fn new(transport: impl ServerTransport) -> Self {
+        Self {
+            transport: Box::new(transport),
+            clients: Vec::new(),
+            new_clients: Vec::new(),
+            tick: ReplicationTick::default(),
+            last_run: Tick::new(0),
+            replicated: HashMap::default(),
+        }
+    }
+
+    /// Returns the connected clients.
+    pub fn clients(&self) -> impl Iterator<Item = ClientId> + '_ {
+        self.clients.iter().chain(&self.new_clients).copied()
+    }
+
+    /// Returns the tick of the last update sent to the clients.
+    pub fn tick(&self) -> ReplicationTick {
+        self.tick
+    }
+
+    /// Sends a message to `client`, received as a [`FromServer`](crate::FromServer) event.
+    pub fn send(&mut self, client: ClientId, channel: Channel, message: Vec<u8>) {
+        match Packet::User(message).encode() {
+            Ok(bytes) => self.transport.send(client, channel, bytes),
+            Err(err) => error!("failed to send a message to {client:?}: {err}"),
+        }
+    }
+
+    fn send_packet(&mut self, clients: &[ClientId], channel: Channel, packet: &Packet) {
+        match packet.encode() {
+            Ok(bytes) => {
+                for &client in clients {
+                    self.transport.send(client, channel, bytes.clone());
+                }
+            }
+            Err(err) => error!("failed to send a replication update: {err}"),
+        }
+    }
+}
+
+/// Receives the connections, disconnections and messages of the clients of the
+/// [`ReplicationServer`].
+pub fn receive_client_messages(
+    mut server: ResMut<ReplicationServer>,
+    mut connected: EventWriter<ClientConnected>,
+    mut disconnected: EventWriter<ClientDisconnected>,
+    mut messages: EventWriter<FromClient>,
+) {
+    while let Some(event) = server.transport.receive() {
+        match event {
+            TransportEvent::Connected(client) => {
+                server.new_clients.push(client);
+                connected.send(ClientConnected(client));
+            }
+            TransportEvent::Disconnected(client) => {
+                server.clients.retain(|&other| other != client);
+                server.new_clients.retain(|&other| other != client);
+                disconnected.send(ClientDisconnected(client));
+            }
+            TransportEvent::Message(client, channel, message) => messages.send(FromClient {
+                client,
+                channel,
+                message,
+            }),
+        }
+    }
+}
+
+/// Sends the changes of the [`Replicated`] entities since the last run to the clients of the
+/// [`ReplicationServer`].
+///
+/// Spawned entities, inserted and removed components, despawned entities, and the changes of
+/// components replicated on [`Channel::Reliable`] are sent reliably. Changes of components
+/// replicated on [`Channel::Unreliable`] are sent unreliably. Clients that connected since the
+/// last run reliably receive the whole state instead.
+pub fn send_server_updates(world: &mut World) {
+    let this_run = world.increment_change_tick();
+    world.resource_scope(|world, mut server: Mut<ReplicationServer>| {
+        server.tick.0 += 1;
+        let tick = server.tick;
+        let snapshot = !server.new_clients.is_empty();
+        let mut reliable = Update::new(tick);
+        let mut unreliable = Update::new(tick);
+        let mut full = Update::new(tick);
+        let mut replicated = HashMap::default();
+
+        let rules = world.resource::<ReplicationRules>();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let replicated_id = world.component_id::<Replicated>();
+        for archetype in world
+            .archetypes()
+            .iter()
+            .filter(|archetype| matches!(replicated_id, Some(id) if archetype.contains(id)))
+        {
+            let archetype_rules: Vec<_> = rules
+                .rules
+                .iter()
+                .filter(|rule| archetype.contains(rule.component_id))
+                .collect();
+            let component_ids: Vec<_> = archetype_rules
+                .iter()
+                .map(|rule| rule.component_id)
+                .collect();
+
+            for entity in archetype.entities().iter().map(|entity| entity.entity()) {
+                let entity_ref = world.entity(entity);
+                let known = server.replicated.get(&entity);
+                let mut reliable_update = EntityUpdate::new(entity.to_bits());
+                let mut unreliable_update = EntityUpdate::new(entity.to_bits());
+                let mut full_update = EntityUpdate::new(entity.to_bits());
+
+                for rule in &archetype_rules {
+                    let inserted = !matches!(known, Some(ids) if ids.contains(&rule.component_id));
+                    let changed = matches!(
+                        entity_ref.get_change_ticks_by_id(rule.component_id),
+                        Some(ticks) if ticks.is_changed(server.last_run, this_run)
+                    );
+                    if !(inserted || changed || snapshot) {
+                        continue;
+                    }
+
+                    let Some(registration) = registry.get(rule.type_id) else {
+                        continue;
+                    };
+                    let type_path = registration.type_path();
+                    let serialized = rule
+                        .reflect_component
+                        .reflect(entity_ref)
+                        .ok_or_else(|| ReplicationError::MissingComponent(type_path.to_string()))
+                        .and_then(|value| serialize_component(value, &registry));
+                    let bytes = match serialized {
+                        Ok(bytes) => bytes,
+                        Err(err) => {
+                            error!("failed to replicate `{type_path}` of {entity:?}: {err}");
+                            continue;
+                        }
+                    };
+
+                    if snapshot {
+                        full_update
+                            .changed
+                            .push((type_path.to_string(), bytes.clone()));
+                    }
+                    if inserted || (changed && rule.channel == Channel::Reliable) {
+                        reliable_update.changed.push((type_path.to_string(), bytes));
+                    } else if changed {
+                        unreliable_update
+                            .changed
+                            .push((type_path.to_string(), bytes));
+                    }
+                }
+
+                if let Some(ids) = known {
+                    for id in ids.iter().filter(|id| !component_ids.contains(id)) {
+                        if let Some(registration) = rules
+                            .get_by_component_id(*id)
+                            .and_then(|rule| registry.get(rule.type_id))
+                        {
+                            reliable_update
+                                .removed
+                                .push(registration.type_path().to_string());
+                        }
+                    }
+                }
+
+                // Entities are spawned on the clients even if none of their components are
+                // replicated yet.
+                if known.is_none() || !reliable_update.is_empty() {
+                    reliable.entities.push(reliable_update);
+                }
+                if !unreliable_update.is_empty() {
+                    unreliable.entities.push(unreliable_update);
+                }
+                if snapshot {
+                    full.entities.push(full_update);
+                }
+                replicated.insert(entity, component_ids.clone());
+            }
+        }
+        drop(registry);
+
+        reliable.despawned = server
+            .replicated
+            .keys()
+            .filter(|entity| !replicated.contains_key(*entity))
+            .map(|entity| entity.to_bits())
+            .collect();
+        server.replicated = replicated;
+        server.last_run = this_run;
+
+        let clients = std::mem::take(&mut server.clients);
+        if !reliable.is_empty() {
+            server.send_packet(&clients, Channel::Reliable, &Packet::Update(reliable));
+        }
+        if !unreliable.is_empty() {
+            server.send_packet(&clients, Channel::Unreliable, &Packet::Update(unreliable));
+        }
+        let new_clients = std::mem::take(&mut server.new_clients);
+        if snapshot {
+            server.send_packet(&new_clients, Channel::Reliable, &Packet::Update(full));
+        }
+        server.clients = clients;
+        server.clients.extend(new_clients);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AppReplicationExt, ClientTransport, LoopbackClient, LoopbackServer, ReplicationPlugin,
+    };
+    use bevy_app::App;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    fn receive_update(client: &mut LoopbackClient) -> Update {
+        let (channel, bytes) = client.receive().unwrap();
+        assert_eq!(channel, Channel::Reliable);
+        let Ok(Packet::Update(update)) = Packet::decode(&bytes) else {
+            panic!("the message isn't an update");
+        };
+        update
+    }
+
+    #[test]
+    fn send_spawns_and_despawns() {
+        let transport = LoopbackServer::default();
+        let mut client = transport.connector().connect();
+        let mut app = App::new();
+        app.add_plugin(ReplicationPlugin)
+            .replicate::<Health>()
+            .insert_resource(ReplicationServer::new(transport));
+        let entity = app.world.spawn((Replicated, Health(1.0))).id();
+        let unreplicated = app.world.spawn((Replicated, Health(2.0))).id();
+        app.update();
+
+        let update = receive_update(&mut client);
+        assert_eq!(update.tick, ReplicationTick(1));
+        assert_eq!(update.entities.len(), 2);
+        let entity_update = update
+            .entities
+            .iter()
+            .find(|update| update.entity == entity.to_bits())
+            .unwrap();
+        assert_eq!(entity_update.changed.len(), 1);
+        assert!(update.despawned.is_empty());
+        assert!(client.receive().is_none());
+
+        // Despawned entities, and entities that stopped being replicated, are despawned on the
+        // clients.
+        app.world.despawn(entity);
+        app.world.entity_mut(unreplicated).remove::<Replicated>();
+        app.update();
+        let mut update = receive_update(&mut client);
+        assert!(update.entities.is_empty());
+        update.despawned.sort();
+        let mut despawned = vec![entity.to_bits(), unreplicated.to_bits()];
+        despawned.sort();
+        assert_eq!(update.despawned, despawned);
+
+        // Nothing is sent once they are despawned.
+        app.update();
+        assert!(client.receive().is_none());
+    }
+}
@@ -0,0 +1,261 @@
+//! The transports carrying messages between the server and its clients.
+
+use bevy_utils::HashMap;
+use crossbeam_channel::{Receiver, Sender};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// The largest message, in bytes, sent by a [`ReplicationServer`](crate::ReplicationServer) and
+/// decoded by a [`ReplicationClient`](crate::ReplicationClient).
+///
+/// Longer messages are dropped with an error, so that a malformed message can't make a client
+/// allocate more memory.
+pub const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Identifies a client connected to a [`ServerTransport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClientId(pub u64);
+
+/// The delivery guarantees of a message.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// The message is delivered, after the reliable messages sent before it.
+    #[default]
+    Reliable,
+    /// The message may be lost, or delivered out of order.
+    ///
+    /// Suited to state that changes every tick, like positions, which is sent again anyway.
+    Unreliable,
+}
+
+/// An event received by a [`ServerTransport`].
+#[derive(Debug)]
+pub enum TransportEvent {
+    /// A client connected.
+    Connected(ClientId),
+    /// A client disconnected, no more messages can be sent to it.
+    Disconnected(ClientId),
+    /// A client sent a message.
+    Message(ClientId, Channel, Vec<u8>),
+}
+
+/// The server side of a transport, exchanging messages with connected clients.
+///
+/// Implementations must honor the guarantees of each [`Channel`], for messages up to
+/// [`MAX_MESSAGE_SIZE`] bytes.
+pub trait ServerTransport: Send + Sync + 'static {
+    /// Returns the next event received, or `None` if there is none left for now.
+    fn receive(&mut self) -> Option<TransportEvent>;
+
+    /// Sends a message to `client`.
+    ///
+    /// Messages sent to a client that disconnected are dropped.
+    fn send(&mut self, client: ClientId, channel: Channel, message: Vec<u8>);
+}
+
+/// The client side of a transport, exchanging messages with the server.
+///
+/// Implementations must honor the guarantees of each [`Channel`], for messages up to
+/// [`MAX_MESSAGE_SIZE`] bytes.
+pub trait ClientTransport: Send + Sync + 'static {
+    /// Returns the next message received, or `None` if there is none left for now.
+    fn receive(&mut self) -> Option<(Channel, Vec<u8>)>;
+
+    /// Sends a message to the server.
+    fn send(&mut self, channel: Channel, message: Vec<u8>);
+}
+
+enum LoopbackEvent {
+    Connected(ClientId, Sender<(Channel, Vec<u8>)>),
+    Disconnected(ClientId),
+    Message(ClientId, Channel, Vec<u8>),
+}
+
+/// A [`ServerTransport`] exchanging messages in memory with [`LoopbackClient`]s, for tests and
+/// local sessions.
+///
+/// Clients connect through a [`LoopbackConnector`], which can be kept after the server is moved
+/// into a [`ReplicationServer`](crate::ReplicationServer).
+pub struct LoopbackServer {
+    sender: Sender<LoopbackEvent>,
+    receiver: Receiver<LoopbackEvent>,
+    clients: HashMap<ClientId, Sender<(Channel, Vec<u8>)>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Default for LoopbackServer {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            sender,
+            receiver,
+            clients: HashMap::default(),
+            next_id: Arc::default(),
+        }
+    }
+}
+
+impl LoopbackServer {
+    /// Returns a handle connecting clients to this server.
+    pub fn connector(&self) -> LoopbackConnector {
+        LoopbackConnector {
+            sender: self.sender.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+}
+
+impl ServerTransport for LoopbackServer {
+    fn receive(&mut self) -> Option<TransportEvent> {
+        let event = match self.receiver.try_recv().ok()? {
+            LoopbackEvent::Connected(client, sender) => {
+                self.clients.insert(client, sender);
+                TransportEvent::Connected(client)
+            }
+            LoopbackEvent::Disconnected(client) => {
+                self.clients.remove(&client);
+                TransportEvent::Disconnected(client)
+            }
+            LoopbackEvent::Message(client, channel, message) => {
+                TransportEvent::Message(client, channel, message)
+            }
+        };
+        Some(event)
+    }
+
+    fn send(&mut self, client: ClientId, channel: Channel, message: Vec<u8>) {
+        if let Some(sender) = self.clients.get(&client) {
+            // The client may have been dropped without its disconnection being received yet.
+            let _ = sender.send((channel, message));
+        }
+    }
+}
+
+/// Connects [`LoopbackClient`]s to a [`LoopbackServer`].
+#[derive(Clone)]
+pub struct LoopbackConnector {
+    sender: Sender<LoopbackEvent>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl LoopbackConnector {
+    /// Connects a new client to the server.
+    ///
+    /// The server receives the connection the next time it receives events.
+    pub fn connect(&self) -> LoopbackClient {
+        let id = ClientId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        // The server may have been dropped, in which case nothing is ever received.
+        let _ = self.sender.send(LoopbackEvent::Connected(id, sender));
+        LoopbackClient {
+            id,
+            sender: self.sender.clone(),
+            receiver,
+            drop_unreliable: false,
+        }
+    }
+}
+
+/// A [`ClientTransport`] connected to a [`LoopbackServer`], created by a [`LoopbackConnector`].
+///
+/// The client disconnects when dropped.
+pub struct LoopbackClient {
+    id: ClientId,
+    sender: Sender<LoopbackEvent>,
+    receiver: Receiver<(Channel, Vec<u8>)>,
+    drop_unreliable: bool,
+}
+
+impl LoopbackClient {
+    /// Returns the id of this client on the server.
+    pub fn id(&self) -> ClientId {
+        self.id
+    }
+
+    /// Drops all the [`Channel::Unreliable`] messages received by this client, to simulate their
+    /// loss.
+    pub fn with_unreliable_dropped(mut self) -> Self {
+        self.drop_unreliable = true;
+        self
+    }
+}
+
+impl ClientTransport for LoopbackClient {
+    fn receive(&mut self) -> Option<(Channel, Vec<u8>)> {
+        loop {
+            let (channel, message) = self.receiver.try_recv().ok()?;
+            if !(self.drop_unreliable && channel == Channel::Unreliable) {
+                return Some((channel, message));
+            }
+        }
+    }
+
+    fn send(&mut self, channel: Channel, message: Vec<u8>) {
+        let _ = self
+            .sender
+            .send(LoopbackEvent::Message(self.id, channel, message));
+    }
+}
+
+impl Drop for LoopbackClient {
+    fn drop(&mut self) {
+        let _ = self.sender.send(LoopbackEvent::Disconnected(self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loopback() {
+        let mut server = LoopbackServer::default();
+        let connector = server.connector();
+        let mut client = connector.connect();
+        let other = connector.connect().with_unreliable_dropped();
+        assert_ne!(client.id(), other.id());
+        assert!(
+            matches!(server.receive(), Some(TransportEvent::Connected(id)) if id == client.id())
+        );
+        assert!(
+            matches!(server.receive(), Some(TransportEvent::Connected(id)) if id == other.id())
+        );
+        assert!(server.receive().is_none());
+
+        client.send(Channel::Unreliable, b"input".to_vec());
+        assert!(matches!(
+            server.receive(),
+            Some(TransportEvent::Message(id, Channel::Unreliable, message))
+                if id == client.id() && message == b"input"
+        ));
+
+        server.send(client.id(), Channel::Reliable, b"state".to_vec());
+        server.send(client.id(), Channel::Unreliable, b"position".to_vec());
+        assert_eq!(
+            client.receive(),
+            Some((Channel::Reliable, b"state".to_vec()))
+        );
+        assert_eq!(
+            client.receive(),
+            Some((Channel::Unreliable, b"position".to_vec()))
+        );
+        assert_eq!(client.receive(), None);
+
+        // Messages to a dropped client are dropped.
+        let mut other = other;
+        server.send(other.id(), Channel::Unreliable, b"position".to_vec());
+        server.send(other.id(), Channel::Reliable, b"state".to_vec());
+        assert_eq!(
+            other.receive(),
+            Some((Channel::Reliable, b"state".to_vec()))
+        );
+        let id = other.id();
+        drop(other);
+        assert!(
+            matches!(server.receive(), Some(TransportEvent::Disconnected(other)) if other == id)
+        );
+        server.send(id, Channel::Reliable, b"state".to_vec());
+    }
+}
@@ -0,0 +1,494 @@
+use crate::{serde::SceneDeserializer, serde::SceneSerializer, DynamicScene};
+use bevy_reflect::{
+    serde::SerializationData, ReflectSerialize, TypeInfo, TypeRegistry, TypeRegistryArc,
+    VariantInfo,
+};
+use bevy_utils::HashSet;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::any::TypeId;
+use thiserror::Error;
+
+/// The bytes every binary scene starts with.
+pub const SCENE_BINARY_MAGIC: [u8; 4] = *b"BSCN";
+
+/// The version of the binary scene format written by [`serialize_binary`].
+pub const SCENE_BINARY_VERSION: u8 = 1;
+
+/// An error while reading or writing a binary scene.
+#[derive(Error, Debug)]
+pub enum SceneBinaryError {
+    #[error("data is not a binary scene")]
+    InvalidHeader,
+    #[error(
+        "binary scene format version {0} is not supported, expected version {SCENE_BINARY_VERSION}"
+    )]
+    UnsupportedVersion(u8),
+    #[error("scene contains type `{type_name}` which is not registered in the type registry")]
+    UnregisteredType { type_name: String },
+    #[error("the layout of type `{type_name}` changed since the scene was serialized")]
+    FingerprintMismatch { type_name: String },
+    #[error("failed to encode or decode binary scene: {0}")]
+    Postcard(#[from] postcard::Error),
+}
+
+/// The header of a binary scene, following [`SCENE_BINARY_MAGIC`] and the format version.
+///
+/// The binary format isn't self-describing: a scene can only be read back if every type it
+/// contains still has the layout it was written with. The header lists the fingerprint of each of
+/// these types, see [`type_fingerprint`], so that changes are reported instead of producing
+/// garbage.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct SceneBinaryHeader {
+    /// The type path and fingerprint of each resource and component type in the scene.
+    pub types: Vec<(String, u64)>,
+}
+
+impl SceneBinaryHeader {
+    /// Builds the header of `scene`.
+    pub fn new(scene: &DynamicScene, registry: &TypeRegistry) -> Result<Self, SceneBinaryError> {
+        let mut seen = HashSet::new();
+        let mut types = Vec::new();
+        let reflects = scene.resources.iter().chain(
+            scene
+                .entities
+                .iter()
+                .flat_map(|entity| entity.components.iter()),
+        );
+        for reflect in reflects {
+            let type_name = reflect.type_name();
+            if !seen.insert(type_name) {
+                continue;
+            }
+            let registration = registry.get_with_name(type_name).ok_or_else(|| {
+                SceneBinaryError::UnregisteredType {
+                    type_name: type_name.to_string(),
+                }
+            })?;
+            types.push((
+                registration.type_path().to_string(),
+                type_fingerprint(registration.type_id(), registry),
+            ));
+        }
+        Ok(Self { types })
+    }
+
+    /// Checks that every type of the header is registered with the same fingerprint.
+    pub fn validate(&self, registry: &TypeRegistry) -> Result<(), SceneBinaryError> {
+        for (type_name, fingerprint) in &self.types {
+            let registration = registry.get_with_type_path(type_name).ok_or_else(|| {
+                SceneBinaryError::UnregisteredType {
+                    type_name: type_name.clone(),
+                }
+            })?;
+            if type_fingerprint(registration.type_id(), registry) != *fingerprint {
+                return Err(SceneBinaryError::FingerprintMismatch {
+                    type_name: type_name.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if `bytes` start like a binary scene.
+pub fn is_binary_scene(bytes: &[u8]) -> bool {
+    bytes.starts_with(&SCENE_BINARY_MAGIC)
+}
+
+/// Serializes `scene` into the compact binary format.
+///
+/// The data is a [`SCENE_BINARY_MAGIC`], the [`SCENE_BINARY_VERSION`], a [`SceneBinaryHeader`]
+/// and the [`SceneSerializer`] output, the last two encoded with [`postcard`].
+pub fn serialize_binary(
+    scene: &DynamicScene,
+    registry: &TypeRegistryArc,
+) -> Result<Vec<u8>, SceneBinaryError> {
+    let header = SceneBinaryHeader::new(scene, &registry.read())?;
+    let mut bytes = SCENE_BINARY_MAGIC.to_vec();
+    bytes.push(SCENE_BINARY_VERSION);
+    let bytes = postcard::to_extend(&header, bytes)?;
+    Ok(postcard::to_extend(
+        &SceneSerializer::new(scene, registry),
+        bytes,
+    )?)
+}
+
+/// Deserializes a scene written by [`serialize_binary`].
+///
+/// Fails without reading the scene if one of its types isn't registered or has a different
+/// layout than when it was written.
+pub fn deserialize_binary(
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, SceneBinaryError> {
+    let bytes = bytes
+        .strip_prefix(&SCENE_BINARY_MAGIC)
+        .ok_or(SceneBinaryError::InvalidHeader)?;
+    let (&version, bytes) = bytes.split_first().ok_or(SceneBinaryError::InvalidHeader)?;
+    if version != SCENE_BINARY_VERSION {
+        return Err(SceneBinaryError::UnsupportedVersion(version));
+    }
+    let (header, bytes) = postcard::take_from_bytes::<SceneBinaryHeader>(bytes)?;
+    header.validate(registry)?;
+
+    let scene_deserializer = SceneDeserializer {
+        type_registry: registry,
+    };
+    Ok(scene_deserializer.deserialize(&mut postcard::Deserializer::from_bytes(bytes))?)
+}
+
+/// Returns a hash of the layout of the type with the given [`TypeId`], as far as the binary
+/// format is concerned.
+///
+/// The hash covers the field and variant names and the field types of the type and,
+/// recursively, of the types of its fields and items. It is stable across runs and platforms,
+/// and changes whenever a field or variant is added, removed, renamed, reordered or skipped.
+///
+/// The type paths of structs, tuple structs and enums are left out, so that moving or renaming
+/// one of these types, and registering its former path with
+/// [`TypeRegistry::register_type_alias`], keeps the scenes it is in readable. Types serialized
+/// with serde and value types have no reflected layout and are identified by their type path.
+pub fn type_fingerprint(type_id: TypeId, registry: &TypeRegistry) -> u64 {
+    let mut hasher = Fnv1a::default();
+    hash_type(type_id, registry, &mut Vec::new(), &mut hasher);
+    hasher.0
+}
+
+fn hash_type(
+    type_id: TypeId,
+    registry: &TypeRegistry,
+    visited: &mut Vec<TypeId>,
+    hasher: &mut Fnv1a,
+) {
+    let Some(registration) = registry.get(type_id) else {
+        hasher.write_str("?");
+        return;
+    };
+    // Types already described, like recursive ones, are referred to by their position.
+    if let Some(index) = visited.iter().position(|visited| *visited == type_id) {
+        hasher.write_str("visited");
+        hasher.write_u64(index as u64);
+        return;
+    }
+    visited.push(type_id);
+    // Types with a serde implementation are serialized with it instead of their reflected layout.
+    if registration.data::<ReflectSerialize>().is_some() {
+        hasher.write_str("serde");
+        hasher.write_str(registration.type_path());
+        return;
+    }
+    let serialization_data = registration.data::<SerializationData>();
+    let is_ignored = |index: usize| {
+        serialization_data
+            .map(|data| data.is_ignored_field(index))
+            .unwrap_or(false)
+    };
+
+    let mut hash_field = |name: &str, field_type: TypeId, hasher: &mut Fnv1a| {
+        hasher.write_str(name);
+        hash_type(field_type, registry, visited, hasher);
+    };
+    match registration.type_info() {
+        TypeInfo::Struct(info) => {
+            hasher.write_str("struct");
+            for (index, field) in info.iter().enumerate() {
+                if is_ignored(index) {
+                    hasher.write_str("skipped");
+                }
+                hash_field(field.name(), field.type_id(), hasher);
+            }
+        }
+        TypeInfo::TupleStruct(info) => {
+            hasher.write_str("tuple struct");
+            for (index, field) in info.iter().enumerate() {
+                if is_ignored(index) {
+                    hasher.write_str("skipped");
+                }
+                hash_field("", field.type_id(), hasher);
+            }
+        }
+        TypeInfo::Tuple(info) => {
+            hasher.write_str("tuple");
+            for field in info.iter() {
+                hash_field("", field.type_id(), hasher);
+            }
+        }
+        TypeInfo::List(info) => {
+            hasher.write_str("list");
+            hash_field("", info.item_type_id(), hasher);
+        }
+        TypeInfo::Array(info) => {
+            hasher.write_str("array");
+            hasher.write_u64(info.capacity() as u64);
+            hash_field("", info.item_type_id(), hasher);
+        }
+        TypeInfo::Map(info) => {
+            hasher.write_str("map");
+            hash_field("", info.key_type_id(), hasher);
+            hash_field("", info.value_type_id(), hasher);
+        }
+        TypeInfo::Enum(info) => {
+            hasher.write_str("enum");
+            for variant in info.iter() {
+                hasher.write_str(variant.name());
+                match variant {
+                    VariantInfo::Struct(variant) => {
+                        for field in variant.iter() {
+                            hash_field(field.name(), field.type_id(), hasher);
+                        }
+                    }
+                    VariantInfo::Tuple(variant) => {
+                        for field in variant.iter() {
+                            hash_field("", field.type_id(), hasher);
+                        }
+                    }
+                    VariantInfo::Unit(_) => {}
+                }
+            }
+        }
+        TypeInfo::Value(_) => {
+            hasher.write_str("value");
+            hasher.write_str(registration.type_path());
+        }
+        TypeInfo::Dynamic(_) => hasher.write_str("dynamic"),
+    }
+}
+
+/// The 64 bit FNV-1a hash, which unlike the hashers of `std` and `ahash` is guaranteed to stay
+/// the same across versions.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write(value.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        deserialize_binary, is_binary_scene, serialize_binary, DynamicScene, SceneBinaryError,
+        SceneBinaryHeader, SCENE_BINARY_MAGIC, SCENE_BINARY_VERSION,
+    };
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+    };
+    use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
+    use bevy_utils::HashMap;
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Everything {
+        unit: MyEnum,
+        tuple_variant: MyEnum,
+        struct_variant: MyEnum,
+        map: HashMap<String, u32>,
+        tuple: (u8, String, f32),
+        nested: Vec<Vec<i32>>,
+        option: Option<u64>,
+    }
+
+    #[derive(Reflect, FromReflect, Default, Debug, PartialEq)]
+    enum MyEnum {
+        #[default]
+        Unit,
+        Tuple(String, i16),
+        Struct {
+            value: u32,
+            list: Vec<f32>,
+        },
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Marker;
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct MyResource {
+        seed: u64,
+    }
+
+    mod renamed {
+        use bevy_ecs::prelude::*;
+        use bevy_reflect::Reflect;
+
+        /// `MyResource`, after it was moved and renamed.
+        #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+        #[reflect(Resource)]
+        pub struct Settings {
+            pub seed: u64,
+        }
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Everything>();
+            registry.register::<MyEnum>();
+            registry.register::<Marker>();
+            registry.register::<MyResource>();
+            registry.register::<String>();
+            registry.register_type_data::<String, ReflectSerialize>();
+            registry.register::<HashMap<String, u32>>();
+            registry.register::<(u8, String, f32)>();
+            registry.register::<Vec<Vec<i32>>>();
+            registry.register::<Vec<i32>>();
+            registry.register::<Vec<f32>>();
+            registry.register::<Option<u64>>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    fn everything() -> Everything {
+        Everything {
+            unit: MyEnum::Unit,
+            tuple_variant: MyEnum::Tuple("tuple".to_string(), -7),
+            struct_variant: MyEnum::Struct {
+                value: 42,
+                list: vec![1.5, -2.25],
+            },
+            map: [("a".to_string(), 1), ("b".to_string(), 2)]
+                .into_iter()
+                .collect(),
+            tuple: (3, "three".to_string(), 3.0),
+            nested: vec![vec![1, 2], vec![], vec![3]],
+            option: Some(9),
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_binary() {
+        let mut world = create_world();
+        world.spawn(everything());
+        world.spawn((Everything::default(), Marker));
+        world.insert_resource(MyResource { seed: 1234 });
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = DynamicScene::from_world(&world, &registry);
+        let bytes = scene.serialize_binary(&registry.0).unwrap();
+        assert!(is_binary_scene(&bytes));
+        assert!(
+            bytes.len() < scene.serialize_ron(&registry.0).unwrap().len() / 2,
+            "binary scenes should be much smaller than RON scenes"
+        );
+
+        let deserialized = deserialize_binary(&bytes, &registry.read()).unwrap();
+        assert_eq!(2, deserialized.entities.len());
+        assert_eq!(1, deserialized.resources.len());
+
+        let mut dst_world = create_world();
+        deserialized
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(
+            &MyResource { seed: 1234 },
+            dst_world.resource::<MyResource>()
+        );
+        let mut query = dst_world.query::<(&Everything, Option<&Marker>)>();
+        let mut results = query.iter(&dst_world).collect::<Vec<_>>();
+        results.sort_by_key(|(_, marker)| marker.is_some());
+        assert_eq!(&everything(), results[0].0);
+        assert_eq!((&Everything::default(), Some(&Marker)), results[1]);
+    }
+
+    #[test]
+    fn should_reject_invalid_data() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+
+        assert!(matches!(
+            deserialize_binary(b"(resources: {}, entities: {})", &registry),
+            Err(SceneBinaryError::InvalidHeader)
+        ));
+
+        let mut bytes = SCENE_BINARY_MAGIC.to_vec();
+        bytes.push(SCENE_BINARY_VERSION + 1);
+        assert!(matches!(
+            deserialize_binary(&bytes, &registry),
+            Err(SceneBinaryError::UnsupportedVersion(_))
+        ));
+    }
+
+    #[test]
+    fn should_check_type_fingerprints() {
+        let mut world = create_world();
+        world.spawn(everything());
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = DynamicScene::from_world(&world, &registry);
+        let bytes = serialize_binary(&scene, &registry.0).unwrap();
+
+        // A registry missing a type of the scene.
+        let other_registry = AppTypeRegistry::default();
+        other_registry.write().register::<Marker>();
+        assert!(matches!(
+            deserialize_binary(&bytes, &other_registry.read()),
+            Err(SceneBinaryError::UnregisteredType { .. })
+        ));
+
+        // A type whose layout changed since the scene was written.
+        let mut header = SceneBinaryHeader::new(&scene, &registry.read()).unwrap();
+        assert_eq!(1, header.types.len());
+        header.types[0].1 ^= 1;
+        let mut tampered = SCENE_BINARY_MAGIC.to_vec();
+        tampered.push(SCENE_BINARY_VERSION);
+        let tampered = postcard::to_extend(&header, tampered).unwrap();
+        assert!(matches!(
+            deserialize_binary(&tampered, &registry.read()),
+            Err(SceneBinaryError::FingerprintMismatch { .. })
+        ));
+    }
+    #[test]
+    fn should_read_renamed_types_through_aliases() {
+        let mut world = create_world();
+        world.insert_resource(MyResource { seed: 7 });
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let scene = DynamicScene::from_world(&world, &registry);
+        let bytes = serialize_binary(&scene, &registry.0).unwrap();
+        let old_path = registry
+            .read()
+            .get(TypeId::of::<MyResource>())
+            .unwrap()
+            .type_path()
+            .to_string();
+
+        let mut dst_world = World::new();
+        let dst_registry = AppTypeRegistry::default();
+        dst_registry
+            .write()
+            .register_type_alias::<renamed::Settings>(old_path);
+        let deserialized = deserialize_binary(&bytes, &dst_registry.read()).unwrap();
+        dst_world.insert_resource(dst_registry);
+        deserialized
+            .write_to_world(&mut dst_world, &mut EntityMap::default())
+            .unwrap();
+        assert_eq!(
+            &renamed::Settings { seed: 7 },
+            dst_world.resource::<renamed::Settings>()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::any::TypeId;
 
-use crate::{DynamicSceneBuilder, Scene, SceneSpawnError};
+use crate::{DynamicSceneBuilder, Scene, SceneDiff, SceneSpawnError};
 use anyhow::Result;
 use bevy_app::AppTypeRegistry;
 use bevy_ecs::{
@@ -60,6 +60,11 @@ impl DynamicScene {
         builder.build()
     }
 
+    /// Compares this scene with a later snapshot of the same world, see [`SceneDiff`].
+    pub fn diff(&self, other: &DynamicScene) -> SceneDiff {
+        SceneDiff::new(self, other)
+    }
+
     /// Write the resources, the dynamic entities, and their corresponding components to the given world.
     ///
     /// This method will return a [`SceneSpawnError`] if a type either is not registered
@@ -170,6 +175,18 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    // TODO: move to AssetSaver when it is implemented
+    /// Serialize this dynamic scene into the compact binary format, see [`serialize_binary`].
+    ///
+    /// [`serialize_binary`]: crate::serialize_binary
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(
+        &self,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, crate::SceneBinaryError> {
+        crate::serialize_binary(self, registry)
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
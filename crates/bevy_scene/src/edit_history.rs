@@ -0,0 +1,895 @@
+use crate::DynamicSceneBuilder;
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Command, Resource},
+    world::{Mut, World},
+};
+use bevy_reflect::{ParsedPath, Reflect, TypeRegistry};
+use thiserror::Error;
+
+/// An edit of the world, applied and recorded with [`EditHistory::apply`].
+///
+/// Components are identified by their type path or type name, and must be registered with
+/// `#[reflect(Component)]`.
+#[derive(Debug)]
+pub enum Edit {
+    /// Sets the field at `path` of a component, parsed as a [`ParsedPath`]. An empty path sets
+    /// the whole component.
+    SetField {
+        /// The entity with the component.
+        entity: Entity,
+        /// The type path or type name of the component.
+        component: String,
+        /// The path of the field in the component.
+        path: String,
+        /// The new value of the field, which must have the type of the field.
+        value: Box<dyn Reflect>,
+    },
+    /// Inserts a component, replacing the existing one.
+    InsertComponent {
+        /// The entity to insert the component on.
+        entity: Entity,
+        /// The component to insert.
+        component: Box<dyn Reflect>,
+    },
+    /// Removes a component.
+    RemoveComponent {
+        /// The entity to remove the component from.
+        entity: Entity,
+        /// The type path or type name of the component.
+        component: String,
+    },
+    /// Spawns an entity with the given components.
+    Spawn {
+        /// The components of the new entity.
+        components: Vec<Box<dyn Reflect>>,
+    },
+    /// Despawns an entity.
+    ///
+    /// Undoing it spawns a new entity with the reflected components of the despawned one: the
+    /// entity id changes, and the components that aren't registered are lost.
+    Despawn {
+        /// The entity to despawn.
+        entity: Entity,
+    },
+}
+
+/// A recorded [`Edit`], with the state it replaced so that it can be reverted.
+///
+/// The entity of an operation is updated when undoing or redoing another operation respawns it.
+#[derive(Debug)]
+pub enum EditOperation {
+    /// A recorded [`Edit::SetField`].
+    SetField {
+        /// The entity with the component.
+        entity: Entity,
+        /// The type path or type name of the component.
+        component: String,
+        /// The path of the field in the component.
+        path: String,
+        /// The value of the field before the edit.
+        old: Box<dyn Reflect>,
+        /// The value of the field after the edit.
+        new: Box<dyn Reflect>,
+    },
+    /// A recorded [`Edit::InsertComponent`].
+    InsertComponent {
+        /// The entity the component was inserted on.
+        entity: Entity,
+        /// The component it replaced, if the entity had one.
+        old: Option<Box<dyn Reflect>>,
+        /// The inserted component.
+        new: Box<dyn Reflect>,
+    },
+    /// A recorded [`Edit::RemoveComponent`].
+    RemoveComponent {
+        /// The entity the component was removed from.
+        entity: Entity,
+        /// The removed component.
+        old: Box<dyn Reflect>,
+    },
+    /// A recorded [`Edit::Spawn`].
+    Spawn {
+        /// The spawned entity.
+        entity: Entity,
+        /// The components the entity was spawned with.
+        components: Vec<Box<dyn Reflect>>,
+    },
+    /// A recorded [`Edit::Despawn`].
+    Despawn {
+        /// The despawned entity.
+        entity: Entity,
+        /// The reflected components the entity had when it was despawned.
+        components: Vec<Box<dyn Reflect>>,
+    },
+}
+
+/// An error while applying, undoing or redoing an edit.
+#[derive(Error, Debug)]
+pub enum EditError {
+    #[error("edit targets the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
+    UnregisteredType { type_name: String },
+    #[error("edit targets the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
+    UnregisteredComponent { type_name: String },
+    #[error("edit targets the entity {0:?}, which doesn't exist")]
+    MissingEntity(Entity),
+    #[error("edit targets the component `{type_name}`, which the entity doesn't have")]
+    MissingComponent { type_name: String },
+    #[error("edit targets the invalid path `{path}`: {message}")]
+    InvalidPath { path: String, message: String },
+    #[error("edit sets the field `{path}` of type `{expected}` to a value of type `{found}`")]
+    MismatchedType {
+        path: String,
+        expected: String,
+        found: String,
+    },
+}
+
+/// A step of the [`EditHistory`]: the operations undone and redone together.
+#[derive(Debug, Default)]
+pub struct EditGroup {
+    /// The operations of the group, in the order they were applied.
+    pub operations: Vec<EditOperation>,
+    /// Edits applied with this key are merged into the group, see [`EditHistory::apply_merged`].
+    merge_key: Option<u64>,
+}
+
+/// The history of the edits of the world, to undo and redo them.
+///
+/// Each edit applied with [`EditHistory::apply`] is a step of the history, unless a group is
+/// opened with [`EditHistory::begin_group`]: the edits applied until [`EditHistory::end_group`]
+/// are then undone and redone together. Continuous edits, such as dragging a value, can be
+/// merged into a single step with [`EditHistory::apply_merged`].
+///
+/// The history is stored in the world: use it with [`World::resource_scope`], or with the
+/// [`ApplyEdit`], [`UndoEdit`] and [`RedoEdit`] commands.
+#[derive(Resource, Debug, Default)]
+pub struct EditHistory {
+    undo: Vec<EditGroup>,
+    redo: Vec<EditGroup>,
+    group_depth: usize,
+    max_len: Option<usize>,
+}
+
+impl EditHistory {
+    /// Limits the number of steps kept in the history, dropping the oldest ones.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Applies `edit` to the world and records it.
+    ///
+    /// This clears the steps that were undone.
+    pub fn apply(&mut self, world: &mut World, edit: Edit) -> Result<(), EditError> {
+        self.apply_inner(world, edit, None)
+    }
+
+    /// Applies `edit` to the world and records it, merging it into the latest step if that
+    /// step was applied with the same `merge_key`.
+    ///
+    /// Setting the same field repeatedly is merged into a single operation, which restores the
+    /// value from before the first edit when undone. Use a new key for each drag, or call
+    /// [`EditHistory::end_merge`] when it ends.
+    pub fn apply_merged(
+        &mut self,
+        world: &mut World,
+        edit: Edit,
+        merge_key: u64,
+    ) -> Result<(), EditError> {
+        self.apply_inner(world, edit, Some(merge_key))
+    }
+
+    /// Stops merging edits into the latest step.
+    pub fn end_merge(&mut self) {
+        if let Some(group) = self.undo.last_mut() {
+            group.merge_key = None;
+        }
+    }
+
+    /// Opens a group: the edits applied until the matching [`EditHistory::end_group`] are a
+    /// single step of the history. Groups can be nested.
+    pub fn begin_group(&mut self) {
+        if self.group_depth == 0 {
+            self.push(EditGroup::default());
+        }
+        self.group_depth += 1;
+    }
+
+    /// Closes the group opened by [`EditHistory::begin_group`].
+    pub fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        if self.group_depth == 0
+            && matches!(self.undo.last(), Some(group) if group.operations.is_empty())
+        {
+            self.undo.pop();
+        }
+    }
+
+    /// Reverts the latest step, returning `false` if there is nothing to undo.
+    ///
+    /// If an operation of the step fails, the operations of the step already reverted are
+    /// applied again, and the step stays the latest one to undo.
+    pub fn undo(&mut self, world: &mut World) -> Result<bool, EditError> {
+        let Some(mut group) = self.undo.pop() else {
+            return Ok(false);
+        };
+        self.group_depth = 0;
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        for index in (0..group.operations.len()).rev() {
+            match group.operations[index].revert(world, &type_registry) {
+                Ok(respawned) => self.map_respawned(&mut group, respawned),
+                Err(err) => {
+                    for index in index + 1..group.operations.len() {
+                        let respawned = group.operations[index].redo(world, &type_registry);
+                        self.map_respawned(&mut group, respawned.ok().flatten());
+                    }
+                    self.undo.push(group);
+                    return Err(err);
+                }
+            }
+        }
+        group.merge_key = None;
+        self.redo.push(group);
+        Ok(true)
+    }
+
+    /// Applies the latest undone step again, returning `false` if there is nothing to redo.
+    ///
+    /// If an operation of the step fails, the operations of the step already applied are
+    /// reverted, and the step stays the latest one to redo.
+    pub fn redo(&mut self, world: &mut World) -> Result<bool, EditError> {
+        let Some(mut group) = self.redo.pop() else {
+            return Ok(false);
+        };
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        for index in 0..group.operations.len() {
+            match group.operations[index].redo(world, &type_registry) {
+                Ok(respawned) => self.map_respawned(&mut group, respawned),
+                Err(err) => {
+                    for index in (0..index).rev() {
+                        let respawned = group.operations[index].revert(world, &type_registry);
+                        self.map_respawned(&mut group, respawned.ok().flatten());
+                    }
+                    self.redo.push(group);
+                    return Err(err);
+                }
+            }
+        }
+        self.undo.push(group);
+        Ok(true)
+    }
+
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Returns `true` if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets every step.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.group_depth = 0;
+    }
+
+    fn apply_inner(
+        &mut self,
+        world: &mut World,
+        edit: Edit,
+        merge_key: Option<u64>,
+    ) -> Result<(), EditError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let operation = edit.apply(world, &type_registry.read())?;
+        self.redo.clear();
+
+        let merge = merge_key.is_some()
+            && matches!(self.undo.last(), Some(group) if group.merge_key == merge_key);
+        if self.group_depth == 0 && !merge {
+            self.push(EditGroup::default());
+        }
+        let group = self.undo.last_mut().unwrap();
+        if self.group_depth == 0 {
+            group.merge_key = merge_key;
+        }
+        let merged = merge
+            && match group.operations.last_mut() {
+                Some(last) => last.merge(&operation),
+                None => false,
+            };
+        if !merged {
+            group.operations.push(operation);
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, group: EditGroup) {
+        if let Some(last) = self.undo.last_mut() {
+            last.merge_key = None;
+        }
+        self.undo.push(group);
+        if let Some(max_len) = self.max_len {
+            let excess = self.undo.len().saturating_sub(max_len);
+            self.undo.drain(..excess);
+        }
+    }
+
+    /// Replaces the entity respawned by an operation of `group`, if any, in every step.
+    fn map_respawned(&mut self, group: &mut EditGroup, respawned: Option<(Entity, Entity)>) {
+        if let Some((from, to)) = respawned {
+            self.map_entity(from, to);
+            group.map_entity(from, to);
+        }
+    }
+
+    /// Replaces the entity `from`, respawned as `to`, in every step.
+    fn map_entity(&mut self, from: Entity, to: Entity) {
+        for group in self.undo.iter_mut().chain(&mut self.redo) {
+            group.map_entity(from, to);
+        }
+    }
+}
+
+impl EditGroup {
+    fn map_entity(&mut self, from: Entity, to: Entity) {
+        for operation in &mut self.operations {
+            if operation.entity() == from {
+                *operation.entity_mut() = to;
+            }
+        }
+    }
+}
+
+impl Edit {
+    /// Applies the edit, returning the operation to record.
+    pub fn apply(
+        self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+    ) -> Result<EditOperation, EditError> {
+        match self {
+            Edit::SetField {
+                entity,
+                component,
+                path,
+                value,
+            } => {
+                let reflect_component = reflect_component(type_registry, &component)?;
+                let old = with_field(
+                    world,
+                    entity,
+                    reflect_component,
+                    &component,
+                    &path,
+                    |field| {
+                        let old = field.clone_value();
+                        apply_field(field, &*value, &path)?;
+                        Ok(old)
+                    },
+                )?;
+                Ok(EditOperation::SetField {
+                    entity,
+                    component,
+                    path,
+                    old,
+                    new: value,
+                })
+            }
+            Edit::InsertComponent { entity, component } => {
+                let reflect_component = reflect_component(type_registry, component.type_name())?;
+                let old = world
+                    .get_entity(entity)
+                    .ok_or(EditError::MissingEntity(entity))?;
+                let old = reflect_component.reflect(old).map(Reflect::clone_value);
+                reflect_component.insert(&mut world.entity_mut(entity), &*component);
+                Ok(EditOperation::InsertComponent {
+                    entity,
+                    old,
+                    new: component,
+                })
+            }
+            Edit::RemoveComponent { entity, component } => {
+                let reflect_component = reflect_component(type_registry, &component)?;
+                let old = world
+                    .get_entity(entity)
+                    .ok_or(EditError::MissingEntity(entity))?;
+                let old = reflect_component
+                    .reflect(old)
+                    .map(Reflect::clone_value)
+                    .ok_or(EditError::MissingComponent {
+                        type_name: component,
+                    })?;
+                reflect_component.remove(&mut world.entity_mut(entity));
+                Ok(EditOperation::RemoveComponent { entity, old })
+            }
+            Edit::Spawn { components } => {
+                let entity = spawn(world, &components, type_registry)?;
+                Ok(EditOperation::Spawn { entity, components })
+            }
+            Edit::Despawn { entity } => {
+                let components = extract_components(world, entity)?;
+                world.despawn(entity);
+                Ok(EditOperation::Despawn { entity, components })
+            }
+        }
+    }
+}
+
+impl EditOperation {
+    /// The entity the operation targets.
+    pub fn entity(&self) -> Entity {
+        match self {
+            EditOperation::SetField { entity, .. }
+            | EditOperation::InsertComponent { entity, .. }
+            | EditOperation::RemoveComponent { entity, .. }
+            | EditOperation::Spawn { entity, .. }
+            | EditOperation::Despawn { entity, .. } => *entity,
+        }
+    }
+
+    fn entity_mut(&mut self) -> &mut Entity {
+        match self {
+            EditOperation::SetField { entity, .. }
+            | EditOperation::InsertComponent { entity, .. }
+            | EditOperation::RemoveComponent { entity, .. }
+            | EditOperation::Spawn { entity, .. }
+            | EditOperation::Despawn { entity, .. } => entity,
+        }
+    }
+
+    /// Merges `next` into this operation if both set the same field, returning `true` if they
+    /// were merged.
+    fn merge(&mut self, next: &EditOperation) -> bool {
+        match (self, next) {
+            (
+                EditOperation::SetField {
+                    entity,
+                    component,
+                    path,
+                    new,
+                    ..
+                },
+                EditOperation::SetField {
+                    entity: next_entity,
+                    component: next_component,
+                    path: next_path,
+                    new: next_new,
+                    ..
+                },
+            ) if entity == next_entity && component == next_component && path == next_path => {
+                *new = next_new.clone_value();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reverts the operation, returning the entity it respawned, if any.
+    fn revert(
+        &mut self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+    ) -> Result<Option<(Entity, Entity)>, EditError> {
+        match self {
+            EditOperation::SetField {
+                entity,
+                component,
+                path,
+                old,
+                ..
+            } => {
+                let reflect_component = reflect_component(type_registry, component)?;
+                with_field(
+                    world,
+                    *entity,
+                    reflect_component,
+                    component,
+                    path,
+                    |field| apply_field(field, &**old, path),
+                )?;
+            }
+            EditOperation::InsertComponent { entity, old, new } => {
+                let reflect_component = reflect_component(type_registry, new.type_name())?;
+                let mut entity_mut = world
+                    .get_entity_mut(*entity)
+                    .ok_or(EditError::MissingEntity(*entity))?;
+                match old {
+                    Some(old) => reflect_component.insert(&mut entity_mut, &**old),
+                    None => reflect_component.remove(&mut entity_mut),
+                }
+            }
+            EditOperation::RemoveComponent { entity, old } => {
+                let reflect_component = reflect_component(type_registry, old.type_name())?;
+                let mut entity_mut = world
+                    .get_entity_mut(*entity)
+                    .ok_or(EditError::MissingEntity(*entity))?;
+                reflect_component.insert(&mut entity_mut, &**old);
+            }
+            EditOperation::Spawn { entity, .. } => {
+                if !world.despawn(*entity) {
+                    return Err(EditError::MissingEntity(*entity));
+                }
+            }
+            EditOperation::Despawn { entity, components } => {
+                let despawned = *entity;
+                *entity = spawn(world, components, type_registry)?;
+                return Ok(Some((despawned, *entity)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Applies the operation again, returning the entity it respawned, if any.
+    fn redo(
+        &mut self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+    ) -> Result<Option<(Entity, Entity)>, EditError> {
+        match self {
+            EditOperation::SetField {
+                entity,
+                component,
+                path,
+                new,
+                ..
+            } => {
+                let reflect_component = reflect_component(type_registry, component)?;
+                with_field(
+                    world,
+                    *entity,
+                    reflect_component,
+                    component,
+                    path,
+                    |field| apply_field(field, &**new, path),
+                )?;
+            }
+            EditOperation::InsertComponent { entity, new, .. } => {
+                let reflect_component = reflect_component(type_registry, new.type_name())?;
+                let mut entity_mut = world
+                    .get_entity_mut(*entity)
+                    .ok_or(EditError::MissingEntity(*entity))?;
+                reflect_component.insert(&mut entity_mut, &**new);
+            }
+            EditOperation::RemoveComponent { entity, old } => {
+                let reflect_component = reflect_component(type_registry, old.type_name())?;
+                let mut entity_mut = world
+                    .get_entity_mut(*entity)
+                    .ok_or(EditError::MissingEntity(*entity))?;
+                reflect_component.remove(&mut entity_mut);
+            }
+            EditOperation::Spawn { entity, components } => {
+                let despawned = *entity;
+                *entity = spawn(world, components, type_registry)?;
+                return Ok(Some((despawned, *entity)));
+            }
+            EditOperation::Despawn { entity, components } => {
+                *components = extract_components(world, *entity)?;
+                world.despawn(*entity);
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn reflect_component<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<&'a ReflectComponent, EditError> {
+    type_registry
+        .get_with_type_path(type_name)
+        .ok_or_else(|| EditError::UnregisteredType {
+            type_name: type_name.to_string(),
+        })?
+        .data::<ReflectComponent>()
+        .ok_or_else(|| EditError::UnregisteredComponent {
+            type_name: type_name.to_string(),
+        })
+}
+
+/// Calls `f` with the field at `path` of the component of `entity`.
+fn with_field<T>(
+    world: &mut World,
+    entity: Entity,
+    reflect_component: &ReflectComponent,
+    type_name: &str,
+    path: &str,
+    f: impl FnOnce(&mut dyn Reflect) -> Result<T, EditError>,
+) -> Result<T, EditError> {
+    let mut entity_mut = world
+        .get_entity_mut(entity)
+        .ok_or(EditError::MissingEntity(entity))?;
+    let mut component = reflect_component
+        .reflect_mut(&mut entity_mut)
+        .ok_or_else(|| EditError::MissingComponent {
+            type_name: type_name.to_string(),
+        })?;
+    if path.is_empty() {
+        return f(&mut *component);
+    }
+    let invalid_path = |message: String| EditError::InvalidPath {
+        path: path.to_string(),
+        message,
+    };
+    let mut parsed_path = ParsedPath::parse(path).map_err(|err| invalid_path(err.to_string()))?;
+    let field = parsed_path
+        .reflect_element_mut(&mut *component)
+        .map_err(|err| invalid_path(err.to_string()))?;
+    f(field)
+}
+
+/// Applies `value` to `field`, checking first that it has the same type, which
+/// [`Reflect::apply`] panics on otherwise.
+fn apply_field(field: &mut dyn Reflect, value: &dyn Reflect, path: &str) -> Result<(), EditError> {
+    if field.type_name() != value.type_name() {
+        return Err(EditError::MismatchedType {
+            path: path.to_string(),
+            expected: field.type_name().to_string(),
+            found: value.type_name().to_string(),
+        });
+    }
+    field.apply(value);
+    Ok(())
+}
+
+fn spawn(
+    world: &mut World,
+    components: &[Box<dyn Reflect>],
+    type_registry: &TypeRegistry,
+) -> Result<Entity, EditError> {
+    let reflect_components = components
+        .iter()
+        .map(|component| reflect_component(type_registry, component.type_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut entity_mut = world.spawn_empty();
+    for (reflect_component, component) in reflect_components.into_iter().zip(components) {
+        reflect_component.insert(&mut entity_mut, &**component);
+    }
+    Ok(entity_mut.id())
+}
+
+/// Returns a copy of the registered components of `entity`.
+fn extract_components(world: &World, entity: Entity) -> Result<Vec<Box<dyn Reflect>>, EditError> {
+    if world.get_entity(entity).is_none() {
+        return Err(EditError::MissingEntity(entity));
+    }
+    let mut builder = DynamicSceneBuilder::from_world(world);
+    builder.extract_entity(entity);
+    Ok(builder
+        .build()
+        .entities
+        .pop()
+        .map(|entity| entity.components)
+        .unwrap_or_default())
+}
+
+/// A [`Command`] that applies an [`Edit`] and records it in the [`EditHistory`].
+pub struct ApplyEdit(pub Edit);
+
+impl Command for ApplyEdit {
+    fn write(self, world: &mut World) {
+        world.resource_scope(|world, mut history: Mut<EditHistory>| {
+            if let Err(err) = history.apply(world, self.0) {
+                bevy_utils::tracing::error!("Failed to apply an edit: {}", err);
+            }
+        });
+    }
+}
+
+/// A [`Command`] that undoes the latest step of the [`EditHistory`].
+pub struct UndoEdit;
+
+impl Command for UndoEdit {
+    fn write(self, world: &mut World) {
+        world.resource_scope(|world, mut history: Mut<EditHistory>| {
+            if let Err(err) = history.undo(world) {
+                bevy_utils::tracing::error!("Failed to undo an edit: {}", err);
+            }
+        });
+    }
+}
+
+/// A [`Command`] that redoes the latest undone step of the [`EditHistory`].
+pub struct RedoEdit;
+
+impl Command for RedoEdit {
+    fn write(self, world: &mut World) {
+        world.resource_scope(|world, mut history: Mut<EditHistory>| {
+            if let Err(err) = history.redo(world) {
+                bevy_utils::tracing::error!("Failed to redo an edit: {}", err);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent, world::World};
+    use bevy_reflect::Reflect;
+
+    use super::{Edit, EditError, EditHistory};
+
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Label(String);
+
+    fn setup() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Position>();
+            registry.register::<Label>();
+        }
+        let entity = world.spawn(Position { x: 1.0, y: 2.0 }).id();
+        (world, entity)
+    }
+
+    fn set_x(entity: Entity, x: f32) -> Edit {
+        Edit::SetField {
+            entity,
+            component: std::any::type_name::<Position>().to_string(),
+            path: "x".to_string(),
+            value: Box::new(x),
+        }
+    }
+
+    #[test]
+    fn undo_redo_and_merge() {
+        let (mut world, entity) = setup();
+        let mut history = EditHistory::default();
+
+        // A drag is a single step.
+        for x in [3.0, 4.0, 5.0] {
+            history
+                .apply_merged(&mut world, set_x(entity, x), 7)
+                .unwrap();
+        }
+        history.end_merge();
+        history
+            .apply_merged(&mut world, set_x(entity, 6.0), 7)
+            .unwrap();
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 6.0);
+
+        assert!(history.undo(&mut world).unwrap());
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 5.0);
+        assert!(history.undo(&mut world).unwrap());
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 1.0);
+        assert!(!history.undo(&mut world).unwrap());
+
+        assert!(history.redo(&mut world).unwrap());
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 5.0);
+
+        // A new edit clears the redo steps.
+        history
+            .apply(
+                &mut world,
+                Edit::InsertComponent {
+                    entity,
+                    component: Box::new(Label("a".into())),
+                },
+            )
+            .unwrap();
+        assert!(!history.can_redo());
+        history
+            .apply(
+                &mut world,
+                Edit::RemoveComponent {
+                    entity,
+                    component: std::any::type_name::<Position>().to_string(),
+                },
+            )
+            .unwrap();
+        assert!(world.get::<Position>(entity).is_none());
+        history.undo(&mut world).unwrap();
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 5.0, y: 2.0 })
+        );
+        history.undo(&mut world).unwrap();
+        assert!(world.get::<Label>(entity).is_none());
+    }
+
+    #[test]
+    fn groups_and_respawned_entities() {
+        let (mut world, entity) = setup();
+        let mut history = EditHistory::default();
+
+        history.begin_group();
+        history.apply(&mut world, set_x(entity, 10.0)).unwrap();
+        history.apply(&mut world, Edit::Despawn { entity }).unwrap();
+        history.end_group();
+        assert!(world.get_entity(entity).is_none());
+
+        // Undoing the despawn respawns the entity, and the earlier edit targets the new one.
+        history.undo(&mut world).unwrap();
+        let mut query = world.query::<(Entity, &Position)>();
+        let (respawned, position) = query.single(&world);
+        assert_eq!(position, &Position { x: 1.0, y: 2.0 });
+
+        history.redo(&mut world).unwrap();
+        assert!(world.get_entity(respawned).is_none());
+        history.undo(&mut world).unwrap();
+        let (respawned, _) = query.single(&world);
+        history.apply(&mut world, set_x(respawned, 20.0)).unwrap();
+        history.undo(&mut world).unwrap();
+        assert_eq!(world.get::<Position>(respawned).unwrap().x, 1.0);
+
+        assert!(history.apply(&mut world, set_x(entity, 0.0)).is_err());
+    }
+
+    #[test]
+    fn mismatched_field_type() {
+        let (mut world, entity) = setup();
+        let mut history = EditHistory::default();
+
+        let edit = Edit::SetField {
+            entity,
+            component: std::any::type_name::<Position>().to_string(),
+            path: "x".to_string(),
+            value: Box::new("one".to_string()),
+        };
+        assert!(matches!(
+            history.apply(&mut world, edit),
+            Err(EditError::MismatchedType { .. })
+        ));
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 1.0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn failed_steps_are_rolled_back() {
+        let (mut world, entity) = setup();
+        let other = world.spawn(Position::default()).id();
+        let mut history = EditHistory::default();
+
+        history.begin_group();
+        history.apply(&mut world, set_x(other, 10.0)).unwrap();
+        history.apply(&mut world, set_x(entity, 20.0)).unwrap();
+        history.end_group();
+
+        // Reverting `entity` succeeds, then reverting `other` fails: `entity` is set again.
+        let mut removed = world.entity_mut(other).take::<Position>().unwrap();
+        assert!(matches!(
+            history.undo(&mut world),
+            Err(EditError::MissingComponent { .. })
+        ));
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 20.0);
+        assert!(history.can_undo());
+        assert!(!history.can_redo());
+
+        world.entity_mut(other).insert(removed.clone());
+        history.undo(&mut world).unwrap();
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 1.0);
+
+        // Setting `other` succeeds, then setting `entity` fails: `other` is reverted.
+        removed = world.entity_mut(entity).take::<Position>().unwrap();
+        assert!(matches!(
+            history.redo(&mut world),
+            Err(EditError::MissingComponent { .. })
+        ));
+        assert_eq!(world.get::<Position>(other).unwrap().x, 0.0);
+        assert!(history.can_redo());
+        assert!(!history.can_undo());
+
+        world.entity_mut(entity).insert(removed);
+        history.redo(&mut world).unwrap();
+        assert_eq!(world.get::<Position>(other).unwrap().x, 10.0);
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 20.0);
+    }
+}
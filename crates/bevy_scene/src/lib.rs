@@ -1,19 +1,31 @@
 #![allow(clippy::type_complexity)]
 
+#[cfg(feature = "serialize")]
+mod binary;
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod edit_history;
+#[cfg(feature = "serialize")]
+mod prefab;
 mod scene;
+mod scene_diff;
 mod scene_loader;
 mod scene_spawner;
 
 #[cfg(feature = "serialize")]
 pub mod serde;
 
+#[cfg(feature = "serialize")]
+pub use binary::*;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use edit_history::*;
+#[cfg(feature = "serialize")]
+pub use prefab::*;
 pub use scene::*;
+pub use scene_diff::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
 
@@ -22,10 +34,15 @@ pub mod prelude {
     pub use crate::{
         DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneSpawner,
     };
+
+    #[cfg(feature = "serialize")]
+    #[doc(hidden)]
+    pub use crate::{Prefab, PrefabOverride};
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
+use bevy_ecs::schedule::IntoSystemConfigs;
 
 #[derive(Default)]
 pub struct ScenePlugin;
@@ -37,9 +54,15 @@ impl Plugin for ScenePlugin {
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
             .init_resource::<SceneSpawner>()
-            .add_systems(Update, scene_spawner_system)
+            .register_type::<Prefab>()
+            .register_type::<PrefabOverride>()
+            .register_type::<Vec<PrefabOverride>>()
+            .add_systems(
+                Update,
+                (scene_spawner_system, apply_prefab_overrides).chain(),
+            )
             // Systems `*_bundle_spawner` must run before `scene_spawner_system`
-            .add_systems(PreUpdate, scene_spawner);
+            .add_systems(PreUpdate, (scene_spawner, prefab_spawner));
     }
 }
 
@@ -0,0 +1,422 @@
+use crate::{DynamicScene, InstanceId, SceneSpawner};
+use bevy_app::AppTypeRegistry;
+use bevy_asset::{AssetEvent, AssetServer, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    event::{Events, ManualEventReader},
+    prelude::{Changed, Component, RemovedComponents, Without},
+    reflect::ReflectComponent,
+    system::{Commands, Local, Query, Res, ResMut},
+    world::{Mut, World},
+};
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    FromReflect, ParsedPath, Reflect, TypeRegistry,
+};
+use bevy_utils::tracing::error;
+use serde::de::DeserializeSeed;
+use thiserror::Error;
+
+/// A reference to a [`DynamicScene`] asset, spawned as a child of the entity with this component
+/// with [`PrefabOverride`]s applied on top of it.
+///
+/// Scenes can reference other scenes with this component: the prefabs in a prefab are spawned in
+/// turn. When the prefab asset is reloaded, its instances are updated in place by the
+/// [`SceneSpawner`] and their overrides applied again, so changes to the prefab propagate to the
+/// fields that aren't overridden.
+///
+/// Once the prefab is spawned, the entity will have a [`PrefabInstance`] component.
+#[derive(Component, Reflect, Default, Clone, Debug, PartialEq)]
+#[reflect(Component)]
+pub struct Prefab {
+    /// The path of the scene asset to spawn.
+    pub path: String,
+    /// The overrides to apply to the spawned instance.
+    pub overrides: Vec<PrefabOverride>,
+}
+
+impl Prefab {
+    /// Creates a prefab of the scene at `path`, without overrides.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Adds an override to the prefab.
+    pub fn with_override(mut self, prefab_override: PrefabOverride) -> Self {
+        self.overrides.push(prefab_override);
+        self
+    }
+}
+
+/// A value replacing a field of a component of an entity of a [`Prefab`] instance.
+#[derive(Reflect, FromReflect, Default, Clone, Debug, PartialEq)]
+pub struct PrefabOverride {
+    /// The id of the entity in the prefab scene, as in [`DynamicEntity::entity`].
+    ///
+    /// [`DynamicEntity::entity`]: crate::DynamicEntity::entity
+    pub entity: u32,
+    /// The [type path](bevy_reflect::TypeRegistration::type_path) of the component.
+    pub component: String,
+    /// The path of the field in the component, parsed as a [`ParsedPath`]. An empty path
+    /// overrides the whole component.
+    pub path: String,
+    /// The new value of the field, in RON.
+    pub value: String,
+}
+
+impl PrefabOverride {
+    /// Creates an override setting the field at `path` of `component` to the RON `value`.
+    pub fn new(
+        entity: u32,
+        component: impl Into<String>,
+        path: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            entity,
+            component: component.into(),
+            path: path.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Creates an override setting the field at `path` of `component` to `value`.
+    pub fn from_value(
+        entity: u32,
+        component: impl Into<String>,
+        path: impl Into<String>,
+        value: &dyn Reflect,
+        type_registry: &TypeRegistry,
+    ) -> Result<Self, PrefabOverrideError> {
+        let serializer = TypedReflectSerializer::new(value, type_registry);
+        let value =
+            ron::to_string(&serializer).map_err(|err| PrefabOverrideError::InvalidValue {
+                type_name: value.type_name().to_string(),
+                message: err.to_string(),
+            })?;
+        Ok(Self::new(entity, component, path, value))
+    }
+
+    /// Applies the override to the component of `entity`.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), PrefabOverrideError> {
+        let registration = type_registry
+            .get_with_type_path(&self.component)
+            .ok_or_else(|| PrefabOverrideError::UnregisteredType {
+                type_name: self.component.clone(),
+            })?;
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            PrefabOverrideError::UnregisteredComponent {
+                type_name: self.component.clone(),
+            }
+        })?;
+        let mut entity_mut =
+            world
+                .get_entity_mut(entity)
+                .ok_or_else(|| PrefabOverrideError::MissingComponent {
+                    type_name: self.component.clone(),
+                })?;
+        let mut component = reflect_component
+            .reflect_mut(&mut entity_mut)
+            .ok_or_else(|| PrefabOverrideError::MissingComponent {
+                type_name: self.component.clone(),
+            })?;
+
+        let field = if self.path.is_empty() {
+            &mut *component
+        } else {
+            let mut path =
+                ParsedPath::parse(&self.path).map_err(|err| PrefabOverrideError::InvalidPath {
+                    path: self.path.clone(),
+                    message: err.to_string(),
+                })?;
+            path.reflect_element_mut(&mut *component).map_err(|err| {
+                PrefabOverrideError::InvalidPath {
+                    path: self.path.clone(),
+                    message: err.to_string(),
+                }
+            })?
+        };
+
+        let field_registration =
+            type_registry
+                .get_with_name(field.type_name())
+                .ok_or_else(|| PrefabOverrideError::UnregisteredType {
+                    type_name: field.type_name().to_string(),
+                })?;
+        let invalid_value = |message: String| PrefabOverrideError::InvalidValue {
+            type_name: field_registration.type_name().to_string(),
+            message,
+        };
+        let mut deserializer = ron::de::Deserializer::from_str(&self.value)
+            .map_err(|err| invalid_value(err.to_string()))?;
+        let value = TypedReflectDeserializer::new(field_registration, type_registry)
+            .deserialize(&mut deserializer)
+            .map_err(|err| invalid_value(err.to_string()))?;
+        field.apply(&*value);
+        Ok(())
+    }
+}
+
+/// An error while applying a [`PrefabOverride`].
+#[derive(Error, Debug)]
+pub enum PrefabOverrideError {
+    #[error("prefab override targets the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
+    UnregisteredType { type_name: String },
+    #[error("prefab override targets the unregistered component `{type_name}`. consider adding `#[reflect(Component)]` to your type")]
+    UnregisteredComponent { type_name: String },
+    #[error("prefab override targets the component `{type_name}`, which the entity doesn't have")]
+    MissingComponent { type_name: String },
+    #[error("prefab override targets the invalid path `{path}`: {message}")]
+    InvalidPath { path: String, message: String },
+    #[error("prefab override has an invalid value of type `{type_name}`: {message}")]
+    InvalidValue { type_name: String, message: String },
+    #[error("prefab has no entity {entity}")]
+    MissingEntity { entity: u32 },
+}
+
+/// The spawned instance of a [`Prefab`].
+#[derive(Component, Debug)]
+pub struct PrefabInstance {
+    /// The prefab scene.
+    pub handle: Handle<DynamicScene>,
+    /// The instance of the prefab scene.
+    pub instance_id: InstanceId,
+    /// Whether the overrides of the [`Prefab`] are applied to the instance.
+    overrides_applied: bool,
+}
+
+/// System that spawns the scene of each new or changed [`Prefab`], and despawns it when the
+/// component is removed.
+pub fn prefab_spawner(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut scene_spawner: ResMut<SceneSpawner>,
+    mut prefabs: Query<(Entity, &Prefab, Option<&mut PrefabInstance>), Changed<Prefab>>,
+    mut removed: RemovedComponents<Prefab>,
+    instances: Query<&PrefabInstance, Without<Prefab>>,
+) {
+    for entity in &mut removed {
+        if let Ok(instance) = instances.get(entity) {
+            scene_spawner.despawn_instance(instance.instance_id);
+            commands.entity(entity).remove::<PrefabInstance>();
+        }
+    }
+
+    for (entity, prefab, instance) in &mut prefabs {
+        let handle = asset_server.load(prefab.path.as_str());
+        match instance {
+            Some(mut instance) if instance.handle == handle => {
+                instance.overrides_applied = false;
+            }
+            instance => {
+                let instance_id = scene_spawner.spawn_dynamic_as_child(handle.clone(), entity);
+                let new_instance = PrefabInstance {
+                    handle,
+                    instance_id,
+                    overrides_applied: false,
+                };
+                if let Some(mut old_instance) = instance {
+                    scene_spawner.despawn_instance(old_instance.instance_id);
+                    *old_instance = new_instance;
+                } else {
+                    commands.entity(entity).insert(new_instance);
+                }
+            }
+        }
+    }
+}
+
+/// System that applies the overrides of each [`Prefab`] once its instance is spawned, and
+/// again after its scene is reloaded.
+///
+/// This must run after [`scene_spawner_system`](crate::scene_spawner_system).
+pub fn apply_prefab_overrides(
+    world: &mut World,
+    mut scene_asset_event_reader: Local<ManualEventReader<AssetEvent<DynamicScene>>>,
+) {
+    let modified = scene_asset_event_reader
+        .iter(world.resource::<Events<AssetEvent<DynamicScene>>>())
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.clone_weak()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let mut pending = Vec::new();
+    world.resource_scope(|world, scene_spawner: Mut<SceneSpawner>| {
+        let mut prefabs = world.query::<(&Prefab, &mut PrefabInstance)>();
+        for (prefab, mut instance) in prefabs.iter_mut(world) {
+            if !scene_spawner.instance_is_ready(instance.instance_id) {
+                continue;
+            }
+            if instance.overrides_applied && !modified.contains(&instance.handle) {
+                continue;
+            }
+            instance.overrides_applied = true;
+            for prefab_override in &prefab.overrides {
+                let entity = Entity::from_raw(prefab_override.entity);
+                match scene_spawner.instance_entity(instance.instance_id, entity) {
+                    Some(entity) => pending.push((entity, prefab_override.clone())),
+                    None => error!(
+                        "Failed to apply an override of prefab `{}`: {}",
+                        prefab.path,
+                        PrefabOverrideError::MissingEntity {
+                            entity: prefab_override.entity
+                        }
+                    ),
+                }
+            }
+        }
+    });
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+    for (entity, prefab_override) in pending {
+        if let Err(err) = prefab_override.apply(world, entity, &type_registry) {
+            error!("Failed to apply a prefab override: {}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, AppTypeRegistry};
+    use bevy_asset::{AssetPath, AssetPlugin, Assets, HandleId};
+    use bevy_core::TaskPoolPlugin;
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_hierarchy::Children;
+    use bevy_reflect::Reflect;
+
+    use crate::{DynamicEntity, DynamicScene, Prefab, PrefabOverride, ScenePlugin};
+
+    #[derive(Component, Reflect, Default, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Stats {
+        health: u32,
+        speed: f32,
+    }
+
+    fn prefab_scene(health: u32, speed: f32) -> DynamicScene {
+        DynamicScene {
+            resources: Vec::new(),
+            entities: vec![DynamicEntity {
+                entity: 0,
+                components: vec![Box::new(Stats { health, speed })],
+            }],
+        }
+    }
+
+    fn instance_stats(app: &mut App, root: Entity) -> Stats {
+        let children = app.world.get::<Children>(root).expect("prefab not spawned");
+        app.world.get::<Stats>(children[0]).unwrap().clone()
+    }
+
+    #[test]
+    fn overrides_survive_hot_reload() {
+        let mut app = App::new();
+        app.add_plugin(TaskPoolPlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_plugin(ScenePlugin)
+            .register_type::<Stats>();
+
+        let path = "prefabs/enemy.scn.ron";
+        let handle = app
+            .world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set(HandleId::from(AssetPath::from(path)), prefab_scene(10, 1.0));
+
+        let override_health = {
+            let type_registry = app.world.resource::<AppTypeRegistry>().read();
+            PrefabOverride::from_value(
+                0,
+                std::any::type_name::<Stats>(),
+                "health",
+                &25u32,
+                &type_registry,
+            )
+            .unwrap()
+        };
+        let root = app
+            .world
+            .spawn(Prefab::new(path).with_override(override_health))
+            .id();
+        app.update();
+        app.update();
+        assert_eq!(
+            instance_stats(&mut app, root),
+            Stats {
+                health: 25,
+                speed: 1.0
+            }
+        );
+
+        // Changes to the prefab propagate, except to the overridden fields.
+        *app.world
+            .resource_mut::<Assets<DynamicScene>>()
+            .get_mut(&handle)
+            .unwrap() = prefab_scene(50, 2.0);
+        app.update();
+        app.update();
+        assert_eq!(
+            instance_stats(&mut app, root),
+            Stats {
+                health: 25,
+                speed: 2.0
+            }
+        );
+
+        // Changing the overrides applies them again.
+        app.world.get_mut::<Prefab>(root).unwrap().overrides[0] =
+            PrefabOverride::new(0, std::any::type_name::<Stats>(), "speed", "3.0");
+        app.update();
+        assert_eq!(
+            instance_stats(&mut app, root),
+            Stats {
+                health: 25,
+                speed: 3.0
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_override() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Stats>();
+        let entity = world.spawn(Stats::default()).id();
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+        let type_name = std::any::type_name::<Stats>();
+
+        assert!(PrefabOverride::new(0, type_name, "mana", "1")
+            .apply(&mut world, entity, &type_registry)
+            .is_err());
+        assert!(PrefabOverride::new(0, type_name, "health", "\"a\"")
+            .apply(&mut world, entity, &type_registry)
+            .is_err());
+        assert!(PrefabOverride::new(0, "Unknown", "health", "1")
+            .apply(&mut world, entity, &type_registry)
+            .is_err());
+        PrefabOverride::new(0, type_name, "", "(health: 4, speed: 0.5)")
+            .apply(&mut world, entity, &type_registry)
+            .unwrap();
+        assert_eq!(
+            world.get::<Stats>(entity),
+            Some(&Stats {
+                health: 4,
+                speed: 0.5
+            })
+        );
+    }
+}
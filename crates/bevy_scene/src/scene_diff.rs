@@ -0,0 +1,334 @@
+use std::any::TypeId;
+
+use crate::{DynamicEntity, DynamicScene, SceneSpawnError};
+use bevy_app::AppTypeRegistry;
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistration, TypeRegistry};
+use bevy_utils::HashMap;
+
+/// The values added, changed or removed between two versions of a list of reflected values, such
+/// as the resources of a scene or the components of one of its entities.
+#[derive(Debug, Default)]
+pub struct ReflectListDiff {
+    /// The values that are new or changed, with their new value.
+    pub changed: Vec<Box<dyn Reflect>>,
+    /// The type names of the values that were removed.
+    pub removed: Vec<String>,
+}
+
+impl ReflectListDiff {
+    /// Compares two lists of values, matching them by type name.
+    ///
+    /// Values that can't be compared with [`Reflect::reflect_partial_eq`] are considered
+    /// changed.
+    pub fn new(old: &[Box<dyn Reflect>], new: &[Box<dyn Reflect>]) -> Self {
+        let changed = new
+            .iter()
+            .filter(|new| {
+                !old.iter().any(|old| {
+                    old.type_name() == new.type_name()
+                        && old.reflect_partial_eq(&***new).unwrap_or(false)
+                })
+            })
+            .map(|new| new.clone_value())
+            .collect();
+        let removed = old
+            .iter()
+            .filter(|old| !new.iter().any(|new| new.type_name() == old.type_name()))
+            .map(|old| old.type_name().to_string())
+            .collect();
+        Self { changed, removed }
+    }
+
+    /// Returns `true` if no value changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The differences between two versions of a [`DynamicScene`], used to update the instances of a
+/// scene in place when it is reloaded.
+///
+/// Applying a diff to an instance only touches what changed in the scene: components and entities
+/// added at runtime, and runtime changes to the values that didn't change in the scene, are
+/// preserved, and entities that are still in the scene keep their [`Entity`] id.
+#[derive(Default)]
+pub struct SceneDiff {
+    /// The changes to the resources of the scene.
+    pub resources: ReflectListDiff,
+    /// The entities that are new in the scene.
+    pub spawned: Vec<DynamicEntity>,
+    /// The ids of the entities that were removed from the scene.
+    pub despawned: Vec<u32>,
+    /// The changes to the components of the entities that are in both versions of the scene.
+    pub changed: Vec<(u32, ReflectListDiff)>,
+}
+
+impl SceneDiff {
+    /// Compares the `old` and `new` versions of a scene.
+    pub fn new(old: &DynamicScene, new: &DynamicScene) -> Self {
+        let old_entities = old
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect::<HashMap<_, _>>();
+        let mut spawned = Vec::new();
+        let mut changed = Vec::new();
+        for new_entity in &new.entities {
+            match old_entities.get(&new_entity.entity) {
+                Some(old_entity) => {
+                    let diff = ReflectListDiff::new(&old_entity.components, &new_entity.components);
+                    if !diff.is_empty() {
+                        changed.push((new_entity.entity, diff));
+                    }
+                }
+                None => spawned.push(DynamicEntity {
+                    entity: new_entity.entity,
+                    components: new_entity
+                        .components
+                        .iter()
+                        .map(|component| component.clone_value())
+                        .collect(),
+                }),
+            }
+        }
+        let despawned = old
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .filter(|id| !new.entities.iter().any(|entity| entity.entity == *id))
+            .collect();
+
+        Self {
+            resources: ReflectListDiff::new(&old.resources, &new.resources),
+            spawned,
+            despawned,
+            changed,
+        }
+    }
+
+    /// Returns `true` if the two versions of the scene are the same.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+            && self.spawned.is_empty()
+            && self.despawned.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// Applies the diff to an instance of the scene in `world`, whose scene entities are mapped to
+    /// world entities by `entity_map`.
+    ///
+    /// Changed components and resources are replaced by their new value, and entities of the
+    /// instance that were despawned at runtime are ignored. `entity_map` is updated with the
+    /// spawned and despawned entities.
+    pub fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+
+        for resource in &self.resources.changed {
+            reflect_resource(&type_registry, resource.type_name())?.insert(world, &**resource);
+        }
+        for type_name in &self.resources.removed {
+            reflect_resource(&type_registry, type_name)?.remove(world);
+        }
+
+        for id in &self.despawned {
+            if let Some(entity) = entity_map.remove(Entity::from_raw(*id)) {
+                world.despawn(entity);
+            }
+        }
+
+        // Components referencing other entities are mapped once every entity exists.
+        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+        let mut write_components =
+            |world: &mut World, entity: Entity, components: &ReflectListDiff| {
+                let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                    return Ok(());
+                };
+                for component in &components.changed {
+                    let registration = registration(&type_registry, component.type_name())?;
+                    if registration.data::<ReflectMapEntities>().is_some() {
+                        scene_mappings
+                            .entry(registration.type_id())
+                            .or_default()
+                            .push(entity);
+                    }
+                    reflect_component(registration)?.insert(&mut entity_mut, &**component);
+                }
+                for type_name in &components.removed {
+                    let registration = registration(&type_registry, type_name)?;
+                    reflect_component(registration)?.remove(&mut entity_mut);
+                }
+                Ok(())
+            };
+
+        for scene_entity in &self.spawned {
+            let entity = world.spawn_empty().id();
+            entity_map.insert(Entity::from_raw(scene_entity.entity), entity);
+            let components = ReflectListDiff {
+                changed: scene_entity
+                    .components
+                    .iter()
+                    .map(|component| component.clone_value())
+                    .collect(),
+                removed: Vec::new(),
+            };
+            write_components(world, entity, &components)?;
+        }
+        for (id, components) in &self.changed {
+            if let Ok(entity) = entity_map.get(Entity::from_raw(*id)) {
+                write_components(world, entity, components)?;
+            }
+        }
+
+        for (type_id, entities) in scene_mappings {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect
+                    .map_specific_entities(world, entity_map, &entities)
+                    .unwrap();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn registration<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<&'a TypeRegistration, SceneSpawnError> {
+    type_registry
+        .get_with_name(type_name)
+        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+            type_name: type_name.to_string(),
+        })
+}
+
+fn reflect_component(
+    registration: &TypeRegistration,
+) -> Result<&ReflectComponent, SceneSpawnError> {
+    registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+            type_name: registration.type_name().to_string(),
+        })
+}
+
+fn reflect_resource<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<&'a ReflectResource, SceneSpawnError> {
+    registration(type_registry, type_name)?
+        .data::<ReflectResource>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredResource {
+            type_name: type_name.to_string(),
+        })
+}
+
+/// Returns a copy of `scene`, with each value cloned with [`Reflect::clone_value`].
+pub(crate) fn clone_dynamic_scene(scene: &DynamicScene) -> DynamicScene {
+    DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(|resource| resource.clone_value())
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|component| component.clone_value())
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::AppTypeRegistry;
+    use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectComponent, world::World};
+    use bevy_reflect::Reflect;
+
+    use crate::{scene_diff::clone_dynamic_scene, DynamicSceneBuilder, SceneDiff};
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct Runtime;
+
+    #[test]
+    fn apply_diff_preserves_runtime_state() {
+        let mut source = World::new();
+        source.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = source.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register::<Name>();
+        }
+        let kept = source.spawn((Health(10), Name("kept".into()))).id();
+        let removed = source.spawn(Health(5)).id();
+        let mut builder = DynamicSceneBuilder::from_world(&source);
+        builder.extract_entities([kept, removed].into_iter());
+        let old = builder.build();
+
+        let mut world = World::new();
+        world.insert_resource(source.resource::<AppTypeRegistry>().clone());
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let mut entity_map = EntityMap::default();
+        old.write_to_world(&mut world, &mut entity_map).unwrap();
+        let instance_kept = entity_map.get(kept).unwrap();
+        let instance_removed = entity_map.get(removed).unwrap();
+        world.entity_mut(instance_kept).insert(Runtime);
+
+        // The new version changes `Health`, removes `Name` and one entity, and adds another.
+        source.entity_mut(kept).insert(Health(20)).remove::<Name>();
+        let added = source.spawn(Name("added".into())).id();
+        source.despawn(removed);
+        let mut builder = DynamicSceneBuilder::from_world(&source);
+        builder.extract_entities([kept, added].into_iter());
+        let new = builder.build();
+
+        let diff = SceneDiff::new(&old, &new);
+        assert_eq!(diff.despawned, vec![removed.index()]);
+        assert_eq!(diff.spawned.len(), 1);
+        assert_eq!(diff.changed.len(), 1);
+        assert!(SceneDiff::new(&new, &clone_dynamic_scene(&new)).is_empty());
+
+        diff.apply(&mut world, &mut entity_map, &type_registry)
+            .unwrap();
+
+        let instance = world.entity(instance_kept);
+        assert_eq!(instance.get::<Health>(), Some(&Health(20)));
+        assert_eq!(instance.get::<Name>(), None);
+        assert_eq!(instance.get::<Runtime>(), Some(&Runtime));
+        assert!(world.get_entity(instance_removed).is_none());
+        assert!(entity_map.get(removed).is_err());
+        let instance_added = entity_map.get(added).unwrap();
+        assert_eq!(
+            world.get::<Name>(instance_added),
+            Some(&Name("added".into()))
+        );
+    }
+}
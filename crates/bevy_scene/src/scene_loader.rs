@@ -1,5 +1,5 @@
 #[cfg(feature = "serialize")]
-use crate::serde::SceneDeserializer;
+use crate::{deserialize_binary, is_binary_scene, serde::SceneDeserializer};
 use anyhow::{anyhow, Result};
 use bevy_app::AppTypeRegistry;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
@@ -32,6 +32,13 @@ impl AssetLoader for SceneLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
+            if is_binary_scene(bytes) {
+                let scene = deserialize_binary(bytes, &self.type_registry.read())
+                    .map_err(|e| anyhow!("{} at {}", e, load_context.path().to_string_lossy()))?;
+                load_context.set_default_asset(LoadedAsset::new(scene));
+                return Ok(());
+            }
+
             let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
             let scene_deserializer = SceneDeserializer {
                 type_registry: &self.type_registry.read(),
@@ -53,6 +60,6 @@ impl AssetLoader for SceneLoader {
     }
 
     fn extensions(&self) -> &[&str] {
-        &["scn", "scn.ron"]
+        &["scn", "scn.ron", "scn.bin"]
     }
 }
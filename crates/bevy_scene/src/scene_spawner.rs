@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Scene};
+use crate::{scene_diff::clone_dynamic_scene, DynamicScene, Scene, SceneDiff};
 use bevy_app::AppTypeRegistry;
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
@@ -34,6 +34,10 @@ pub struct SceneSpawner {
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
+    real_scene_asset_event_reader: ManualEventReader<AssetEvent<Scene>>,
+    /// The version of each scene its instances are at, to diff it against when it is reloaded.
+    dynamic_scene_snapshots: HashMap<Handle<DynamicScene>, DynamicScene>,
+    real_scene_snapshots: HashMap<Handle<Scene>, DynamicScene>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
@@ -101,6 +105,7 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
+        self.dynamic_scene_snapshots.remove(&scene_handle);
         if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
@@ -115,6 +120,29 @@ impl SceneSpawner {
                 let _ = world.despawn(entity);
             }
         }
+        self.forget_instance(instance_id);
+    }
+
+    /// Removes `instance_id` from the instances of its scene, and drops the snapshot of the
+    /// scene once it has no instances left. The next instance snapshots the scene again.
+    fn forget_instance(&mut self, instance_id: &InstanceId) {
+        let snapshots = &mut self.dynamic_scene_snapshots;
+        self.spawned_dynamic_scenes
+            .retain(|scene_handle, instance_ids| {
+                instance_ids.retain(|id| id != instance_id);
+                if instance_ids.is_empty() {
+                    snapshots.remove(scene_handle);
+                }
+                !instance_ids.is_empty()
+            });
+        let snapshots = &mut self.real_scene_snapshots;
+        self.spawned_scenes.retain(|scene_handle, instance_ids| {
+            instance_ids.retain(|id| id != instance_id);
+            if instance_ids.is_empty() {
+                snapshots.remove(scene_handle);
+            }
+            !instance_ids.is_empty()
+        });
     }
 
     pub fn spawn_dynamic_sync(
@@ -124,6 +152,7 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let mut entity_map = EntityMap::default();
         Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        self.snapshot_dynamic_scene(world, scene_handle);
         let instance_id = InstanceId::new();
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
@@ -173,8 +202,14 @@ impl SceneSpawner {
                         handle: scene_handle.clone(),
                     })?;
 
-            let instance_info =
-                scene.write_to_world_with(world, &world.resource::<AppTypeRegistry>().clone())?;
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            let instance_info = scene.write_to_world_with(world, &type_registry)?;
+            if !self.real_scene_snapshots.contains_key(&scene_handle) {
+                self.real_scene_snapshots.insert(
+                    scene_handle.clone_weak(),
+                    DynamicScene::from_scene(scene, &type_registry),
+                );
+            }
 
             self.spawned_instances.insert(instance_id, instance_info);
             let spawned = self
@@ -186,27 +221,103 @@ impl SceneSpawner {
         })
     }
 
+    /// Updates the instances of each of `scene_handles` to the current version of the scene.
+    ///
+    /// Only the differences with the version the instances were spawned or last updated with are
+    /// applied, see [`SceneDiff`]: components and entities added to the instances at runtime are
+    /// preserved, and entities still in the scene keep their id.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
         for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+            let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) else {
+                continue;
+            };
+            let scene = world
+                .resource::<Assets<DynamicScene>>()
+                .get(scene_handle)
+                .map(clone_dynamic_scene)
+                .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                    handle: scene_handle.clone_weak(),
+                })?;
+            let Some(snapshot) = self.dynamic_scene_snapshots.get(scene_handle) else {
+                // Without the previous version, overwrite the instances with the new one.
                 for instance_id in spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
+                        scene.write_to_world_with(
                             world,
-                            scene_handle,
                             &mut instance_info.entity_map,
+                            &type_registry,
                         )?;
                     }
                 }
+                self.dynamic_scene_snapshots
+                    .insert(scene_handle.clone_weak(), scene);
+                continue;
+            };
+
+            let diff = SceneDiff::new(snapshot, &scene);
+            for instance_id in spawned_instances {
+                if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                    diff.apply(world, &mut instance_info.entity_map, &type_registry)?;
+                }
+            }
+            self.dynamic_scene_snapshots
+                .insert(scene_handle.clone_weak(), scene);
+        }
+        Ok(())
+    }
+
+    /// Updates the instances of each of `scene_handles` to the current version of the scene.
+    ///
+    /// This is the [`Scene`] counterpart of [`Self::update_spawned_scenes`].
+    pub fn update_spawned_real_scenes(
+        &mut self,
+        world: &mut World,
+        scene_handles: &[Handle<Scene>],
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        for scene_handle in scene_handles {
+            let Some(spawned_instances) = self.spawned_scenes.get(scene_handle) else {
+                continue;
+            };
+            let scene = world
+                .resource::<Assets<Scene>>()
+                .get(scene_handle)
+                .map(|scene| DynamicScene::from_scene(scene, &type_registry))
+                .ok_or_else(|| SceneSpawnError::NonExistentRealScene {
+                    handle: scene_handle.clone_weak(),
+                })?;
+            let diff = match self.real_scene_snapshots.get(scene_handle) {
+                Some(snapshot) => SceneDiff::new(snapshot, &scene),
+                None => continue,
+            };
+            for instance_id in spawned_instances {
+                if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                    diff.apply(world, &mut instance_info.entity_map, &type_registry)?;
+                }
             }
+            self.real_scene_snapshots
+                .insert(scene_handle.clone_weak(), scene);
         }
         Ok(())
     }
 
+    /// Keeps the version of the scene its first instance is spawned with, to update the
+    /// instances in place when it is reloaded.
+    fn snapshot_dynamic_scene(&mut self, world: &World, scene_handle: &Handle<DynamicScene>) {
+        if self.dynamic_scene_snapshots.contains_key(scene_handle) {
+            return;
+        }
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) {
+            self.dynamic_scene_snapshots
+                .insert(scene_handle.clone_weak(), clone_dynamic_scene(scene));
+        }
+    }
+
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
 
@@ -232,6 +343,7 @@ impl SceneSpawner {
 
             match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
                 Ok(_) => {
+                    self.snapshot_dynamic_scene(world, &scene_handle);
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
                     let spawned = self
@@ -298,6 +410,19 @@ impl SceneSpawner {
         self.spawned_instances.contains_key(&instance_id)
     }
 
+    /// Get the entity an entity of the scene was spawned as in an instance, once it's spawned.
+    ///
+    /// `scene_entity` is the id of the entity in the scene, such as [`DynamicEntity::entity`].
+    ///
+    /// [`DynamicEntity::entity`]: crate::DynamicEntity::entity
+    pub fn instance_entity(&self, instance_id: InstanceId, scene_entity: Entity) -> Option<Entity> {
+        self.spawned_instances
+            .get(&instance_id)?
+            .entity_map
+            .get(scene_entity)
+            .ok()
+    }
+
     /// Get an iterator over the entities in an instance, once it's spawned.
     ///
     /// Before the scene is spawned, the iterator will be empty. Use [`Self::instance_is_ready`]
@@ -331,14 +456,130 @@ pub fn scene_spawner_system(world: &mut World) {
             }
         }
 
+        let real_scene_asset_events = world.resource::<Events<AssetEvent<Scene>>>();
+        let mut updated_spawned_real_scenes = Vec::new();
+        for event in scene_spawner
+            .real_scene_asset_event_reader
+            .iter(real_scene_asset_events)
+        {
+            if let AssetEvent::Modified { handle } = event {
+                if scene_spawner.spawned_scenes.contains_key(handle) {
+                    updated_spawned_real_scenes.push(handle.clone_weak());
+                }
+            }
+        }
+
         scene_spawner.despawn_queued_scenes(world).unwrap();
         scene_spawner.despawn_queued_instances(world);
-        scene_spawner
-            .spawn_queued_scenes(world)
-            .unwrap_or_else(|err| panic!("{}", err));
+        // Update the existing instances before spawning new ones, which already use the new
+        // version of the scenes.
         scene_spawner
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
+        scene_spawner
+            .update_spawned_real_scenes(world, &updated_spawned_real_scenes)
+            .unwrap();
+        scene_spawner
+            .spawn_queued_scenes(world)
+            .unwrap_or_else(|err| panic!("{}", err));
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_ecs::{component::Component, prelude::ReflectComponent};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Runtime;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_asset::<Scene>()
+            .add_asset::<DynamicScene>()
+            .init_resource::<SceneSpawner>()
+            .register_type::<Health>();
+        app
+    }
+
+    #[test]
+    fn reloaded_scene_patches_instances_in_place() {
+        let mut app = app();
+        let mut scene_world = World::new();
+        let scene_entity = scene_world.spawn(Health(1)).id();
+        let scene_handle = app
+            .world
+            .resource_mut::<Assets<Scene>>()
+            .add(Scene::new(scene_world));
+
+        app.world
+            .resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                spawner.spawn_sync(world, scene_handle.clone()).unwrap();
+            });
+        let instance_id = app.world.resource::<SceneSpawner>().spawned_scenes[&scene_handle][0];
+        let entity = app
+            .world
+            .resource::<SceneSpawner>()
+            .instance_entity(instance_id, scene_entity)
+            .unwrap();
+        app.world.entity_mut(entity).insert(Runtime);
+
+        let mut scenes = app.world.resource_mut::<Assets<Scene>>();
+        let scene = scenes.get_mut(&scene_handle).unwrap();
+        scene.world.entity_mut(scene_entity).insert(Health(2));
+        app.world
+            .resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                spawner
+                    .update_spawned_real_scenes(world, std::slice::from_ref(&scene_handle))
+                    .unwrap();
+            });
+
+        // The same entity is updated, and keeps the components added at runtime.
+        assert_eq!(Some(&Health(2)), app.world.get::<Health>(entity));
+        assert!(app.world.get::<Runtime>(entity).is_some());
+        assert_eq!(1, app.world.query::<&Health>().iter(&app.world).count());
+
+        app.world
+            .resource_scope(|world, mut spawner: Mut<SceneSpawner>| {
+                spawner.despawn_instance_sync(world, &instance_id);
+            });
+        let spawner = app.world.resource::<SceneSpawner>();
+        assert!(app.world.get_entity(entity).is_none());
+        assert!(!spawner.spawned_scenes.contains_key(&scene_handle));
+        assert!(spawner.real_scene_snapshots.is_empty());
+    }
+
+    #[test]
+    fn despawned_dynamic_instances_drop_the_snapshot() {
+        let mut app = app();
+        let scene_handle = app
+            .world
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(DynamicScene::default());
+
+        let mut spawner = SceneSpawner::default();
+        spawner
+            .spawn_dynamic_sync(&mut app.world, &scene_handle)
+            .unwrap();
+        spawner
+            .spawn_dynamic_sync(&mut app.world, &scene_handle)
+            .unwrap();
+        let instance_ids = spawner.spawned_dynamic_scenes[&scene_handle].clone();
+        assert!(spawner.dynamic_scene_snapshots.contains_key(&scene_handle));
+
+        spawner.despawn_instance_sync(&mut app.world, &instance_ids[0]);
+        assert!(spawner.dynamic_scene_snapshots.contains_key(&scene_handle));
+        spawner.despawn_instance_sync(&mut app.world, &instance_ids[1]);
+        assert!(!spawner.spawned_dynamic_scenes.contains_key(&scene_handle));
+        assert!(spawner.dynamic_scene_snapshots.is_empty());
+    }
+}
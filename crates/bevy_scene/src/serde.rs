@@ -2,7 +2,7 @@ use crate::{DynamicEntity, DynamicScene};
 use anyhow::Result;
 use bevy_reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
 use bevy_reflect::{
-    serde::{TypeRegistrationDeserializer, UntypedReflectDeserializer},
+    serde::{UntypedReflectDeserializer, VersionedTypeRegistrationDeserializer},
     Reflect, TypeRegistry, TypeRegistryArc,
 };
 use bevy_utils::HashSet;
@@ -112,11 +112,12 @@ impl<'a> Serialize for SceneMapSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        let registry = self.registry.read();
         let mut state = serializer.serialize_map(Some(self.entries.len()))?;
         for reflect in self.entries {
             state.serialize_entry(
-                reflect.type_name(),
-                &TypedReflectSerializer::new(&**reflect, &self.registry.read()),
+                &*registry.versioned_type_path_of(&**reflect),
+                &TypedReflectSerializer::new(&**reflect, &registry),
             )?;
         }
         state.end()
@@ -386,8 +387,8 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
     {
         let mut added = HashSet::new();
         let mut entries = Vec::new();
-        while let Some(registration) =
-            map.next_key_seed(TypeRegistrationDeserializer::new(self.registry))?
+        while let Some((registration, version)) =
+            map.next_key_seed(VersionedTypeRegistrationDeserializer::new(self.registry))?
         {
             if !added.insert(registration.type_id()) {
                 return Err(Error::custom(format_args!(
@@ -396,9 +397,9 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
                 )));
             }
 
-            entries.push(
-                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?,
-            );
+            entries.push(map.next_value_seed(
+                TypedReflectDeserializer::new(registration, self.registry).with_version(version),
+            )?);
         }
 
         Ok(entries)
@@ -424,9 +425,10 @@ mod tests {
     use crate::serde::{SceneDeserializer, SceneSerializer};
     use crate::{DynamicScene, DynamicSceneBuilder};
     use bevy_app::AppTypeRegistry;
-    use bevy_ecs::entity::EntityMap;
+    use bevy_ecs::entity::{Entity, EntityMap};
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
-    use bevy_reflect::{FromReflect, Reflect, ReflectSerialize};
+    use bevy_reflect::serde::Migration;
+    use bevy_reflect::{DynamicStruct, FromReflect, Reflect, ReflectSerialize};
     use bincode::Options;
     use serde::de::DeserializeSeed;
     use serde::Serialize;
@@ -596,6 +598,62 @@ mod tests {
         assert_eq!(1, dst_world.query::<&Baz>().iter(&dst_world).count());
     }
 
+    #[test]
+    fn should_migrate_old_versions() {
+        #[derive(Component, Reflect, FromReflect, Default, PartialEq, Debug)]
+        #[reflect(Component, version = 1)]
+        struct Health {
+            current: u32,
+        }
+
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register_migration::<Health>(
+                // Version 0 named the field `hp`.
+                Migration::new_struct(0, |health: &mut DynamicStruct| {
+                    if let Some(hp) = health.remove("hp") {
+                        health.insert_boxed("current", hp);
+                    }
+                })
+                .with_field::<u32>("hp"),
+            );
+
+        let entity = world.spawn(Health { current: 3 }).id();
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entity(entity);
+        let output = builder
+            .build()
+            .serialize_ron(&world.resource::<AppTypeRegistry>().0)
+            .unwrap();
+        assert!(output.contains("::Health@1\": ("));
+
+        let input = r#"(
+  resources: {},
+  entities: {
+    0: (
+      components: {
+        "bevy_scene::serde::tests::should_migrate_old_versions::Health": (
+          hp: 10,
+        ),
+      },
+    ),
+  },
+)"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &world.resource::<AppTypeRegistry>().read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        let mut map = EntityMap::default();
+        scene.write_to_world(&mut world, &mut map).unwrap();
+        let entity = map.get(Entity::from_raw(0)).unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health { current: 10 }));
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
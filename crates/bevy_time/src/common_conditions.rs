@@ -1,11 +1,13 @@
-use crate::{fixed_timestep::FixedTime, Time, Timer, TimerMode};
+use crate::{Fixed, Real, Time, Timer, TimerMode};
 use bevy_ecs::system::Res;
 use bevy_utils::Duration;
 
 /// Run condition that is active on a regular time interval, using [`Time`] to advance
 /// the timer.
 ///
-/// If used for a fixed timestep system, use [`on_fixed_timer`] instead.
+/// [`Time`] follows the virtual clock, so the timer doesn't advance while it is paused, except in
+/// the [`FixedUpdate`](bevy_app::FixedUpdate) schedule where it follows the fixed clock. To follow
+/// the wall-clock time instead, use [`on_real_timer`].
 ///
 /// ```rust,no_run
 /// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup, Update};
@@ -40,10 +42,42 @@ pub fn on_timer(duration: Duration) -> impl FnMut(Res<Time>) -> bool + Clone {
     }
 }
 
-/// Run condition that is active on a regular time interval, using [`FixedTime`] to
+/// Run condition that is active on a regular time interval, using [`Time<Real>`] to advance
+/// the timer.
+///
+/// The timer ignores pausing and scaling of the virtual clock, which makes this condition suited
+/// to things like menus or diagnostics that should keep running while the game is paused.
+///
+/// ```rust,no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup, Update};
+/// # use bevy_ecs::schedule::IntoSystemConfigs;
+/// # use bevy_utils::Duration;
+/// # use bevy_time::common_conditions::on_real_timer;
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_systems(Update, tick.run_if(on_real_timer(Duration::from_secs(1))))
+///         .run();
+/// }
+/// fn tick() {
+///     // ran once a second of wall-clock time
+/// }
+/// ```
+///
+/// The same caveats as [`on_timer`] apply regarding large delta times.
+pub fn on_real_timer(duration: Duration) -> impl FnMut(Res<Time<Real>>) -> bool + Clone {
+    let mut timer = Timer::new(duration, TimerMode::Repeating);
+    move |time: Res<Time<Real>>| {
+        timer.tick(time.delta());
+        timer.just_finished()
+    }
+}
+
+/// Run condition that is active on a regular time interval, using [`Time<Fixed>`] to
 /// advance the timer.
 ///
-/// If used for a non-fixed timestep system, use [`on_timer`] instead.
+/// This is meant for systems in the [`FixedUpdate`](bevy_app::FixedUpdate) schedule, where
+/// [`on_timer`] behaves the same.
 ///
 /// ```rust,no_run
 /// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, PluginGroup, FixedUpdate};
@@ -66,10 +100,10 @@ pub fn on_timer(duration: Duration) -> impl FnMut(Res<Time>) -> bool + Clone {
 /// Note that this run condition may not behave as expected if `duration` is smaller
 /// than the fixed timestep period, since the timer may complete multiple times in
 /// one fixed update.
-pub fn on_fixed_timer(duration: Duration) -> impl FnMut(Res<FixedTime>) -> bool + Clone {
+pub fn on_fixed_timer(duration: Duration) -> impl FnMut(Res<Time<Fixed>>) -> bool + Clone {
     let mut timer = Timer::new(duration, TimerMode::Repeating);
-    move |time: Res<FixedTime>| {
-        timer.tick(time.period);
+    move |time: Res<Time<Fixed>>| {
+        timer.tick(time.delta());
         timer.just_finished()
     }
 }
@@ -87,6 +121,7 @@ mod tests {
         Schedule::default().add_systems(
             (test_system, test_system)
                 .distributive_run_if(on_timer(Duration::new(1, 0)))
+                .distributive_run_if(on_real_timer(Duration::new(1, 0)))
                 .distributive_run_if(on_fixed_timer(Duration::new(1, 0))),
         );
     }
@@ -6,8 +6,8 @@
 //! via the [`run_fixed_update_schedule`] exclusive system.
 //!
 //! This schedule will be run a number of times each frame,
-//! equal to the accumulated divided by the period resource, rounded down,
-//! as tracked in the [`FixedTime`] resource.
+//! equal to the time accumulated by the [`Time<Virtual>`](crate::Virtual) clock divided by the
+//! timestep, rounded down, as tracked by the [`Time<Fixed>`] clock.
 //! Unused time will be carried over.
 //!
 //! This does not guarantee that the time elapsed between executions is exact,
@@ -18,104 +18,179 @@
 //! with ~4.167ms frames. However, the same criteria may not result in exactly 8.333ms passing
 //! between each execution.
 //!
-//! When using fixed time steps, it is advised not to rely on [`Time::delta`] or any of it's
-//! variants for game simulation, but rather use the value of [`FixedTime`] instead.
+//! While [`FixedUpdate`] runs, [`Time`] is a copy of [`Time<Fixed>`], which advances by exactly
+//! one timestep at each run, so systems can read `Res<Time>` whether they run on a fixed timestep
+//! or not.
 
-use crate::Time;
+use crate::{Time, Virtual};
 use bevy_app::FixedUpdate;
-use bevy_ecs::{system::Resource, world::World};
+use bevy_ecs::world::World;
+use bevy_reflect::{FromReflect, Reflect};
 use bevy_utils::Duration;
-use thiserror::Error;
 
-/// The amount of time that must pass before the fixed timestep schedule is run again.
-#[derive(Resource, Debug)]
-pub struct FixedTime {
-    accumulated: Duration,
-    /// Defaults to 1/60th of a second.
-    /// To configure this value, simply mutate or overwrite this resource.
-    pub period: Duration,
+/// The context of the fixed clock, [`Time<Fixed>`], which follows the
+/// [`Time<Virtual>`](crate::Virtual) clock in steps of a fixed duration.
+///
+/// The time the virtual clock advanced by is accumulated, and the clock advances by one
+/// [`timestep`](Time::<Fixed>::timestep) each time [`FixedUpdate`] runs, until less than a timestep
+/// is accumulated. The time left, the [`overstep`](Time::<Fixed>::overstep), is carried over to
+/// the next frame, and can be used to interpolate between fixed updates.
+#[derive(Debug, Copy, Clone, Reflect, FromReflect)]
+pub struct Fixed {
+    timestep: Duration,
+    overstep: Duration,
 }
 
-impl FixedTime {
-    /// Creates a new [`FixedTime`] struct
-    pub fn new(period: Duration) -> Self {
-        FixedTime {
-            accumulated: Duration::ZERO,
-            period,
+impl Default for Fixed {
+    fn default() -> Self {
+        Self {
+            timestep: Time::<Fixed>::DEFAULT_TIMESTEP,
+            overstep: Duration::ZERO,
         }
     }
+}
 
-    /// Creates a new [`FixedTime`] struct with a period specified in `f32` seconds
-    pub fn new_from_secs(period: f32) -> Self {
-        FixedTime {
-            accumulated: Duration::ZERO,
-            period: Duration::from_secs_f32(period),
-        }
+impl Time<Fixed> {
+    /// Defaults to 1/60th of a second.
+    const DEFAULT_TIMESTEP: Duration = Duration::from_nanos(16_666_667);
+
+    /// Creates a new fixed clock with the given timestep.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    pub fn from_duration(timestep: Duration) -> Self {
+        let mut time = Self::default();
+        time.set_timestep(timestep);
+        time
+    }
+
+    /// Creates a new fixed clock with a timestep specified in `f64` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is zero, negative or not finite.
+    pub fn from_seconds(seconds: f64) -> Self {
+        let mut time = Self::default();
+        time.set_timestep_seconds(seconds);
+        time
     }
 
-    /// Adds the `delta_time` to the accumulated time so far.
-    pub fn tick(&mut self, delta_time: Duration) {
-        self.accumulated += delta_time;
+    /// Creates a new fixed clock running `hz` times per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    pub fn from_hz(hz: f64) -> Self {
+        let mut time = Self::default();
+        time.set_timestep_hz(hz);
+        time
     }
 
-    /// Returns the current amount of accumulated time
-    pub fn accumulated(&self) -> Duration {
-        self.accumulated
+    /// Returns the amount of time the clock advances by at each run of [`FixedUpdate`].
+    #[inline]
+    pub fn timestep(&self) -> Duration {
+        self.context().timestep
     }
 
-    /// Expends one `period` of accumulated time.
+    /// Sets the amount of time the clock advances by at each run of [`FixedUpdate`].
     ///
-    /// [`Err(FixedUpdateError`)] will be returned
-    pub fn expend(&mut self) -> Result<(), FixedUpdateError> {
-        if let Some(new_value) = self.accumulated.checked_sub(self.period) {
-            self.accumulated = new_value;
-            Ok(())
-        } else {
-            Err(FixedUpdateError::NotEnoughTime {
-                accumulated: self.accumulated,
-                period: self.period,
-            })
-        }
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    #[inline]
+    pub fn set_timestep(&mut self, timestep: Duration) {
+        assert_ne!(
+            timestep,
+            Duration::ZERO,
+            "attempted to set fixed timestep to zero"
+        );
+        self.context_mut().timestep = timestep;
     }
-}
 
-impl Default for FixedTime {
-    fn default() -> Self {
-        FixedTime {
-            accumulated: Duration::ZERO,
-            period: Duration::from_secs_f32(1. / 60.),
-        }
+    /// Sets the timestep of the clock in `f64` seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seconds` is zero, negative or not finite.
+    #[inline]
+    pub fn set_timestep_seconds(&mut self, seconds: f64) {
+        assert!(
+            seconds.is_sign_positive(),
+            "seconds less than or equal to zero"
+        );
+        assert!(seconds.is_finite(), "seconds is infinite");
+        self.set_timestep(Duration::from_secs_f64(seconds));
+    }
+
+    /// Sets the timestep of the clock for [`FixedUpdate`] to run `hz` times per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    #[inline]
+    pub fn set_timestep_hz(&mut self, hz: f64) {
+        assert!(hz.is_sign_positive(), "Hz less than or equal to zero");
+        assert!(hz.is_finite(), "Hz is infinite");
+        self.set_timestep_seconds(1.0 / hz);
+    }
+
+    /// Returns the amount of time accumulated toward the next run of [`FixedUpdate`].
+    #[inline]
+    pub fn overstep(&self) -> Duration {
+        self.context().overstep
+    }
+
+    /// Returns the amount of time accumulated toward the next run of [`FixedUpdate`], as a
+    /// fraction of the timestep in `f32`.
+    #[inline]
+    pub fn overstep_percentage(&self) -> f32 {
+        self.overstep_percentage_f64() as f32
     }
-}
 
-/// An error returned when working with [`FixedTime`].
-#[derive(Debug, Error)]
-pub enum FixedUpdateError {
-    #[error("At least one period worth of time must be accumulated.")]
-    NotEnoughTime {
-        accumulated: Duration,
-        period: Duration,
-    },
+    /// Returns the amount of time accumulated toward the next run of [`FixedUpdate`], as a
+    /// fraction of the timestep in `f64`.
+    #[inline]
+    pub fn overstep_percentage_f64(&self) -> f64 {
+        self.context().overstep.as_secs_f64() / self.context().timestep.as_secs_f64()
+    }
+
+    /// Adds `delta` to the time accumulated toward the next run of [`FixedUpdate`].
+    pub fn accumulate(&mut self, delta: Duration) {
+        self.context_mut().overstep += delta;
+    }
+
+    /// Expends one timestep of the accumulated time, advancing the clock by it, if at least one
+    /// timestep is accumulated.
+    ///
+    /// Returns whether [`FixedUpdate`] should run.
+    pub fn expend(&mut self) -> bool {
+        let timestep = self.timestep();
+        let Some(overstep) = self.context().overstep.checked_sub(timestep) else {
+            return false;
+        };
+        self.context_mut().overstep = overstep;
+        self.advance_by(timestep);
+        true
+    }
 }
 
-/// Ticks the [`FixedTime`] resource then runs the [`FixedUpdate`].
+/// Accumulates the time the [`Time<Virtual>`](crate::Virtual) clock advanced by into the
+/// [`Time<Fixed>`] clock, then runs the [`FixedUpdate`] schedule for each timestep accumulated.
+///
+/// [`Time`] is a copy of [`Time<Fixed>`] while the schedule runs, and of
+/// [`Time<Virtual>`](crate::Virtual) again afterwards.
 pub fn run_fixed_update_schedule(world: &mut World) {
-    // Tick the time
-    let delta_time = world.resource::<Time>().delta();
-    let mut fixed_time = world.resource_mut::<FixedTime>();
-    fixed_time.tick(delta_time);
+    let delta = world.resource::<Time<Virtual>>().delta();
+    world.resource_mut::<Time<Fixed>>().accumulate(delta);
 
     // Run the schedule until we run out of accumulated time
-    let mut check_again = true;
-    while check_again {
-        let mut fixed_time = world.resource_mut::<FixedTime>();
-        let fixed_time_run = fixed_time.expend().is_ok();
-        if fixed_time_run {
-            let _ = world.try_run_schedule(FixedUpdate);
-        } else {
-            check_again = false;
-        }
+    while world.resource_mut::<Time<Fixed>>().expend() {
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        let _ = world.try_run_schedule(FixedUpdate);
     }
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
 }
 
 #[cfg(test)]
@@ -124,39 +199,44 @@ mod test {
 
     #[test]
     fn fixed_time_starts_at_zero() {
-        let new_time = FixedTime::new_from_secs(42.);
-        assert_eq!(new_time.accumulated(), Duration::ZERO);
+        let new_time = Time::<Fixed>::from_seconds(42.);
+        assert_eq!(new_time.overstep(), Duration::ZERO);
+        assert_eq!(new_time.elapsed(), Duration::ZERO);
 
-        let default_time = FixedTime::default();
-        assert_eq!(default_time.accumulated(), Duration::ZERO);
+        let default_time = Time::<Fixed>::default();
+        assert_eq!(default_time.overstep(), Duration::ZERO);
     }
 
     #[test]
-    fn fixed_time_ticks_up() {
-        let mut fixed_time = FixedTime::default();
-        fixed_time.tick(Duration::from_secs(1));
-        assert_eq!(fixed_time.accumulated(), Duration::from_secs(1));
+    fn fixed_time_accumulates() {
+        let mut fixed_time = Time::<Fixed>::default();
+        fixed_time.accumulate(Duration::from_secs(1));
+        assert_eq!(fixed_time.overstep(), Duration::from_secs(1));
     }
 
     #[test]
     fn enough_accumulated_time_is_required() {
-        let mut fixed_time = FixedTime::new(Duration::from_secs(2));
-        fixed_time.tick(Duration::from_secs(1));
-        assert!(fixed_time.expend().is_err());
-        assert_eq!(fixed_time.accumulated(), Duration::from_secs(1));
-
-        fixed_time.tick(Duration::from_secs(1));
-        assert!(fixed_time.expend().is_ok());
-        assert_eq!(fixed_time.accumulated(), Duration::ZERO);
+        let mut fixed_time = Time::<Fixed>::from_duration(Duration::from_secs(2));
+        fixed_time.accumulate(Duration::from_secs(1));
+        assert!(!fixed_time.expend());
+        assert_eq!(fixed_time.overstep(), Duration::from_secs(1));
+        assert_eq!(fixed_time.overstep_percentage(), 0.5);
+
+        fixed_time.accumulate(Duration::from_secs(1));
+        assert!(fixed_time.expend());
+        assert_eq!(fixed_time.overstep(), Duration::ZERO);
+        assert_eq!(fixed_time.delta(), Duration::from_secs(2));
+        assert_eq!(fixed_time.elapsed(), Duration::from_secs(2));
     }
 
     #[test]
     fn repeatedly_expending_time() {
-        let mut fixed_time = FixedTime::new(Duration::from_secs(1));
-        fixed_time.tick(Duration::from_secs_f32(3.2));
-        assert!(fixed_time.expend().is_ok());
-        assert!(fixed_time.expend().is_ok());
-        assert!(fixed_time.expend().is_ok());
-        assert!(fixed_time.expend().is_err());
+        let mut fixed_time = Time::<Fixed>::from_duration(Duration::from_secs(1));
+        fixed_time.accumulate(Duration::from_secs_f32(3.2));
+        assert!(fixed_time.expend());
+        assert!(fixed_time.expend());
+        assert!(fixed_time.expend());
+        assert!(!fixed_time.expend());
+        assert_eq!(fixed_time.elapsed(), Duration::from_secs(3));
     }
 }
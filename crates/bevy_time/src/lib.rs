@@ -3,15 +3,19 @@
 /// Common run conditions
 pub mod common_conditions;
 pub mod fixed_timestep;
+mod real;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
+mod virt;
 
-use fixed_timestep::FixedTime;
+pub use fixed_timestep::Fixed;
+pub use real::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
+pub use virt::*;
 
 use bevy_ecs::system::{Res, ResMut};
 use bevy_utils::{tracing::warn, Duration, Instant};
@@ -20,10 +24,10 @@ use crossbeam_channel::{Receiver, Sender};
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{fixed_timestep::FixedTime, Time, Timer, TimerMode};
+    pub use crate::{Fixed, Real, Time, Timer, TimerMode, Virtual};
 }
 
-use bevy_app::{prelude::*, RunFixedUpdateLoop};
+use bevy_app::{prelude::*, RunFixedUpdateLoop, RunMode, ScheduleRunnerSettings};
 use bevy_ecs::prelude::*;
 
 use crate::fixed_timestep::run_fixed_update_schedule;
@@ -33,18 +37,23 @@ use crate::fixed_timestep::run_fixed_update_schedule;
 pub struct TimePlugin;
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
-/// Updates the elapsed time. Any system that interacts with [Time] component should run after
+/// Updates the elapsed time. Any system that interacts with [`Time`] resources should run after
 /// this.
 pub struct TimeSystem;
 
 impl Plugin for TimePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Time>()
+            .init_resource::<Time<Real>>()
+            .init_resource::<Time<Virtual>>()
+            .init_resource::<Time<Fixed>>()
             .init_resource::<TimeUpdateStrategy>()
             .register_type::<Timer>()
             .register_type::<Time>()
+            .register_type::<Time<Real>>()
+            .register_type::<Time<Virtual>>()
+            .register_type::<Time<Fixed>>()
             .register_type::<Stopwatch>()
-            .init_resource::<FixedTime>()
             .add_systems(First, time_system.in_set(TimeSystem))
             .add_systems(RunFixedUpdateLoop, run_fixed_update_schedule);
     }
@@ -53,15 +62,22 @@ impl Plugin for TimePlugin {
 /// Configuration resource used to determine how the time system should run.
 ///
 /// For most cases, [`TimeUpdateStrategy::Automatic`] is fine. When writing tests, dealing with networking, or similar
-/// you may prefer to set the next [`Time`] value manually.
+/// you may prefer to set the next [`Time<Real>`] value manually. The [`Time<Virtual>`] clock then
+/// follows it as usual.
 #[derive(Resource, Default)]
 pub enum TimeUpdateStrategy {
     #[default]
     Automatic,
-    // Update [`Time`] with an exact `Instant` value
+    // Update [`Time<Real>`] with an exact `Instant` value
     ManualInstant(Instant),
-    // Update [`Time`] with the current time + a specified `Duration`
+    // Update [`Time<Real>`] with the current time + a specified `Duration`
     ManualDuration(Duration),
+    /// Update [`Time<Real>`] by advancing it by exactly the specified `Duration` since its last update,
+    /// regardless of the wall-clock time.
+    ///
+    /// This is used when the [`App`] runs with [`RunMode::FixedStep`], if the strategy is
+    /// [`TimeUpdateStrategy::Automatic`].
+    FixedDelta(Duration),
 }
 
 /// Channel resource used to receive time from render world
@@ -82,9 +98,15 @@ pub fn create_time_channels() -> (TimeSender, TimeReceiver) {
 
 /// The system used to update the [`Time`] used by app logic. If there is a render world the time is sent from
 /// there to this system through channels. Otherwise the time is updated in this system.
+///
+/// [`Time<Real>`] is updated first, then [`Time<Virtual>`] advances by the real time elapsed, and
+/// [`Time`] is set to a copy of it.
 fn time_system(
+    mut real_time: ResMut<Time<Real>>,
+    mut virtual_time: ResMut<Time<Virtual>>,
     mut time: ResMut<Time>,
     update_strategy: Res<TimeUpdateStrategy>,
+    runner_settings: Option<Res<ScheduleRunnerSettings>>,
     time_recv: Option<Res<TimeReceiver>>,
     mut has_received_time: Local<bool>,
 ) {
@@ -103,11 +125,110 @@ fn time_system(
         Instant::now()
     };
 
+    let fixed_step = match runner_settings.map(|settings| settings.run_mode) {
+        Some(RunMode::FixedStep { delta, .. }) => Some(delta),
+        _ => None,
+    };
+
     match update_strategy.as_ref() {
-        TimeUpdateStrategy::Automatic => time.update_with_instant(new_time),
-        TimeUpdateStrategy::ManualInstant(instant) => time.update_with_instant(*instant),
+        TimeUpdateStrategy::Automatic => match fixed_step {
+            Some(delta) => real_time.update_with_duration(delta),
+            None => real_time.update_with_instant(new_time),
+        },
+        TimeUpdateStrategy::ManualInstant(instant) => real_time.update_with_instant(*instant),
         TimeUpdateStrategy::ManualDuration(duration) => {
-            time.update_with_instant(Instant::now() + *duration);
+            real_time.update_with_instant(Instant::now() + *duration);
+        }
+        TimeUpdateStrategy::FixedDelta(delta) => real_time.update_with_duration(*delta),
+    }
+
+    virtual_time.advance_with_raw_delta(real_time.delta());
+    *time = virtual_time.as_generic();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Fixed, Real, Time, TimePlugin, TimeUpdateStrategy, Virtual};
+    use bevy_app::{prelude::*, FixedUpdate, ScheduleRunnerSettings};
+    use bevy_ecs::prelude::*;
+    use bevy_utils::Duration;
+
+    #[derive(Resource, Default)]
+    struct FixedUpdates(u32);
+
+    fn fixed_step_app() -> App {
+        let mut app = App::new();
+        app.add_plugin(TimePlugin)
+            .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(20)))
+            .init_resource::<FixedUpdates>()
+            .add_systems(
+                FixedUpdate,
+                |time: Res<Time>, mut updates: ResMut<FixedUpdates>| {
+                    updates.0 += 1;
+                    assert_eq!(time.delta(), Duration::from_millis(20));
+                    assert_eq!(time.elapsed(), Duration::from_millis(20) * updates.0);
+                },
+            );
+        app
+    }
+
+    #[test]
+    fn fixed_step_runner_time() {
+        let mut app = fixed_step_app();
+        app.insert_resource(ScheduleRunnerSettings::fixed_step(
+            Duration::from_millis(10),
+            None,
+        ));
+        for _ in 0..5 {
+            app.update();
+        }
+        let time = app.world.resource::<Time>();
+        assert_eq!(time.delta(), Duration::from_millis(10));
+        assert_eq!(time.elapsed(), Duration::from_millis(40));
+        assert_eq!(app.world.resource::<FixedUpdates>().0, 2);
+    }
+
+    #[test]
+    fn fixed_delta_strategy() {
+        let mut app = fixed_step_app();
+        app.insert_resource(TimeUpdateStrategy::FixedDelta(Duration::from_millis(30)));
+        for _ in 0..3 {
+            app.update();
         }
+        let time = app.world.resource::<Time>();
+        assert_eq!(time.elapsed(), Duration::from_millis(60));
+        assert_eq!(app.world.resource::<FixedUpdates>().0, 3);
+    }
+
+    #[test]
+    fn virtual_time_follows_real_time() {
+        let mut app = fixed_step_app();
+        app.insert_resource(TimeUpdateStrategy::FixedDelta(Duration::from_millis(100)));
+        app.update();
+
+        app.world
+            .resource_mut::<Time<Virtual>>()
+            .set_relative_speed(0.5);
+        app.update();
+        assert_eq!(
+            app.world.resource::<Time>().delta(),
+            Duration::from_millis(50)
+        );
+
+        app.world.resource_mut::<Time<Virtual>>().pause();
+        app.update();
+        app.update();
+        let time = app.world.resource::<Time>();
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.elapsed(), Duration::from_millis(50));
+        assert_eq!(app.world.resource::<FixedUpdates>().0, 2);
+        assert_eq!(
+            app.world.resource::<Time<Fixed>>().overstep(),
+            Duration::from_millis(10)
+        );
+
+        let real_time = app.world.resource::<Time<Real>>();
+        assert_eq!(real_time.delta(), Duration::from_millis(100));
+        assert_eq!(real_time.elapsed(), Duration::from_millis(300));
     }
 }
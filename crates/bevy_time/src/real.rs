@@ -0,0 +1,146 @@
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::{Duration, Instant};
+
+use crate::time::Time;
+
+/// The context of the real clock, [`Time<Real>`], which follows the wall-clock time.
+///
+/// It is updated by the [`TimePlugin`](crate::TimePlugin) at the start of each frame, as set by
+/// the [`TimeUpdateStrategy`](crate::TimeUpdateStrategy), and is not affected by pausing or scaling
+/// the virtual clock. It measures the time elapsed since its first update, so that the time spent
+/// starting the app is not counted.
+///
+/// Use it for things that should follow the wall-clock time, like measuring frame times or
+/// animating menus while the game is paused.
+#[derive(Debug, Copy, Clone, Reflect, FromReflect)]
+pub struct Real {
+    startup: Instant,
+    first_update: Option<Instant>,
+    last_update: Option<Instant>,
+}
+
+impl Default for Real {
+    fn default() -> Self {
+        Self {
+            startup: Instant::now(),
+            first_update: None,
+            last_update: None,
+        }
+    }
+}
+
+impl Time<Real> {
+    /// Constructs a new real clock with a specific startup `Instant`.
+    pub fn new(startup: Instant) -> Self {
+        Self::new_with(Real {
+            startup,
+            ..Default::default()
+        })
+    }
+
+    /// Updates the clock with the current instant.
+    ///
+    /// Calling this method as part of your app will most likely result in inaccurate timekeeping,
+    /// as the clock is ordinarily managed by the [`TimePlugin`](crate::TimePlugin).
+    pub fn update(&mut self) {
+        self.update_with_instant(Instant::now());
+    }
+
+    /// Updates the clock as if `duration` passed since its last update, or since its startup for
+    /// its first update.
+    ///
+    /// This method is provided for use in tests. Calling this method as part of your app will most
+    /// likely result in inaccurate timekeeping, as the clock is ordinarily managed by the
+    /// [`TimePlugin`](crate::TimePlugin).
+    pub fn update_with_duration(&mut self, duration: Duration) {
+        let last_update = self.context().last_update.unwrap_or(self.context().startup);
+        self.update_with_instant(last_update + duration);
+    }
+
+    /// Updates the clock with a specified [`Instant`].
+    ///
+    /// The first update doesn't advance the clock, it sets the instant the following updates are
+    /// measured from.
+    ///
+    /// This method is provided for use in tests. Calling this method as part of your app will most
+    /// likely result in inaccurate timekeeping, as the clock is ordinarily managed by the
+    /// [`TimePlugin`](crate::TimePlugin).
+    pub fn update_with_instant(&mut self, instant: Instant) {
+        let Some(last_update) = self.context().last_update else {
+            let context = self.context_mut();
+            context.first_update = Some(instant);
+            context.last_update = Some(instant);
+            return;
+        };
+        self.advance_by(instant - last_update);
+        self.context_mut().last_update = Some(instant);
+    }
+
+    /// Returns the [`Instant`] the clock was created.
+    ///
+    /// This usually represents when the app was started.
+    #[inline]
+    pub fn startup(&self) -> Instant {
+        self.context().startup
+    }
+
+    /// Returns the [`Instant`] when the clock was first updated, if it exists.
+    ///
+    /// This usually represents when the first app update started.
+    #[inline]
+    pub fn first_update(&self) -> Option<Instant> {
+        self.context().first_update
+    }
+
+    /// Returns the [`Instant`] when the clock was last updated, if it exists.
+    ///
+    /// This usually represents when the current app update started.
+    #[inline]
+    pub fn last_update(&self) -> Option<Instant> {
+        self.context().last_update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Real, Time};
+    use bevy_utils::{Duration, Instant};
+
+    #[test]
+    fn update_test() {
+        let startup = Instant::now();
+        let mut time = Time::<Real>::new(startup);
+
+        assert_eq!(time.startup(), startup);
+        assert_eq!(time.first_update(), None);
+        assert_eq!(time.last_update(), None);
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.elapsed(), Duration::ZERO);
+
+        let first_update = Instant::now();
+        time.update_with_instant(first_update);
+
+        assert_eq!(time.startup(), startup);
+        assert_eq!(time.first_update(), Some(first_update));
+        assert_eq!(time.last_update(), Some(first_update));
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.elapsed(), Duration::ZERO);
+
+        let second_update = first_update + Duration::from_millis(16);
+        time.update_with_instant(second_update);
+
+        assert_eq!(time.first_update(), Some(first_update));
+        assert_eq!(time.last_update(), Some(second_update));
+        assert_eq!(time.delta(), Duration::from_millis(16));
+        assert_eq!(time.elapsed(), Duration::from_millis(16));
+
+        time.update_with_duration(Duration::from_millis(4));
+
+        assert_eq!(
+            time.last_update(),
+            Some(second_update + Duration::from_millis(4))
+        );
+        assert_eq!(time.delta(), Duration::from_millis(4));
+        assert_eq!(time.elapsed(), Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,243 @@
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::{tracing::debug, Duration};
+
+use crate::time::Time;
+
+/// The context of the virtual clock, [`Time<Virtual>`], which follows the real clock but can be
+/// paused and scaled. It is the game time.
+///
+/// It is advanced by the [`TimePlugin`](crate::TimePlugin) after the [`Time<Real>`](crate::Real)
+/// clock at the start of each frame, by the real time elapsed:
+/// - clamped to [`max_delta`](Time::<Virtual>::max_delta), so that a long frame, for example while
+///   the window was being dragged, doesn't make the game jump forward,
+/// - multiplied by the [`relative_speed`](Time::<Virtual>::relative_speed), also known as "time
+///   scaling" or "time dilation" in other engines,
+/// - or not at all while the clock is [paused](Time::<Virtual>::pause).
+///
+/// The [`Time<Fixed>`](crate::fixed_timestep::Fixed) clock follows this clock, and `Time` is a
+/// copy of it outside of [`FixedUpdate`](bevy_app::FixedUpdate).
+#[derive(Debug, Copy, Clone, Reflect, FromReflect)]
+pub struct Virtual {
+    max_delta: Duration,
+    paused: bool,
+    relative_speed: f64, // using `f64` instead of `f32` to minimize drift from rounding errors
+    effective_speed: f64,
+}
+
+impl Default for Virtual {
+    fn default() -> Self {
+        Self {
+            max_delta: Time::<Virtual>::DEFAULT_MAX_DELTA,
+            paused: false,
+            relative_speed: 1.0,
+            effective_speed: 1.0,
+        }
+    }
+}
+
+impl Time<Virtual> {
+    const DEFAULT_MAX_DELTA: Duration = Duration::from_millis(250);
+
+    /// Creates a new virtual clock with the given maximum advance per update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_delta` is zero.
+    pub fn from_max_delta(max_delta: Duration) -> Self {
+        let mut time = Self::default();
+        time.set_max_delta(max_delta);
+        time
+    }
+
+    /// Returns the maximum amount the clock advances by in a single update, `250ms` by default.
+    #[inline]
+    pub fn max_delta(&self) -> Duration {
+        self.context().max_delta
+    }
+
+    /// Sets the maximum amount the clock advances by in a single update.
+    ///
+    /// When the real time elapsed since the last update is larger, the virtual clock falls behind
+    /// it. This prevents the [`FixedUpdate`](bevy_app::FixedUpdate) schedule from running many
+    /// times to catch up after a long frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_delta` is zero.
+    #[inline]
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        assert_ne!(max_delta, Duration::ZERO, "tried to set max delta to zero");
+        self.context_mut().max_delta = max_delta;
+    }
+
+    /// Returns the speed the clock advances relative to the real clock, as [`f32`].
+    ///
+    /// **Note:** This is the speed set with [`set_relative_speed`](Self::set_relative_speed), it
+    /// is not zero while the clock is paused, see [`effective_speed`](Self::effective_speed).
+    #[inline]
+    pub fn relative_speed(&self) -> f32 {
+        self.relative_speed_f64() as f32
+    }
+
+    /// Returns the speed the clock advances relative to the real clock, as [`f64`].
+    #[inline]
+    pub fn relative_speed_f64(&self) -> f64 {
+        self.context().relative_speed
+    }
+
+    /// Returns the speed the clock advanced at during the last update, as [`f32`]: zero if it was
+    /// paused, its relative speed otherwise.
+    #[inline]
+    pub fn effective_speed(&self) -> f32 {
+        self.effective_speed_f64() as f32
+    }
+
+    /// Returns the speed the clock advanced at during the last update, as [`f64`].
+    #[inline]
+    pub fn effective_speed_f64(&self) -> f64 {
+        self.context().effective_speed
+    }
+
+    /// Sets the speed the clock advances relative to the real clock, given as an [`f32`].
+    ///
+    /// For example, setting this to `2.0` will make the clock advance twice as fast as the real
+    /// clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is negative or not finite.
+    #[inline]
+    pub fn set_relative_speed(&mut self, ratio: f32) {
+        self.set_relative_speed_f64(ratio as f64);
+    }
+
+    /// Sets the speed the clock advances relative to the real clock, given as an [`f64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is negative or not finite.
+    #[inline]
+    pub fn set_relative_speed_f64(&mut self, ratio: f64) {
+        assert!(ratio.is_finite(), "tried to go infinitely fast");
+        assert!(ratio >= 0.0, "tried to go back in time");
+        self.context_mut().relative_speed = ratio;
+    }
+
+    /// Stops the clock, preventing it from advancing until resumed.
+    #[inline]
+    pub fn pause(&mut self) {
+        self.context_mut().paused = true;
+    }
+
+    /// Resumes the clock if paused.
+    #[inline]
+    pub fn unpause(&mut self) {
+        self.context_mut().paused = false;
+    }
+
+    /// Returns `true` if the clock is currently paused.
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.context().paused
+    }
+
+    /// Returns `true` if the clock was paused during the last update.
+    #[inline]
+    pub fn was_paused(&self) -> bool {
+        self.context().effective_speed == 0.0
+    }
+
+    /// Advances the clock by the real time elapsed since the last update, clamped, scaled or not
+    /// at all depending on its context.
+    pub(crate) fn advance_with_raw_delta(&mut self, raw_delta: Duration) {
+        let max_delta = self.context().max_delta;
+        let clamped_delta = if raw_delta > max_delta {
+            debug!(
+                "delta time larger than maximum delta, clamping delta to {:?} and skipping {:?}",
+                max_delta,
+                raw_delta - max_delta
+            );
+            max_delta
+        } else {
+            raw_delta
+        };
+        let effective_speed = if self.context().paused {
+            0.0
+        } else {
+            self.context().relative_speed
+        };
+        let delta = if effective_speed != 1.0 {
+            clamped_delta.mul_f64(effective_speed)
+        } else {
+            // avoid rounding when at normal speed
+            clamped_delta
+        };
+        self.context_mut().effective_speed = effective_speed;
+        self.advance_by(delta);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod tests {
+    use crate::{Time, Virtual};
+    use bevy_utils::Duration;
+
+    #[test]
+    fn relative_speed_test() {
+        let mut time = Time::<Virtual>::default();
+        assert_eq!(time.relative_speed(), 1.0);
+
+        time.advance_with_raw_delta(Duration::from_millis(100));
+        assert_eq!(time.delta(), Duration::from_millis(100));
+
+        time.set_relative_speed(2.0);
+        assert_eq!(time.relative_speed(), 2.0);
+        assert_eq!(time.effective_speed(), 1.0);
+
+        time.advance_with_raw_delta(Duration::from_millis(100));
+        assert_eq!(time.effective_speed(), 2.0);
+        assert_eq!(time.delta(), Duration::from_millis(200));
+        assert_eq!(time.elapsed(), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn pause_test() {
+        let mut time = Time::<Virtual>::default();
+        time.advance_with_raw_delta(Duration::from_millis(100));
+
+        time.pause();
+        assert!(time.is_paused());
+        assert!(!time.was_paused());
+        assert_eq!(time.relative_speed(), 1.0);
+
+        time.advance_with_raw_delta(Duration::from_millis(100));
+        assert!(time.was_paused());
+        assert_eq!(time.effective_speed(), 0.0);
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.elapsed(), Duration::from_millis(100));
+
+        time.unpause();
+        assert!(!time.is_paused());
+
+        time.advance_with_raw_delta(Duration::from_millis(100));
+        assert!(!time.was_paused());
+        assert_eq!(time.delta(), Duration::from_millis(100));
+        assert_eq!(time.elapsed(), Duration::from_millis(200));
+    }
+
+    #[test]
+    fn max_delta_test() {
+        let mut time = Time::<Virtual>::from_max_delta(Duration::from_millis(50));
+        time.advance_with_raw_delta(Duration::from_millis(40));
+        assert_eq!(time.delta(), Duration::from_millis(40));
+
+        time.advance_with_raw_delta(Duration::from_millis(200));
+        assert_eq!(time.delta(), Duration::from_millis(50));
+        assert_eq!(time.elapsed(), Duration::from_millis(90));
+
+        time.set_relative_speed(2.0);
+        time.advance_with_raw_delta(Duration::from_millis(200));
+        assert_eq!(time.delta(), Duration::from_millis(100));
+    }
+}
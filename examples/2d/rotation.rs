@@ -8,7 +8,7 @@ const BOUNDS: Vec2 = Vec2::new(1200.0, 640.0);
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .insert_resource(FixedTime::new_from_secs(TIME_STEP))
+        .insert_resource(Time::<Fixed>::from_seconds(TIME_STEP as f64))
         .add_systems(Startup, setup)
         .add_systems(
             FixedUpdate,
@@ -16,6 +16,17 @@ fn main() {
         .add_systems(Update, hello_world_system)
         .run();
 
+    // this app runs 120 frames as fast as possible, with time advancing by 1/60th of a second each
+    // frame, so that its results are the same on every run
+    App::new()
+        .insert_resource(ScheduleRunnerSettings::fixed_step(
+            Duration::from_secs_f64(1.0 / 60.0),
+            Some(120),
+        ))
+        .add_plugins(MinimalPlugins)
+        .add_systems(Update, elapsed_time)
+        .run();
+
     // this app loops forever at 60 fps
     App::new()
         .insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
@@ -30,6 +41,12 @@ fn hello_world_system() {
     println!("hello world");
 }
 
+fn elapsed_time(time: Res<Time>) {
+    if time.elapsed_seconds() >= 1.0 && time.elapsed_seconds() - time.delta_seconds() < 1.0 {
+        println!("one second elapsed");
+    }
+}
+
 fn counter(mut state: Local<CounterState>) {
     if state.count % 60 == 0 {
         println!("{}", state.count);
@@ -54,7 +54,7 @@ fn main() {
                 counter_system,
             ),
         )
-        .insert_resource(FixedTime::new_from_secs(0.2))
+        .insert_resource(Time::<Fixed>::from_seconds(0.2))
         .run();
 }
 